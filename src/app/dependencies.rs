@@ -7,7 +7,20 @@
 
 use std::sync::Arc;
 use crate::app::config::Config;
+use crate::app::metrics::InstrumentedGitHubApi;
 use crate::error::AppResult;
+use crate::handlers::auth::oauth_redirect_uri;
+use crate::infra::adapters::oauth::{GitHubOAuthAdapter, OAuthAdapter};
+use crate::infra::adapters::storage::{CloudStorageAdapter, StorageAdapter};
+use crate::infra::cache_service::RedisCacheService;
+use crate::infra::event_store::FirestoreEventStore;
+use crate::infra::github_api::GitHubApiClient;
+use crate::infra::notification_service::{
+    CompositeNotificationService, EmailNotificationService, HttpEmailSender,
+    NoOpNotificationService, WebhookNotificationService,
+};
+use crate::infra::rate_limiter::RedisRateLimiter;
+use crate::use_cases::generate_badge::ActivityFetchLocks;
 
 // インフラ層のインポート（実装時に追加）
 // use crate::infra::{
@@ -24,9 +37,13 @@ pub struct AppDependencies {
     pub config: Arc<Config>,
     
     /// GitHub APIクライアント
-    /// octocrabを使用したGitHub API v4（GraphQL）クライアント
+    /// reqwestを使用したGitHub API v4（GraphQL）クライアント
     pub github_api: Arc<dyn GitHubApi>,
     
+    /// GitHub OAuthアダプター
+    /// 認証コードの交換・認可の取り消し（HTTPクライアントはリクエスト間で共有する）
+    pub oauth_adapter: Arc<dyn OAuthAdapter>,
+    
     /// ユーザーリポジトリ
     /// Firestoreを使用したユーザーデータの永続化
     pub user_repository: Arc<dyn UserRepository>,
@@ -40,12 +57,24 @@ pub struct AppDependencies {
     pub cache_service: Arc<dyn CacheService>,
     
     /// 通知サービス
-    /// Webhook/Email通知の送信（将来実装）
+    /// 設定されたWebhook・受信者のメールへのPoke通知の送信
     pub notification_service: Arc<dyn NotificationService>,
     
     /// レート制限サービス
     /// Redisを使用したレート制限の実装
     pub rate_limiter: Arc<dyn RateLimiter>,
+    
+    /// バッジストレージ
+    /// Cloud Storageを使用したバッジのコールドキャッシュ
+    pub storage: Arc<dyn StorageAdapter>,
+    
+    /// 時計
+    /// 時刻に依存するロジックの基準時刻（テストでは固定できる）
+    pub clock: Arc<dyn Clock>,
+    
+    /// アクティビティ取得ロック
+    /// 同じユーザーのGitHub APIへの同時取得を1回にまとめる
+    pub activity_fetch_locks: Arc<ActivityFetchLocks>,
 }
 
 impl AppDependencies {
@@ -61,10 +90,10 @@ impl AppDependencies {
     /// * `Err(AppError)` - 初期化失敗
     pub async fn new(config: &Config) -> AppResult<Self> {
         // GitHub APIクライアントの初期化
-        // - octocrabインスタンスの作成
-        // - GitHub App認証の設定
-        // - レート制限ミドルウェアの追加
-        let github_api = Self::init_github_api(config).await?;
+        // - アクセストークンの設定
+        // - 呼び出し回数をメトリクスに記録
+        let github_api: Arc<dyn GitHubApi> =
+            Arc::new(InstrumentedGitHubApi::new(Self::init_github_api(config).await?));
         
         // Redisクライアントの初期化
         // - 接続プールの作成
@@ -86,26 +115,81 @@ impl AppDependencies {
         let rate_limiter = Arc::new(RedisRateLimiter::new(redis_pool.clone()));
         let user_repository = Arc::new(FirestoreUserRepository::new(firestore_client.clone()));
         let event_store = Arc::new(FirestoreEventStore::new(firestore_client.clone()));
-        let notification_service = Arc::new(NoOpNotificationService); // 初期実装は何もしない
+        let notification_service = Self::init_notification_service(
+            config,
+            user_repository.clone(),
+            cache_service.clone(),
+        );
+        let storage = Arc::new(CloudStorageAdapter::new(storage_client, &config.storage));
         
         Ok(Self {
             config: Arc::new(config.clone()),
             github_api,
+            oauth_adapter: Self::init_oauth_adapter(config),
             user_repository,
             event_store,
             cache_service,
             notification_service,
             rate_limiter,
+            storage,
+            clock: Arc::new(SystemClock),
+            activity_fetch_locks: Arc::new(ActivityFetchLocks::new()),
         })
     }
     
     /// GitHub APIクライアントを初期化
+    /// 
+    /// ベースURLは設定から取得する（GitHub Enterprise対応）
     async fn init_github_api(config: &Config) -> AppResult<Arc<dyn GitHubApi>> {
-        // TODO: 実装
-        // - octocrab::OctocrabBuilderを使用
-        // - GitHub App認証の設定
-        // - ベースURLの設定（GitHub Enterprise対応）
-        unimplemented!()
+        if config.github.api_token.is_none() {
+            tracing::warn!("GITHUB_TOKENが未設定です。GraphQL APIへのリクエストは失敗します");
+        }
+        
+        Ok(Arc::new(GitHubApiClient::new(&config.github)))
+    }
+    
+    /// GitHub OAuthアダプターを初期化
+    /// 
+    /// OAuthのベースURLは設定から取得する（GitHub Enterprise対応）
+    fn init_oauth_adapter(config: &Config) -> Arc<dyn OAuthAdapter> {
+        Arc::new(
+            GitHubOAuthAdapter::new(
+                config.github.client_id.clone(),
+                config.github.client_secret.clone(),
+                oauth_redirect_uri(config),
+            )
+            .with_base_url(config.github.oauth_base_url.clone())
+            .with_api_base_url(config.github.api_base_url.clone()),
+        )
+    }
+    
+    /// 通知サービスを初期化
+    /// 
+    /// 設定されている通知先（Webhook・メール）にのみ通知する
+    fn init_notification_service(
+        config: &Config,
+        user_repository: Arc<dyn UserRepository>,
+        cache_service: Arc<dyn CacheService>,
+    ) -> Arc<dyn NotificationService> {
+        let mut services: Vec<Arc<dyn NotificationService>> = Vec::new();
+        if let Some(url) = &config.app.notification_webhook_url {
+            services.push(Arc::new(WebhookNotificationService::new(url.clone())));
+        }
+        if let Some(api_url) = &config.email.api_url {
+            let sender = Arc::new(HttpEmailSender::new(api_url.clone(), &config.email));
+            services.push(Arc::new(EmailNotificationService::new(
+                user_repository,
+                cache_service,
+                sender,
+                config.app.base_url.clone(),
+            )));
+        }
+        
+        match services.len() {
+            0 => Arc::new(NoOpNotificationService),
+            1 => services.remove(0),
+            _ => Arc::new(CompositeNotificationService::new(services)),
+        }
     }
     
     /// Redis接続プールを初期化
@@ -147,6 +231,14 @@ pub trait GitHubApi: Send + Sync {
     
     /// ユーザー情報を取得
     async fn get_user(&self, username: &str) -> AppResult<GitHubUser>;
+    
+    /// APIのレート制限状況を取得
+    async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit>;
+    
+    /// 組織の公開メンバーのユーザー名を取得
+    /// 
+    /// 組織が存在しない場合は `DomainError::UserNotFound`
+    async fn get_org_members(&self, org: &str) -> AppResult<Vec<String>>;
 }
 
 /// ユーザーリポジトリのトレイト
@@ -176,6 +268,36 @@ pub trait EventStore: Send + Sync {
     
     /// 特定ユーザーからの今日のPokeを検索
     async fn find_today_pokes_from(&self, username: &str) -> AppResult<Vec<PokeEvent>>;
+    
+    /// 特定ユーザーへの全期間のPokeを検索
+    async fn find_pokes_to(&self, username: &str) -> AppResult<Vec<PokeEvent>>;
+    
+    /// 特定ユーザーからの全期間のPokeを検索
+    async fn find_pokes_from(&self, username: &str) -> AppResult<Vec<PokeEvent>>;
+    
+    /// 送信者から受信者への最新のPokeを取得
+    async fn find_latest_poke(&self, from: &str, to: &str) -> AppResult<Option<PokeEvent>>;
+    
+    /// 特定ユーザーのPoke履歴を新しい順に取得
+    /// 
+    /// # Arguments
+    /// * `username` - ユーザー名
+    /// * `direction` - 送信・受信のどちらの履歴か
+    /// * `cursor` - 指定した場合、このカーソルより後ろのイベントだけを返す
+    /// * `limit` - 最大件数
+    async fn find_poke_history(
+        &self,
+        username: &str,
+        direction: PokeDirection,
+        cursor: Option<&PokeCursor>,
+        limit: usize,
+    ) -> AppResult<Vec<PokeEvent>>;
+    
+    /// 特定ユーザーが送信・受信したPokeをすべて削除
+    /// 
+    /// # Returns
+    /// * `Ok(u64)` - 削除したイベント数
+    async fn delete_by_user(&self, username: &str) -> AppResult<u64>;
 }
 
 /// キャッシュサービスのトレイト
@@ -190,8 +312,19 @@ pub trait CacheService: Send + Sync {
     /// 値を削除
     async fn delete(&self, key: &str) -> AppResult<()>;
     
+    /// 値を取得して同時に削除（アトミック）
+    /// 
+    /// 一度だけ使用できる値（OAuthのstate等）の消費に使用する
+    /// 同じキーに対する同時呼び出しのうち、値を受け取れるのは1つだけ
+    async fn take(&self, key: &str) -> AppResult<Option<String>>;
+    
     /// パターンに一致するキーを削除
     async fn delete_pattern(&self, pattern: &str) -> AppResult<()>;
+    
+    /// 接続を閉じる
+    /// 
+    /// シャットダウン時に呼び出す（デフォルトは何もしない）
+    fn close(&self) {}
 }
 
 /// 通知サービスのトレイト
@@ -199,6 +332,20 @@ pub trait CacheService: Send + Sync {
 pub trait NotificationService: Send + Sync {
     /// Poke通知を送信
     async fn notify_poke(&self, event: &PokeEvent) -> AppResult<()>;
+    
+    /// 日次ダイジェストを送信
+    /// 
+    /// 指定日に蓄積したPokeを受信者ごとに1通にまとめて送る。
+    /// ダイジェストに対応しない通知サービスは何もしない
+    /// 
+    /// # Arguments
+    /// * `date` - 集計対象の日付（UTC）
+    /// 
+    /// # Returns
+    /// * `Ok(n)` - 送信したダイジェストの数
+    async fn flush_daily_digest(&self, _date: chrono::NaiveDate) -> AppResult<usize> {
+        Ok(0)
+    }
 }
 
 /// レート制限サービスのトレイト
@@ -213,50 +360,620 @@ pub trait RateLimiter: Send + Sync {
     
     /// レート制限をインクリメント
     async fn increment(&self, key: &str, window_seconds: u64) -> AppResult<u32>;
+    
+    /// 制限が解除されるまでの秒数を取得
+    /// 
+    /// # Returns
+    /// * `Ok(0)` - すでに制限内
+    /// * `Ok(n)` - n秒後に再試行可能
+    async fn retry_after(&self, key: &str, window_seconds: u64) -> AppResult<u64>;
+    
+    /// ウィンドウ内の残り回数を取得
+    /// 
+    /// # Returns
+    /// * `Ok(n)` - あとn回リクエスト可能（上限に達している場合は0）
+    async fn remaining(&self, key: &str, limit: u32, window_seconds: u64) -> AppResult<u32>;
 }
 
 // 型のインポート（ドメイン層から）
 use crate::domain::{
+    clock::{Clock, SystemClock},
     user::{UserState, RegisteredUser},
-    poke::PokeEvent,
-    github::{GitHubActivity, FollowRelation},
+    poke::{PokeCursor, PokeDirection, PokeEvent},
+    github::{GitHubActivity, GitHubRateLimit, FollowRelation},
 };
 
 // 仮の型定義（実装時に削除）
 #[derive(Debug)]
-struct GitHubUser;
+pub struct GitHubUser;
 
 // 仮の実装（実装時に各infraモジュールに移動）
-struct RedisCacheService;
-struct RedisRateLimiter;
 struct FirestoreUserRepository;
-struct FirestoreEventStore;
-struct NoOpNotificationService;
-
-impl RedisCacheService {
-    fn new(_pool: deadpool_redis::Pool) -> Self { Self }
-}
-
-impl RedisRateLimiter {
-    fn new(_pool: deadpool_redis::Pool) -> Self { Self }
-}
 
 impl FirestoreUserRepository {
     fn new(_client: firestore::FirestoreDb) -> Self { Self }
 }
 
-impl FirestoreEventStore {
-    fn new(_client: firestore::FirestoreDb) -> Self { Self }
-}
-
 // テスト用モック実装
 #[cfg(test)]
 pub mod mocks {
+    //! インメモリのモック実装
+    //! 
+    //! ユースケース・ハンドラーのテストで外部サービスなしに `AppDependencies` を組み立てる。
+    //! ユーザー名はすべて正規化（小文字化）して保持する
+    
     use super::*;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+    use crate::domain::user::Username;
+    use crate::error::{DomainError, InfraError};
+    use crate::infra::adapters::oauth::{OAuthErrorResponse, OAuthToken};
+    use crate::infra::adapters::storage::InMemoryStorageAdapter;
+    use crate::infra::event_store::InMemoryEventStore;
+    
+    /// ユーザー名を正規化（不正な名前はそのまま小文字化）
+    fn normalize(username: &str) -> String {
+        Username::parse(username.to_string())
+            .map(|username| username.normalized())
+            .unwrap_or_else(|_| username.to_lowercase())
+    }
+    
+    /// GitHub APIのモック
+    /// 
+    /// 登録したユーザーだけがGitHubに存在するものとして応答する。
+    /// フォロー関係は登録がなければ `FollowRelation::None`
+    #[derive(Default)]
+    pub struct MockGitHubApi {
+        users: Mutex<HashSet<String>>,
+        activities: Mutex<HashMap<String, GitHubActivity>>,
+        follow_relations: Mutex<HashMap<(String, String), FollowRelation>>,
+        org_members: Mutex<HashMap<String, Vec<String>>>,
+        rate_limit_remaining: Option<u32>,
+    }
+    
+    impl MockGitHubApi {
+        pub fn new() -> Self {
+            Self::default()
+        }
+        
+        /// GitHubに存在するユーザーを追加
+        pub fn with_user(self, username: &str) -> Self {
+            self.users.lock().unwrap().insert(normalize(username));
+            self
+        }
+        
+        /// アクティビティを追加（ユーザーも存在するものとして扱う）
+        pub fn with_activity(self, activity: GitHubActivity) -> Self {
+            let username = normalize(&activity.username);
+            self.users.lock().unwrap().insert(username.clone());
+            self.activities.lock().unwrap().insert(username, activity);
+            self
+        }
+        
+        /// `from` から見た `to` とのフォロー関係を追加
+        pub fn with_follow_relation(self, from: &str, to: &str, relation: FollowRelation) -> Self {
+            self.follow_relations
+                .lock()
+                .unwrap()
+                .insert((normalize(from), normalize(to)), relation);
+            self
+        }
+        
+        /// 組織の公開メンバーを追加
+        pub fn with_org_members(self, org: &str, members: &[&str]) -> Self {
+            self.org_members.lock().unwrap().insert(
+                normalize(org),
+                members.iter().map(|member| member.to_string()).collect(),
+            );
+            self
+        }
+        
+        /// レート制限の残り回数を設定（既定は上限と同じ5000）
+        pub fn with_rate_limit_remaining(mut self, remaining: u32) -> Self {
+            self.rate_limit_remaining = Some(remaining);
+            self
+        }
+        
+        /// 存在しないユーザーのエラー
+        fn not_found(username: &str) -> crate::error::AppError {
+            DomainError::UserNotFound(username.to_string()).into()
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl GitHubApi for MockGitHubApi {
+        async fn get_user_activity(&self, username: &str) -> AppResult<GitHubActivity> {
+            self.activities
+                .lock()
+                .unwrap()
+                .get(&normalize(username))
+                .cloned()
+                .ok_or_else(|| Self::not_found(username))
+        }
+        
+        async fn get_follow_relation(&self, from: &str, to: &str) -> AppResult<FollowRelation> {
+            Ok(self.follow_relations
+                .lock()
+                .unwrap()
+                .get(&(normalize(from), normalize(to)))
+                .copied()
+                .unwrap_or(FollowRelation::None))
+        }
+        
+        async fn get_user(&self, username: &str) -> AppResult<GitHubUser> {
+            if self.users.lock().unwrap().contains(&normalize(username)) {
+                Ok(GitHubUser)
+            } else {
+                Err(Self::not_found(username))
+            }
+        }
+        
+        async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit> {
+            Ok(GitHubRateLimit {
+                limit: 5000,
+                remaining: self.rate_limit_remaining.unwrap_or(5000),
+                reset_at: chrono::Utc::now() + chrono::Duration::hours(1),
+            })
+        }
+        
+        async fn get_org_members(&self, org: &str) -> AppResult<Vec<String>> {
+            self.org_members
+                .lock()
+                .unwrap()
+                .get(&normalize(org))
+                .cloned()
+                .ok_or_else(|| Self::not_found(org))
+        }
+    }
+    
+    /// ユーザーリポジトリのモック
+    /// 
+    /// 本番と同じく大文字小文字を区別せずに検索する。
+    /// ユーザーは正規化したユーザー名をキーに保持し、表示用の表記はそのまま残す
+    #[derive(Default)]
+    pub struct MockUserRepository {
+        users: Mutex<HashMap<String, RegisteredUser>>,
+    }
+    
+    impl MockUserRepository {
+        pub fn new() -> Self {
+            Self::default()
+        }
+        
+        /// 登録済みユーザーを追加
+        pub fn with_user(self, user: RegisteredUser) -> Self {
+            self.users.lock().unwrap().insert(user.username.normalized(), user);
+            self
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl UserRepository for MockUserRepository {
+        async fn find_by_username(&self, username: &str) -> AppResult<Option<UserState>> {
+            Ok(self.users
+                .lock()
+                .unwrap()
+                .get(&normalize(username))
+                .cloned()
+                .map(UserState::Registered))
+        }
+        
+        async fn save(&self, user: &RegisteredUser) -> AppResult<()> {
+            self.users.lock().unwrap().insert(user.username.normalized(), user.clone());
+            Ok(())
+        }
+        
+        async fn update(&self, user: &RegisteredUser) -> AppResult<()> {
+            self.save(user).await
+        }
+        
+        async fn delete(&self, username: &str) -> AppResult<()> {
+            self.users.lock().unwrap().remove(&normalize(username));
+            Ok(())
+        }
+    }
+    
+    /// キャッシュサービスのモック
+    /// 
+    /// TTLは記録するだけで期限切れにはしない
+    #[derive(Default)]
+    pub struct MockCacheService {
+        entries: Mutex<HashMap<String, (String, u64)>>,
+    }
+    
+    impl MockCacheService {
+        pub fn new() -> Self {
+            Self::default()
+        }
+        
+        /// 最後に保存したときのTTL（秒）
+        pub fn ttl(&self, key: &str) -> Option<u64> {
+            self.entries.lock().unwrap().get(key).map(|(_, ttl)| *ttl)
+        }
+        
+        /// 保存されているキーの一覧（昇順）
+        pub fn keys(&self) -> Vec<String> {
+            let mut keys: Vec<String> = self.entries.lock().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl CacheService for MockCacheService {
+        async fn get(&self, key: &str) -> AppResult<Option<String>> {
+            Ok(self.entries.lock().unwrap().get(key).map(|(value, _)| value.clone()))
+        }
+        
+        async fn set(&self, key: &str, value: &str, ttl_seconds: u64) -> AppResult<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), (value.to_string(), ttl_seconds));
+            Ok(())
+        }
+        
+        async fn delete(&self, key: &str) -> AppResult<()> {
+            self.entries.lock().unwrap().remove(key);
+            Ok(())
+        }
+        
+        async fn take(&self, key: &str) -> AppResult<Option<String>> {
+            Ok(self.entries.lock().unwrap().remove(key).map(|(value, _)| value))
+        }
+        
+        async fn delete_pattern(&self, pattern: &str) -> AppResult<()> {
+            self.entries.lock().unwrap().retain(|key, _| !glob_matches(pattern, key));
+            Ok(())
+        }
+    }
+    
+    /// `*` だけを含むglobパターンに一致するか
+    fn glob_matches(pattern: &str, key: &str) -> bool {
+        let mut parts = pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = key.strip_prefix(first) else {
+            return false;
+        };
+        
+        let parts: Vec<&str> = parts.collect();
+        let Some((last, middle)) = parts.split_last() else {
+            // `*` を含まない場合は完全一致
+            return rest.is_empty();
+        };
+        for part in middle {
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+        rest.ends_with(last)
+    }
+    
+    /// 通知サービスのモック（送信した通知を記録する）
+    #[derive(Default)]
+    pub struct MockNotificationService {
+        pub sent: Mutex<Vec<PokeEvent>>,
+    }
+    
+    #[async_trait::async_trait]
+    impl NotificationService for MockNotificationService {
+        async fn notify_poke(&self, event: &PokeEvent) -> AppResult<()> {
+            self.sent.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+    
+    /// レート制限のモック
+    /// 
+    /// キーごとの回数を数えるだけで、時間窓はリセットしない
+    #[derive(Default)]
+    pub struct MockRateLimiter {
+        counts: Mutex<HashMap<String, u32>>,
+    }
+    
+    impl MockRateLimiter {
+        /// すべてのキーの時間窓が過ぎた状態にする
+        pub fn reset(&self) {
+            self.counts.lock().unwrap().clear();
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl RateLimiter for MockRateLimiter {
+        async fn check_limit(&self, key: &str, limit: u32, _window_seconds: u64) -> AppResult<bool> {
+            Ok(self.counts.lock().unwrap().get(key).copied().unwrap_or(0) < limit)
+        }
+        
+        async fn increment(&self, key: &str, _window_seconds: u64) -> AppResult<u32> {
+            let mut counts = self.counts.lock().unwrap();
+            let count = counts.entry(key.to_string()).or_insert(0);
+            *count += 1;
+            Ok(*count)
+        }
+        
+        async fn retry_after(&self, key: &str, window_seconds: u64) -> AppResult<u64> {
+            let used = self.counts.lock().unwrap().contains_key(key);
+            Ok(if used { window_seconds } else { 0 })
+        }
+        
+        async fn remaining(&self, key: &str, limit: u32, _window_seconds: u64) -> AppResult<u32> {
+            let count = self.counts.lock().unwrap().get(key).copied().unwrap_or(0);
+            Ok(limit.saturating_sub(count))
+        }
+    }
+    
+    /// 障害の種類
+    #[derive(Clone, Copy)]
+    pub enum Outage {
+        /// 応答が返らない（タイムアウトの確認用）
+        Slow,
+        /// 接続エラーを返す
+        Failing,
+    }
+    
+    /// 障害中の外部サービス（ヘルスチェックのテスト用）
+    pub struct UnavailableService(pub Outage);
+    
+    impl UnavailableService {
+        async fn respond<T>(&self) -> AppResult<T> {
+            match self.0 {
+                Outage::Slow => std::future::pending().await,
+                Outage::Failing => Err(InfraError::Database("connection refused".to_string()).into()),
+            }
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl CacheService for UnavailableService {
+        async fn get(&self, _key: &str) -> AppResult<Option<String>> {
+            self.respond().await
+        }
+        
+        async fn set(&self, _key: &str, _value: &str, _ttl_seconds: u64) -> AppResult<()> {
+            self.respond().await
+        }
+        
+        async fn delete(&self, _key: &str) -> AppResult<()> {
+            self.respond().await
+        }
+        
+        async fn take(&self, _key: &str) -> AppResult<Option<String>> {
+            self.respond().await
+        }
+        
+        async fn delete_pattern(&self, _pattern: &str) -> AppResult<()> {
+            self.respond().await
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl UserRepository for UnavailableService {
+        async fn find_by_username(&self, _username: &str) -> AppResult<Option<UserState>> {
+            self.respond().await
+        }
+        
+        async fn save(&self, _user: &RegisteredUser) -> AppResult<()> {
+            self.respond().await
+        }
+        
+        async fn update(&self, _user: &RegisteredUser) -> AppResult<()> {
+            self.respond().await
+        }
+        
+        async fn delete(&self, _username: &str) -> AppResult<()> {
+            self.respond().await
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl GitHubApi for UnavailableService {
+        async fn get_user_activity(&self, _username: &str) -> AppResult<GitHubActivity> {
+            self.respond().await
+        }
+        
+        async fn get_follow_relation(&self, _from: &str, _to: &str) -> AppResult<FollowRelation> {
+            self.respond().await
+        }
+        
+        async fn get_user(&self, _username: &str) -> AppResult<GitHubUser> {
+            self.respond().await
+        }
+        
+        async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit> {
+            self.respond().await
+        }
+        
+        async fn get_org_members(&self, _org: &str) -> AppResult<Vec<String>> {
+            self.respond().await
+        }
+    }
+    
+    /// OAuthアダプターのモック
+    /// 
+    /// 認証コードの交換は常に `bad_verification_code` で失敗し、取り消したトークンを記録する
+    #[derive(Default)]
+    pub struct MockOAuthAdapter {
+        pub revoked: Mutex<Vec<String>>,
+    }
+    
+    #[async_trait::async_trait]
+    impl OAuthAdapter for MockOAuthAdapter {
+        async fn exchange_code(&self, _code: &str) -> Result<OAuthToken, Box<dyn std::error::Error>> {
+            Err(Box::new(OAuthErrorResponse {
+                error: "bad_verification_code".to_string(),
+                description: None,
+            }))
+        }
+        
+        async fn refresh_token(&self, _refresh_token: &str) -> Result<OAuthToken, Box<dyn std::error::Error>> {
+            Err("refresh_tokenは未対応です".into())
+        }
+        
+        async fn revoke_token(&self, token: &str) -> Result<(), Box<dyn std::error::Error>> {
+            self.revoked.lock().unwrap().push(token.to_string());
+            Ok(())
+        }
+    }
+    
+    /// テスト用の依存性コンテナのビルダー
+    /// 
+    /// ユーザー・アクティビティ・フォロー関係を登録してから `build` する
+    #[derive(Default)]
+    pub struct TestDependenciesBuilder {
+        config: Config,
+        github_api: MockGitHubApi,
+        github_api_override: Option<Arc<dyn GitHubApi>>,
+        user_repository: MockUserRepository,
+        clock: Option<Arc<dyn Clock>>,
+    }
+    
+    impl TestDependenciesBuilder {
+        pub fn new() -> Self {
+            Self::default()
+        }
+        
+        /// 設定を差し替える
+        pub fn with_config(mut self, config: Config) -> Self {
+            self.config = config;
+            self
+        }
+        
+        /// GitPokeに登録済みのユーザーを追加（GitHubにも存在する）
+        pub fn with_registered_user(mut self, user: RegisteredUser) -> Self {
+            self.github_api = self.github_api.with_user(user.username.as_str());
+            self.user_repository = self.user_repository.with_user(user);
+            self
+        }
+        
+        /// GitHubにのみ存在する（GitPoke未登録の）ユーザーを追加
+        pub fn with_github_user(mut self, username: &str) -> Self {
+            self.github_api = self.github_api.with_user(username);
+            self
+        }
+        
+        /// GitHubのアクティビティを追加
+        pub fn with_activity(mut self, activity: GitHubActivity) -> Self {
+            self.github_api = self.github_api.with_activity(activity);
+            self
+        }
+        
+        /// `from` から見た `to` とのフォロー関係を追加
+        pub fn with_follow_relation(mut self, from: &str, to: &str, relation: FollowRelation) -> Self {
+            self.github_api = self.github_api.with_follow_relation(from, to, relation);
+            self
+        }
+        
+        /// 組織の公開メンバーを追加
+        pub fn with_org_members(mut self, org: &str, members: &[&str]) -> Self {
+            self.github_api = self.github_api.with_org_members(org, members);
+            self
+        }
+        
+        /// GitHub APIをテスト固有の実装に差し替える
+        /// 
+        /// 差し替えた場合、`with_activity` などで登録した内容は使われない
+        pub fn with_github_api(mut self, github_api: Arc<dyn GitHubApi>) -> Self {
+            self.github_api_override = Some(github_api);
+            self
+        }
+        
+        /// 時計を差し替える（未指定の場合はシステム時計）
+        pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+            self.clock = Some(clock);
+            self
+        }
+        
+        /// 依存性コンテナを組み立てる
+        pub fn build(self) -> AppDependencies {
+            AppDependencies {
+                config: Arc::new(self.config),
+                github_api: self.github_api_override.unwrap_or_else(|| Arc::new(self.github_api)),
+                oauth_adapter: Arc::new(MockOAuthAdapter::default()),
+                user_repository: Arc::new(self.user_repository),
+                event_store: Arc::new(InMemoryEventStore::new()),
+                cache_service: Arc::new(MockCacheService::new()),
+                notification_service: Arc::new(MockNotificationService::default()),
+                rate_limiter: Arc::new(MockRateLimiter::default()),
+                storage: Arc::new(InMemoryStorageAdapter::new()),
+                clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+                activity_fetch_locks: Arc::new(ActivityFetchLocks::new()),
+            }
+        }
+    }
     
     /// テスト用の依存性コンテナを作成
+    /// 
+    /// すべてインメモリのモックで、ユーザーは登録されていない
     pub fn create_test_dependencies() -> AppDependencies {
-        // TODO: モック実装
-        unimplemented!()
+        TestDependenciesBuilder::new().build()
+    }
+    
+    mod tests {
+        use super::*;
+        
+        #[test]
+        fn test_glob_matches() {
+            assert!(glob_matches("badge:alice:*", "badge:alice:v1:flat"));
+            assert!(!glob_matches("badge:alice:*", "badge:alicia:v1"));
+            assert!(glob_matches("follow:*:bob", "follow:alice:bob"));
+            assert!(!glob_matches("follow:*:bob", "follow:alice:bobby"));
+            assert!(glob_matches("exact", "exact"));
+            assert!(!glob_matches("exact", "exactly"));
+        }
+        
+        #[tokio::test]
+        async fn test_user_lookup_ignores_case() {
+            let octocat = RegisteredUser::new(
+                crate::domain::user::GitHubUserId::new(583231),
+                Username::parse("Octocat".to_string()).unwrap(),
+            );
+            let repository = MockUserRepository::new().with_user(octocat.clone());
+            
+            for lookup in ["octocat", "OCTOCAT", "Octocat"] {
+                match repository.find_by_username(lookup).await.unwrap() {
+                    Some(UserState::Registered(user)) => {
+                        assert_eq!(user, octocat);
+                        assert_eq!(user.username.as_str(), "Octocat");
+                    }
+                    other => panic!("Expected Octocat for {}, got {:?}", lookup, other),
+                }
+            }
+            
+            // 表記の違う更新・削除も同じユーザーに対して行う
+            let mut renamed = octocat.clone();
+            renamed.update_username(Username::parse("OctoCat".to_string()).unwrap());
+            repository.update(&renamed).await.unwrap();
+            assert_eq!(repository.users.lock().unwrap().len(), 1);
+            
+            repository.delete("OCTOCAT").await.unwrap();
+            assert!(repository.find_by_username("octocat").await.unwrap().is_none());
+        }
+        
+        #[tokio::test]
+        async fn test_seeded_dependencies() {
+            let alice = RegisteredUser::new(
+                crate::domain::user::GitHubUserId::new(1),
+                Username::parse("alice".to_string()).unwrap(),
+            );
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(alice)
+                .with_github_user("carol")
+                .with_follow_relation("Bob", "alice", FollowRelation::Following)
+                .build();
+            
+            assert!(deps.user_repository.find_by_username("alice").await.unwrap().is_some());
+            assert!(deps.user_repository.find_by_username("carol").await.unwrap().is_none());
+            assert!(deps.github_api.get_user("Carol").await.is_ok());
+            assert!(deps.github_api.get_user("ghost").await.is_err());
+            assert_eq!(
+                deps.github_api.get_follow_relation("bob", "alice").await.unwrap(),
+                FollowRelation::Following
+            );
+            assert_eq!(
+                deps.github_api.get_follow_relation("alice", "bob").await.unwrap(),
+                FollowRelation::None
+            );
+        }
     }
-}
\ No newline at end of file
+}
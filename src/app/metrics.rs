@@ -0,0 +1,206 @@
+//! アプリケーションメトリクス
+//!
+//! このファイルは以下を定義：
+//! - Prometheusのメトリクスレジストリ
+//! - HTTPリクエスト・バッジキャッシュ・GitHub API呼び出しの計測
+//! - GitHub API呼び出しを計測するデコレーター
+//!
+//! メトリクスはプロセス全体で1つの `METRICS` に集約し、/metrics で公開する
+
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+
+use crate::app::dependencies::{GitHubApi, GitHubUser};
+use crate::domain::github::{FollowRelation, GitHubActivity, GitHubRateLimit};
+use crate::error::AppResult;
+
+/// プロセス全体のメトリクス
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Prometheusメトリクスの集合
+pub struct Metrics {
+    registry: Registry,
+    
+    /// HTTPリクエスト数（method, route, status）
+    http_requests_total: IntCounterVec,
+    
+    /// HTTPリクエストの処理時間（method, route）
+    http_request_duration_seconds: HistogramVec,
+    
+    /// バッジキャッシュの参照結果（result = hit / miss）
+    badge_cache_total: IntCounterVec,
+    
+    /// GitHub API呼び出し数（operation, outcome = success / error）
+    github_api_calls_total: IntCounterVec,
+}
+
+impl Metrics {
+    /// 空のレジストリにメトリクスを登録して作成
+    pub fn new() -> Self {
+        let registry = Registry::new_custom(Some("gitpoke".to_string()), None)
+            .expect("プレフィックスは有効な名前");
+        
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            &["method", "route", "status"],
+        )
+        .expect("メトリクス定義は有効");
+        
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request duration in seconds"),
+            &["method", "route"],
+        )
+        .expect("メトリクス定義は有効");
+        
+        let badge_cache_total = IntCounterVec::new(
+            Opts::new("badge_cache_total", "Badge cache lookups by result"),
+            &["result"],
+        )
+        .expect("メトリクス定義は有効");
+        
+        let github_api_calls_total = IntCounterVec::new(
+            Opts::new("github_api_calls_total", "Total number of GitHub API calls"),
+            &["operation", "outcome"],
+        )
+        .expect("メトリクス定義は有効");
+        
+        for collector in [
+            Box::new(http_requests_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration_seconds.clone()),
+            Box::new(badge_cache_total.clone()),
+            Box::new(github_api_calls_total.clone()),
+        ] {
+            registry.register(collector).expect("メトリクス名は重複しない");
+        }
+        
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            badge_cache_total,
+            github_api_calls_total,
+        }
+    }
+    
+    /// HTTPリクエストを記録
+    ///
+    /// # Arguments
+    /// * `method` - HTTPメソッド
+    /// * `route` - マッチしたルートのパターン（例: `/badge/:filename`）
+    /// * `status` - レスポンスのステータスコード
+    /// * `elapsed` - 処理時間
+    pub fn record_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        self.http_requests_total
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_request_duration_seconds
+            .with_label_values(&[method, route])
+            .observe(elapsed.as_secs_f64());
+    }
+    
+    /// バッジキャッシュの参照結果を記録
+    pub fn record_badge_cache(&self, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        self.badge_cache_total.with_label_values(&[result]).inc();
+    }
+    
+    /// GitHub API呼び出しを記録
+    pub fn record_github_api_call(&self, operation: &str, success: bool) {
+        let outcome = if success { "success" } else { "error" };
+        self.github_api_calls_total
+            .with_label_values(&[operation, outcome])
+            .inc();
+    }
+    
+    /// Prometheusのテキスト形式で出力
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Vecへの書き込みは失敗しない");
+        String::from_utf8(buffer).expect("テキスト形式はUTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// GitHub API呼び出しを計測するデコレーター
+///
+/// 実装を包んで呼び出し回数と成否を `METRICS` に記録する
+pub struct InstrumentedGitHubApi {
+    inner: Arc<dyn GitHubApi>,
+}
+
+impl InstrumentedGitHubApi {
+    pub fn new(inner: Arc<dyn GitHubApi>) -> Self {
+        Self { inner }
+    }
+}
+
+/// 呼び出し結果を記録してそのまま返す
+fn record_github_call<T>(operation: &str, result: AppResult<T>) -> AppResult<T> {
+    METRICS.record_github_api_call(operation, result.is_ok());
+    result
+}
+
+#[async_trait]
+impl GitHubApi for InstrumentedGitHubApi {
+    async fn get_user_activity(&self, username: &str) -> AppResult<GitHubActivity> {
+        record_github_call("get_user_activity", self.inner.get_user_activity(username).await)
+    }
+    
+    async fn get_follow_relation(&self, from: &str, to: &str) -> AppResult<FollowRelation> {
+        record_github_call("get_follow_relation", self.inner.get_follow_relation(from, to).await)
+    }
+    
+    async fn get_user(&self, username: &str) -> AppResult<GitHubUser> {
+        record_github_call("get_user", self.inner.get_user(username).await)
+    }
+    
+    async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit> {
+        record_github_call("get_rate_limit", self.inner.get_rate_limit().await)
+    }
+    
+    async fn get_org_members(&self, org: &str) -> AppResult<Vec<String>> {
+        record_github_call("get_org_members", self.inner.get_org_members(org).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_render_prometheus_text() {
+        let metrics = Metrics::new();
+        
+        metrics.record_http_request("GET", "/badge/:filename", 200, Duration::from_millis(5));
+        metrics.record_http_request("GET", "/badge/:filename", 200, Duration::from_millis(7));
+        metrics.record_badge_cache(true);
+        metrics.record_badge_cache(false);
+        metrics.record_badge_cache(false);
+        metrics.record_github_api_call("get_user_activity", false);
+        
+        let text = metrics.render();
+        
+        assert!(text.contains("# TYPE gitpoke_http_requests_total counter"));
+        assert!(text.contains(
+            r#"gitpoke_http_requests_total{method="GET",route="/badge/:filename",status="200"} 2"#
+        ));
+        assert!(text.contains(
+            r#"gitpoke_http_request_duration_seconds_count{method="GET",route="/badge/:filename"} 2"#
+        ));
+        assert!(text.contains(r#"gitpoke_badge_cache_total{result="hit"} 1"#));
+        assert!(text.contains(r#"gitpoke_badge_cache_total{result="miss"} 2"#));
+        assert!(text.contains(
+            r#"gitpoke_github_api_calls_total{operation="get_user_activity",outcome="error"} 1"#
+        ));
+    }
+}
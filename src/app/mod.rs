@@ -3,11 +3,15 @@
 //! このモジュールは以下を含む：
 //! - アプリケーション設定の管理
 //! - 依存性注入コンテナ
+//! - 定期実行タスク
 //! - アプリケーション全体の初期化
 
 pub mod config;
 pub mod dependencies;
+pub mod metrics;
+pub mod scheduler;
 pub mod services;
+pub mod shutdown;
 
 pub use config::Config;
 pub use dependencies::AppDependencies;
\ No newline at end of file
@@ -0,0 +1,89 @@
+//! 定期実行タスク
+//! 
+//! このファイルは以下を定義：
+//! - 日次ダイジェスト通知を毎日送信するループ
+//! 
+//! ダイジェストの取り出しはキャッシュ上で1操作で行うため、
+//! 複数のインスタンスで同時に動いても同じダイジェストを二重に送ることはない
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+use crate::app::dependencies::NotificationService;
+use crate::domain::clock::Clock;
+
+/// 日付が変わってからダイジェストを送るまでの待ち時間
+/// 
+/// 日付の変わり目に保存されたPokeがダイジェストに入りきるよう、少し遅らせる
+const DIGEST_FLUSH_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// 日次ダイジェストを毎日送信する
+/// 
+/// UTCの日付が変わった直後に、前日分のダイジェストを送る。終了しないため
+/// `tokio::spawn` で起動する
+/// 
+/// # Arguments
+/// * `notification_service` - ダイジェストを送る通知サービス
+/// * `clock` - 送信時刻の基準となる時計
+pub async fn run_daily_digest(
+    notification_service: Arc<dyn NotificationService>,
+    clock: Arc<dyn Clock>,
+) {
+    loop {
+        tokio::time::sleep(until_next_digest(clock.now())).await;
+        
+        let Some(date) = digest_date(clock.now()) else {
+            continue;
+        };
+        match notification_service.flush_daily_digest(date).await {
+            Ok(sent) => tracing::info!(%date, sent, "Pokeダイジェストを送信しました"),
+            Err(e) => tracing::error!(%date, error = %e, "Pokeダイジェストを送信できませんでした"),
+        }
+    }
+}
+
+/// 次のダイジェスト送信までの待ち時間
+/// 
+/// # Arguments
+/// * `now` - 現在時刻
+fn until_next_digest(now: DateTime<Utc>) -> Duration {
+    let next_midnight = now
+        .date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc());
+    
+    match next_midnight {
+        Some(midnight) => (midnight - now).to_std().unwrap_or_default() + DIGEST_FLUSH_DELAY,
+        None => Duration::from_secs(24 * 60 * 60),
+    }
+}
+
+/// 送信するダイジェストの日付（現在時刻の前日）
+fn digest_date(now: DateTime<Utc>) -> Option<NaiveDate> {
+    now.date_naive().pred_opt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    #[test]
+    fn test_until_next_digest_waits_for_midnight_plus_delay() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 23, 0, 0).unwrap();
+        assert_eq!(until_next_digest(now), Duration::from_secs(60 * 60) + DIGEST_FLUSH_DELAY);
+        
+        // 送信直後は翌日まで待つ
+        let now = Utc.with_ymd_and_hms(2024, 3, 16, 0, 5, 0).unwrap();
+        assert_eq!(until_next_digest(now), Duration::from_secs(24 * 60 * 60));
+    }
+    
+    #[test]
+    fn test_digest_date_is_previous_day() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 16, 0, 5, 0).unwrap();
+        assert_eq!(digest_date(now), NaiveDate::from_ymd_opt(2024, 3, 15));
+    }
+}
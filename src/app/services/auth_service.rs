@@ -1,4 +1,4 @@
-use crate::domain::user::{User, UserId};
+use crate::domain::user::{GitHubUserId, RegisteredUser};
 use std::error::Error;
 
 pub struct AuthService {
@@ -13,7 +13,7 @@ impl AuthService {
     pub async fn authenticate_github_user(
         &self,
         code: &str
-    ) -> Result<User, Box<dyn Error>> {
+    ) -> Result<RegisteredUser, Box<dyn Error>> {
         todo!()
     }
     
@@ -27,7 +27,7 @@ impl AuthService {
     pub async fn validate_token(
         &self,
         token: &str
-    ) -> Result<UserId, Box<dyn Error>> {
+    ) -> Result<GitHubUserId, Box<dyn Error>> {
         todo!()
     }
     
@@ -1,5 +1,5 @@
 use crate::domain::badge::{Badge, BadgeStyle};
-use crate::domain::user::UserId;
+use crate::domain::user::GitHubUserId;
 use crate::infra::repositories::badge::BadgeRepository;
 use std::error::Error;
 use std::sync::Arc;
@@ -15,13 +15,13 @@ impl BadgeService {
     
     pub async fn generate_badge(
         &self,
-        user_id: &UserId,
+        user_id: &GitHubUserId,
         style: BadgeStyle
     ) -> Result<Badge, Box<dyn Error>> {
         todo!()
     }
     
-    pub async fn get_poke_count(&self, user_id: &UserId) -> Result<u64, Box<dyn Error>> {
+    pub async fn get_poke_count(&self, user_id: &GitHubUserId) -> Result<u64, Box<dyn Error>> {
         todo!()
     }
 }
\ No newline at end of file
@@ -1,8 +1,16 @@
-use crate::domain::poke::{Poke, PokeId};
-use crate::domain::user::UserId;
+//! Pokeサービス
+//! 
+//! このファイルは以下を定義：
+//! - Pokeの送信・取得・集計
+//! 
+//! ドメインの `PokeEvent` を `PokeRepository` にそのまま受け渡す
+
+use crate::domain::poke::PokeEvent;
+use crate::domain::user::Username;
+use crate::error::AppResult;
 use crate::infra::repositories::poke::PokeRepository;
-use std::error::Error;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub struct PokeService {
     poke_repository: Arc<dyn PokeRepository>,
@@ -13,35 +21,47 @@ impl PokeService {
         Self { poke_repository }
     }
     
+    /// Pokeを記録
     pub async fn send_poke(
         &self,
-        sender_id: UserId,
-        recipient_id: UserId
-    ) -> Result<Poke, Box<dyn Error>> {
-        todo!()
+        sender: Username,
+        recipient: Username
+    ) -> AppResult<PokeEvent> {
+        let poke = PokeEvent::new(sender, recipient);
+        self.poke_repository.save(&poke).await?;
+        Ok(poke)
     }
     
-    pub async fn get_poke(&self, id: &PokeId) -> Result<Option<Poke>, Box<dyn Error>> {
-        todo!()
+    /// IDでPokeを取得
+    pub async fn get_poke(&self, id: &Uuid) -> AppResult<Option<PokeEvent>> {
+        self.poke_repository.find_by_id(id).await
     }
     
+    /// 送信者から受信者へのPokeを取得
     pub async fn get_pokes_between(
         &self,
-        sender_id: &UserId,
-        recipient_id: &UserId
-    ) -> Result<Vec<Poke>, Box<dyn Error>> {
-        todo!()
+        sender: &Username,
+        recipient: &Username
+    ) -> AppResult<Vec<PokeEvent>> {
+        self.poke_repository.find_by_sender_and_recipient(sender, recipient).await
     }
     
-    pub async fn count_received_pokes(&self, user_id: &UserId) -> Result<u64, Box<dyn Error>> {
-        todo!()
+    /// ユーザーが受信したPokeの件数
+    pub async fn count_received_pokes(&self, recipient: &Username) -> AppResult<u64> {
+        self.poke_repository.count_by_recipient(recipient).await
     }
     
+    /// 全ユーザーのPokeを新しい順に取得
+    /// 
+    /// # Returns
+    /// * `(pokes, total)` - 指定範囲のPokeと全件数（ページ数の計算用）
     pub async fn get_recent_pokes(
         &self,
         limit: usize,
         offset: usize
-    ) -> Result<Vec<Poke>, Box<dyn Error>> {
-        todo!()
+    ) -> AppResult<(Vec<PokeEvent>, u64)> {
+        let pokes = self.poke_repository.list_recent(limit, offset).await?;
+        let total = self.poke_repository.count_all().await?;
+        Ok((pokes, total))
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,13 @@
-use crate::domain::user::{User, UserId};
+//! ユーザーサービス
+//! 
+//! このファイルは以下を定義：
+//! - 登録済みユーザーの取得・作成・更新・削除
+//! 
+//! ドメインの `RegisteredUser` / `GitHubUserId` を `UserRepository` にそのまま受け渡す
+
+use crate::domain::user::{GitHubUserId, RegisteredUser, Username};
+use crate::error::AppResult;
 use crate::infra::repositories::user::UserRepository;
-use std::error::Error;
 use std::sync::Arc;
 
 pub struct UserService {
@@ -12,23 +19,67 @@ impl UserService {
         Self { user_repository }
     }
     
-    pub async fn get_user(&self, id: &UserId) -> Result<Option<User>, Box<dyn Error>> {
-        todo!()
+    /// GitHub IDでユーザーを取得
+    /// 
+    /// # Returns
+    /// * `Ok(None)` - 未登録
+    pub async fn get_user(&self, id: &GitHubUserId) -> AppResult<Option<RegisteredUser>> {
+        self.user_repository.find_by_github_id(id).await
+    }
+    
+    /// ユーザー名でユーザーを取得
+    /// 
+    /// # Returns
+    /// * `Ok(None)` - 未登録
+    pub async fn get_user_by_username(&self, username: &Username) -> AppResult<Option<RegisteredUser>> {
+        self.user_repository.find_by_username(username).await
     }
     
-    pub async fn get_user_by_github_id(&self, github_id: u64) -> Result<Option<User>, Box<dyn Error>> {
-        todo!()
+    /// ユーザーを作成
+    pub async fn create_user(&self, user: RegisteredUser) -> AppResult<RegisteredUser> {
+        self.user_repository.save(&user).await?;
+        Ok(user)
     }
     
-    pub async fn create_user(&self, user: User) -> Result<User, Box<dyn Error>> {
-        todo!()
+    /// ユーザーを更新
+    pub async fn update_user(&self, user: RegisteredUser) -> AppResult<RegisteredUser> {
+        self.user_repository.update(&user).await?;
+        Ok(user)
     }
     
-    pub async fn update_user(&self, user: User) -> Result<User, Box<dyn Error>> {
-        todo!()
+    /// ユーザーを削除
+    pub async fn delete_user(&self, id: &GitHubUserId) -> AppResult<()> {
+        self.user_repository.delete(id).await
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user::PokeSetting;
+    use crate::infra::repositories::user::InMemoryUserRepository;
     
-    pub async fn delete_user(&self, id: &UserId) -> Result<(), Box<dyn Error>> {
-        todo!()
+    #[tokio::test]
+    async fn test_domain_user_round_trip() {
+        let service = UserService::new(Arc::new(InMemoryUserRepository::new()));
+        let id = GitHubUserId::new(583231);
+        let mut user = RegisteredUser::new(id, Username::parse("octocat".to_string()).unwrap());
+        user.block_sender(Username::parse("spammer".to_string()).unwrap());
+        
+        let created = service.create_user(user.clone()).await.unwrap();
+        assert_eq!(created, user);
+        assert_eq!(service.get_user(&id).await.unwrap(), Some(user.clone()));
+        
+        user.update_poke_setting(PokeSetting::FollowersOnly);
+        service.update_user(user.clone()).await.unwrap();
+        let by_name = service
+            .get_user_by_username(&Username::parse("OctoCat".to_string()).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_name.poke_setting, PokeSetting::FollowersOnly);
+        
+        service.delete_user(&id).await.unwrap();
+        assert_eq!(service.get_user(&id).await.unwrap(), None);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,213 @@
+//! グレースフルシャットダウン
+//! 
+//! このファイルは以下を定義：
+//! - SIGTERM/SIGINTを待つシャットダウンシグナル
+//! - シャットダウン時に待機するバックグラウンドタスクの管理
+//! 
+//! Cloud Runはインスタンス停止時にSIGTERMを送り、猶予期間後に強制終了する。
+//! 猶予期間内に処理中のリクエストとバックグラウンドタスクを終わらせる
+
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use tokio::task::JoinSet;
+
+/// 受信したシャットダウンシグナル
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// SIGINT（Ctrl+C）
+    Interrupt,
+    
+    /// SIGTERM（Cloud Runのインスタンス停止）
+    Terminate,
+}
+
+/// シャットダウンシグナルを待つ
+/// 
+/// `axum::serve(...).with_graceful_shutdown` に渡すと、
+/// シグナル受信後は新しい接続を受け付けず、処理中のリクエストの完了を待つ
+pub async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("SIGINTハンドラーを設定できませんでした: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("SIGTERMハンドラーを設定できませんでした: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    
+    let signal = wait_for_signal(interrupt, terminate).await;
+    tracing::info!(
+        "シャットダウンシグナルを受信しました（{:?}）。処理中のリクエストの完了を待ちます",
+        signal
+    );
+}
+
+/// いずれかのシグナルを待つ
+/// 
+/// # Arguments
+/// * `interrupt` - SIGINTで完了するFuture
+/// * `terminate` - SIGTERMで完了するFuture
+async fn wait_for_signal(
+    interrupt: impl Future<Output = ()>,
+    terminate: impl Future<Output = ()>,
+) -> ShutdownSignal {
+    tokio::select! {
+        _ = interrupt => ShutdownSignal::Interrupt,
+        _ = terminate => ShutdownSignal::Terminate,
+    }
+}
+
+/// 実行中のバックグラウンドタスク
+static BACKGROUND_TASKS: LazyLock<Mutex<JoinSet<()>>> =
+    LazyLock::new(|| Mutex::new(JoinSet::new()));
+
+/// シャットダウン時に完了を待つバックグラウンドタスクを起動
+/// 
+/// レスポンスとは別に行う処理（キャッシュの更新や保存など）に使う
+/// 
+/// # Arguments
+/// * `task` - 実行するタスク
+pub fn spawn_background<F>(task: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let mut tasks = BACKGROUND_TASKS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    
+    // 完了済みのタスクを取り除いてから追加する
+    while tasks.try_join_next().is_some() {}
+    tasks.spawn(task);
+}
+
+/// バックグラウンドタスクの完了を待つ
+/// 
+/// タイムアウトまでに終わらなかったタスクは中断する
+/// 
+/// # Arguments
+/// * `timeout` - 待機する最大時間
+/// 
+/// # Returns
+/// * 中断したタスクの数
+pub async fn drain_background_tasks(timeout: Duration) -> usize {
+    let mut tasks = std::mem::take(
+        &mut *BACKGROUND_TASKS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    );
+    
+    let drained = tokio::time::timeout(timeout, async {
+        while tasks.join_next().await.is_some() {}
+    })
+    .await;
+    
+    if drained.is_ok() {
+        return 0;
+    }
+    
+    let remaining = tasks.len();
+    tracing::warn!("{}件のバックグラウンドタスクが完了しなかったため中断します", remaining);
+    tasks.shutdown().await;
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use axum::{routing::get, Router};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::{oneshot, Notify};
+    
+    #[tokio::test]
+    async fn test_either_signal_triggers_shutdown() {
+        let signal = wait_for_signal(std::future::pending(), async {}).await;
+        assert_eq!(signal, ShutdownSignal::Terminate);
+        
+        let signal = wait_for_signal(async {}, std::future::pending()).await;
+        assert_eq!(signal, ShutdownSignal::Interrupt);
+    }
+    
+    #[tokio::test]
+    async fn test_in_flight_request_is_drained() {
+        let started = Arc::new(Notify::new());
+        let handler_started = started.clone();
+        let app = Router::new().route("/slow", get(move || {
+            let started = handler_started.clone();
+            async move {
+                started.notify_one();
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                "done"
+            }
+        }));
+        
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        
+        // シグナルの代わりにチャネルでシャットダウンを発火させる
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    signal_rx.await.ok();
+                })
+                .await
+        });
+        
+        let in_flight = tokio::spawn(async move {
+            reqwest::get(format!("http://{}/slow", addr)).await?.text().await
+        });
+        started.notified().await;
+        signal_tx.send(()).unwrap();
+        
+        // 新しい接続は受け付けない
+        let refused = tokio::time::timeout(Duration::from_secs(1), async {
+            while TcpStream::connect(addr).await.is_ok() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await;
+        assert!(refused.is_ok(), "シャットダウン後も接続を受け付けています");
+        
+        // 処理中のリクエストは最後まで返される
+        assert_eq!(in_flight.await.unwrap().unwrap(), "done");
+        
+        tokio::time::timeout(Duration::from_secs(1), server)
+            .await
+            .expect("サーバーが停止しません")
+            .unwrap()
+            .unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_background_tasks_are_drained() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let task_finished = finished.clone();
+        spawn_background(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            task_finished.store(true, Ordering::SeqCst);
+        });
+        
+        let aborted = drain_background_tasks(Duration::from_secs(1)).await;
+        
+        assert_eq!(aborted, 0);
+        assert!(finished.load(Ordering::SeqCst));
+    }
+}
@@ -2,9 +2,14 @@
 //! 
 //! このファイルは以下を定義：
 //! - バッジの状態表現
+//! - 組織メンバーのアクティビティ集計
 //! - バッジSVG生成ロジック
+//! - コントリビューション推移（スパークライン）の描画
 //! - バッジに関するビジネスルール
 
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
 use crate::domain::github::GitHubActivity;
 use crate::domain::user::UserState;
@@ -14,7 +19,7 @@ use crate::domain::user::UserState;
 /// ユーザーのアクティビティ状態とインタラクション可能性を表現
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BadgeState {
-    /// アクティブ状態（7日以内に活動あり）
+    /// アクティブ状態（閾値以内に活動あり、デフォルト7日）
     Active {
         /// 最後の活動からの経過日数
         days_since_last_activity: i64,
@@ -22,7 +27,15 @@ pub enum BadgeState {
         streak_days: Option<i64>,
     },
     
-    /// 非アクティブ状態（7日以上活動なし）
+    /// 警告状態（アクティブの閾値を超えたが、警告期間内）
+    Warning {
+        /// 最後の活動からの経過日数
+        days_since_last_activity: i64,
+        /// Pokeが可能かどうか
+        pokeable: bool,
+    },
+    
+    /// 非アクティブ状態（警告期間を超えて活動なし）
     Inactive {
         /// 最後の活動からの経過日数
         days_since_last_activity: i64,
@@ -40,22 +53,38 @@ impl BadgeState {
     /// # Arguments
     /// * `activity` - GitHubのアクティビティ情報
     /// * `user_state` - ユーザーの登録状態
+    /// * `active_threshold_days` - アクティブとみなす最終活動からの日数
+    /// * `warning_threshold_days` - 警告状態とみなす最終活動からの日数
+    ///   （`active_threshold_days` 以下の場合は警告状態にならない）
     /// 
     /// # Returns
     /// * `BadgeState` - 判定されたバッジ状態
-    pub fn from_activity(activity: &GitHubActivity, user_state: &UserState) -> Self {
+    pub fn from_activity(
+        activity: &GitHubActivity,
+        user_state: &UserState,
+        active_threshold_days: i64,
+        warning_threshold_days: i64,
+    ) -> Self {
         let days = activity.days_since_last_activity();
         
-        if days <= 7 {
+        if days <= active_threshold_days {
             // アクティブ状態
-            BadgeState::Active {
+            return BadgeState::Active {
                 days_since_last_activity: days,
                 streak_days: activity.current_streak_days(),
+            };
+        }
+        
+        // 登録済みユーザーのみPoke可能
+        let pokeable = user_state.is_registered();
+        if days <= warning_threshold_days {
+            // 警告状態
+            BadgeState::Warning {
+                days_since_last_activity: days,
+                pokeable,
             }
         } else {
             // 非アクティブ状態
-            // 登録済みユーザーのみPoke可能
-            let pokeable = user_state.is_registered();
             BadgeState::Inactive {
                 days_since_last_activity: days,
                 pokeable,
@@ -63,35 +92,372 @@ impl BadgeState {
         }
     }
     
+    /// Pokeが可能な状態かどうか（警告・非アクティブかつ登録済み）
+    pub fn is_pokeable(&self) -> bool {
+        matches!(
+            self,
+            BadgeState::Warning { pokeable: true, .. } | BadgeState::Inactive { pokeable: true, .. }
+        )
+    }
+    
     /// バッジの色を取得
     /// 
     /// # Returns
     /// * 緑（#44cc11） - アクティブ
+    /// * 黄（#dfb317） - 警告
     /// * 赤（#e05d44） - 非アクティブ
     pub fn color(&self) -> &'static str {
         match self {
             BadgeState::Active { .. } => "#44cc11", // 緑
+            BadgeState::Warning { .. } => "#dfb317", // 黄
             BadgeState::Inactive { .. } => "#e05d44", // 赤
             BadgeState::NotFound => "#9f9f9f", // グレー
         }
     }
     
+    /// 連続活動日数の節目に応じたステータス部分の色
+    /// 
+    /// アクティブ状態で連続活動日数が節目に達している場合のみ、緑の代わりに使う
+    /// 
+    /// # Returns
+    /// * `Some("#daa520")` - 金（`GOLD_STREAK_DAYS` 日以上）
+    /// * `Some("#8a2be2")` - 紫（`PURPLE_STREAK_DAYS` 日以上）
+    /// * `None` - 節目に達していない、またはアクティブ状態でない
+    pub fn accent_color(&self) -> Option<&'static str> {
+        match self {
+            BadgeState::Active { streak_days: Some(days), .. } if *days >= GOLD_STREAK_DAYS => {
+                Some("#daa520") // 金
+            }
+            BadgeState::Active { streak_days: Some(days), .. } if *days >= PURPLE_STREAK_DAYS => {
+                Some("#8a2be2") // 紫
+            }
+            _ => None,
+        }
+    }
+    
+    /// 状態に応じたキャッシュTTL（秒）
+    /// 
+    /// アクティブは変化しやすいため短く、見つからない場合は長くする
+    pub fn cache_ttl(&self) -> u64 {
+        match self {
+            BadgeState::Active { .. } => 300, // 5分
+            BadgeState::Warning { .. } | BadgeState::Inactive { .. } => 3600, // 1時間
+            BadgeState::NotFound => 86400, // 24時間
+        }
+    }
+    
     /// バッジのテキストを取得
     pub fn text(&self) -> String {
         match self {
-            BadgeState::Active { days_since_last_activity, .. } => {
-                if *days_since_last_activity == 0 {
+            BadgeState::Active { days_since_last_activity, streak_days } => {
+                let text = if *days_since_last_activity == 0 {
                     "Active today".to_string()
                 } else {
                     format!("Active {} days ago", days_since_last_activity)
+                };
+                
+                // 2日以上の連続活動がある場合のみstreakを表示
+                match streak_days {
+                    Some(days) if *days > 1 => format!("{} · {} day streak", text, days),
+                    _ => text,
                 }
             }
+            BadgeState::Warning { days_since_last_activity, .. } => {
+                format!("Idle for {} days", days_since_last_activity)
+            }
             BadgeState::Inactive { days_since_last_activity, .. } => {
                 format!("Inactive for {} days", days_since_last_activity)
             }
             BadgeState::NotFound => "User not found".to_string(),
         }
     }
+    
+    /// 総コントリビューション数付きのテキストを取得
+    /// 
+    /// アクティブ状態かつ総数がある場合のみ " · 1,234 contributions" を付加する
+    /// 
+    /// # Arguments
+    /// * `total_contributions` - 総コントリビューション数（表示しない場合はNone）
+    pub fn text_with_contributions(&self, total_contributions: Option<i32>) -> String {
+        let text = self.text();
+        match (self, total_contributions) {
+            (BadgeState::Active { .. }, Some(total)) => {
+                format!("{} · {} contributions", text, format_thousands(total))
+            }
+            _ => text,
+        }
+    }
+}
+
+/// 最終活動日の説明文
+/// 
+/// # Returns
+/// * `"Last active: 2024-05-01"` - 活動あり
+/// * `"No recent activity"` - 活動なし
+fn last_active_text(last_active_date: Option<NaiveDate>) -> String {
+    match last_active_date {
+        Some(date) => format!("Last active: {}", date.format("%Y-%m-%d")),
+        None => "No recent activity".to_string(),
+    }
+}
+
+/// 数値を3桁区切りでフォーマット（例: 1234 → "1,234"）
+fn format_thousands(n: i32) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(c);
+    }
+    if n < 0 {
+        formatted.insert(0, '-');
+    }
+    formatted
+}
+
+/// バッジのスタイル
+/// 
+/// shields.ioのスタイル名に対応
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BadgeStyle {
+    /// フラット（角丸、デフォルト）
+    #[default]
+    Flat,
+    
+    /// フラットスクエア（角丸なし）
+    FlatSquare,
+    
+    /// プラスチック（グラデーションと影付き）
+    Plastic,
+    
+    /// for-the-badge（大文字・太字・高さ28px）
+    ForTheBadge,
+}
+
+impl BadgeStyle {
+    /// 文字列からスタイルを解析
+    /// 
+    /// 未知の文字列の場合はFlatにフォールバック
+    pub fn parse_or_default(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "flat-square" | "flat_square" => BadgeStyle::FlatSquare,
+            "plastic" => BadgeStyle::Plastic,
+            "for-the-badge" | "for_the_badge" => BadgeStyle::ForTheBadge,
+            _ => BadgeStyle::Flat,
+        }
+    }
+    
+    /// スタイル名を取得（キャッシュキー等で使用）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BadgeStyle::Flat => "flat",
+            BadgeStyle::FlatSquare => "flat-square",
+            BadgeStyle::Plastic => "plastic",
+            BadgeStyle::ForTheBadge => "for-the-badge",
+        }
+    }
+    
+    /// 角丸の半径（角丸なしの場合はNone）
+    fn corner_radius(&self) -> Option<u32> {
+        match self {
+            BadgeStyle::Flat => Some(3),
+            BadgeStyle::FlatSquare | BadgeStyle::ForTheBadge => None,
+            BadgeStyle::Plastic => Some(4),
+        }
+    }
+    
+    /// バッジの高さ（px）
+    fn height(&self) -> u32 {
+        match self {
+            BadgeStyle::ForTheBadge => 28,
+            _ => 20,
+        }
+    }
+    
+    /// テキストのベースラインのY座標
+    fn text_y(&self) -> u32 {
+        match self {
+            BadgeStyle::ForTheBadge => 18,
+            _ => 14,
+        }
+    }
+    
+    /// フォントサイズ（px）
+    fn font_size(&self) -> f64 {
+        match self {
+            BadgeStyle::ForTheBadge => 10.0,
+            _ => BADGE_FONT_SIZE,
+        }
+    }
+    
+    /// 文字間隔（px）
+    fn letter_spacing(&self) -> f64 {
+        match self {
+            BadgeStyle::ForTheBadge => 1.0,
+            _ => 0.0,
+        }
+    }
+    
+    /// テキスト左右のパディング（px）
+    fn padding(&self) -> u32 {
+        match self {
+            BadgeStyle::ForTheBadge => 14,
+            _ => BADGE_PADDING,
+        }
+    }
+    
+    /// 表示用にテキストを整形
+    /// 
+    /// for-the-badgeスタイルでは大文字に変換する
+    fn format_text(&self, text: &str) -> String {
+        match self {
+            BadgeStyle::ForTheBadge => text.to_uppercase(),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// バッジのテーマ
+/// 
+/// 埋め込み先の背景色に合わせてラベル部分の色を切り替える
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BadgeTheme {
+    /// ライト（デフォルト）
+    #[default]
+    Light,
+    
+    /// ダーク（暗い背景のREADME向け）
+    Dark,
+}
+
+impl BadgeTheme {
+    /// 文字列からテーマを解析
+    /// 
+    /// 未知の文字列の場合はLightにフォールバック
+    pub fn parse_or_default(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "dark" => BadgeTheme::Dark,
+            _ => BadgeTheme::Light,
+        }
+    }
+    
+    /// テーマ名を取得（キャッシュキー等で使用）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BadgeTheme::Light => "light",
+            BadgeTheme::Dark => "dark",
+        }
+    }
+    
+    /// ラベル部分の背景色
+    /// 
+    /// ステータス部分の色はテーマに関わらずBadgeStateで決まる
+    /// ライトテーマで既に `#555` を使っているため、ダークテーマは区別できるよう `#333` にする
+    fn label_color(&self) -> &'static str {
+        match self {
+            BadgeTheme::Light => "#555",
+            BadgeTheme::Dark => "#333",
+        }
+    }
+}
+
+/// ユーザーが見つからない場合のバッジの表示方法
+/// 
+/// 存在しないユーザー名が埋め込まれていても目立たせたくない埋め込み先向け
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum NotFoundDisplay {
+    /// 既定のメッセージ（"User not found"）を表示（デフォルト）
+    #[default]
+    Message,
+    
+    /// 指定したメッセージを表示
+    CustomMessage(String),
+    
+    /// 1x1の透明なSVGを返し、何も表示しない
+    Hidden,
+}
+
+impl NotFoundDisplay {
+    /// クエリパラメータから表示方法を解析
+    /// 
+    /// `hide` 以外（未指定・未知の値を含む）はメッセージを表示する
+    /// 
+    /// # Arguments
+    /// * `mode` - `notfound` の値（`hide` または `message`）
+    /// * `text` - `notfound_text` の値（メッセージ表示時のみ使用）
+    pub fn from_query(mode: Option<&str>, text: Option<&str>) -> Self {
+        match (mode.map(str::to_lowercase).as_deref(), text) {
+            (Some("hide"), _) => NotFoundDisplay::Hidden,
+            (_, Some(text)) => NotFoundDisplay::CustomMessage(text.to_string()),
+            (_, None) => NotFoundDisplay::Message,
+        }
+    }
+}
+
+/// 組織メンバーのアクティビティ集計
+/// 
+/// 組織バッジに「5/12 active」のように表示する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgActivitySummary {
+    /// 閾値以内に活動したメンバー数
+    pub active_members: u32,
+    
+    /// 集計したメンバー数
+    pub total_members: u32,
+}
+
+impl OrgActivitySummary {
+    /// メンバーのアクティビティから集計
+    /// 
+    /// アクティブかどうかは個人バッジと同じ閾値で判定する
+    /// 
+    /// # Arguments
+    /// * `activities` - メンバーごとのアクティビティ
+    /// * `active_threshold_days` - アクティブとみなす最終活動からの日数
+    pub fn from_activities(activities: &[GitHubActivity], active_threshold_days: i64) -> Self {
+        let active_members = activities
+            .iter()
+            .filter(|activity| activity.days_since_last_activity() <= active_threshold_days)
+            .count();
+        
+        Self {
+            active_members: active_members as u32,
+            total_members: activities.len() as u32,
+        }
+    }
+    
+    /// バッジの色を取得
+    /// 
+    /// # Returns
+    /// * 緑（#44cc11） - 半数以上がアクティブ
+    /// * 黄（#dfb317） - アクティブなメンバーが半数未満
+    /// * 赤（#e05d44） - アクティブなメンバーがいない
+    /// * グレー（#9f9f9f） - 公開メンバーがいない
+    pub fn color(&self) -> &'static str {
+        if self.total_members == 0 {
+            "#9f9f9f"
+        } else if self.active_members * 2 >= self.total_members {
+            "#44cc11"
+        } else if self.active_members > 0 {
+            "#dfb317"
+        } else {
+            "#e05d44"
+        }
+    }
+    
+    /// バッジのテキストを取得
+    pub fn text(&self) -> String {
+        format!("{}/{} active", self.active_members, self.total_members)
+    }
+    
+    /// 集計のキャッシュTTL（秒）
+    /// 
+    /// メンバー全員のアクティビティを取得し直すのは重いため、個人バッジより長くする
+    pub fn cache_ttl(&self) -> u64 {
+        3600 // 1時間
+    }
 }
 
 /// バッジSVG
@@ -117,35 +483,91 @@ impl BadgeSvg {
     /// # Arguments
     /// * `state` - バッジの状態
     /// * `username` - ユーザー名
-    pub fn static_badge(state: &BadgeState, username: &str) -> Self {
-        let color = state.color();
-        let text = state.text();
-        
-        // SVGテンプレート
-        // TODO: 実際のSVG生成ロジックを実装
-        // - テキスト幅に基づいてSVG全体の幅を動的に計算（font-family: Arial, font-size: 12px）
-        // - 左右のパディング（各10px）を考慮した配置
-        // - shields.io風のグラデーションとシャドウ効果を追加
-        let content = format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="20">
-                <rect width="200" height="20" fill="{}"/>
-                <text x="10" y="14" fill="white" font-family="Arial" font-size="12">
-                    GitPoke: {}
-                </text>
-            </svg>"#,
-            color, text
-        );
+    /// * `label` - 左側に表示するラベル
+    /// * `style` - バッジのスタイル
+    /// * `theme` - バッジのテーマ
+    /// * `total_contributions` - 表示する総コントリビューション数（表示しない場合はNone）
+    /// * `last_active_date` - 最後に活動した日付（活動がない場合はNone）
+    pub fn static_badge(
+        state: &BadgeState,
+        username: &str,
+        label: &str,
+        style: BadgeStyle,
+        theme: BadgeTheme,
+        total_contributions: Option<i32>,
+        last_active_date: Option<NaiveDate>,
+    ) -> Self {
+        let color = state.accent_color().unwrap_or(state.color());
+        let status = state.text_with_contributions(total_contributions);
         
-        // キャッシュTTLの決定
-        let cache_ttl = match state {
-            BadgeState::Active { .. } => 300, // 5分
-            BadgeState::Inactive { .. } => 3600, // 1時間
-            BadgeState::NotFound => 86400, // 24時間
+        // スクリーンリーダー・ツールチップ向けの説明文
+        // （例: "GitPoke status for octocat: Active today (Last active: 2024-05-01)"）
+        let title = match state {
+            BadgeState::NotFound => format!("{} status for {}: {}", label, username, status),
+            BadgeState::Active { .. } | BadgeState::Warning { .. } | BadgeState::Inactive { .. } => format!(
+                "{} status for {}: {} ({})",
+                label,
+                username,
+                status,
+                last_active_text(last_active_date)
+            ),
+        };
+        
+        let label = style.format_text(label);
+        let text = style.format_text(&status);
+        let layout = BadgeLayout::new(&label, &text, style);
+        
+        // SVGを生成（shields.io風のレイアウト）
+        let content = render_svg(&layout, &label, &text, &title, color, style, theme);
+        
+        Self {
+            content,
+            cache_ttl: state.cache_ttl(),
+            is_interactive: false,
+        }
+    }
+    
+    /// ユーザーが見つからない場合のバッジを生成
+    /// 
+    /// # Arguments
+    /// * `display` - 表示方法
+    /// * `username` - ユーザー名
+    /// * `label` - 左側に表示するラベル
+    /// * `style` - バッジのスタイル
+    /// * `theme` - バッジのテーマ
+    /// 
+    /// # Returns
+    /// * 表示方法に応じたバッジ（`Hidden` の場合は1x1の透明なSVG）
+    pub fn not_found_badge(
+        display: &NotFoundDisplay,
+        username: &str,
+        label: &str,
+        style: BadgeStyle,
+        theme: BadgeTheme,
+    ) -> Self {
+        let state = BadgeState::NotFound;
+        let status = match display {
+            NotFoundDisplay::Message => state.text(),
+            NotFoundDisplay::CustomMessage(text) => text.clone(),
+            NotFoundDisplay::Hidden => {
+                return Self {
+                    content: HIDDEN_BADGE_SVG.to_string(),
+                    cache_ttl: state.cache_ttl(),
+                    is_interactive: false,
+                };
+            }
         };
         
+        let title = format!("{} status for {}: {}", label, username, status);
+        
+        let label = style.format_text(label);
+        let text = style.format_text(&status);
+        let layout = BadgeLayout::new(&label, &text, style);
+        let content = render_svg(&layout, &label, &text, &title, state.color(), style, theme);
+        
         Self {
             content,
-            cache_ttl,
+            cache_ttl: state.cache_ttl(),
             is_interactive: false,
         }
     }
@@ -157,23 +579,118 @@ impl BadgeSvg {
     /// # Arguments
     /// * `state` - バッジの状態
     /// * `username` - ユーザー名
-    pub fn interactive_badge(state: &BadgeState, username: &str) -> Self {
+    /// * `label` - 左側に表示するラベル
+    /// * `style` - バッジのスタイル
+    /// * `theme` - バッジのテーマ
+    /// * `total_contributions` - 表示する総コントリビューション数（表示しない場合はNone）
+    /// * `last_active_date` - 最後に活動した日付（活動がない場合はNone）
+    pub fn interactive_badge(
+        state: &BadgeState,
+        username: &str,
+        label: &str,
+        style: BadgeStyle,
+        theme: BadgeTheme,
+        total_contributions: Option<i32>,
+        last_active_date: Option<NaiveDate>,
+    ) -> Self {
         // 基本的な静的バッジを生成
-        let mut badge = Self::static_badge(state, username);
+        let mut badge = Self::static_badge(
+            state,
+            username,
+            label,
+            style,
+            theme,
+            total_contributions,
+            last_active_date,
+        );
         
         // インタラクティブ要素を追加
-        if let BadgeState::Inactive { pokeable: true, .. } = state {
-            // TODO: クリック可能な要素とJavaScriptを追加
-            // - SVG全体にonclickハンドラーを設定（<svg onclick="...">）
-            // - fetch APIを使用してPOST /api/pokeを実行（CORS対応）
-            // - クリック時の視覚的フィードバック（一時的に色を変更）
-            // - エラー時はconsole.errorに出力（セキュリティ上アラートは避ける）
+        if state.is_pokeable() {
+            badge.content = embed_poke_script(&badge.content, username);
             badge.is_interactive = true;
         }
         
         badge
     }
     
+    /// 直近のコントリビューション推移（スパークライン）を右端に追加
+    /// 
+    /// `today` までの `SPARKLINE_DAYS` 日分を1日1本の棒で描き、
+    /// 棒の高さは期間内の最大値を基準に比例させる。
+    /// バッジの幅は棒の分だけ広げ、既存の要素はそのまま残す
+    /// 
+    /// # Arguments
+    /// * `contributions` - 日別のコントリビューション数（キーは `YYYY-MM-DD`）
+    /// * `today` - 期間の最終日
+    /// 
+    /// # Returns
+    /// * スパークラインを追加したバッジ（`contributions` が `None` の場合はそのまま）
+    pub fn with_sparkline(
+        mut self,
+        contributions: Option<&HashMap<String, i32>>,
+        today: NaiveDate,
+    ) -> Self {
+        let Some(contributions) = contributions else {
+            return self;
+        };
+        let (Some(width), Some(height)) = (
+            svg_dimension(&self.content, "width"),
+            svg_dimension(&self.content, "height"),
+        ) else {
+            return self;
+        };
+        
+        let counts = sparkline_counts(contributions, today);
+        let sparkline = render_sparkline(&counts, width, height);
+        
+        // ルート要素の幅を広げ、閉じタグの直前に棒を追加する
+        let content = self.content.replacen(
+            &format!(r#" width="{}""#, width),
+            &format!(r#" width="{}""#, width + SPARKLINE_WIDTH),
+            1,
+        );
+        self.content = match content.rfind("</svg>") {
+            Some(end) => format!("{}{}{}", &content[..end], sparkline, &content[end..]),
+            None => content,
+        };
+        
+        self
+    }
+    
+    /// 組織バッジを生成
+    /// 
+    /// 組織のメンバーのうちアクティブな人数を表示する（クリック不可）
+    /// 
+    /// # Arguments
+    /// * `summary` - メンバーのアクティビティ集計
+    /// * `org` - 組織名
+    /// * `label` - 左側に表示するラベル
+    /// * `style` - バッジのスタイル
+    /// * `theme` - バッジのテーマ
+    pub fn org_badge(
+        summary: &OrgActivitySummary,
+        org: &str,
+        label: &str,
+        style: BadgeStyle,
+        theme: BadgeTheme,
+    ) -> Self {
+        let status = summary.text();
+        
+        // スクリーンリーダー向けの説明文（例: "GitPoke activity for octo-org: 5/12 active"）
+        let title = format!("{} activity for {}: {}", label, org, status);
+        
+        let label = style.format_text(label);
+        let text = style.format_text(&status);
+        let layout = BadgeLayout::new(&label, &text, style);
+        let content = render_svg(&layout, &label, &text, &title, summary.color(), style, theme);
+        
+        Self {
+            content,
+            cache_ttl: summary.cache_ttl(),
+            is_interactive: false,
+        }
+    }
+    
     /// Content-Typeヘッダーを取得
     pub fn content_type(&self) -> &'static str {
         "image/svg+xml"
@@ -188,73 +705,503 @@ impl BadgeSvg {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::user::{Username, RegisteredUser, GitHubUserId};
-    use chrono::{DateTime, Utc, Duration};
+/// shields.ioのendpointバッジ用JSON
+/// 
+/// https://shields.io/badges/endpoint-badge のスキーマに対応
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShieldsEndpoint {
+    /// スキーマバージョン（常に1）
+    pub schema_version: u8,
     
-    mod badge_state_tests {
-        use super::*;
-        
-        fn create_activity(days_since_last: i64, streak_days: Option<i64>) -> GitHubActivity {
-            let last_activity = if days_since_last == 0 {
-                Some(Utc::now())
-            } else {
-                Some(Utc::now() - Duration::days(days_since_last))
-            };
-            
-            GitHubActivity {
-                username: "testuser".to_string(),
-                last_activity_at: last_activity,
-                current_streak_days: streak_days,
-            }
-        }
-        
-        #[test]
-        fn test_active_state_today() {
-            let activity = create_activity(0, Some(10));
-            let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
-            
-            let badge_state = BadgeState::from_activity(&activity, &user_state);
-            
-            match badge_state {
-                BadgeState::Active { days_since_last_activity, streak_days } => {
-                    assert_eq!(days_since_last_activity, 0);
-                    assert_eq!(streak_days, Some(10));
-                }
-                _ => panic!("Expected Active state"),
-            }
+    /// 左側のラベル
+    pub label: String,
+    
+    /// 右側のメッセージ
+    pub message: String,
+    
+    /// 右側の色（shields.ioの色名、対応がなければHEX）
+    pub color: String,
+}
+
+impl ShieldsEndpoint {
+    /// BadgeStateからendpoint JSONを生成
+    /// 
+    /// メッセージと色はSVGバッジと同じ判定結果を使う
+    /// 
+    /// # Arguments
+    /// * `state` - バッジの状態
+    pub fn from_state(state: &BadgeState) -> Self {
+        let hex = state.color();
+        Self {
+            schema_version: 1,
+            label: SHIELDS_ENDPOINT_LABEL.to_string(),
+            message: state.text(),
+            color: shields_color_name(hex).unwrap_or(hex).to_string(),
         }
-        
-        #[test]
-        fn test_active_state_within_week() {
-            let activity = create_activity(5, None);
-            let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
-            
-            let badge_state = BadgeState::from_activity(&activity, &user_state);
-            
-            match badge_state {
-                BadgeState::Active { days_since_last_activity, streak_days } => {
-                    assert_eq!(days_since_last_activity, 5);
-                    assert_eq!(streak_days, None);
-                }
-                _ => panic!("Expected Active state"),
+    }
+}
+
+/// endpoint JSONのラベル
+const SHIELDS_ENDPOINT_LABEL: &str = "gitpoke";
+
+/// HEXカラーをshields.ioの色名に変換
+/// 
+/// # Returns
+/// * `Some(name)` - 対応する色名がある
+/// * `None` - 対応なし（HEXをそのまま使う）
+fn shields_color_name(hex: &str) -> Option<&'static str> {
+    match hex {
+        "#4c1" | "#44cc11" => Some("brightgreen"),
+        "#97ca00" => Some("green"),
+        "#dfb317" => Some("yellow"),
+        "#a4a61d" => Some("yellowgreen"),
+        "#fe7d37" => Some("orange"),
+        "#e05d44" => Some("red"),
+        "#007ec6" => Some("blue"),
+        "#555" => Some("grey"),
+        "#9f9f9f" => Some("lightgrey"),
+        _ => None,
+    }
+}
+
+/// アクティブとみなす最終活動からの日数（デフォルト）
+pub const DEFAULT_ACTIVE_THRESHOLD_DAYS: i64 = 7;
+
+/// 警告状態とみなす最終活動からの日数（デフォルト）
+/// 
+/// アクティブの閾値を超えてからこの日数までを黄色で表示する
+pub const DEFAULT_WARNING_THRESHOLD_DAYS: i64 = 21;
+
+/// バッジを紫にする連続活動日数
+pub const PURPLE_STREAK_DAYS: i64 = 30;
+
+/// バッジを金にする連続活動日数
+pub const GOLD_STREAK_DAYS: i64 = 100;
+
+/// バッジ左側に表示するデフォルトのラベル
+pub const DEFAULT_BADGE_LABEL: &str = "GitPoke";
+
+/// 見つからないユーザーを非表示にする場合のSVG（1x1の透明な画像）
+const HIDDEN_BADGE_SVG: &str =
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1" role="presentation"/>"#;
+
+/// テキスト左右のパディング（px）
+const BADGE_PADDING: u32 = 10;
+
+/// バッジのフォントサイズ（px）
+const BADGE_FONT_SIZE: f64 = 12.0;
+
+/// バッジのレイアウト
+/// 
+/// ラベル部分とステータス部分の幅をそれぞれ独立に計算する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BadgeLayout {
+    /// ラベル部分の幅（px）
+    label_width: u32,
+    
+    /// ステータス部分の幅（px）
+    status_width: u32,
+}
+
+impl BadgeLayout {
+    /// ラベルとステータスのテキストからレイアウトを計算
+    /// 
+    /// 各セクションの幅 = テキスト幅 + 左右のパディング
+    /// 
+    /// フォントサイズ・文字間隔・パディングはスタイルに従う
+    fn new(label: &str, status: &str, style: BadgeStyle) -> Self {
+        Self {
+            label_width: calculate_section_width(label, style),
+            status_width: calculate_section_width(status, style),
+        }
+    }
+    
+    /// SVG全体の幅
+    fn total_width(&self) -> u32 {
+        self.label_width + self.status_width
+    }
+    
+    /// ラベルテキストの中心X座標
+    fn label_center_x(&self) -> f64 {
+        f64::from(self.label_width) / 2.0
+    }
+    
+    /// ステータステキストの中心X座標
+    fn status_center_x(&self) -> f64 {
+        f64::from(self.label_width) + f64::from(self.status_width) / 2.0
+    }
+}
+
+/// SVGを生成
+/// 
+/// 左側にラベル、右側にステータスを配置する。
+/// スタイルに応じて角丸・グラデーション・テキストの影を切り替え、
+/// テーマに応じてラベル部分の背景色を切り替える。
+/// 
+/// アクセシビリティのため、`title`を`<title>`要素と`aria-label`に埋め込む。
+fn render_svg(
+    layout: &BadgeLayout,
+    label: &str,
+    status: &str,
+    title: &str,
+    color: &str,
+    style: BadgeStyle,
+    theme: BadgeTheme,
+) -> String {
+    // 動的なテキストはすべてエスケープしてから埋め込む
+    let label = escape_xml(label);
+    let status = escape_xml(status);
+    let title = escape_xml(title);
+    let color = escape_xml(color);
+    let width = layout.total_width();
+    let height = style.height();
+    let text_y = style.text_y();
+    let corner = style
+        .corner_radius()
+        .map(|rx| format!(r#" rx="{}""#, rx))
+        .unwrap_or_default();
+    
+    // プラスチックスタイルのみグラデーションを重ねる
+    let (gradient_def, gradient_overlay) = match style {
+        BadgeStyle::Plastic => (
+            r##"<linearGradient id="s" x2="0" y2="100%">
+                    <stop offset="0" stop-color="#fff" stop-opacity=".7"/>
+                    <stop offset=".1" stop-color="#aaa" stop-opacity=".1"/>
+                    <stop offset=".9" stop-opacity=".3"/>
+                    <stop offset="1" stop-opacity=".5"/>
+                </linearGradient>"##
+                .to_string(),
+            format!(r#"<rect width="{}" height="{}" fill="url(#s)"/>"#, width, height),
+        ),
+        BadgeStyle::Flat | BadgeStyle::FlatSquare | BadgeStyle::ForTheBadge => {
+            (String::new(), String::new())
+        }
+    };
+    
+    // プラスチックスタイルのみテキストに影をつける
+    let text_shadow = match style {
+        BadgeStyle::Plastic => format!(
+            r##"<text x="{}" y="{}" fill="#010101" fill-opacity=".3">{}</text>
+                    <text x="{}" y="{}" fill="#010101" fill-opacity=".3">{}</text>"##,
+            layout.label_center_x(),
+            text_y + 1,
+            label,
+            layout.status_center_x(),
+            text_y + 1,
+            status,
+        ),
+        BadgeStyle::Flat | BadgeStyle::FlatSquare | BadgeStyle::ForTheBadge => String::new(),
+    };
+    
+    // for-the-badgeスタイルは太字・文字間隔あり
+    let font_attrs = match style {
+        BadgeStyle::ForTheBadge => format!(
+            r#"font-size="{}" font-weight="bold" letter-spacing="{}""#,
+            style.font_size(),
+            style.letter_spacing(),
+        ),
+        _ => format!(r#"font-size="{}""#, style.font_size()),
+    };
+    
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" role="img" aria-label="{title}">
+                <title>{title}</title>
+                {gradient_def}
+                <clipPath id="r">
+                    <rect width="{width}" height="{height}"{corner} fill="#fff"/>
+                </clipPath>
+                <g clip-path="url(#r)">
+                    <rect width="{label_width}" height="{height}" fill="{label_color}"/>
+                    <rect x="{label_width}" width="{status_width}" height="{height}" fill="{color}"/>
+                    {gradient_overlay}
+                </g>
+                <g fill="#fff" text-anchor="middle" font-family="Arial,Helvetica,sans-serif" {font_attrs}>
+                    {text_shadow}
+                    <text x="{label_x}" y="{text_y}">{label}</text>
+                    <text x="{status_x}" y="{text_y}">{status}</text>
+                </g>
+            </svg>"##,
+        width = width,
+        height = height,
+        title = title,
+        font_attrs = font_attrs,
+        text_y = text_y,
+        gradient_def = gradient_def,
+        corner = corner,
+        label_width = layout.label_width,
+        label_color = theme.label_color(),
+        status_width = layout.status_width,
+        color = color,
+        gradient_overlay = gradient_overlay,
+        text_shadow = text_shadow,
+        label_x = layout.label_center_x(),
+        status_x = layout.status_center_x(),
+        label = label,
+        status = status,
+    )
+}
+
+/// クリック時にPokeを送信するスクリプト
+/// 
+/// - SVG全体のonclickから呼び出し、`data-username`の相手にPOST /api/pokeを実行
+/// - Cookieを送るため`credentials: 'include'`を指定（CORS対応）
+/// - 成功時はステータス部分の色を一時的に変更する
+/// - 失敗時はconsole.errorに出力（セキュリティ上アラートは避ける）
+/// 
+/// XMLとして解釈されても安全なように`<`と`&`を含めない
+const POKE_SCRIPT: &str = "function gitpokePoke(svg){\
+var rect=svg.querySelector('g[clip-path] rect:nth-of-type(2)');\
+var color=rect.getAttribute('fill');\
+fetch('/api/poke',{method:'POST',credentials:'include',\
+headers:{'Content-Type':'application/json'},\
+body:JSON.stringify({username:svg.getAttribute('data-username')})})\
+.then(function(res){\
+if(!res.ok){throw new Error('GitPoke: poke failed with status '+res.status);}\
+rect.setAttribute('fill','#007ec6');\
+setTimeout(function(){rect.setAttribute('fill',color);},1500);})\
+.catch(function(err){console.error(err);});}";
+
+/// SVGにクリックでPokeを送信するスクリプトを埋め込む
+/// 
+/// ユーザー名はスクリプトに直接埋め込まず、エスケープした`data-username`属性から読み取る
+fn embed_poke_script(svg: &str, username: &str) -> String {
+    let attrs = format!(
+        r#"<svg onclick="gitpokePoke(this)" data-username="{}" style="cursor:pointer" "#,
+        escape_xml(username),
+    );
+    let script = format!(
+        r#"<script type="text/javascript"><![CDATA[{}]]></script></svg>"#,
+        POKE_SCRIPT,
+    );
+    
+    let content = svg.replacen("<svg ", &attrs, 1);
+    match content.rfind("</svg>") {
+        Some(end) => format!("{}{}", &content[..end], script),
+        None => content,
+    }
+}
+
+/// スパークラインに表示する日数
+pub const SPARKLINE_DAYS: usize = 14;
+
+/// スパークラインの棒の幅（px）
+const SPARKLINE_BAR_WIDTH: u32 = 3;
+
+/// スパークラインの棒の間隔（px）
+const SPARKLINE_BAR_GAP: u32 = 1;
+
+/// スパークラインの左右・上下の余白（px）
+const SPARKLINE_PADDING: u32 = 4;
+
+/// スパークラインで広がるバッジの幅（px）
+const SPARKLINE_WIDTH: u32 = SPARKLINE_PADDING * 2
+    + SPARKLINE_BAR_WIDTH * SPARKLINE_DAYS as u32
+    + SPARKLINE_BAR_GAP * (SPARKLINE_DAYS as u32 - 1);
+
+/// 活動のあった日の棒の色
+const SPARKLINE_ACTIVE_COLOR: &str = "#40c463";
+
+/// 活動のなかった日の棒の色
+const SPARKLINE_EMPTY_COLOR: &str = "#9f9f9f";
+
+/// `today` までの `SPARKLINE_DAYS` 日分のコントリビューション数（古い順）
+/// 
+/// データのない日は0とする
+fn sparkline_counts(contributions: &HashMap<String, i32>, today: NaiveDate) -> Vec<i32> {
+    (0..SPARKLINE_DAYS as i64)
+        .rev()
+        .map(|days_ago| {
+            let date = today - Duration::days(days_ago);
+            contributions
+                .get(&date.format("%Y-%m-%d").to_string())
+                .copied()
+                .unwrap_or(0)
+                .max(0)
+        })
+        .collect()
+}
+
+/// スパークラインの棒を描画
+/// 
+/// 活動がなかった日も高さ1pxの棒を描き、期間の長さがわかるようにする
+/// 
+/// # Arguments
+/// * `counts` - 日別のコントリビューション数（古い順）
+/// * `offset_x` - 描画を始めるX座標（元のバッジの幅）
+/// * `height` - バッジの高さ
+fn render_sparkline(counts: &[i32], offset_x: u32, height: u32) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0);
+    let max_bar_height = height.saturating_sub(SPARKLINE_PADDING * 2).max(1);
+    
+    let bars: String = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let bar_height = if max > 0 {
+                ((f64::from(count) / f64::from(max)) * f64::from(max_bar_height)).round() as u32
+            } else {
+                0
+            }
+            .max(1);
+            let color = if count > 0 { SPARKLINE_ACTIVE_COLOR } else { SPARKLINE_EMPTY_COLOR };
+            format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                offset_x + SPARKLINE_PADDING + i as u32 * (SPARKLINE_BAR_WIDTH + SPARKLINE_BAR_GAP),
+                height - SPARKLINE_PADDING - bar_height,
+                SPARKLINE_BAR_WIDTH,
+                bar_height,
+                color,
+            )
+        })
+        .collect();
+    
+    format!(r#"<g class="sparkline">{}</g>"#, bars)
+}
+
+/// ルート要素の `width`・`height` 属性を読み取る
+/// 
+/// 生成済みのSVGでは最初に現れる属性がルート要素のもの
+fn svg_dimension(svg: &str, attribute: &str) -> Option<u32> {
+    let start = svg.find(&format!(r#" {}=""#, attribute))? + attribute.len() + 3;
+    let end = start + svg[start..].find('"')?;
+    svg[start..end].parse().ok()
+}
+
+/// XMLの特殊文字をエスケープ
+/// 
+/// SVGに埋め込むテキストは必ずこの関数を通す
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// セクションの幅を計算（テキスト幅 + 左右パディング）
+fn calculate_section_width(text: &str, style: BadgeStyle) -> u32 {
+    let text_width = calculate_text_width(text, style.font_size())
+        + style.letter_spacing() * text.chars().count() as f64;
+    text_width.ceil() as u32 + style.padding() * 2
+}
+
+/// テキストの描画幅を計算（Arial）
+/// 
+/// 1文字ずつ文字幅テーブルを参照して合計する
+fn calculate_text_width(text: &str, font_size: f64) -> f64 {
+    let units: u32 = text.chars().map(arial_char_width).sum();
+    f64::from(units) * font_size / 1000.0
+}
+
+/// Arialの文字幅（1em = 1000単位）
+/// 
+/// テーブルにない文字は平均的な幅として扱い、全角文字は1emとする
+fn arial_char_width(c: char) -> u32 {
+    match c {
+        ' ' | '!' | ',' | '.' | '/' | ':' | ';' | '[' | '\\' | ']' | 'I' | 'f' | 't' | '·' => 278,
+        'i' | 'j' | 'l' => 222,
+        '|' => 260,
+        '\'' => 191,
+        '"' => 355,
+        '(' | ')' | '-' | '`' | 'r' => 333,
+        '{' | '}' => 334,
+        '*' => 389,
+        '^' => 469,
+        'J' | 'c' | 'k' | 's' | 'v' | 'x' | 'y' | 'z' => 500,
+        '+' | '<' | '=' | '>' | '~' => 584,
+        'F' | 'T' | 'Z' => 611,
+        'A' | 'B' | 'E' | 'K' | 'P' | 'S' | 'V' | 'X' | 'Y' | '&' => 667,
+        'C' | 'D' | 'H' | 'N' | 'R' | 'U' | 'w' => 722,
+        'G' | 'O' | 'Q' => 778,
+        'M' | 'm' => 833,
+        '%' => 889,
+        'W' => 944,
+        '@' => 1015,
+        c if c.is_ascii() => 556,
+        c if (c as u32) >= 0x2E80 => 1000,
+        _ => 556,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user::{Username, RegisteredUser, GitHubUserId};
+    use chrono::{DateTime, Utc, Duration};
+    
+    mod badge_state_tests {
+        use super::*;
+        
+        fn create_activity(days_since_last: i64, streak_days: Option<i64>) -> GitHubActivity {
+            let last_activity = if days_since_last == 0 {
+                Some(Utc::now())
+            } else {
+                Some(Utc::now() - Duration::days(days_since_last))
+            };
+            
+            GitHubActivity {
+                username: "testuser".to_string(),
+                last_activity_at: last_activity,
+                current_streak_days: streak_days,
+                contributions: None,
+                total_contributions: None,
+                tz_offset_seconds: None,
+                fetched_at: Utc::now(),
+            }
+        }
+        
+        #[test]
+        fn test_active_state_today() {
+            let activity = create_activity(0, Some(10));
+            let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
+            
+            let badge_state = BadgeState::from_activity(&activity, &user_state, DEFAULT_ACTIVE_THRESHOLD_DAYS, DEFAULT_WARNING_THRESHOLD_DAYS);
+            
+            match badge_state {
+                BadgeState::Active { days_since_last_activity, streak_days } => {
+                    assert_eq!(days_since_last_activity, 0);
+                    assert_eq!(streak_days, Some(10));
+                }
+                _ => panic!("Expected Active state"),
+            }
+        }
+        
+        #[test]
+        fn test_active_state_within_week() {
+            let activity = create_activity(5, None);
+            let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
+            
+            let badge_state = BadgeState::from_activity(&activity, &user_state, DEFAULT_ACTIVE_THRESHOLD_DAYS, DEFAULT_WARNING_THRESHOLD_DAYS);
+            
+            match badge_state {
+                BadgeState::Active { days_since_last_activity, streak_days } => {
+                    assert_eq!(days_since_last_activity, 5);
+                    assert_eq!(streak_days, None);
+                }
+                _ => panic!("Expected Active state"),
             }
         }
         
         #[test]
         fn test_inactive_state_registered_user() {
-            let activity = create_activity(10, None);
+            let activity = create_activity(30, None);
             let username = Username::new("testuser".to_string()).unwrap();
             let user = RegisteredUser::new(GitHubUserId::new(12345), username);
             let user_state = UserState::Registered(user);
             
-            let badge_state = BadgeState::from_activity(&activity, &user_state);
+            let badge_state = BadgeState::from_activity(&activity, &user_state, DEFAULT_ACTIVE_THRESHOLD_DAYS, DEFAULT_WARNING_THRESHOLD_DAYS);
             
             match badge_state {
                 BadgeState::Inactive { days_since_last_activity, pokeable } => {
-                    assert_eq!(days_since_last_activity, 10);
+                    assert_eq!(days_since_last_activity, 30);
                     assert!(pokeable); // 登録済みユーザーはPoke可能
                 }
                 _ => panic!("Expected Inactive state"),
@@ -263,27 +1210,89 @@ mod tests {
         
         #[test]
         fn test_inactive_state_anonymous_user() {
-            let activity = create_activity(10, None);
+            let activity = create_activity(30, None);
             let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
             
-            let badge_state = BadgeState::from_activity(&activity, &user_state);
+            let badge_state = BadgeState::from_activity(&activity, &user_state, DEFAULT_ACTIVE_THRESHOLD_DAYS, DEFAULT_WARNING_THRESHOLD_DAYS);
             
             match badge_state {
                 BadgeState::Inactive { days_since_last_activity, pokeable } => {
-                    assert_eq!(days_since_last_activity, 10);
+                    assert_eq!(days_since_last_activity, 30);
                     assert!(!pokeable); // 未登録ユーザーはPoke不可
                 }
                 _ => panic!("Expected Inactive state"),
             }
         }
         
+        #[test]
+        fn test_configurable_threshold() {
+            let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
+            let is_active = |days: i64, threshold: i64| {
+                matches!(
+                    BadgeState::from_activity(&create_activity(days, None), &user_state, threshold, threshold),
+                    BadgeState::Active { .. }
+                )
+            };
+            
+            // 3日: 3日目まではアクティブ
+            assert!(is_active(3, 3));
+            assert!(!is_active(4, 3));
+            
+            // 7日（デフォルト）
+            assert!(is_active(7, 7));
+            assert!(!is_active(8, 7));
+            
+            // 14日: デフォルトでは非アクティブの10日もアクティブ扱い
+            assert!(is_active(10, 14));
+            assert!(is_active(14, 14));
+            assert!(!is_active(15, 14));
+        }
+        
+        #[test]
+        fn test_three_color_bands() {
+            let user_state = UserState::Anonymous(Username::new("testuser".to_string()).unwrap());
+            let color_after = |days: i64| {
+                BadgeState::from_activity(
+                    &create_activity(days, None),
+                    &user_state,
+                    DEFAULT_ACTIVE_THRESHOLD_DAYS,
+                    DEFAULT_WARNING_THRESHOLD_DAYS,
+                )
+                .color()
+            };
+            
+            assert_eq!(color_after(5), "#44cc11"); // 緑
+            assert_eq!(color_after(14), "#dfb317"); // 黄
+            assert_eq!(color_after(30), "#e05d44"); // 赤
+            
+            // 境界
+            assert_eq!(color_after(8), "#dfb317");
+            assert_eq!(color_after(21), "#dfb317");
+            assert_eq!(color_after(22), "#e05d44");
+        }
+        
+        #[test]
+        fn test_warning_state() {
+            let activity = create_activity(14, None);
+            let username = Username::new("testuser".to_string()).unwrap();
+            let user_state = UserState::Registered(RegisteredUser::new(GitHubUserId::new(12345), username));
+            
+            let badge_state = BadgeState::from_activity(&activity, &user_state, DEFAULT_ACTIVE_THRESHOLD_DAYS, DEFAULT_WARNING_THRESHOLD_DAYS);
+            
+            assert_eq!(badge_state, BadgeState::Warning { days_since_last_activity: 14, pokeable: true });
+            assert_eq!(badge_state.text(), "Idle for 14 days");
+            assert!(badge_state.is_pokeable());
+        }
+        
         #[test]
         fn test_badge_colors() {
             let active = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
-            let inactive = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
+            let warning = BadgeState::Warning { days_since_last_activity: 10, pokeable: true };
+            let inactive = BadgeState::Inactive { days_since_last_activity: 30, pokeable: true };
             let not_found = BadgeState::NotFound;
             
             assert_eq!(active.color(), "#44cc11");
+            assert_eq!(warning.color(), "#dfb317");
             assert_eq!(inactive.color(), "#e05d44");
             assert_eq!(not_found.color(), "#9f9f9f");
         }
@@ -308,13 +1317,228 @@ mod tests {
         }
     }
     
+    mod badge_text_streak_tests {
+        use super::*;
+        
+        #[test]
+        fn test_text_with_streak() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: Some(42) };
+            assert_eq!(state.text(), "Active today · 42 day streak");
+            
+            let state = BadgeState::Active { days_since_last_activity: 2, streak_days: Some(5) };
+            assert_eq!(state.text(), "Active 2 days ago · 5 day streak");
+        }
+        
+        #[test]
+        fn test_text_without_streak() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            assert_eq!(state.text(), "Active today");
+        }
+        
+        #[test]
+        fn test_text_with_single_day_streak() {
+            // 1日だけのstreakは表示しない
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: Some(1) };
+            assert_eq!(state.text(), "Active today");
+            
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: Some(0) };
+            assert_eq!(state.text(), "Active today");
+        }
+    }
+    
+    mod streak_milestone_tests {
+        use super::*;
+        
+        fn active(streak_days: i64) -> BadgeState {
+            BadgeState::Active { days_since_last_activity: 0, streak_days: Some(streak_days) }
+        }
+        
+        /// SVGのステータス部分が指定した色で塗られているか
+        fn has_status_color(state: &BadgeState, color: &str) -> bool {
+            let badge = BadgeSvg::static_badge(state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            badge.content.contains(&format!(r#"fill="{}""#, color))
+        }
+        
+        #[test]
+        fn test_short_streak_stays_green() {
+            let state = active(10);
+            assert_eq!(state.accent_color(), None);
+            assert!(has_status_color(&state, "#44cc11"));
+            
+            // 節目の直前
+            assert_eq!(active(PURPLE_STREAK_DAYS - 1).accent_color(), None);
+        }
+        
+        #[test]
+        fn test_thirty_day_streak_is_purple() {
+            let state = active(30);
+            assert_eq!(state.accent_color(), Some("#8a2be2"));
+            assert!(has_status_color(&state, "#8a2be2"));
+            assert!(!has_status_color(&state, "#44cc11"));
+            assert_eq!(active(GOLD_STREAK_DAYS - 1).accent_color(), Some("#8a2be2"));
+        }
+        
+        #[test]
+        fn test_long_streak_is_gold() {
+            let state = active(150);
+            assert_eq!(state.accent_color(), Some("#daa520"));
+            assert!(has_status_color(&state, "#daa520"));
+            assert!(!has_status_color(&state, "#44cc11"));
+            assert_eq!(active(GOLD_STREAK_DAYS).accent_color(), Some("#daa520"));
+        }
+        
+        #[test]
+        fn test_no_accent_outside_active_state() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            assert_eq!(state.accent_color(), None);
+            
+            let state = BadgeState::Warning { days_since_last_activity: 10, pokeable: true };
+            assert_eq!(state.accent_color(), None);
+            assert_eq!(BadgeState::NotFound.accent_color(), None);
+        }
+    }
+    
+    mod badge_text_contributions_tests {
+        use super::*;
+        
+        #[test]
+        fn test_format_thousands() {
+            assert_eq!(format_thousands(0), "0");
+            assert_eq!(format_thousands(999), "999");
+            assert_eq!(format_thousands(1000), "1,000");
+            assert_eq!(format_thousands(1234), "1,234");
+            assert_eq!(format_thousands(1234567), "1,234,567");
+            assert_eq!(format_thousands(-1234), "-1,234");
+        }
+        
+        #[test]
+        fn test_text_with_contributions() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            assert_eq!(
+                state.text_with_contributions(Some(1234)),
+                "Active today · 1,234 contributions"
+            );
+        }
+        
+        #[test]
+        fn test_text_without_contributions_data() {
+            // データがない場合は何も付加しない
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            assert_eq!(state.text_with_contributions(None), "Active today");
+        }
+        
+        #[test]
+        fn test_contributions_only_for_active() {
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: false };
+            assert_eq!(state.text_with_contributions(Some(1234)), "Inactive for 10 days");
+        }
+        
+        #[test]
+        fn test_badge_renders_contributions() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            let badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, Some(1234), None);
+            
+            assert!(badge.content.contains("Active today · 1,234 contributions"));
+        }
+    }
+    
+    mod shields_endpoint_tests {
+        use super::*;
+        
+        #[test]
+        fn test_active_endpoint() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            let endpoint = ShieldsEndpoint::from_state(&state);
+            
+            assert_eq!(
+                serde_json::to_value(&endpoint).unwrap(),
+                serde_json::json!({
+                    "schemaVersion": 1,
+                    "label": "gitpoke",
+                    "message": "Active today",
+                    "color": "brightgreen",
+                })
+            );
+        }
+        
+        #[test]
+        fn test_inactive_endpoint() {
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
+            let endpoint = ShieldsEndpoint::from_state(&state);
+            
+            assert_eq!(endpoint.message, "Inactive for 10 days");
+            assert_eq!(endpoint.color, "red");
+        }
+        
+        #[test]
+        fn test_not_found_endpoint() {
+            let endpoint = ShieldsEndpoint::from_state(&BadgeState::NotFound);
+            
+            assert_eq!(endpoint.message, "User not found");
+            assert_eq!(endpoint.color, "lightgrey");
+        }
+        
+        #[test]
+        fn test_unknown_color_falls_back_to_hex() {
+            assert_eq!(shields_color_name("#123456"), None);
+        }
+    }
+    
+    mod org_summary_tests {
+        use super::*;
+        
+        fn activity_days_ago(days: i64) -> GitHubActivity {
+            GitHubActivity {
+                username: "member".to_string(),
+                last_activity_at: Some(Utc::now() - Duration::days(days)),
+                current_streak_days: None,
+                contributions: None,
+                total_contributions: None,
+                tz_offset_seconds: None,
+                fetched_at: Utc::now(),
+            }
+        }
+        
+        #[test]
+        fn test_counts_members_within_threshold() {
+            let activities: Vec<_> = [0, 3, 7, 8, 30].into_iter().map(activity_days_ago).collect();
+            
+            let summary = OrgActivitySummary::from_activities(&activities, DEFAULT_ACTIVE_THRESHOLD_DAYS);
+            
+            assert_eq!(summary, OrgActivitySummary { active_members: 3, total_members: 5 });
+            assert_eq!(summary.text(), "3/5 active");
+        }
+        
+        #[test]
+        fn test_member_without_activity_is_inactive() {
+            let mut activity = activity_days_ago(0);
+            activity.last_activity_at = None;
+            
+            let summary = OrgActivitySummary::from_activities(&[activity], DEFAULT_ACTIVE_THRESHOLD_DAYS);
+            
+            assert_eq!(summary.active_members, 0);
+        }
+        
+        #[test]
+        fn test_org_colors() {
+            let color = |active_members, total_members| {
+                OrgActivitySummary { active_members, total_members }.color()
+            };
+            
+            assert_eq!(color(6, 12), "#44cc11");
+            assert_eq!(color(5, 12), "#dfb317");
+            assert_eq!(color(0, 12), "#e05d44");
+            assert_eq!(color(0, 0), "#9f9f9f");
+        }
+    }
+    
     mod badge_svg_tests {
         use super::*;
         
         #[test]
         fn test_static_badge_generation() {
             let state = BadgeState::Active { days_since_last_activity: 0, streak_days: Some(5) };
-            let badge = BadgeSvg::static_badge(&state, "testuser");
+            let badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
             
             assert!(!badge.is_interactive);
             assert_eq!(badge.cache_ttl, 300); // アクティブ状態は5分
@@ -325,18 +1549,37 @@ mod tests {
         #[test]
         fn test_interactive_badge_when_pokeable() {
             let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
-            let badge = BadgeSvg::interactive_badge(&state, "testuser");
+            let badge = BadgeSvg::interactive_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
             
             assert!(badge.is_interactive);
             assert_eq!(badge.cache_ttl, 3600); // 非アクティブ状態は1時間
+            assert!(badge.content.contains("fetch('/api/poke'"));
+            assert!(badge.content.contains(r#"data-username="testuser""#));
+            assert!(badge.content.contains(r#"onclick="gitpokePoke(this)""#));
+            assert!(badge.content.trim_end().ends_with("</script></svg>"));
         }
         
         #[test]
         fn test_non_interactive_badge_when_not_pokeable() {
             let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: false };
-            let badge = BadgeSvg::interactive_badge(&state, "testuser");
+            let badge = BadgeSvg::interactive_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
             
             assert!(!badge.is_interactive); // Poke不可の場合はインタラクティブでない
+            assert!(!badge.content.contains("<script"));
+            assert!(!badge.content.contains("onclick"));
+            assert!(!badge.content.contains("/api/poke"));
+        }
+        
+        #[test]
+        fn test_poke_script_is_xml_safe() {
+            // CDATA内でも壊れないよう、XMLの特殊文字を含めない
+            assert!(!POKE_SCRIPT.contains('<'));
+            assert!(!POKE_SCRIPT.contains('&'));
+            assert!(!POKE_SCRIPT.contains("]]>"));
+            
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
+            let badge = BadgeSvg::interactive_badge(&state, "a\"b", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            assert!(badge.content.contains(r#"data-username="a&quot;b""#));
         }
         
         #[test]
@@ -345,27 +1588,268 @@ mod tests {
             let inactive = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
             let not_found = BadgeState::NotFound;
             
-            let badge_active = BadgeSvg::static_badge(&active, "user");
-            let badge_inactive = BadgeSvg::static_badge(&inactive, "user");
-            let badge_not_found = BadgeSvg::static_badge(&not_found, "user");
+            let badge_active = BadgeSvg::static_badge(&active, "user", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            let badge_inactive = BadgeSvg::static_badge(&inactive, "user", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            let badge_not_found = BadgeSvg::static_badge(&not_found, "user", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
             
             assert_eq!(badge_active.cache_ttl, 300); // 5分
             assert_eq!(badge_inactive.cache_ttl, 3600); // 1時間
             assert_eq!(badge_not_found.cache_ttl, 86400); // 24時間
         }
         
+        #[test]
+        fn test_badge_width_depends_on_text() {
+            let active = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            let inactive = BadgeState::Inactive { days_since_last_activity: 365, pokeable: false };
+            
+            let active_layout = BadgeLayout::new(DEFAULT_BADGE_LABEL, &active.text(), BadgeStyle::Flat);
+            let inactive_layout = BadgeLayout::new(DEFAULT_BADGE_LABEL, &inactive.text(), BadgeStyle::Flat);
+            
+            // 「Active today」は「Inactive for 365 days」より狭い
+            assert!(active_layout.total_width() < inactive_layout.total_width());
+            // ラベル部分は同じ幅
+            assert_eq!(active_layout.label_width, inactive_layout.label_width);
+            
+            let active_badge = BadgeSvg::static_badge(&active, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            let inactive_badge = BadgeSvg::static_badge(&inactive, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            assert!(active_badge.content.contains(&format!(r#"width="{}""#, active_layout.total_width())));
+            assert!(inactive_badge.content.contains(&format!(r#"width="{}""#, inactive_layout.total_width())));
+        }
+        
+        #[test]
+        fn test_badge_layout_sections() {
+            let layout = BadgeLayout::new("GitPoke", "Active today", BadgeStyle::Flat);
+            
+            // 各セクションは左右10pxのパディングを含む
+            assert_eq!(layout.label_width, calculate_text_width("GitPoke", 12.0).ceil() as u32 + 20);
+            assert_eq!(layout.status_width, calculate_text_width("Active today", 12.0).ceil() as u32 + 20);
+            assert_eq!(layout.total_width(), layout.label_width + layout.status_width);
+            
+            let badge = BadgeSvg::static_badge(
+                &BadgeState::Active { days_since_last_activity: 0, streak_days: None },
+                "testuser",
+                DEFAULT_BADGE_LABEL,
+                BadgeStyle::Flat,
+                BadgeTheme::Light,
+                None,
+                None,
+            );
+            assert!(badge.content.contains(&format!(
+                r#"<rect x="{}" width="{}""#,
+                layout.label_width, layout.status_width
+            )));
+        }
+        
+        #[test]
+        fn test_styles_produce_distinct_content() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            
+            let flat = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            let flat_square = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::FlatSquare, BadgeTheme::Light, None, None);
+            let plastic = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Plastic, BadgeTheme::Light, None, None);
+            
+            assert_ne!(flat.content, flat_square.content);
+            assert_ne!(flat.content, plastic.content);
+            assert_ne!(flat_square.content, plastic.content);
+            
+            // フラットスクエアは角丸なし
+            assert!(flat.content.contains("rx="));
+            assert!(!flat_square.content.contains("rx="));
+            
+            // プラスチックのみグラデーション付き
+            assert!(plastic.content.contains("<linearGradient"));
+            assert!(!flat.content.contains("<linearGradient"));
+            assert!(!flat_square.content.contains("<linearGradient"));
+        }
+        
+        #[test]
+        fn test_style_parse_or_default() {
+            assert_eq!(BadgeStyle::parse_or_default("flat"), BadgeStyle::Flat);
+            assert_eq!(BadgeStyle::parse_or_default("flat-square"), BadgeStyle::FlatSquare);
+            assert_eq!(BadgeStyle::parse_or_default("plastic"), BadgeStyle::Plastic);
+            assert_eq!(BadgeStyle::parse_or_default("PLASTIC"), BadgeStyle::Plastic);
+            assert_eq!(BadgeStyle::parse_or_default("for-the-badge"), BadgeStyle::ForTheBadge);
+        }
+        
+        #[test]
+        fn test_invalid_style_falls_back_to_flat() {
+            assert_eq!(BadgeStyle::parse_or_default("unknown"), BadgeStyle::Flat);
+            assert_eq!(BadgeStyle::parse_or_default(""), BadgeStyle::Flat);
+            assert_eq!(BadgeStyle::default(), BadgeStyle::Flat);
+        }
+        
+        #[test]
+        fn test_for_the_badge_style() {
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: false };
+            let badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::ForTheBadge, BadgeTheme::Light, None, None);
+            
+            // ラベルとステータスは大文字
+            assert!(badge.content.contains("INACTIVE FOR 10 DAYS"));
+            assert!(badge.content.contains("GITPOKE"));
+            assert!(!badge.content.contains(">Inactive for 10 days</text>"));
+            // 高さ28px・太字
+            assert!(badge.content.contains(r#"height="28""#));
+            assert!(!badge.content.contains(r#"height="20""#));
+            assert!(badge.content.contains(r#"font-weight="bold""#));
+            assert!(badge.content.contains("letter-spacing="));
+            
+            // パディングが広いためフラットより幅が広い
+            let flat = BadgeLayout::new("GITPOKE", "INACTIVE FOR 10 DAYS", BadgeStyle::Flat);
+            let for_the_badge = BadgeLayout::new("GITPOKE", "INACTIVE FOR 10 DAYS", BadgeStyle::ForTheBadge);
+            assert!(for_the_badge.label_width >= flat.label_width);
+        }
+        
+        #[test]
+        fn test_dark_theme_changes_label_fill() {
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: false };
+            let light = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            let dark = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Dark, None, None);
+            
+            // ラベル部分の背景色のみ変わる
+            assert!(light.content.contains(&format!(r#"fill="{}""#, BadgeTheme::Light.label_color())));
+            assert!(dark.content.contains(&format!(r#"fill="{}""#, BadgeTheme::Dark.label_color())));
+            assert!(!dark.content.contains(&format!(r#"fill="{}""#, BadgeTheme::Light.label_color())));
+            
+            // ステータス部分の色はテーマに関わらず同じ
+            assert!(light.content.contains(&format!(r#"fill="{}""#, state.color())));
+            assert!(dark.content.contains(&format!(r#"fill="{}""#, state.color())));
+        }
+        
+        #[test]
+        fn test_theme_parse_or_default() {
+            assert_eq!(BadgeTheme::parse_or_default("dark"), BadgeTheme::Dark);
+            assert_eq!(BadgeTheme::parse_or_default("DARK"), BadgeTheme::Dark);
+            assert_eq!(BadgeTheme::parse_or_default("light"), BadgeTheme::Light);
+            assert_eq!(BadgeTheme::parse_or_default("unknown"), BadgeTheme::Light);
+            assert_eq!(BadgeTheme::default(), BadgeTheme::Light);
+        }
+        
+        #[test]
+        fn test_accessibility_metadata() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            let last_active = NaiveDate::from_ymd_opt(2024, 5, 1);
+            let badge = BadgeSvg::static_badge(&state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, last_active);
+            
+            let expected = "GitPoke status for octocat: Active today (Last active: 2024-05-01)";
+            assert!(badge.content.contains(r#"role="img""#));
+            assert!(badge.content.contains(&format!(r#"aria-label="{}""#, expected)));
+            assert!(badge.content.contains(&format!("<title>{}</title>", expected)));
+        }
+        
+        #[test]
+        fn test_accessibility_metadata_keeps_original_case() {
+            // for-the-badgeスタイルでも説明文は大文字化しない
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
+            let last_active = NaiveDate::from_ymd_opt(2024, 5, 1);
+            let badge = BadgeSvg::static_badge(&state, "octocat", "Status", BadgeStyle::ForTheBadge, BadgeTheme::Light, None, last_active);
+            
+            assert!(badge.content.contains("<title>Status status for octocat: Inactive for 10 days (Last active: 2024-05-01)</title>"));
+        }
+        
+        #[test]
+        fn test_tooltip_shows_last_active_date() {
+            let state = BadgeState::Inactive { days_since_last_activity: 12, pokeable: false };
+            let last_active = NaiveDate::from_ymd_opt(2023, 12, 31);
+            
+            let badge = BadgeSvg::static_badge(&state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, last_active);
+            
+            assert!(badge.content.contains("<title>GitPoke status for octocat: Inactive for 12 days (Last active: 2023-12-31)</title>"));
+        }
+        
+        #[test]
+        fn test_tooltip_without_activity() {
+            let state = BadgeState::Inactive { days_since_last_activity: 365, pokeable: false };
+            let badge = BadgeSvg::static_badge(&state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            assert!(badge.content.contains("(No recent activity)</title>"));
+            
+            // ユーザーが見つからない場合は最終活動日を表示しない
+            let badge = BadgeSvg::static_badge(&BadgeState::NotFound, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            assert!(badge.content.contains("<title>GitPoke status for octocat: User not found</title>"));
+        }
+        
+        #[test]
+        fn test_default_label() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            let badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            
+            assert!(badge.content.contains(">GitPoke</text>"));
+        }
+        
+        #[test]
+        fn test_custom_label() {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            let badge = BadgeSvg::static_badge(&state, "testuser", "streak", BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            let default_badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            
+            assert!(badge.content.contains(">streak</text>"));
+            assert!(!badge.content.contains("GitPoke"));
+            // ラベル部分の幅もラベルに合わせて変わる
+            assert_ne!(
+                BadgeLayout::new("streak", "Active today", BadgeStyle::Flat),
+                BadgeLayout::new(DEFAULT_BADGE_LABEL, "Active today", BadgeStyle::Flat),
+            );
+            assert_ne!(badge.content, default_badge.content);
+        }
+        
+        #[test]
+        fn test_escape_xml() {
+            assert_eq!(escape_xml("a & b"), "a &amp; b");
+            assert_eq!(escape_xml("<script>"), "&lt;script&gt;");
+            assert_eq!(escape_xml(r#"say "hi""#), "say &quot;hi&quot;");
+            assert_eq!(escape_xml("it's"), "it&apos;s");
+            assert_eq!(escape_xml("Active today"), "Active today");
+        }
+        
+        #[test]
+        fn test_render_svg_escapes_dynamic_text() {
+            let label = "A&B";
+            let status = r#"<x y="1">'quoted'</x>"#;
+            let layout = BadgeLayout::new(label, status, BadgeStyle::Plastic);
+            
+            let content = render_svg(&layout, label, status, status, "#44cc11", BadgeStyle::Plastic, BadgeTheme::Light);
+            
+            assert!(content.contains("A&amp;B"));
+            assert!(content.contains("&lt;x y=&quot;1&quot;&gt;&apos;quoted&apos;&lt;/x&gt;"));
+            assert!(content.contains("<title>&lt;x y=&quot;1&quot;&gt;"));
+            // 生の特殊文字がテキストとして埋め込まれていない
+            assert!(!content.contains("A&B"));
+            assert!(!content.contains("<x "));
+            assert!(!content.contains("'quoted'"));
+            // エスケープされていない&はエンティティとしてのみ現れる
+            for (i, _) in content.match_indices('&') {
+                let rest = &content[i..];
+                assert!(
+                    ["&amp;", "&lt;", "&gt;", "&quot;", "&apos;"].iter().any(|e| rest.starts_with(e)),
+                    "unescaped & at {}",
+                    i
+                );
+            }
+        }
+        
         #[test]
         fn test_content_type() {
             let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
-            let badge = BadgeSvg::static_badge(&state, "testuser");
+            let badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
             
             assert_eq!(badge.content_type(), "image/svg+xml");
         }
         
+        #[test]
+        fn test_org_badge_shows_active_fraction() {
+            let summary = OrgActivitySummary { active_members: 5, total_members: 12 };
+            let badge = BadgeSvg::org_badge(&summary, "octo-org", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light);
+            
+            assert!(!badge.is_interactive);
+            assert_eq!(badge.cache_ttl, 3600);
+            assert!(badge.content.contains("5/12 active"));
+            assert!(badge.content.contains("GitPoke activity for octo-org: 5/12 active"));
+            assert!(badge.content.contains("#dfb317")); // 半数未満は黄色
+        }
+        
         #[test]
         fn test_cache_control_header() {
             let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
-            let badge = BadgeSvg::static_badge(&state, "testuser");
+            let badge = BadgeSvg::static_badge(&state, "testuser", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
             
             let cache_control = badge.cache_control();
             assert!(cache_control.contains("public"));
@@ -373,4 +1857,137 @@ mod tests {
             assert!(cache_control.contains("stale-while-revalidate=86400"));
         }
     }
+    
+    mod not_found_badge_tests {
+        use super::*;
+        
+        fn not_found_badge(display: &NotFoundDisplay) -> BadgeSvg {
+            BadgeSvg::not_found_badge(display, "ghost", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light)
+        }
+        
+        #[test]
+        fn test_default_matches_static_badge() {
+            let badge = not_found_badge(&NotFoundDisplay::Message);
+            let static_badge = BadgeSvg::static_badge(&BadgeState::NotFound, "ghost", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None);
+            
+            assert_eq!(badge.content, static_badge.content);
+            assert!(badge.content.contains(">User not found</text>"));
+            assert!(badge.content.contains("#9f9f9f"));
+        }
+        
+        #[test]
+        fn test_hidden_is_tiny_transparent_svg() {
+            let badge = not_found_badge(&NotFoundDisplay::Hidden);
+            
+            assert!(badge.content.contains(r#"width="1""#));
+            assert!(badge.content.contains(r#"height="1""#));
+            // 何も描画しない
+            assert!(!badge.content.contains("<rect"));
+            assert!(!badge.content.contains("<text"));
+            assert!(!badge.content.contains("ghost"));
+            assert_eq!(badge.cache_ttl, BadgeState::NotFound.cache_ttl());
+        }
+        
+        #[test]
+        fn test_custom_message() {
+            let badge = not_found_badge(&NotFoundDisplay::CustomMessage("Unknown".to_string()));
+            
+            assert!(badge.content.contains(">Unknown</text>"));
+            assert!(badge.content.contains("<title>GitPoke status for ghost: Unknown</title>"));
+            assert!(!badge.content.contains("User not found"));
+            
+            // メッセージもエスケープする
+            let badge = not_found_badge(&NotFoundDisplay::CustomMessage("<gone>".to_string()));
+            assert!(badge.content.contains(">&lt;gone&gt;</text>"));
+        }
+        
+        #[test]
+        fn test_from_query() {
+            assert_eq!(NotFoundDisplay::from_query(None, None), NotFoundDisplay::Message);
+            assert_eq!(NotFoundDisplay::from_query(Some("message"), None), NotFoundDisplay::Message);
+            assert_eq!(NotFoundDisplay::from_query(Some("unknown"), None), NotFoundDisplay::Message);
+            assert_eq!(NotFoundDisplay::from_query(Some("HIDE"), None), NotFoundDisplay::Hidden);
+            
+            // 非表示の場合はメッセージを無視する
+            assert_eq!(NotFoundDisplay::from_query(Some("hide"), Some("Unknown")), NotFoundDisplay::Hidden);
+            assert_eq!(
+                NotFoundDisplay::from_query(None, Some("Unknown")),
+                NotFoundDisplay::CustomMessage("Unknown".to_string())
+            );
+        }
+    }
+    
+    mod sparkline_tests {
+        use super::*;
+        
+        fn today() -> NaiveDate {
+            NaiveDate::from_ymd_opt(2024, 5, 14).unwrap()
+        }
+        
+        fn badge() -> BadgeSvg {
+            let state = BadgeState::Active { days_since_last_activity: 0, streak_days: None };
+            BadgeSvg::static_badge(&state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None)
+        }
+        
+        #[test]
+        fn test_sparkline_adds_one_bar_per_day() {
+            // 期間外（15日前）の値は使わない
+            let contributions: HashMap<String, i32> = [
+                ("2024-04-30", 50),
+                ("2024-05-01", 2),
+                ("2024-05-10", 8),
+                ("2024-05-14", 4),
+            ]
+            .into_iter()
+            .map(|(date, count)| (date.to_string(), count))
+            .collect();
+            let original = badge();
+            let original_width = svg_dimension(&original.content, "width").unwrap();
+            
+            let badge = original.clone().with_sparkline(Some(&contributions), today());
+            
+            assert_eq!(
+                badge.content.matches("<rect").count(),
+                original.content.matches("<rect").count() + SPARKLINE_DAYS
+            );
+            assert_eq!(svg_dimension(&badge.content, "width"), Some(original_width + SPARKLINE_WIDTH));
+            // 最大値の日が最も高い棒になる（高さ20 - 上下の余白8）
+            assert!(badge.content.contains(r##"width="3" height="12" fill="#40c463""##));
+            assert!(badge.content.trim_end().ends_with("</g></svg>"));
+        }
+        
+        #[test]
+        fn test_sparkline_counts_fill_missing_days() {
+            let contributions: HashMap<String, i32> =
+                [("2024-05-01".to_string(), 3), ("2024-05-14".to_string(), 1)].into_iter().collect();
+            
+            let counts = sparkline_counts(&contributions, today());
+            
+            assert_eq!(counts.len(), SPARKLINE_DAYS);
+            assert_eq!(counts[0], 3);
+            assert_eq!(counts[SPARKLINE_DAYS - 1], 1);
+            assert_eq!(counts.iter().sum::<i32>(), 4);
+        }
+        
+        #[test]
+        fn test_missing_contributions_omit_sparkline() {
+            let original = badge();
+            
+            let badge = original.clone().with_sparkline(None, today());
+            
+            assert_eq!(badge.content, original.content);
+            assert!(!badge.content.contains("sparkline"));
+        }
+        
+        #[test]
+        fn test_sparkline_keeps_poke_script() {
+            let state = BadgeState::Inactive { days_since_last_activity: 10, pokeable: true };
+            let badge = BadgeSvg::interactive_badge(&state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None)
+                .with_sparkline(Some(&HashMap::new()), today());
+            
+            assert!(badge.is_interactive);
+            assert!(badge.content.contains(r#"onclick="gitpokePoke(this)""#));
+            assert_eq!(badge.content.matches(SPARKLINE_EMPTY_COLOR).count(), SPARKLINE_DAYS);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,90 @@
+//! 時刻の取得
+//! 
+//! このファイルは以下を定義：
+//! - 現在時刻を返す `Clock` トレイト
+//! - システム時計を使う `SystemClock`
+//! - 時刻を固定・操作できる `FixedClock`（テスト用）
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// 現在時刻の取得元
+/// 
+/// 時刻に依存するロジックに注入し、テストで時刻を固定できるようにする
+pub trait Clock: Send + Sync {
+    /// 現在時刻を取得
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// システム時計
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 固定された時刻を返す時計
+/// 
+/// 明示的に進めない限り時刻は変わらないため、テストで待機せずに時間経過を再現できる
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// 指定時刻で止まった時計を作成
+    /// 
+    /// # Arguments
+    /// * `now` - 現在時刻として返す日時
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now: Mutex::new(now) }
+    }
+    
+    /// 時刻を進める
+    /// 
+    /// # Arguments
+    /// * `duration` - 進める時間
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += duration;
+    }
+    
+    /// 時刻を設定
+    /// 
+    /// # Arguments
+    /// * `now` - 新しい現在時刻
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    #[test]
+    fn test_fixed_clock_only_moves_when_advanced() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 15, 9, 0, 0).unwrap();
+        let clock = FixedClock::new(start);
+        
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.now(), start);
+        
+        clock.advance(Duration::minutes(90));
+        assert_eq!(clock.now(), start + Duration::minutes(90));
+        
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
@@ -12,10 +12,15 @@ pub mod poke;
 pub mod badge;
 pub mod github;
 pub mod validation;
+pub mod clock;
 
 // 主要な型を再エクスポート
 pub use user::{Username, GitHubUserId, UserState, RegisteredUser, PokeSetting};
 pub use poke::{PokeCapability, PokeEvent, PokeResult};
-pub use badge::{BadgeState, BadgeSvg};
+pub use badge::{
+    BadgeState, BadgeStyle, BadgeSvg, BadgeTheme, ShieldsEndpoint, DEFAULT_ACTIVE_THRESHOLD_DAYS,
+    DEFAULT_BADGE_LABEL, DEFAULT_WARNING_THRESHOLD_DAYS,
+};
 pub use github::{GitHubActivity, FollowRelation, ActivityState};
-pub use validation::{Validated, ValidationError};
\ No newline at end of file
+pub use validation::{Validated, ValidationError};
+pub use clock::{Clock, FixedClock, SystemClock};
\ No newline at end of file
@@ -5,12 +5,14 @@
 //! - Poke可能性の判定
 //! - Pokeに関するビジネスルール
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::clock::{Clock, SystemClock};
 use crate::domain::user::{Username, RegisteredUser, PokeSetting};
 use crate::domain::github::FollowRelation;
+use crate::domain::validation::ValidationError;
 use crate::error::PokeError;
 
 /// Poke可能性を表す型
@@ -34,6 +36,8 @@ impl PokeCapability {
     /// Poke可能性をチェック
     /// 
     /// ビジネスルールに基づいてPokeの可否を判定
+    /// 自分自身へのPokeは、受信設定やフォロー関係に関係なく不可（最初に判定する）
+    /// 受信者にブロックされている送信者は、受信設定やフォロー関係に関係なく不可
     /// 
     /// # Arguments
     /// * `sender` - Poke送信者
@@ -48,6 +52,16 @@ impl PokeCapability {
         recipient: &RegisteredUser,
         follow_relation: &FollowRelation,
     ) -> Self {
+        // 自分自身へのPokeを確認（ユーザー名は大文字小文字を区別しない）
+        if *sender == recipient.username {
+            return Self::CannotPoke(PokeError::CannotPokeSelf);
+        }
+        
+        // ブロックリストを確認
+        if recipient.is_blocked(sender) {
+            return Self::CannotPoke(PokeError::SenderBlocked);
+        }
+        
         // 受信者の設定を確認
         match recipient.poke_setting {
             PokeSetting::Disabled => {
@@ -57,7 +71,7 @@ impl PokeCapability {
                 // 誰でもOK
             }
             PokeSetting::FollowersOnly => {
-                // フォロワーチェック
+                // フォロワーチェック（送信者が一方的にフォローしているだけでは不可）
                 if !follow_relation.is_follower() {
                     return Self::CannotPoke(PokeError::NotFollower);
                 }
@@ -102,6 +116,10 @@ pub struct PokeEvent {
     /// 送信元のコンテキスト（任意の文字列）
     /// 例: リポジトリ名、プロジェクト名、URL等
     pub context: Option<String>,
+    
+    /// 送信者が添えたメッセージ（任意）
+    #[serde(default)]
+    pub message: Option<String>,
 }
 
 impl PokeEvent {
@@ -111,12 +129,23 @@ impl PokeEvent {
     /// * `from` - 送信者
     /// * `to` - 受信者
     pub fn new(from: Username, to: Username) -> Self {
+        Self::with_clock(from, to, &SystemClock)
+    }
+    
+    /// 指定した時計の現在時刻でPokeイベントを作成
+    /// 
+    /// # Arguments
+    /// * `from` - 送信者
+    /// * `to` - 受信者
+    /// * `clock` - 発生日時の取得元
+    pub fn with_clock(from: Username, to: Username, clock: &dyn Clock) -> Self {
         Self {
             id: Uuid::new_v4(),
             from,
             to,
-            occurred_at: Utc::now(),
+            occurred_at: clock.now(),
             context: None,
+            message: None,
         }
     }
     
@@ -132,22 +161,88 @@ impl PokeEvent {
         event
     }
     
-    /// 同日の重複Pokeかどうかをチェック
+    /// クールダウン期間内の重複Pokeかどうかをチェック
     /// 
     /// # Arguments
     /// * `other` - 比較対象のPokeイベント
+    /// * `cooldown` - 同一ユーザーへ再度Pokeできるまでの間隔
     /// 
     /// # Returns
-    /// * `true` - 同じ送信者から同じ受信者への同日のPoke
+    /// * `true` - 同じ送信者から同じ受信者へのPokeで、発生日時の差がクールダウン未満
     /// * `false` - それ以外
-    pub fn is_duplicate_today(&self, other: &PokeEvent) -> bool {
+    pub fn is_within_cooldown(&self, other: &PokeEvent, cooldown: Duration) -> bool {
         // 同じ送信者・受信者かチェック
         if self.from != other.from || self.to != other.to {
             return false;
         }
         
-        // 同じ日付（UTCベース）かチェック
-        self.occurred_at.date_naive() == other.occurred_at.date_naive()
+        // 発生日時の差がクールダウン未満かチェック
+        (self.occurred_at - other.occurred_at).abs() < cooldown
+    }
+}
+
+/// Poke履歴の向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PokeDirection {
+    /// 送信したPoke
+    Sent,
+    
+    /// 受信したPoke
+    Received,
+}
+
+/// Poke履歴のページングカーソル
+/// 
+/// 履歴は (発生日時, イベントID) の降順に並ぶため、
+/// 前ページ最後のイベントのこの組で次ページの開始位置を表す
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokeCursor {
+    /// 前ページ最後のイベントの発生日時
+    pub occurred_at: DateTime<Utc>,
+    
+    /// 前ページ最後のイベントID
+    pub id: Uuid,
+}
+
+impl PokeCursor {
+    /// イベントの位置を指すカーソルを作成
+    pub fn from_event(event: &PokeEvent) -> Self {
+        Self {
+            occurred_at: event.occurred_at,
+            id: event.id,
+        }
+    }
+    
+    /// クエリパラメータ用の文字列に変換
+    /// 
+    /// 形式: `{発生日時のUNIXナノ秒}_{イベントID}`
+    pub fn encode(&self) -> String {
+        let nanos = self.occurred_at.timestamp_nanos_opt().unwrap_or_default();
+        format!("{}_{}", nanos, self.id)
+    }
+    
+    /// クエリパラメータの文字列からカーソルを復元
+    /// 
+    /// # Returns
+    /// * `Ok(PokeCursor)` - 復元成功
+    /// * `Err(ValidationError)` - 形式が不正
+    pub fn decode(s: &str) -> Result<Self, ValidationError> {
+        let invalid = || ValidationError::InvalidFormat { field: "cursor".to_string() };
+        
+        let (nanos, id) = s.split_once('_').ok_or_else(invalid)?;
+        let nanos: i64 = nanos.parse().map_err(|_| invalid())?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+        
+        Ok(Self {
+            occurred_at: DateTime::from_timestamp_nanos(nanos),
+            id,
+        })
+    }
+    
+    /// イベントがカーソルより後ろ（次ページ側）にあるかどうか
+    pub fn is_followed_by(&self, event: &PokeEvent) -> bool {
+        (event.occurred_at, event.id) < (self.occurred_at, self.id)
     }
 }
 
@@ -162,6 +257,8 @@ pub enum PokeResult {
         event_id: Uuid,
         /// メッセージ
         message: String,
+        /// 送信者がPokeに添えたメッセージ
+        poke_message: Option<String>,
     },
     
     /// Poke失敗
@@ -177,6 +274,7 @@ impl PokeResult {
         Self::Success {
             event_id: event.id,
             message: format!("{}さんをつつきました！", event.to.as_str()),
+            poke_message: event.message.clone(),
         }
     }
     
@@ -252,6 +350,42 @@ mod tests {
             }
         }
         
+        #[test]
+        fn test_can_poke_anyone_when_following() {
+            let sender = Username::new("sender".to_string()).unwrap();
+            let recipient = create_test_user("recipient", PokeSetting::Anyone);
+            
+            // 誰でもPokeできる設定なので、一方的なフォローでもOK
+            let follow_relation = FollowRelation::Following;
+            let capability = PokeCapability::check(&sender, &recipient, &follow_relation);
+            
+            assert!(capability.can_poke());
+        }
+        
+        #[test]
+        fn test_cannot_poke_followers_only_when_following() {
+            let sender = Username::new("sender".to_string()).unwrap();
+            let recipient = create_test_user("recipient", PokeSetting::FollowersOnly);
+            
+            // フォロワーのみ設定で、送信者が一方的にフォローしている場合
+            let follow_relation = FollowRelation::Following;
+            let capability = PokeCapability::check(&sender, &recipient, &follow_relation);
+            
+            assert_eq!(capability, PokeCapability::CannotPoke(PokeError::NotFollower));
+        }
+        
+        #[test]
+        fn test_cannot_poke_mutual_only_when_following() {
+            let sender = Username::new("sender".to_string()).unwrap();
+            let recipient = create_test_user("recipient", PokeSetting::MutualOnly);
+            
+            // 相互フォローのみ設定で、送信者が一方的にフォローしている場合
+            let follow_relation = FollowRelation::Following;
+            let capability = PokeCapability::check(&sender, &recipient, &follow_relation);
+            
+            assert_eq!(capability, PokeCapability::CannotPoke(PokeError::NotMutualFollower));
+        }
+        
         #[test]
         fn test_can_poke_mutual_only_when_mutual() {
             let sender = Username::new("sender".to_string()).unwrap();
@@ -299,10 +433,61 @@ mod tests {
                 _ => panic!("Expected CannotPoke"),
             }
         }
+        
+        #[test]
+        fn test_cannot_poke_when_blocked() {
+            let sender = Username::new("sender".to_string()).unwrap();
+            let mut recipient = create_test_user("recipient", PokeSetting::Anyone);
+            recipient.block_sender(sender.clone());
+            
+            // 誰でもPokeできる設定・相互フォローでも、ブロックされていればNG
+            let follow_relation = FollowRelation::Mutual;
+            let capability = PokeCapability::check(&sender, &recipient, &follow_relation);
+            
+            assert_eq!(capability, PokeCapability::CannotPoke(PokeError::SenderBlocked));
+        }
+        
+        #[test]
+        fn test_cannot_poke_self_regardless_of_setting() {
+            for setting in [
+                PokeSetting::Anyone,
+                PokeSetting::FollowersOnly,
+                PokeSetting::MutualOnly,
+                PokeSetting::Disabled,
+            ] {
+                let sender = Username::new("Octocat".to_string()).unwrap();
+                let mut recipient = create_test_user("octocat", setting);
+                // ブロックより先に判定する
+                recipient.block_sender(sender.clone());
+                
+                let capability = PokeCapability::check(&sender, &recipient, &FollowRelation::Mutual);
+                
+                assert_eq!(
+                    capability,
+                    PokeCapability::CannotPoke(PokeError::CannotPokeSelf),
+                    "{:?}",
+                    setting
+                );
+            }
+        }
+        
+        #[test]
+        fn test_can_poke_when_other_sender_blocked() {
+            let sender = Username::new("sender".to_string()).unwrap();
+            let mut recipient = create_test_user("recipient", PokeSetting::Anyone);
+            recipient.block_sender(Username::new("spammer".to_string()).unwrap());
+            
+            // ブロックされていない送信者は通常どおり
+            let capability = PokeCapability::check(&sender, &recipient, &FollowRelation::None);
+            
+            assert!(capability.can_poke());
+        }
     }
     
     mod poke_event_tests {
         use super::*;
+        use chrono::TimeZone;
+        use crate::domain::clock::FixedClock;
         
         #[test]
         fn test_new_poke_event() {
@@ -330,19 +515,37 @@ mod tests {
         }
         
         #[test]
-        fn test_is_duplicate_today_same_day() {
+        fn test_poke_event_message_serialization() {
+            let from = Username::new("sender".to_string()).unwrap();
+            let to = Username::new("recipient".to_string()).unwrap();
+            let mut event = PokeEvent::new(from, to);
+            event.message = Some("レビューお願いします".to_string());
+            
+            let json = serde_json::to_value(&event).unwrap();
+            let restored: PokeEvent = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(restored, event);
+            
+            // messageを持たない既存のイベントも読み込める
+            let mut legacy = json;
+            legacy.as_object_mut().unwrap().remove("message");
+            let restored: PokeEvent = serde_json::from_value(legacy).unwrap();
+            assert_eq!(restored.message, None);
+        }
+        
+        #[test]
+        fn test_is_within_cooldown_same_pair() {
             let from = Username::new("sender".to_string()).unwrap();
             let to = Username::new("recipient".to_string()).unwrap();
             
             let event1 = PokeEvent::new(from.clone(), to.clone());
             let event2 = PokeEvent::new(from.clone(), to.clone());
             
-            // 同じ日に作成されたイベントは重複
-            assert!(event1.is_duplicate_today(&event2));
+            // 直後のPokeはクールダウン中
+            assert!(event1.is_within_cooldown(&event2, Duration::hours(1)));
         }
         
         #[test]
-        fn test_is_duplicate_today_different_users() {
+        fn test_is_within_cooldown_different_users() {
             let sender1 = Username::new("sender1".to_string()).unwrap();
             let sender2 = Username::new("sender2".to_string()).unwrap();
             let recipient = Username::new("recipient".to_string()).unwrap();
@@ -351,24 +554,28 @@ mod tests {
             let event2 = PokeEvent::new(sender2, recipient);
             
             // 異なる送信者の場合は重複でない
-            assert!(!event1.is_duplicate_today(&event2));
+            assert!(!event1.is_within_cooldown(&event2, Duration::hours(1)));
         }
         
         #[test]
-        fn test_is_duplicate_today_different_day() {
-            use chrono::Duration;
-            
+        fn test_is_within_cooldown_elapsed() {
             let from = Username::new("sender".to_string()).unwrap();
             let to = Username::new("recipient".to_string()).unwrap();
+            let cooldown = Duration::seconds(3600);
+            let clock = FixedClock::new(Utc.with_ymd_and_hms(2024, 3, 15, 23, 50, 0).unwrap());
             
-            let mut event1 = PokeEvent::new(from.clone(), to.clone());
-            let event2 = PokeEvent::new(from, to);
+            let previous = PokeEvent::with_clock(from.clone(), to.clone(), &clock);
+            clock.advance(Duration::minutes(30));
+            let after_30_minutes = PokeEvent::with_clock(from.clone(), to.clone(), &clock);
+            clock.advance(Duration::minutes(60));
+            let after_90_minutes = PokeEvent::with_clock(from, to, &clock);
             
-            // event1を昨日に設定
-            event1.occurred_at = event1.occurred_at - Duration::days(1);
+            // 日付をまたいでも経過時間で判定する
+            assert_ne!(previous.occurred_at.date_naive(), after_30_minutes.occurred_at.date_naive());
             
-            // 異なる日の場合は重複でない
-            assert!(!event1.is_duplicate_today(&event2));
+            // 30分後はブロック、90分後は許可
+            assert!(after_30_minutes.is_within_cooldown(&previous, cooldown));
+            assert!(!after_90_minutes.is_within_cooldown(&previous, cooldown));
         }
     }
     
@@ -385,9 +592,10 @@ mod tests {
             let result = PokeResult::success(&event);
             
             match result {
-                PokeResult::Success { event_id: id, message } => {
+                PokeResult::Success { event_id: id, message, poke_message } => {
                     assert_eq!(id, event_id);
                     assert_eq!(message, "recipientさんをつつきました！");
+                    assert_eq!(poke_message, None);
                 }
                 _ => panic!("Expected Success"),
             }
@@ -405,4 +613,45 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+    
+    mod poke_cursor_tests {
+        use super::*;
+        use chrono::Duration;
+        
+        fn create_event() -> PokeEvent {
+            let from = Username::new("sender".to_string()).unwrap();
+            let to = Username::new("recipient".to_string()).unwrap();
+            PokeEvent::new(from, to)
+        }
+        
+        #[test]
+        fn test_encode_decode_round_trip() {
+            let cursor = PokeCursor::from_event(&create_event());
+            
+            assert_eq!(PokeCursor::decode(&cursor.encode()), Ok(cursor));
+        }
+        
+        #[test]
+        fn test_decode_invalid_cursor() {
+            for input in ["", "123", "abc_not-a-uuid", "123_not-a-uuid"] {
+                assert!(PokeCursor::decode(input).is_err(), "{}", input);
+            }
+        }
+        
+        #[test]
+        fn test_is_followed_by() {
+            let event = create_event();
+            let cursor = PokeCursor::from_event(&event);
+            
+            let mut older = create_event();
+            older.occurred_at = event.occurred_at - Duration::seconds(1);
+            let mut newer = create_event();
+            newer.occurred_at = event.occurred_at + Duration::seconds(1);
+            
+            assert!(cursor.is_followed_by(&older));
+            assert!(!cursor.is_followed_by(&newer));
+            // カーソル自身は含まない
+            assert!(!cursor.is_followed_by(&event));
+        }
+    }
+}
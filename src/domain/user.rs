@@ -38,7 +38,10 @@ impl GitHubUserId {
 /// - 1〜39文字
 /// - 英数字とハイフンのみ
 /// - ハイフンで始まったり終わったりしない
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// 
+/// GitHubのユーザー名は大文字小文字を区別しないため、比較とハッシュも
+/// 大文字小文字を無視する。元の表記は表示用に保持する
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Username(String);
 
 impl Username {
@@ -72,6 +75,31 @@ impl Username {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+    
+    /// 小文字に正規化したユーザー名を取得
+    /// 
+    /// キャッシュキーやリポジトリの検索キーには、表示用の `as_str` ではなくこちらを使う
+    pub fn normalized(&self) -> String {
+        self.0.to_ascii_lowercase()
+    }
+}
+
+impl PartialEq for Username {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Username {}
+
+impl std::hash::Hash for Username {
+    /// `PartialEq` と一致させるため、小文字に正規化した値をハッシュする
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
 }
 
 impl Validated for Username {
@@ -131,6 +159,29 @@ pub struct RegisteredUser {
     /// Poke受信設定
     pub poke_setting: PokeSetting,
     
+    /// Pokeを受け付けない送信者
+    /// 
+    /// Poke受信設定に関係なく、ここに含まれるユーザーからのPokeは拒否する
+    #[serde(default)]
+    pub blocked_senders: Vec<Username>,
+    
+    /// インタラクティブバッジを許可するか
+    /// 
+    /// `false` の場合、Pokeできる状態でも常に静的バッジを表示する
+    /// （項目がない保存済みデータは許可として扱う）
+    #[serde(default = "default_allow_interactive_badge")]
+    pub allow_interactive_badge: bool,
+    
+    /// 通知用のメールアドレス（任意）
+    #[serde(default)]
+    pub email: Option<String>,
+    
+    /// Pokeを受信したときのメール通知方法
+    /// 
+    /// 明示的に有効にしたユーザーにのみ送る（項目がない保存済みデータは通知しない）
+    #[serde(default)]
+    pub notification_mode: NotificationMode,
+    
     /// アカウント作成日時
     pub created_at: DateTime<Utc>,
     
@@ -152,6 +203,10 @@ impl RegisteredUser {
             github_id,
             username,
             poke_setting: PokeSetting::default(),
+            blocked_senders: Vec::new(),
+            allow_interactive_badge: true,
+            email: None,
+            notification_mode: NotificationMode::default(),
             created_at: now,
             updated_at: now,
         }
@@ -166,6 +221,85 @@ impl RegisteredUser {
         self.updated_at = Utc::now();
     }
     
+    /// インタラクティブバッジの許可を更新
+    /// 
+    /// # Arguments
+    /// * `allow` - `false` で常に静的バッジを表示
+    pub fn set_allow_interactive_badge(&mut self, allow: bool) {
+        self.allow_interactive_badge = allow;
+        self.updated_at = Utc::now();
+    }
+    
+    /// 通知用のメールアドレスを更新
+    /// 
+    /// # Arguments
+    /// * `email` - メールアドレス（`None` で削除）
+    pub fn set_email(&mut self, email: Option<String>) {
+        self.email = email;
+        self.updated_at = Utc::now();
+    }
+    
+    /// Poke受信時のメール通知方法を更新
+    /// 
+    /// # Arguments
+    /// * `mode` - 新しい通知方法
+    pub fn set_notification_mode(&mut self, mode: NotificationMode) {
+        self.notification_mode = mode;
+        self.updated_at = Utc::now();
+    }
+    
+    /// Poke通知を送るメールアドレス
+    /// 
+    /// # Returns
+    /// * `Some(email)` - メール通知が有効で、アドレスが登録されている
+    /// * `None` - 通知しない
+    pub fn poke_notification_email(&self) -> Option<&str> {
+        if !self.notification_mode.is_enabled() {
+            return None;
+        }
+        self.email.as_deref()
+    }
+    
+    /// 送信者をブロック
+    /// 
+    /// # Arguments
+    /// * `sender` - ブロックする送信者
+    /// 
+    /// # Returns
+    /// * `true` - 追加した
+    /// * `false` - すでにブロック済み
+    pub fn block_sender(&mut self, sender: Username) -> bool {
+        if self.is_blocked(&sender) {
+            return false;
+        }
+        self.blocked_senders.push(sender);
+        self.updated_at = Utc::now();
+        true
+    }
+    
+    /// 送信者のブロックを解除
+    /// 
+    /// # Arguments
+    /// * `sender` - ブロックを解除する送信者
+    /// 
+    /// # Returns
+    /// * `true` - 解除した
+    /// * `false` - ブロックしていなかった
+    pub fn unblock_sender(&mut self, sender: &Username) -> bool {
+        let before = self.blocked_senders.len();
+        self.blocked_senders.retain(|blocked| blocked != sender);
+        if self.blocked_senders.len() == before {
+            return false;
+        }
+        self.updated_at = Utc::now();
+        true
+    }
+    
+    /// 送信者をブロックしているか
+    pub fn is_blocked(&self, sender: &Username) -> bool {
+        self.blocked_senders.contains(sender)
+    }
+    
     /// ユーザー名を更新
     /// 
     /// GitHubでユーザー名が変更された場合に使用
@@ -178,6 +312,11 @@ impl RegisteredUser {
     }
 }
 
+/// `allow_interactive_badge` の既定値
+fn default_allow_interactive_badge() -> bool {
+    true
+}
+
 /// Poke受信設定
 /// 
 /// どのユーザーからPokeを受け取るかの設定
@@ -220,6 +359,33 @@ impl PokeSetting {
     }
 }
 
+/// Poke受信時のメール通知方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationMode {
+    /// Pokeごとにすぐ通知
+    Immediate,
+    
+    /// 1日分のPokeをまとめて翌日に通知
+    DailyDigest,
+    
+    /// 通知しない
+    Off,
+}
+
+impl Default for NotificationMode {
+    /// デフォルトは通知しない
+    fn default() -> Self {
+        NotificationMode::Off
+    }
+}
+
+impl NotificationMode {
+    /// 通知を受け取るかどうか
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, NotificationMode::Off)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,6 +442,21 @@ mod tests {
             assert_eq!(username1, username2);
             assert_ne!(username1, username3);
         }
+
+        #[test]
+        fn test_username_is_case_insensitive() {
+            use std::collections::HashSet;
+
+            let upper = Username::parse("Octocat".to_string()).unwrap();
+            let lower = Username::parse("octocat".to_string()).unwrap();
+
+            assert_eq!(upper, lower);
+            assert_eq!(upper.normalized(), "octocat");
+            assert_eq!(upper.as_str(), "Octocat");
+
+            let set: HashSet<Username> = [upper, lower].into_iter().collect();
+            assert_eq!(set.len(), 1);
+        }
     }
 
     mod github_user_id_tests {
@@ -332,9 +513,53 @@ mod tests {
             assert_eq!(user.github_id.value(), 12345);
             assert_eq!(user.username.as_str(), "octocat");
             assert_eq!(user.poke_setting, PokeSetting::Anyone);
+            assert!(user.allow_interactive_badge);
             assert_eq!(user.created_at, user.updated_at);
         }
 
+        #[test]
+        fn test_missing_allow_interactive_badge_defaults_to_true() {
+            let username = Username::parse("octocat".to_string()).unwrap();
+            let mut json = serde_json::to_value(RegisteredUser::new(GitHubUserId::new(12345), username)).unwrap();
+            json.as_object_mut().unwrap().remove("allow_interactive_badge");
+
+            let user: RegisteredUser = serde_json::from_value(json).unwrap();
+
+            assert!(user.allow_interactive_badge);
+        }
+
+        #[test]
+        fn test_poke_notification_email_requires_opt_in() {
+            let username = Username::parse("octocat".to_string()).unwrap();
+            let mut user = RegisteredUser::new(GitHubUserId::new(12345), username);
+            assert_eq!(user.email, None);
+            assert_eq!(user.notification_mode, NotificationMode::Off);
+
+            user.set_email(Some("octocat@example.com".to_string()));
+            assert_eq!(user.poke_notification_email(), None);
+
+            user.set_notification_mode(NotificationMode::Immediate);
+            assert_eq!(user.poke_notification_email(), Some("octocat@example.com"));
+
+            user.set_notification_mode(NotificationMode::DailyDigest);
+            assert_eq!(user.poke_notification_email(), Some("octocat@example.com"));
+
+            user.set_email(None);
+            assert_eq!(user.poke_notification_email(), None);
+        }
+
+        #[test]
+        fn test_missing_email_fields_default_to_disabled() {
+            let username = Username::parse("octocat".to_string()).unwrap();
+            let mut json = serde_json::to_value(RegisteredUser::new(GitHubUserId::new(12345), username)).unwrap();
+            json.as_object_mut().unwrap().remove("email");
+            json.as_object_mut().unwrap().remove("notification_mode");
+
+            let user: RegisteredUser = serde_json::from_value(json).unwrap();
+            assert_eq!(user.email, None);
+            assert_eq!(user.notification_mode, NotificationMode::Off);
+        }
+
         #[test]
         fn test_update_poke_setting() {
             let username = Username::parse("octocat".to_string()).unwrap();
@@ -365,6 +590,23 @@ mod tests {
             assert_eq!(user.username.as_str(), "new-octocat");
             assert!(user.updated_at > original_updated_at);
         }
+
+        #[test]
+        fn test_block_and_unblock_sender() {
+            let username = Username::parse("octocat".to_string()).unwrap();
+            let mut user = RegisteredUser::new(GitHubUserId::new(12345), username);
+            let spammer = Username::parse("Spammer".to_string()).unwrap();
+
+            assert!(user.block_sender(spammer.clone()));
+            // 二重に追加しない
+            assert!(!user.block_sender(Username::parse("spammer".to_string()).unwrap()));
+            assert_eq!(user.blocked_senders.len(), 1);
+            assert!(user.is_blocked(&Username::parse("spammer".to_string()).unwrap()));
+
+            assert!(user.unblock_sender(&spammer));
+            assert!(!user.unblock_sender(&spammer));
+            assert!(!user.is_blocked(&spammer));
+        }
     }
 
     mod poke_setting_tests {
@@ -4,6 +4,7 @@
 //! - バリデーショントレイト
 //! - 共通バリデーションエラー
 //! - バリデーションヘルパー関数
+//! - 長さと文字種を組み合わせた文字列バリデーター
 
 use thiserror::Error;
 
@@ -35,6 +36,10 @@ pub enum ValidationError {
     /// 必須フィールドが空
     #[error("必須フィールドが空です: {field}")]
     Required { field: String },
+    
+    /// 予約された名前
+    #[error("予約された名前は使用できません: {name}")]
+    Reserved { name: String },
 }
 
 /// バリデーション可能な型のトレイト
@@ -98,6 +103,103 @@ pub fn validate_required(value: &str, field_name: &str) -> Result<(), Validation
     Ok(())
 }
 
+/// 文字列フィールドのバリデーター
+/// 
+/// 必須・長さ・文字種のチェックを組み合わせ、フィールドごとの検証を1か所で定義する。
+/// `const` で定義できるため、フィールドの制約を定数として共有できる
+/// 
+/// # Example
+/// ```ignore
+/// const LABEL: ValidatedString = ValidatedString::new("label")
+///     .required()
+///     .length(1, 32)
+///     .charset(|c| c.is_ascii_graphic());
+/// 
+/// LABEL.validate("streak")?;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ValidatedString {
+    /// フィールド名（エラーメッセージ用）
+    field: &'static str,
+    
+    /// 空文字列を拒否するか
+    required: bool,
+    
+    /// 長さの範囲（最小, 最大）
+    length: Option<(usize, usize)>,
+    
+    /// 使用できる文字の判定
+    charset: Option<fn(char) -> bool>,
+}
+
+impl ValidatedString {
+    /// 制約のないバリデーターを作成
+    /// 
+    /// # Arguments
+    /// * `field` - フィールド名（エラーメッセージ用）
+    pub const fn new(field: &'static str) -> Self {
+        Self {
+            field,
+            required: false,
+            length: None,
+            charset: None,
+        }
+    }
+    
+    /// 空文字列を拒否する（`validate_required`）
+    pub const fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+    
+    /// 長さを制限する（`validate_length`、バイト数で数える）
+    /// 
+    /// # Arguments
+    /// * `min` - 最小長
+    /// * `max` - 最大長
+    pub const fn length(mut self, min: usize, max: usize) -> Self {
+        self.length = Some((min, max));
+        self
+    }
+    
+    /// 使用できる文字を制限する
+    /// 
+    /// # Arguments
+    /// * `allowed` - 文字ごとの判定（`true` なら使用可）
+    pub const fn charset(mut self, allowed: fn(char) -> bool) -> Self {
+        self.charset = Some(allowed);
+        self
+    }
+    
+    /// 文字列を検証
+    /// 
+    /// 必須・長さ・文字種の順にチェックし、最初のエラーを返す
+    /// 
+    /// # Arguments
+    /// * `value` - 検証する文字列
+    /// 
+    /// # Returns
+    /// * `Ok(())` - すべての制約を満たす
+    /// * `Err(ValidationError)` - 最初に満たさなかった制約のエラー
+    pub fn validate(&self, value: &str) -> Result<(), ValidationError> {
+        if self.required {
+            validate_required(value, self.field)?;
+        }
+        
+        if let Some((min, max)) = self.length {
+            validate_length(value, self.field, min, max)?;
+        }
+        
+        if self.charset.is_some_and(|allowed| !value.chars().all(allowed)) {
+            return Err(ValidationError::InvalidCharacters {
+                field: self.field.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+}
+
 /// ASCII英数字とハイフンのバリデーション
 /// 
 /// GitHubユーザー名などで使用
@@ -127,11 +229,45 @@ pub fn validate_alphanumeric(
     Ok(())
 }
 
+/// GitHubが予約しているユーザー名
+/// 
+/// github.com/<name> がプロフィール以外のページになるため、実在するユーザーにならない
+const RESERVED_USERNAMES: &[&str] = &[
+    "about", "admin", "api", "blog", "contact", "dashboard", "enterprise", "explore",
+    "features", "help", "issues", "join", "login", "logout", "marketplace", "new",
+    "notifications", "organizations", "orgs", "pricing", "pulls", "search", "security",
+    "sessions", "settings", "signup", "site", "sponsors", "support", "topics", "trending",
+];
+
+/// 予約された名前でないことのバリデーション
+/// 
+/// 大文字・小文字は区別しない
+/// 
+/// # Arguments
+/// * `username` - 検証するユーザー名
+/// 
+/// # Returns
+/// * `Ok(())` - 予約されていない
+/// * `Err(ValidationError::Reserved)` - 予約された名前
+pub fn validate_not_reserved(username: &str) -> Result<(), ValidationError> {
+    let is_reserved = RESERVED_USERNAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(username));
+    
+    if is_reserved {
+        return Err(ValidationError::Reserved {
+            name: username.to_string(),
+        });
+    }
+    Ok(())
+}
+
 /// GitHubユーザー名の形式バリデーション
 /// 
 /// - 英数字とハイフンのみ
 /// - ハイフンで始まらない・終わらない
 /// - 連続するハイフンは不可
+/// - 予約された名前は不可
 /// 
 /// # Arguments
 /// * `username` - 検証するユーザー名
@@ -163,6 +299,89 @@ pub fn validate_github_username_format(username: &str) -> Result<(), ValidationE
         });
     }
     
+    // 予約された名前のチェック
+    validate_not_reserved(username)?;
+    
+    Ok(())
+}
+
+/// Pokeメッセージの最大文字数
+pub const MAX_POKE_MESSAGE_LENGTH: usize = 140;
+
+/// Pokeメッセージの正規化とバリデーション
+/// 
+/// 制御文字（改行を含む）を取り除き、前後の空白を削除した上で文字数を検証する
+/// 
+/// # Arguments
+/// * `message` - 送信者が入力したメッセージ
+/// 
+/// # Returns
+/// * `Ok(String)` - 正規化したメッセージ（空の場合もある）
+/// * `Err(ValidationError::InvalidLength)` - `MAX_POKE_MESSAGE_LENGTH` 文字を超える
+pub fn sanitize_poke_message(message: &str) -> Result<String, ValidationError> {
+    let sanitized: String = message.chars().filter(|c| !c.is_control()).collect();
+    let sanitized = sanitized.trim();
+    
+    if sanitized.chars().count() > MAX_POKE_MESSAGE_LENGTH {
+        return Err(ValidationError::InvalidLength {
+            field: "message".to_string(),
+            min: 0,
+            max: MAX_POKE_MESSAGE_LENGTH,
+        });
+    }
+    Ok(sanitized.to_string())
+}
+
+/// リポジトリ名の最大長
+const MAX_REPOSITORY_NAME_LENGTH: usize = 100;
+
+/// リポジトリコンテキスト（`owner/repo`）の最大長
+/// 
+/// ユーザー名の最大長（39文字）＋区切り文字＋リポジトリ名の最大長
+pub const MAX_REPO_CONTEXT_LENGTH: usize = 39 + 1 + MAX_REPOSITORY_NAME_LENGTH;
+
+/// リポジトリコンテキストのバリデーション
+/// 
+/// - `owner/repo` の形式（スラッシュはちょうど1つ）
+/// - ownerはGitHubユーザー名の形式
+/// - repoは英数字・ハイフン・アンダースコア・ドットのみ（`.` と `..` は不可）
+/// - 全体で最大 `MAX_REPO_CONTEXT_LENGTH` 文字
+/// 
+/// # Arguments
+/// * `context` - 検証する文字列（例: `octocat/hello-world`）
+/// 
+/// # Returns
+/// * `Ok(())` - 有効な形式
+/// * `Err(ValidationError)` - 無効な形式
+pub fn validate_repo_context(context: &str) -> Result<(), ValidationError> {
+    let invalid_format = || ValidationError::InvalidFormat {
+        field: "repository".to_string(),
+    };
+    
+    validate_required(context, "repository")?;
+    validate_length(context, "repository", 1, MAX_REPO_CONTEXT_LENGTH)?;
+    
+    let (owner, repo) = context.split_once('/').ok_or_else(invalid_format)?;
+    if repo.contains('/') {
+        return Err(invalid_format());
+    }
+    
+    // ownerはユーザー名または組織名
+    validate_github_username_format(owner).map_err(|_| invalid_format())?;
+    
+    validate_length(repo, "repository", 1, MAX_REPOSITORY_NAME_LENGTH)?;
+    let is_valid_repo = repo
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !is_valid_repo {
+        return Err(ValidationError::InvalidCharacters {
+            field: "repository".to_string(),
+        });
+    }
+    if repo == "." || repo == ".." {
+        return Err(invalid_format());
+    }
+    
     Ok(())
 }
 
@@ -253,6 +472,138 @@ mod tests {
         assert!(validate_github_username_format("user.name").is_err());
         assert!(validate_github_username_format("user@name").is_err());
         assert!(validate_github_username_format("a".repeat(40).as_str()).is_err());
+        assert!(validate_github_username_format("settings").is_err());
+    }
+    
+    #[test]
+    fn test_validate_not_reserved() {
+        assert!(validate_not_reserved("octocat").is_ok());
+        // 予約語を含むだけの名前は有効
+        assert!(validate_not_reserved("admin-bot").is_ok());
+        
+        assert_eq!(
+            validate_not_reserved("admin"),
+            Err(ValidationError::Reserved { name: "admin".to_string() })
+        );
+        // 大文字・小文字は区別しない
+        assert_eq!(
+            validate_not_reserved("API"),
+            Err(ValidationError::Reserved { name: "API".to_string() })
+        );
+    }
+    
+    #[test]
+    fn test_sanitize_poke_message() {
+        // 正常系
+        assert_eq!(sanitize_poke_message("Ship it!").unwrap(), "Ship it!");
+        // 制御文字と前後の空白は取り除く
+        assert_eq!(sanitize_poke_message("  hi\u{0}\n there\t ").unwrap(), "hi there");
+        // 文字数はバイト数ではなく文字単位で数える
+        assert!(sanitize_poke_message(&"あ".repeat(MAX_POKE_MESSAGE_LENGTH)).is_ok());
+        
+        // 異常系
+        assert!(matches!(
+            sanitize_poke_message(&"a".repeat(MAX_POKE_MESSAGE_LENGTH + 1)),
+            Err(ValidationError::InvalidLength { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_validate_repo_context() {
+        // 正常系
+        assert!(validate_repo_context("octocat/hello-world").is_ok());
+        assert!(validate_repo_context("octo-org/my_repo.rs").is_ok());
+        
+        // 異常系：形式
+        assert_eq!(
+            validate_repo_context("just-owner"),
+            Err(ValidationError::InvalidFormat { field: "repository".to_string() })
+        );
+        assert!(validate_repo_context("octocat/").is_err());
+        assert!(validate_repo_context("/hello-world").is_err());
+        assert!(validate_repo_context("octocat/hello/world").is_err());
+        assert!(validate_repo_context("-octocat/hello-world").is_err());
+        assert!(validate_repo_context("octocat/..").is_err());
+        assert!(validate_repo_context("octocat/hello world").is_err());
+        
+        // 異常系：長さ
+        let too_long = format!("octocat/{}", "a".repeat(MAX_REPO_CONTEXT_LENGTH));
+        assert!(matches!(
+            validate_repo_context(&too_long),
+            Err(ValidationError::InvalidLength { .. })
+        ));
+    }
+    
+    mod validated_string_tests {
+        use super::*;
+        
+        /// バッジラベル：1〜32文字、空白以外の印字可能なASCII
+        const LABEL: ValidatedString = ValidatedString::new("label")
+            .required()
+            .length(1, 32)
+            .charset(|c| c.is_ascii_graphic());
+        
+        /// メッセージ：1〜140文字、空白を含む印字可能なASCII
+        const MESSAGE: ValidatedString = ValidatedString::new("message")
+            .required()
+            .length(1, 140)
+            .charset(|c| c.is_ascii_graphic() || c == ' ');
+        
+        #[test]
+        fn test_label_validator() {
+            // 正常系
+            assert!(LABEL.validate("streak").is_ok());
+            assert!(LABEL.validate("a").is_ok());
+            assert!(LABEL.validate(&"a".repeat(32)).is_ok());
+            assert!(LABEL.validate("v1.0-beta_2").is_ok());
+            
+            // 異常系：空の場合は長さより先に必須エラー
+            assert_eq!(
+                LABEL.validate(""),
+                Err(ValidationError::Required { field: "label".to_string() })
+            );
+            assert_eq!(
+                LABEL.validate(&"a".repeat(33)),
+                Err(ValidationError::InvalidLength { field: "label".to_string(), min: 1, max: 32 })
+            );
+            assert_eq!(
+                LABEL.validate("my label"),
+                Err(ValidationError::InvalidCharacters { field: "label".to_string() })
+            );
+            assert!(LABEL.validate("tab\there").is_err());
+            assert!(LABEL.validate("ラベル").is_err());
+            
+            // 長さと文字種の両方に違反する場合は長さのエラー
+            assert!(matches!(
+                LABEL.validate(&"あ".repeat(32)),
+                Err(ValidationError::InvalidLength { .. })
+            ));
+        }
+        
+        #[test]
+        fn test_message_validator_allows_spaces() {
+            assert!(MESSAGE.validate("Ship it!").is_ok());
+            assert!(MESSAGE.validate("Time to push some commits, friend").is_ok());
+            assert!(MESSAGE.validate(" ").is_ok());
+            
+            assert_eq!(
+                MESSAGE.validate(""),
+                Err(ValidationError::Required { field: "message".to_string() })
+            );
+            assert!(MESSAGE.validate(&"a".repeat(141)).is_err());
+            assert_eq!(
+                MESSAGE.validate("line\nbreak"),
+                Err(ValidationError::InvalidCharacters { field: "message".to_string() })
+            );
+        }
+        
+        #[test]
+        fn test_unconstrained_validator_accepts_anything() {
+            let validator = ValidatedString::new("note");
+            
+            assert!(validator.validate("").is_ok());
+            assert!(validator.validate("何でも\n").is_ok());
+        }
     }
     
     #[test]
@@ -276,5 +627,8 @@ mod tests {
         
         let err = ValidationError::Required { field: "username".to_string() };
         assert_eq!(err.to_string(), "必須フィールドが空です: username");
+        
+        let err = ValidationError::Reserved { name: "admin".to_string() };
+        assert_eq!(err.to_string(), "予約された名前は使用できません: admin");
     }
 }
\ No newline at end of file
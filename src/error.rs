@@ -5,15 +5,19 @@
 //! - 各層のエラー型の定義（ドメイン層、インフラ層、ハンドラー層）
 //! - エラー型間の変換実装
 //! - HTTPステータスコードへのマッピング
+//! - RFC 7807（problem+json）形式のエラー表現
 
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 use thiserror::Error;
 
+use crate::domain::validation::ValidationError;
+
 /// アプリケーション全体の結果型エイリアス
 pub type AppResult<T> = Result<T, AppError>;
 
@@ -58,7 +62,10 @@ pub enum DomainError {
     
     /// レート制限超過
     #[error("レート制限を超過しました")]
-    RateLimitExceeded,
+    RateLimitExceeded {
+        /// 再試行可能になるまでの秒数
+        retry_after_secs: u64,
+    },
     
     /// 無効なアクティビティ状態
     #[error("無効なアクティビティ状態")]
@@ -66,16 +73,26 @@ pub enum DomainError {
 }
 
 /// Poke機能に関するエラー
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum PokeError {
     /// 受信者が登録されていない
     #[error("受信者が登録されていません")]
     RecipientNotRegistered,
     
+    /// 自分自身へのPoke
+    #[error("自分自身にはPokeできません")]
+    CannotPokeSelf,
+    
     /// 受信者がPokeを無効にしている
     #[error("受信者がPokeを無効にしています")]
     RecipientDisabled,
     
+    /// 受信者が送信者をブロックしている
+    /// 
+    /// ブロックされたことが分からないよう、メッセージでは理由を明かさない
+    #[error("このユーザーにはPokeできません")]
+    SenderBlocked,
+    
     /// フォロワーではない
     #[error("フォロワーではありません")]
     NotFollower,
@@ -84,8 +101,8 @@ pub enum PokeError {
     #[error("相互フォローではありません")]
     NotMutualFollower,
     
-    /// 同一ユーザーへの重複Poke
-    #[error("本日すでにPokeしています")]
+    /// クールダウン期間内の同一ユーザーへの重複Poke
+    #[error("このユーザーにはすでにPokeしています。しばらく待ってから再度お試しください")]
     AlreadyPoked,
 }
 
@@ -98,13 +115,23 @@ pub enum InfraError {
     #[error("GitHub APIエラー: {0}")]
     GitHubApi(#[from] octocrab::Error),
     
+    /// GitHub APIの応答エラー（GraphQLのエラーや想定外のステータス）
+    #[error("GitHub APIの応答エラー: {0}")]
+    GitHubResponse(String),
+    
     /// データベースエラー
     #[error("データベースエラー: {0}")]
     Database(String),
     
     /// キャッシュエラー
+    /// 
+    /// 接続プールが返すエラーと型を揃えるため、deadpool_redis が再エクスポートする redis を使う
     #[error("キャッシュエラー: {0}")]
-    Cache(#[from] redis::RedisError),
+    Cache(#[from] deadpool_redis::redis::RedisError),
+    
+    /// キャッシュ接続プールエラー
+    #[error("キャッシュ接続エラー: {0}")]
+    CachePool(#[from] deadpool_redis::PoolError),
     
     /// ストレージエラー
     #[error("ストレージエラー: {0}")]
@@ -143,20 +170,114 @@ pub enum HandlerError {
     /// リクエストタイムアウト
     #[error("リクエストタイムアウト")]
     Timeout,
+    
+    /// ハンドラー・ミドルウェア内部の失敗
+    /// 
+    /// 詳細はログにのみ出力し、クライアントには汎用メッセージを返す
+    #[error("内部サーバーエラー: {0}")]
+    InternalServerError(String),
 }
 
-/// AppErrorをHTTPレスポンスに変換
+impl PokeError {
+    /// クライアントが分岐に使うエラーコード
+    /// 
+    /// 一度公開したコードは変更しない
+    pub fn code(&self) -> &'static str {
+        match self {
+            PokeError::RecipientNotRegistered => "RECIPIENT_NOT_REGISTERED",
+            PokeError::CannotPokeSelf => "CANNOT_POKE_SELF",
+            PokeError::RecipientDisabled => "RECIPIENT_DISABLED",
+            // ブロックされたことが分からないよう、専用のコードは返さない
+            PokeError::SenderBlocked => "POKE_NOT_ALLOWED",
+            PokeError::NotFollower => "NOT_FOLLOWER",
+            PokeError::NotMutualFollower => "NOT_MUTUAL_FOLLOWER",
+            PokeError::AlreadyPoked => "POKE_COOLDOWN",
+        }
+    }
+}
+
+impl DomainError {
+    /// クライアントが分岐に使うエラーコード
+    /// 
+    /// Pokeが許可されていない場合は具体的な理由のコードを返す
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::InvalidUsername(_) => "INVALID_USERNAME",
+            DomainError::UserNotFound(_) => "USER_NOT_FOUND",
+            DomainError::PokeNotAllowed(reason) => reason.code(),
+            DomainError::RateLimitExceeded { .. } => "RATE_LIMITED",
+            DomainError::InvalidActivityState => "INVALID_ACTIVITY_STATE",
+        }
+    }
+}
+
+impl HandlerError {
+    /// クライアントが分岐に使うエラーコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            HandlerError::BadRequest(_) => "BAD_REQUEST",
+            HandlerError::NotFound(_) => "NOT_FOUND",
+            HandlerError::Unauthorized => "UNAUTHORIZED",
+            HandlerError::Forbidden => "FORBIDDEN",
+            HandlerError::Timeout => "TIMEOUT",
+            HandlerError::InternalServerError(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+/// RFC 7807のproblem+jsonのContent-Type
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+/// RFC 7807のproblem document
 /// 
-/// エラーの種類に応じて適切なステータスコードとJSONレスポンスを返す
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match &self {
+/// `AppError` のレスポンスには常に拡張として付与され、
+/// クライアントが `Accept: application/problem+json` を指定した場合に
+/// ミドルウェアがボディをこの形式に差し替える
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProblemDetails {
+    /// エラー種別を表す安定したURI
+    #[serde(rename = "type")]
+    pub problem_type: &'static str,
+    
+    /// エラー種別の要約（種別ごとに固定）
+    pub title: &'static str,
+    
+    /// HTTPステータスコード
+    pub status: u16,
+    
+    /// この発生に固有の説明
+    pub detail: String,
+    
+    /// エラーコード（拡張メンバー）
+    pub code: &'static str,
+}
+
+impl AppError {
+    /// クライアントが分岐に使うエラーコード
+    /// 
+    /// メッセージと同様、内部の詳細を隠蔽するエラーは汎用のコードになる
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Domain(DomainError::InvalidActivityState) | AppError::Internal(_) => {
+                "INTERNAL_ERROR"
+            }
+            AppError::Domain(e) => e.code(),
+            AppError::Handler(e) => e.code(),
+            AppError::Infra(_) => "SERVICE_UNAVAILABLE",
+        }
+    }
+    
+    /// HTTPステータスコードとクライアント向けメッセージを取得
+    /// 
+    /// インフラエラー・内部エラーは詳細を隠蔽する
+    fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
             // ドメインエラーのマッピング
             AppError::Domain(e) => match e {
                 DomainError::InvalidUsername(_) => (StatusCode::BAD_REQUEST, e.to_string()),
                 DomainError::UserNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
                 DomainError::PokeNotAllowed(_) => (StatusCode::FORBIDDEN, e.to_string()),
-                DomainError::RateLimitExceeded => (StatusCode::TOO_MANY_REQUESTS, e.to_string()),
+                DomainError::RateLimitExceeded { .. } => (StatusCode::TOO_MANY_REQUESTS, e.to_string()),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "内部エラーが発生しました".to_string()),
             },
             
@@ -167,6 +288,10 @@ impl IntoResponse for AppError {
                 HandlerError::Unauthorized => (StatusCode::UNAUTHORIZED, e.to_string()),
                 HandlerError::Forbidden => (StatusCode::FORBIDDEN, e.to_string()),
                 HandlerError::Timeout => (StatusCode::REQUEST_TIMEOUT, e.to_string()),
+                HandlerError::InternalServerError(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "内部エラーが発生しました".to_string()
+                ),
             },
             
             // インフラエラーは詳細を隠蔽
@@ -180,17 +305,118 @@ impl IntoResponse for AppError {
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "内部エラーが発生しました".to_string()
             ),
-        };
+        }
+    }
+    
+    /// problem+jsonの `type` と `title` を取得
+    /// 
+    /// `type` はエラーの分類ごとに固定で、クライアントが分岐に使えるよう変更しない
+    fn problem_type(&self) -> (&'static str, &'static str) {
+        match self {
+            AppError::Domain(DomainError::InvalidUsername(_))
+            | AppError::Handler(HandlerError::BadRequest(_)) => {
+                ("urn:gitpoke:problem:invalid-request", "無効なリクエスト")
+            }
+            AppError::Domain(DomainError::UserNotFound(_))
+            | AppError::Handler(HandlerError::NotFound(_)) => {
+                ("urn:gitpoke:problem:not-found", "リソースが見つかりません")
+            }
+            AppError::Domain(DomainError::PokeNotAllowed(_)) => {
+                ("urn:gitpoke:problem:poke-not-allowed", "Pokeが許可されていません")
+            }
+            AppError::Domain(DomainError::RateLimitExceeded { .. }) => {
+                ("urn:gitpoke:problem:rate-limited", "レート制限を超過しました")
+            }
+            AppError::Handler(HandlerError::Unauthorized) => {
+                ("urn:gitpoke:problem:unauthorized", "認証が必要です")
+            }
+            AppError::Handler(HandlerError::Forbidden) => {
+                ("urn:gitpoke:problem:forbidden", "権限がありません")
+            }
+            AppError::Handler(HandlerError::Timeout) => {
+                ("urn:gitpoke:problem:timeout", "リクエストタイムアウト")
+            }
+            AppError::Infra(_) => {
+                ("urn:gitpoke:problem:service-unavailable", "サービスが一時的に利用できません")
+            }
+            AppError::Domain(DomainError::InvalidActivityState)
+            | AppError::Handler(HandlerError::InternalServerError(_))
+            | AppError::Internal(_) => {
+                ("urn:gitpoke:problem:internal", "内部エラー")
+            }
+        }
+    }
+}
+
+/// AppErrorをHTTPレスポンスに変換
+/// 
+/// エラーの種類に応じて適切なステータスコードとJSONレスポンスを返す
+/// problem+json形式への切り替えは `middlewares::problem_json` が行う
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        // クライアントに隠蔽する詳細はログに残す
+        if let AppError::Handler(HandlerError::InternalServerError(detail)) = &self {
+            tracing::error!("内部サーバーエラー: {}", detail);
+        }
+        
+        let (status, error_message) = self.status_and_message();
+        let (problem_type, title) = self.problem_type();
         
         // エラーレスポンスのJSON形式
+        // codeはHTTPステータス、error_codeはクライアントが分岐に使うエラーコード
         let body = Json(json!({
             "error": {
                 "message": error_message,
                 "code": status.as_u16(),
+                "error_code": self.code(),
             }
         }));
         
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        
+        // レート制限時は再試行までの秒数を通知
+        if let AppError::Domain(DomainError::RateLimitExceeded { retry_after_secs }) = &self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(*retry_after_secs));
+        }
+        
+        response.extensions_mut().insert(ProblemDetails {
+            problem_type,
+            title,
+            status: status.as_u16(),
+            detail: error_message,
+            code: self.code(),
+        });
+        
+        response
+    }
+}
+
+/// HandlerErrorをHTTPレスポンスに変換
+/// 
+/// extractorのRejectionとして直接返せるよう、`AppError` と同じ形式にする
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        AppError::from(self).into_response()
+    }
+}
+
+/// バリデーションエラーの変換
+/// 
+/// 入力の不備として400 Bad Requestにし、どの検証に失敗したかをメッセージに残す
+impl From<ValidationError> for HandlerError {
+    fn from(err: ValidationError) -> Self {
+        HandlerError::BadRequest(err.to_string())
+    }
+}
+
+/// バリデーションエラーの変換
+/// 
+/// ハンドラーで `?` を使えるよう `HandlerError::BadRequest` を経由する
+impl From<ValidationError> for AppError {
+    fn from(err: ValidationError) -> Self {
+        AppError::Handler(err.into())
     }
 }
 
@@ -215,4 +441,117 @@ impl From<std::io::Error> for AppError {
     fn from(err: std::io::Error) -> Self {
         AppError::Internal(format!("IOエラー: {}", err))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rate_limit_response_has_retry_after() {
+        let error: AppError = DomainError::RateLimitExceeded { retry_after_secs: 42 }.into();
+        let response = error.into_response();
+        
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "42");
+    }
+    
+    #[test]
+    fn test_poke_error_codes() {
+        let cases = [
+            (PokeError::RecipientNotRegistered, "RECIPIENT_NOT_REGISTERED"),
+            (PokeError::CannotPokeSelf, "CANNOT_POKE_SELF"),
+            (PokeError::RecipientDisabled, "RECIPIENT_DISABLED"),
+            (PokeError::SenderBlocked, "POKE_NOT_ALLOWED"),
+            (PokeError::NotFollower, "NOT_FOLLOWER"),
+            (PokeError::NotMutualFollower, "NOT_MUTUAL_FOLLOWER"),
+            (PokeError::AlreadyPoked, "POKE_COOLDOWN"),
+        ];
+        
+        for (error, code) in cases {
+            assert_eq!(error.code(), code, "{:?}", error);
+            // Pokeが許可されない理由はドメインエラーでもそのまま使う
+            assert_eq!(DomainError::PokeNotAllowed(error).code(), code);
+        }
+    }
+    
+    #[test]
+    fn test_domain_error_codes() {
+        let cases = [
+            (DomainError::InvalidUsername("-bad".to_string()), "INVALID_USERNAME"),
+            (DomainError::UserNotFound("octocat".to_string()), "USER_NOT_FOUND"),
+            (DomainError::PokeNotAllowed(PokeError::NotFollower), "NOT_FOLLOWER"),
+            (DomainError::RateLimitExceeded { retry_after_secs: 1 }, "RATE_LIMITED"),
+            (DomainError::InvalidActivityState, "INVALID_ACTIVITY_STATE"),
+        ];
+        
+        for (error, code) in cases {
+            assert_eq!(error.code(), code, "{:?}", error);
+        }
+    }
+    
+    #[test]
+    fn test_hidden_errors_use_generic_code() {
+        let activity: AppError = DomainError::InvalidActivityState.into();
+        let internal = AppError::Internal("boom".to_string());
+        let infra: AppError = InfraError::Database("timeout".to_string()).into();
+        
+        assert_eq!(activity.code(), "INTERNAL_ERROR");
+        assert_eq!(internal.code(), "INTERNAL_ERROR");
+        assert_eq!(infra.code(), "SERVICE_UNAVAILABLE");
+    }
+    
+    #[tokio::test]
+    async fn test_error_body_includes_error_code() {
+        let error: AppError = DomainError::PokeNotAllowed(PokeError::AlreadyPoked).into();
+        let response = error.into_response();
+        
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], 403);
+        assert_eq!(body["error"]["error_code"], "POKE_COOLDOWN");
+        assert_eq!(body["error"]["message"], "Pokeが許可されていません");
+    }
+    
+    #[tokio::test]
+    async fn test_validation_error_is_bad_request_with_reason() {
+        let error: AppError = ValidationError::InvalidFormat { field: "username".to_string() }.into();
+        assert_eq!(error.code(), "BAD_REQUEST");
+        
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body["error"]["message"],
+            "無効なリクエスト: 無効な形式: usernameの形式が正しくありません"
+        );
+    }
+    
+    #[tokio::test]
+    async fn test_internal_server_error_hides_detail() {
+        let error = HandlerError::InternalServerError("Session lookup failed".to_string());
+        assert_eq!(error.code(), "INTERNAL_ERROR");
+        
+        // extractorのRejectionとして返された場合も同じレスポンスになる
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.extensions().get::<ProblemDetails>().unwrap().problem_type, "urn:gitpoke:problem:internal");
+        
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], 500);
+        assert_eq!(body["error"]["message"], "内部エラーが発生しました");
+        assert!(!body.to_string().contains("Session lookup failed"));
+    }
+    
+    #[test]
+    fn test_other_errors_have_no_retry_after() {
+        let error: AppError = HandlerError::NotFound("user".to_string()).into();
+        let response = error.into_response();
+        
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    }
+}
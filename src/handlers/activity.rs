@@ -0,0 +1,256 @@
+//! アクティビティ取得ハンドラー
+//! 
+//! このファイルは以下を定義：
+//! - バッジの元になるGitHubアクティビティをJSONで返すエンドポイント
+//! 
+//! 独自の可視化を作りたい開発者向け。取得・キャッシュはバッジと同じ経路を通る。
+//! ダッシュボードからのポーリングに備えて、取得日時による条件付きGETに対応する
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, SubsecRound, Utc};
+
+use crate::app::dependencies::AppDependencies;
+use crate::domain::github::GitHubActivity;
+use crate::domain::user::Username;
+use crate::error::AppResult;
+use crate::handlers::utils::{format_http_date, parse_http_date};
+use crate::use_cases::generate_badge as use_case;
+
+/// アクティビティ取得エンドポイント
+/// 
+/// GET /api/activity/:username
+/// 
+/// `Last-Modified` にはアクティビティの取得日時を返す
+/// 
+/// # Arguments
+/// * `username` - GitHubユーザー名
+/// * `deps` - アプリケーション依存性
+/// * `headers` - リクエストヘッダー（If-Modified-Sinceの判定に使用）
+/// 
+/// # Returns
+/// * 200 OK - アクティビティ（日別のコントリビューション数があれば含む）
+/// * 304 Not Modified - If-Modified-Since以降に取得し直していない
+/// * 400 Bad Request - 無効なユーザー名
+/// * 404 Not Found - GitHubに存在しないユーザー
+pub async fn get_activity(
+    Path(username): Path<String>,
+    State(deps): State<AppDependencies>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    // ユーザー名のバリデーション
+    let username = Username::parse(username)?;
+    
+    // HTTP日付として解釈できない値は、ヘッダーがないものとして扱う
+    let if_modified_since = headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_http_date);
+    
+    // バッジと同じキャッシュを使う（キャッシュ切れの場合はGitHub APIから取得して保存）
+    let activity = use_case::get_github_activity(&username, &deps).await?;
+    
+    Ok(build_activity_response(activity, if_modified_since))
+}
+
+/// アクティビティのレスポンスを構築
+/// 
+/// HTTP日付は秒単位のため、取得日時の秒未満を切り捨ててから比較する
+/// 
+/// # Arguments
+/// * `activity` - 返すアクティビティ
+/// * `if_modified_since` - クライアントが持っているデータの日時
+fn build_activity_response(
+    activity: GitHubActivity,
+    if_modified_since: Option<DateTime<Utc>>,
+) -> Response {
+    let last_modified = activity.fetched_at.trunc_subsecs(0);
+    let last_modified_header = [(header::LAST_MODIFIED, format_http_date(last_modified))];
+    
+    // クライアントが最新のデータを持っていればボディなしで返す
+    if if_modified_since.is_some_and(|since| since >= last_modified) {
+        return (StatusCode::NOT_MODIFIED, last_modified_header).into_response();
+    }
+    
+    (last_modified_header, Json(activity)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use axum::response::IntoResponse;
+    use axum::http::StatusCode;
+    use chrono::{Duration, TimeZone};
+    use serde_json::json;
+    use crate::app::dependencies::mocks::{MockGitHubApi, TestDependenciesBuilder};
+    use crate::error::{AppError, HandlerError};
+    
+    /// `octocat` のみGitHubに存在する依存性
+    fn octocat_deps() -> AppDependencies {
+        TestDependenciesBuilder::new()
+            .with_activity(GitHubActivity {
+                username: "octocat".to_string(),
+                last_activity_at: Some(Utc::now()),
+                current_streak_days: Some(2),
+                contributions: Some(HashMap::from([("2024-05-01".to_string(), 3)])),
+                total_contributions: Some(3),
+                tz_offset_seconds: None,
+                fetched_at: Utc::now(),
+            })
+            .build()
+    }
+    
+    async fn request(deps: &AppDependencies, username: &str) -> AppResult<Response> {
+        request_if_modified_since(deps, username, None).await
+    }
+    
+    async fn request_if_modified_since(
+        deps: &AppDependencies,
+        username: &str,
+        if_modified_since: Option<&str>,
+    ) -> AppResult<Response> {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = if_modified_since {
+            headers.insert(header::IF_MODIFIED_SINCE, value.parse().unwrap());
+        }
+        get_activity(Path(username.to_string()), State(deps.clone()), headers).await
+    }
+    
+    async fn read_activity(response: Response) -> GitHubActivity {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+    
+    fn last_modified(response: &Response) -> &str {
+        response.headers().get(header::LAST_MODIFIED).unwrap().to_str().unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_fresh_fetch_is_cached() {
+        let mut deps = octocat_deps();
+        
+        let activity = read_activity(request(&deps, "octocat").await.unwrap()).await;
+        
+        assert_eq!(activity.username, "octocat");
+        assert_eq!(activity.contributions.unwrap()["2024-05-01"], 3);
+        assert!(deps.cache_service.get("activity:octocat").await.unwrap().is_some());
+        
+        // 2回目はGitHub APIを呼ばずにキャッシュから返す
+        deps.github_api = Arc::new(MockGitHubApi::new());
+        let activity = read_activity(request(&deps, "Octocat").await.unwrap()).await;
+        assert_eq!(activity.current_streak_days, Some(2));
+    }
+    
+    #[tokio::test]
+    async fn test_cached_activity_is_returned() {
+        let deps = octocat_deps();
+        let cached_at = Utc::now() - Duration::minutes(1);
+        let cached = json!({
+            "activity": {
+                "username": "hubot",
+                "last_activity_at": cached_at,
+                "current_streak_days": 7,
+                "total_contributions": 1234,
+                "fetched_at": cached_at,
+            },
+            "cached_at": cached_at,
+            "soft_expires_at": cached_at + Duration::hours(1),
+        });
+        deps.cache_service
+            .set("activity:hubot", &cached.to_string(), 3600)
+            .await
+            .unwrap();
+        
+        // hubotはGitHub APIに存在しないので、キャッシュからしか返せない
+        let activity = read_activity(request(&deps, "hubot").await.unwrap()).await;
+        
+        assert_eq!(activity.current_streak_days, Some(7));
+        assert_eq!(activity.total_contributions, Some(1234));
+    }
+    
+    /// 取得日時が固定されたキャッシュ済みアクティビティを用意する
+    async fn cache_hubot(deps: &AppDependencies, fetched_at: DateTime<Utc>) {
+        let cached = json!({
+            "activity": {
+                "username": "hubot",
+                "current_streak_days": 7,
+                "fetched_at": fetched_at,
+            },
+            "cached_at": Utc::now(),
+            "soft_expires_at": Utc::now() + Duration::hours(1),
+        });
+        deps.cache_service
+            .set("activity:hubot", &cached.to_string(), 3600)
+            .await
+            .unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_last_modified_is_fetched_at() {
+        let deps = octocat_deps();
+        let fetched_at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 15).unwrap() + Duration::milliseconds(250);
+        cache_hubot(&deps, fetched_at).await;
+        
+        let response = request(&deps, "hubot").await.unwrap();
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(last_modified(&response), "Wed, 01 May 2024 12:30:15 GMT");
+    }
+    
+    #[tokio::test]
+    async fn test_up_to_date_client_gets_not_modified() {
+        let deps = octocat_deps();
+        let fetched_at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 15).unwrap() + Duration::milliseconds(250);
+        cache_hubot(&deps, fetched_at).await;
+        
+        // 取得日時と同じ（秒未満は切り捨て）・より新しい日時はどちらも最新
+        for since in ["Wed, 01 May 2024 12:30:15 GMT", "Wed, 01 May 2024 13:00:00 GMT"] {
+            let response = request_if_modified_since(&deps, "hubot", Some(since)).await.unwrap();
+            
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+            assert_eq!(last_modified(&response), "Wed, 01 May 2024 12:30:15 GMT");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert!(body.is_empty());
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_stale_client_gets_body() {
+        let deps = octocat_deps();
+        let fetched_at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 15).unwrap();
+        cache_hubot(&deps, fetched_at).await;
+        
+        // 古い日時・解釈できない日時はどちらもボディを返す
+        for since in ["Wed, 01 May 2024 12:30:14 GMT", "yesterday"] {
+            let response = request_if_modified_since(&deps, "hubot", Some(since)).await.unwrap();
+            
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(read_activity(response).await.current_streak_days, Some(7));
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_invalid_username() {
+        let deps = octocat_deps();
+        
+        let result = request(&deps, "-invalid-").await;
+        
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::BadRequest(_)))));
+        assert_eq!(result.unwrap_err().into_response().status(), StatusCode::BAD_REQUEST);
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_user() {
+        let deps = octocat_deps();
+        
+        let result = request(&deps, "ghost").await;
+        
+        assert_eq!(result.unwrap_err().into_response().status(), StatusCode::NOT_FOUND);
+    }
+}
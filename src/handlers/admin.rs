@@ -0,0 +1,263 @@
+//! 管理用ハンドラー
+//! 
+//! このファイルは以下を定義：
+//! - バッジキャッシュのウォームアップエンドポイント
+//! - 共有シークレットによる管理用リクエストの認可
+//! 
+//! シークレットはボディを解析する前に検証し、検証できないリクエストは中身を読まずに拒否する
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::dependencies::AppDependencies;
+use crate::app::shutdown::spawn_background;
+use crate::domain::user::Username;
+use crate::error::{AppResult, HandlerError};
+use crate::handlers::badge::warm_badge;
+use crate::handlers::utils::constant_time_eq;
+
+/// 共有シークレットのヘッダー
+const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// 1回のウォームアップで指定できるユーザーの最大数
+const MAX_WARM_USERNAMES: usize = 100;
+
+/// ウォームアップリクエスト
+#[derive(Debug, Deserialize)]
+pub struct WarmRequest {
+    /// 対象のユーザー名（大文字小文字違いの重複は1件として扱う）
+    pub usernames: Vec<String>,
+}
+
+/// ウォームアップレスポンス
+#[derive(Debug, Serialize)]
+pub struct WarmResponse {
+    /// ウォームアップを開始したユーザー数
+    pub queued: usize,
+}
+
+/// バッジキャッシュのウォームアップエンドポイント
+/// 
+/// POST /api/admin/warm
+/// 
+/// 指定されたユーザーのアクティビティと既定の見た目のバッジを
+/// バックグラウンドで生成し、キャッシュに保存する
+/// 
+/// # Returns
+/// * 202 Accepted - ウォームアップを開始
+/// * 400 Bad Request - ボディが不正・ユーザーが空・上限超過・無効なユーザー名
+/// * 401 Unauthorized - シークレットがない・一致しない
+pub async fn warm_badges(
+    State(deps): State<AppDependencies>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<(StatusCode, Json<WarmResponse>)> {
+    authorize(deps.config.app.admin_token.as_deref(), &headers)?;
+    
+    let request: WarmRequest = serde_json::from_slice(&body)
+        .map_err(|_| HandlerError::BadRequest("Invalid request body".to_string()))?;
+    let usernames = parse_warm_usernames(request.usernames)?;
+    let queued = usernames.len();
+    
+    spawn_background(async move {
+        warm_usernames(&usernames, &deps).await;
+    });
+    
+    Ok((StatusCode::ACCEPTED, Json(WarmResponse { queued })))
+}
+
+/// 共有シークレットを検証
+/// 
+/// # Arguments
+/// * `token` - 設定された共有シークレット（未設定の場合はすべて拒否）
+/// * `headers` - リクエストヘッダー
+/// 
+/// # Returns
+/// * `Ok(())` - シークレットが一致
+/// * `Err(HandlerError::Unauthorized)` - シークレットがない・一致しない
+fn authorize(token: Option<&str>, headers: &HeaderMap) -> Result<(), HandlerError> {
+    let Some(token) = token else {
+        tracing::warn!("ADMIN_TOKEN が未設定のため管理用リクエストを拒否しました");
+        return Err(HandlerError::Unauthorized);
+    };
+    
+    let provided = headers
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(HandlerError::Unauthorized)?;
+    
+    if !constant_time_eq(token.as_bytes(), provided.as_bytes()) {
+        return Err(HandlerError::Unauthorized);
+    }
+    
+    Ok(())
+}
+
+/// ウォームアップ対象のユーザー名を検証し、重複を除く
+/// 
+/// # Returns
+/// * `Ok(usernames)` - 最初の表記を残したユーザー名
+/// * `Err(HandlerError::BadRequest)` - 空・上限超過・無効なユーザー名
+fn parse_warm_usernames(usernames: Vec<String>) -> AppResult<Vec<Username>> {
+    let mut parsed: Vec<Username> = Vec::with_capacity(usernames.len());
+    for username in usernames {
+        let username = Username::parse(username)?;
+        if !parsed.contains(&username) {
+            parsed.push(username);
+        }
+    }
+    
+    if parsed.is_empty() {
+        return Err(HandlerError::BadRequest("At least one username is required".to_string()).into());
+    }
+    if parsed.len() > MAX_WARM_USERNAMES {
+        return Err(HandlerError::BadRequest(format!(
+            "At most {} usernames can be warmed at once",
+            MAX_WARM_USERNAMES
+        )).into());
+    }
+    
+    Ok(parsed)
+}
+
+/// ユーザーのバッジを順にキャッシュに保存
+/// 
+/// GitHub APIへの負荷を抑えるため1件ずつ処理し、失敗したユーザーはログに残して続ける
+/// 
+/// # Arguments
+/// * `usernames` - 対象のユーザー
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * キャッシュに保存できたユーザー数
+pub async fn warm_usernames(usernames: &[Username], deps: &AppDependencies) -> usize {
+    let mut warmed = 0;
+    for username in usernames {
+        match warm_badge(username, deps).await {
+            Ok(()) => warmed += 1,
+            Err(e) => tracing::warn!(
+                username = username.as_str(),
+                error = %e,
+                "バッジのウォームアップに失敗しました"
+            ),
+        }
+    }
+    
+    tracing::info!(warmed, total = usernames.len(), "バッジのウォームアップが完了しました");
+    warmed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::app::config::Config;
+    use crate::app::dependencies::mocks::TestDependenciesBuilder;
+    use crate::domain::github::GitHubActivity;
+    use crate::error::AppError;
+    use crate::use_cases::generate_badge::{activity_cache_key, badge_cache_key, BadgeOptions};
+    
+    const TOKEN: &str = "admin-token";
+    
+    fn activity(username: &str) -> GitHubActivity {
+        GitHubActivity {
+            username: username.to_string(),
+            last_activity_at: Some(Utc::now()),
+            current_streak_days: Some(1),
+            contributions: None,
+            total_contributions: None,
+            tz_offset_seconds: None,
+            fetched_at: Utc::now(),
+        }
+    }
+    
+    fn create_deps() -> AppDependencies {
+        let mut config = Config::default();
+        config.app.admin_token = Some(TOKEN.to_string());
+        
+        TestDependenciesBuilder::new()
+            .with_config(config)
+            .with_activity(activity("octocat"))
+            .with_activity(activity("hubot"))
+            .build()
+    }
+    
+    fn username(value: &str) -> Username {
+        Username::parse(value.to_string()).unwrap()
+    }
+    
+    async fn request(deps: &AppDependencies, token: Option<&str>, body: &str) -> AppResult<StatusCode> {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = token {
+            headers.insert(ADMIN_TOKEN_HEADER, token.parse().unwrap());
+        }
+        let (status, _) = warm_badges(State(deps.clone()), headers, Bytes::from(body.to_string())).await?;
+        Ok(status)
+    }
+    
+    #[tokio::test]
+    async fn test_warming_populates_cache_for_each_user() {
+        let deps = create_deps();
+        let usernames = vec![username("octocat"), username("hubot")];
+        
+        let warmed = warm_usernames(&usernames, &deps).await;
+        
+        assert_eq!(warmed, 2);
+        for username in &usernames {
+            let cache = deps.cache_service.as_ref();
+            assert!(cache.get(&activity_cache_key(username)).await.unwrap().is_some());
+            assert!(cache.get(&badge_cache_key(username, false, &BadgeOptions::default())).await.unwrap().is_some());
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_request_without_secret_is_rejected() {
+        let deps = create_deps();
+        
+        // ボディが不正なJSONでも、シークレットがなければ400ではなく401になる
+        let result = request(&deps, None, "not json").await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+        
+        let result = request(&deps, Some("wrong-token"), r#"{"usernames":["octocat"]}"#).await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_missing_token_config_rejects_everything() {
+        let deps = TestDependenciesBuilder::new().build();
+        
+        let result = request(&deps, Some(TOKEN), r#"{"usernames":["octocat"]}"#).await;
+        
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_request_with_secret_is_accepted() {
+        let deps = create_deps();
+        
+        let status = request(&deps, Some(TOKEN), r#"{"usernames":["octocat"]}"#).await.unwrap();
+        
+        assert_eq!(status, StatusCode::ACCEPTED);
+    }
+    
+    #[test]
+    fn test_parse_warm_usernames() {
+        let usernames = parse_warm_usernames(vec![
+            "octocat".to_string(),
+            "Octocat".to_string(),
+            "hubot".to_string(),
+        ]).unwrap();
+        assert_eq!(usernames, vec![username("octocat"), username("hubot")]);
+        
+        assert!(parse_warm_usernames(Vec::new()).is_err());
+        assert!(parse_warm_usernames(vec!["-invalid-".to_string()]).is_err());
+        
+        let too_many = (0..=MAX_WARM_USERNAMES).map(|i| format!("user{}", i)).collect();
+        assert!(parse_warm_usernames(too_many).is_err());
+    }
+}
@@ -11,12 +11,19 @@ use axum::{
     http::{header, StatusCode},
     Json,
 };
+use axum_extra::extract::CookieJar;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::app::dependencies::AppDependencies;
+use crate::app::config::Config;
+use crate::app::dependencies::{AppDependencies, CacheService};
 use crate::domain::user::{Username, RegisteredUser};
-use crate::error::{AppResult, HandlerError};
+use crate::error::{AppError, AppResult, HandlerError};
+use crate::handlers::utils::constant_time_eq;
+use crate::infra::adapters::oauth::OAuthErrorResponse;
+use crate::middlewares::session::{
+    clear_session_cookie, create_session, delete_session, session_cookie, SESSION_COOKIE_NAME,
+};
 
 /// OAuth開始時のクエリパラメータ
 #[derive(Debug, Deserialize)]
@@ -51,6 +58,7 @@ pub async fn github_oauth_start(
     // セッションに保存（Redisを使用）
     let session_key = format!("oauth_state:{}", state);
     let session_data = serde_json::json!({
+        "state": state,
         "redirect_to": query.redirect_to.as_deref().unwrap_or("/"),
         "created_at": chrono::Utc::now().to_rfc3339(),
     });
@@ -62,10 +70,6 @@ pub async fn github_oauth_start(
     ).await?;
     
     // GitHub OAuth URLを構築
-    // TODO: 実装
-    // - client_id を設定から取得
-    // - redirect_uri を構築（/api/auth/callback）
-    // - scope は不要（公開情報のみ）
     let github_oauth_url = build_github_oauth_url(&deps.config, &state)?;
     
     Ok(Redirect::to(&github_oauth_url).into_response())
@@ -93,28 +97,16 @@ pub struct OAuthCallbackQuery {
 /// 
 /// # Returns
 /// * 302 Found - 元のページまたはダッシュボードへリダイレクト
-/// * 400 Bad Request - 無効なstate
+/// * 400 Bad Request - 無効なstate・無効または期限切れの認証コード
 /// * 500 Internal Server Error - 認証エラー
 pub async fn github_oauth_callback(
     Query(query): Query<OAuthCallbackQuery>,
     State(deps): State<AppDependencies>,
 ) -> AppResult<Response> {
-    // stateパラメータの検証
-    let session_key = format!("oauth_state:{}", query.state);
-    let session_data = deps.cache_service.get(&session_key).await?
-        .ok_or_else(|| HandlerError::BadRequest("Invalid or expired state".to_string()))?;
-    
-    // セッションデータをパース
-    let session: serde_json::Value = serde_json::from_str(&session_data)?;
-    let redirect_to = session["redirect_to"].as_str().unwrap_or("/");
-    
-    // セッションを削除（一度だけ使用）
-    deps.cache_service.delete(&session_key).await?;
+    // stateパラメータの検証（取得と同時に削除し、再利用を防ぐ）
+    let redirect_to = consume_oauth_state(deps.cache_service.as_ref(), &query.state).await?;
     
     // アクセストークンを取得
-    // TODO: 実装
-    // - github_api.exchange_code_for_token(code) を実行
-    // - User Access Tokenを取得
     let access_token = exchange_code_for_token(&deps, &query.code).await?;
     
     // ユーザー情報を取得
@@ -131,14 +123,8 @@ pub async fn github_oauth_callback(
     // Cookieを設定してリダイレクト
     let response = Response::builder()
         .status(StatusCode::FOUND)
-        .header(header::LOCATION, redirect_to)
-        .header(
-            header::SET_COOKIE,
-            format!(
-                "gitpoke_session={}; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=604800",
-                session_id
-            )
-        )
+        .header(header::LOCATION, redirect_to.as_str())
+        .header(header::SET_COOKIE, session_cookie(&session_id))
         .body(Default::default())
         .unwrap();
     
@@ -150,21 +136,35 @@ pub async fn github_oauth_callback(
 /// POST /api/auth/logout
 /// 
 /// セッションを削除してログアウト
+/// セッションがない場合も成功を返す（冪等）
+/// 
+/// # Arguments
+/// * `deps` - アプリケーション依存性
+/// * `jar` - リクエストのCookie
 /// 
 /// # Returns
-/// * 200 OK - ログアウト成功
+/// * 200 OK - ログアウト成功（セッションCookieを削除）
 pub async fn logout(
     State(deps): State<AppDependencies>,
-    // TODO: セッションIDを取得するExtractor
-) -> AppResult<Json<LogoutResponse>> {
-    // TODO: 実装
-    // - Cookieからセッションを取得
-    // - Redisからセッションを削除
-    // - Cookieを削除
+    jar: CookieJar,
+) -> AppResult<Response> {
+    end_session(deps.cache_service.as_ref(), &jar).await
+}
+
+/// Cookieのセッションを削除し、Cookieを消すレスポンスを返す
+async fn end_session(cache: &dyn CacheService, jar: &CookieJar) -> AppResult<Response> {
+    if let Some(cookie) = jar.get(SESSION_COOKIE_NAME) {
+        delete_session(cache, cookie.value()).await?;
+    }
     
-    Ok(Json(LogoutResponse {
-        message: "Logged out successfully".to_string(),
-    }))
+    let response = (
+        [(header::SET_COOKIE, clear_session_cookie())],
+        Json(LogoutResponse {
+            message: "Logged out successfully".to_string(),
+        }),
+    );
+    
+    Ok(response.into_response())
 }
 
 /// ログアウトレスポンス
@@ -173,29 +173,101 @@ pub struct LogoutResponse {
     pub message: String,
 }
 
+/// OAuthのstateを消費してリダイレクト先を取得
+/// 
+/// stateは取得と削除をアトミックに行うため、同じstateで同時にコールバックされても
+/// 成功するのは1つだけになる
+/// 
+/// # Arguments
+/// * `cache` - stateを保存しているキャッシュ
+/// * `state` - コールバックで受け取ったstate
+/// 
+/// # Returns
+/// * `Ok(String)` - 認証後のリダイレクト先
+/// * `Err(HandlerError::BadRequest)` - 無効、期限切れ、または使用済みのstate
+async fn consume_oauth_state(cache: &dyn CacheService, state: &str) -> AppResult<String> {
+    let invalid_state = || HandlerError::BadRequest("Invalid or expired state".to_string());
+    
+    let session_key = format!("oauth_state:{}", state);
+    let session_data = cache.take(&session_key).await?.ok_or_else(invalid_state)?;
+    
+    let session: serde_json::Value =
+        serde_json::from_str(&session_data).map_err(|_| invalid_state())?;
+    
+    // 保存時のstateと定数時間で比較
+    let stored_state = session["state"].as_str().unwrap_or_default();
+    if !constant_time_eq(stored_state.as_bytes(), state.as_bytes()) {
+        return Err(invalid_state().into());
+    }
+    
+    Ok(session["redirect_to"].as_str().unwrap_or("/").to_string())
+}
+
+/// OAuthコールバックのパス
+const OAUTH_CALLBACK_PATH: &str = "/api/auth/callback";
+
 /// GitHub OAuth URLを構築
-fn build_github_oauth_url(
-    config: &crate::app::config::Config,
-    state: &str,
-) -> AppResult<String> {
-    // TODO: 実装
-    // GitHub OAuth URLのフォーマット：
-    // https://github.com/login/oauth/authorize?
-    //   client_id={client_id}&
-    //   redirect_uri={redirect_uri}&
-    //   state={state}&
-    //   scope={scope}
-    unimplemented!()
+/// 
+/// 公開情報のみを使用するため scope は空にする
+/// 
+/// # Arguments
+/// * `config` - アプリケーション設定（client_id、公開URL）
+/// * `state` - CSRF対策用のstateパラメータ
+/// 
+/// # Returns
+/// * `Ok(String)` - GitHubの認証ページURL
+/// * `Err(AppError)` - URLの構築に失敗
+fn build_github_oauth_url(config: &Config, state: &str) -> AppResult<String> {
+    let authorize_url = format!(
+        "{}/login/oauth/authorize",
+        config.github.oauth_base_url.trim_end_matches('/')
+    );
+    let redirect_uri = oauth_redirect_uri(config);
+    
+    let url = reqwest::Url::parse_with_params(
+        &authorize_url,
+        &[
+            ("client_id", config.github.client_id.as_str()),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("state", state),
+            ("scope", ""),
+        ],
+    )
+    .map_err(|e| AppError::Internal(format!("OAuth URLの構築に失敗しました: {}", e)))?;
+    
+    Ok(url.into())
+}
+
+/// OAuthのリダイレクト先URLを構築
+pub(crate) fn oauth_redirect_uri(config: &Config) -> String {
+    format!("{}{}", config.app.base_url.trim_end_matches('/'), OAUTH_CALLBACK_PATH)
 }
 
 /// 認証コードをアクセストークンに交換
+/// 
+/// # Arguments
+/// * `deps` - アプリケーション依存性（OAuthアダプター）
+/// * `code` - GitHubから受け取った認証コード
+/// 
+/// # Returns
+/// * `Ok(String)` - User Access Token
+/// * `Err(HandlerError::BadRequest)` - 認証コードが無効（誤り・使用済み・期限切れ）
+/// * `Err(AppError::Internal)` - その他の交換失敗（設定の誤り・GitHubの障害等）
 async fn exchange_code_for_token(
     deps: &AppDependencies,
     code: &str,
 ) -> AppResult<String> {
-    // TODO: 実装
-    // POST https://github.com/login/oauth/access_token
-    unimplemented!()
+    let token = deps.oauth_adapter
+        .exchange_code(code)
+        .await
+        .map_err(|e| match e.downcast_ref::<OAuthErrorResponse>() {
+            Some(error) if error.is_bad_verification_code() => {
+                HandlerError::BadRequest("Invalid or expired authorization code".to_string()).into()
+            }
+            _ => AppError::Internal(format!("アクセストークンの取得に失敗しました: {}", e)),
+        })?;
+    
+    Ok(token.access_token)
 }
 
 /// GitHubユーザー情報を取得
@@ -221,16 +293,18 @@ async fn create_or_update_user(
 }
 
 /// ユーザーセッションを作成
+/// 
+/// セッション情報は `session:{id}` に7日間保存され、
+/// 認証Extractor（`AuthenticatedUser`）から参照される
+/// 
+/// # Returns
+/// * `Ok(String)` - セッションID（Cookieに設定する）
 async fn create_user_session(
     deps: &AppDependencies,
     user: &RegisteredUser,
     access_token: String,
 ) -> AppResult<String> {
-    // TODO: 実装
-    // - セッションIDを生成（UUID）
-    // - Redisにセッション情報を保存
-    // - TTLは7日間
-    unimplemented!()
+    create_session(deps.cache_service.as_ref(), user, Some(&access_token)).await
 }
 
 /// GitHubユーザー情報（一時的な型定義）
@@ -242,3 +316,192 @@ struct GitHubUser {
     pub email: Option<String>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use axum_extra::extract::cookie::Cookie;
+    use crate::app::dependencies::mocks::MockCacheService;
+    
+    async fn store_state(cache: &MockCacheService, key_state: &str, stored_state: &str) {
+        let session_data = serde_json::json!({
+            "state": stored_state,
+            "redirect_to": "/dashboard",
+        });
+        cache
+            .set(&format!("oauth_state:{}", key_state), &session_data.to_string(), 600)
+            .await
+            .unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_logout_deletes_session() {
+        let cache = MockCacheService::new();
+        cache.set("session:session-1", r#"{"username":"octocat"}"#, 600).await.unwrap();
+        let jar = CookieJar::new().add(Cookie::new(SESSION_COOKIE_NAME, "session-1"));
+        
+        let response = end_session(&cache, &jar).await.unwrap();
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(cache.get("session:session-1").await.unwrap(), None);
+        
+        let set_cookie = response.headers().get(header::SET_COOKIE).unwrap().to_str().unwrap();
+        assert!(set_cookie.starts_with("gitpoke_session=;"));
+        assert!(set_cookie.contains("Max-Age=0"));
+    }
+    
+    #[tokio::test]
+    async fn test_logout_without_cookie() {
+        let cache = MockCacheService::new();
+        
+        // 2回呼んでも同じ結果になる
+        for _ in 0..2 {
+            let response = end_session(&cache, &CookieJar::new()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get(header::SET_COOKIE).is_some());
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_consume_oauth_state() {
+        let cache = MockCacheService::new();
+        store_state(&cache, "state-1", "state-1").await;
+        
+        assert_eq!(consume_oauth_state(&cache, "state-1").await.unwrap(), "/dashboard");
+        
+        // 使用済みのstateは再利用できない
+        let result = consume_oauth_state(&cache, "state-1").await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::BadRequest(_)))));
+    }
+    
+    #[tokio::test]
+    async fn test_consume_oauth_state_mismatch() {
+        let cache = MockCacheService::new();
+        store_state(&cache, "state-1", "other-state").await;
+        
+        let result = consume_oauth_state(&cache, "state-1").await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::BadRequest(_)))));
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_callbacks_with_same_state() {
+        let cache = Arc::new(MockCacheService::new());
+        store_state(&cache, "state-1", "state-1").await;
+        
+        let tasks: Vec<_> = (0..2)
+            .map(|_| {
+                let cache = cache.clone();
+                tokio::spawn(async move { consume_oauth_state(cache.as_ref(), "state-1").await })
+            })
+            .collect();
+        
+        let mut succeeded = 0;
+        for task in tasks {
+            match task.await.unwrap() {
+                Ok(_) => succeeded += 1,
+                Err(e) => assert!(matches!(e, AppError::Handler(HandlerError::BadRequest(_)))),
+            }
+        }
+        assert_eq!(succeeded, 1);
+    }
+    
+    fn oauth_config() -> Config {
+        let mut config = Config::default();
+        config.github.client_id = "Iv1.abc/123".to_string();
+        config.app.base_url = "https://gitpoke.dev/".to_string();
+        config
+    }
+    
+    #[test]
+    fn test_build_github_oauth_url_encodes_params() {
+        let url = build_github_oauth_url(&oauth_config(), "state with&symbols").unwrap();
+        
+        assert!(url.starts_with("https://github.com/login/oauth/authorize?"));
+        assert!(url.contains("client_id=Iv1.abc%2F123"));
+        assert!(url.contains("redirect_uri=https%3A%2F%2Fgitpoke.dev%2Fapi%2Fauth%2Fcallback"));
+        assert!(url.contains("state=state+with%26symbols"));
+        assert!(url.contains("scope="));
+    }
+    
+    #[test]
+    fn test_build_github_oauth_url_round_trip() {
+        let url = build_github_oauth_url(&oauth_config(), "state with&symbols").unwrap();
+        let parsed = reqwest::Url::parse(&url).unwrap();
+        
+        assert_eq!(parsed.host_str(), Some("github.com"));
+        assert_eq!(parsed.path(), "/login/oauth/authorize");
+        
+        let params: std::collections::HashMap<_, _> = parsed.query_pairs().into_owned().collect();
+        assert_eq!(params["client_id"], "Iv1.abc/123");
+        assert_eq!(params["redirect_uri"], "https://gitpoke.dev/api/auth/callback");
+        assert_eq!(params["state"], "state with&symbols");
+        assert_eq!(params["scope"], "");
+    }
+    
+    mod exchange_code_tests {
+        use super::*;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        use crate::app::dependencies::mocks::TestDependenciesBuilder;
+        use crate::infra::adapters::oauth::GitHubOAuthAdapter;
+        
+        /// トークンエンドポイントが `body` を返す依存性
+        async fn deps_with_token_response(server: &MockServer, body: serde_json::Value) -> AppDependencies {
+            Mock::given(method("POST"))
+                .and(path("/login/oauth/access_token"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .mount(server)
+                .await;
+            
+            let mut deps = TestDependenciesBuilder::new().build();
+            deps.oauth_adapter = Arc::new(
+                GitHubOAuthAdapter::new(
+                    "client-id".to_string(),
+                    "client-secret".to_string(),
+                    oauth_redirect_uri(&deps.config),
+                )
+                .with_base_url(server.uri()),
+            );
+            deps
+        }
+        
+        #[tokio::test]
+        async fn test_valid_code_returns_token() {
+            let server = MockServer::start().await;
+            let deps = deps_with_token_response(&server, serde_json::json!({
+                "access_token": "gho_token",
+                "token_type": "bearer",
+            })).await;
+            
+            assert_eq!(exchange_code_for_token(&deps, "valid-code").await.unwrap(), "gho_token");
+        }
+        
+        #[tokio::test]
+        async fn test_expired_code_is_bad_request() {
+            let server = MockServer::start().await;
+            let deps = deps_with_token_response(&server, serde_json::json!({
+                "error": "bad_verification_code",
+                "error_description": "The code passed is incorrect or expired.",
+            })).await;
+            
+            let result = exchange_code_for_token(&deps, "expired-code").await;
+            
+            let error = result.unwrap_err();
+            assert!(matches!(error, AppError::Handler(HandlerError::BadRequest(_))));
+            assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+        
+        #[tokio::test]
+        async fn test_misconfigured_client_is_internal_error() {
+            let server = MockServer::start().await;
+            let deps = deps_with_token_response(&server, serde_json::json!({
+                "error": "incorrect_client_credentials",
+                "error_description": "The client_id and/or client_secret passed are incorrect.",
+            })).await;
+            
+            let result = exchange_code_for_token(&deps, "valid-code").await;
+            
+            assert!(matches!(result, Err(AppError::Internal(_))));
+        }
+    }
+}
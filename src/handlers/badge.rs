@@ -6,26 +6,79 @@
 //! - SVGレスポンスの構築
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
     response::{IntoResponse, Response},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use flate2::{
+    write::{DeflateEncoder, GzEncoder},
+    Compression,
 };
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::sync::Arc;
 
-use crate::app::dependencies::AppDependencies;
-use crate::domain::badge::{BadgeState, BadgeSvg};
+use crate::app::dependencies::{AppDependencies, CacheService};
+use crate::app::metrics::METRICS;
+use crate::app::shutdown::spawn_background;
+use crate::domain::badge::{
+    BadgeState, BadgeStyle, BadgeSvg, BadgeTheme, NotFoundDisplay, ShieldsEndpoint,
+    DEFAULT_BADGE_LABEL,
+};
 use crate::domain::user::Username;
-use crate::error::{AppResult, HandlerError};
-use crate::use_cases::generate_badge as use_case;
+use crate::error::{AppResult, HandlerError, InfraError};
+use crate::infra::adapters::storage::StorageAdapter;
+use crate::use_cases::generate_badge::{self as use_case, BadgeOptions};
+use crate::use_cases::generate_org_badge as org_use_case;
 
 /// バッジリクエストのクエリパラメータ
 #[derive(Debug, Deserialize)]
 pub struct BadgeQuery {
-    /// バッジスタイル（将来実装）
-    /// 例: flat, flat-square, plastic
+    /// バッジスタイル
+    /// 例: flat, flat-square, plastic, for-the-badge
+    /// 未知の値の場合はflatとして扱う
     #[serde(default)]
     pub style: Option<String>,
     
+    /// バッジ左側のラベル
+    /// 例: ?label=streak（未指定の場合は "GitPoke"）
+    #[serde(default)]
+    pub label: Option<String>,
+    
+    /// バッジのテーマ
+    /// 例: ?theme=dark（未指定・未知の値の場合はlight）
+    #[serde(default)]
+    pub theme: Option<String>,
+    
+    /// 追加で表示する項目（カンマ区切り）
+    /// 例: ?show=contributions（総コントリビューション数を表示）
+    #[serde(default)]
+    pub show: Option<String>,
+    
+    /// 直近14日間のコントリビューション推移を表示するか
+    /// 例: ?sparkline=true（日別データがない場合は表示しない）
+    #[serde(default)]
+    pub sparkline: Option<bool>,
+    
+    /// ユーザーが見つからない場合の表示方法
+    /// 例: ?notfound=hide（1x1の透明なSVGを返す）、?notfound=message（デフォルト）
+    #[serde(default)]
+    pub notfound: Option<String>,
+    
+    /// ユーザーが見つからない場合のメッセージ
+    /// 例: ?notfound_text=Unknown（未指定の場合は "User not found"）
+    #[serde(default)]
+    pub notfound_text: Option<String>,
+    
+    /// レスポンスの形式（Acceptヘッダーより優先）
+    /// 例: ?format=json（svg・json・png、拡張子付きのパスでは無視する）
+    #[serde(default)]
+    pub format: Option<String>,
+    
     /// キャッシュ無効化パラメータ
     /// 例: ?cache_bust=1234567890
     #[serde(default)]
@@ -37,7 +90,174 @@ pub struct BadgeQuery {
     pub interactive: Option<bool>,
 }
 
-/// バッジ生成エンドポイント
+/// バッジのファイル形式（パスの拡張子）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeFormat {
+    /// SVGバッジ（`.svg`）
+    Svg,
+    
+    /// shields.io endpoint用JSON（`.json`）
+    Json,
+    
+    /// PNGバッジ（`.png`、`png` フィーチャーが有効な場合のみ）
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl BadgeFormat {
+    /// 形式名（拡張子・`?format=` の値）から形式を取得
+    /// 
+    /// # Returns
+    /// * `Some(format)` - 対応している形式
+    /// * `None` - 未対応（`png` フィーチャーが無効な場合の `png` を含む）
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "svg" => Some(BadgeFormat::Svg),
+            "json" => Some(BadgeFormat::Json),
+            #[cfg(feature = "png")]
+            "png" => Some(BadgeFormat::Png),
+            _ => None,
+        }
+    }
+    
+    /// メディアタイプから形式を取得
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type.to_ascii_lowercase().as_str() {
+            "image/svg+xml" => Some(BadgeFormat::Svg),
+            "application/json" => Some(BadgeFormat::Json),
+            #[cfg(feature = "png")]
+            "image/png" => Some(BadgeFormat::Png),
+            _ => None,
+        }
+    }
+    
+    /// qが同じ場合の優先度（小さいほど優先）
+    fn preference(&self) -> u8 {
+        match self {
+            BadgeFormat::Svg => 0,
+            #[cfg(feature = "png")]
+            BadgeFormat::Png => 1,
+            BadgeFormat::Json => 2,
+        }
+    }
+    
+    /// Acceptヘッダーから形式を選択
+    /// 
+    /// 対応している形式のうちqが最も大きいものを選ぶ。`q=0` の形式は使わず、
+    /// 対応している形式がない場合（`*/*` や `image/*` のみ等）はSVGとする
+    fn negotiate(accept: &str) -> Self {
+        accept
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';').map(str::trim);
+                let format = params.next().and_then(Self::from_media_type)?;
+                let q = params
+                    .find_map(|param| param.strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (q > 0.0).then_some((q, format))
+            })
+            .max_by(|(q1, format1), (q2, format2)| {
+                q1.total_cmp(q2).then(format2.preference().cmp(&format1.preference()))
+            })
+            .map(|(_, format)| format)
+            .unwrap_or(BadgeFormat::Svg)
+    }
+}
+
+/// バッジエンドポイント
+/// 
+/// GET /badge/:filename
+/// 
+/// axumのパスパラメータはセグメント全体に一致するため、
+/// `octocat` や `octocat.svg` のようなファイル名を受け取り、形式を切り替える。
+/// 拡張子付きのパスは形式を固定したエイリアスとして扱う
+/// 
+/// # Arguments
+/// * `filename` - `{username}` または `{username}.{svg|json|png}`
+/// * `query` - クエリパラメータ（`format` 以外はSVG・PNGのみ使用）
+/// * `deps` - アプリケーション依存性
+/// * `headers` - リクエストヘッダー（形式の選択・ETag・圧縮形式の判定に使用）
+/// 
+/// # Returns
+/// * SVG・JSON・PNGの各エンドポイントのレスポンス
+/// * 404 Not Found - 未対応の拡張子・形式（`png` フィーチャーが無効な場合の `png` を含む）
+pub async fn serve_badge(
+    Path(filename): Path<String>,
+    Query(query): Query<BadgeQuery>,
+    State(deps): State<AppDependencies>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok());
+    
+    let Some((username, format)) = select_badge_format(&filename, query.format.as_deref(), accept) else {
+        return Err(HandlerError::NotFound(format!("Unsupported badge format: {}", filename)).into());
+    };
+    
+    // 拡張子も `?format=` もない場合は、同じURLでもAcceptによって形式が変わる
+    let negotiated = username.len() == filename.len() && query.format.is_none();
+    
+    let mut response = match format {
+        BadgeFormat::Svg => generate_badge(username.to_string(), query, &deps, &headers).await?,
+        BadgeFormat::Json => generate_badge_json(username.to_string(), &deps).await?,
+        #[cfg(feature = "png")]
+        BadgeFormat::Png => generate_badge_png(username.to_string(), query, &deps).await?,
+    };
+    
+    if negotiated {
+        response.headers_mut().append(header::VARY, header::HeaderValue::from_static("Accept"));
+    }
+    
+    Ok(response)
+}
+
+/// バッジの形式を決定
+/// 
+/// 以下の順に判定する：
+/// 1. パスの拡張子（`octocat.svg` 等）
+/// 2. `?format=` の値
+/// 3. Acceptヘッダー
+/// 4. SVG（デフォルト）
+/// 
+/// # Arguments
+/// * `filename` - パスのファイル名
+/// * `format` - `?format=` の値
+/// * `accept` - Acceptヘッダーの値
+/// 
+/// # Returns
+/// * `Some((username, format))` - ユーザー名と形式
+/// * `None` - 未対応の拡張子・形式
+fn select_badge_format<'a>(
+    filename: &'a str,
+    format: Option<&str>,
+    accept: Option<&str>,
+) -> Option<(&'a str, BadgeFormat)> {
+    // ユーザー名にドットは含まれないため、ドットがあれば拡張子とみなす
+    if filename.contains('.') {
+        return parse_badge_filename(filename);
+    }
+    
+    let format = match format {
+        Some(name) => BadgeFormat::from_name(&name.to_ascii_lowercase())?,
+        None => accept.map(BadgeFormat::negotiate).unwrap_or(BadgeFormat::Svg),
+    };
+    Some((filename, format))
+}
+
+/// ファイル名をユーザー名と形式に分割
+/// 
+/// # Returns
+/// * `Some((username, format))` - 対応している拡張子
+/// * `None` - 拡張子がない、または未対応
+fn parse_badge_filename(filename: &str) -> Option<(&str, BadgeFormat)> {
+    let (username, extension) = filename.rsplit_once('.')?;
+    let format = BadgeFormat::from_name(extension)?;
+    Some((username, format))
+}
+
+/// SVGバッジを生成
 /// 
 /// GET /badge/:username.svg
 /// 
@@ -47,115 +267,608 @@ pub struct BadgeQuery {
 /// * `username` - GitHubユーザー名
 /// * `query` - クエリパラメータ
 /// * `deps` - アプリケーション依存性
+/// * `headers` - リクエストヘッダー（ETag・圧縮形式の判定に使用）
 /// 
 /// # Returns
-/// * 200 OK - SVGバッジ
-/// * 404 Not Found - ユーザーが見つからない
-/// * 500 Internal Server Error - 生成エラー
-pub async fn generate_badge(
-    Path(username): Path<String>,
-    Query(query): Query<BadgeQuery>,
-    State(deps): State<AppDependencies>,
+/// * 200 OK - SVGバッジ（GitHubに存在しないユーザーは `notfound` の指定に従う）
+/// * 304 Not Modified - If-None-MatchがETagと一致
+/// * 500 Internal Server Error - 生成エラー（GitHub APIの一時的な障害を含む）
+async fn generate_badge(
+    username: String,
+    query: BadgeQuery,
+    deps: &AppDependencies,
+    headers: &HeaderMap,
 ) -> AppResult<Response> {
+    // 条件付きリクエストのETag
+    let if_none_match = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok());
+    
+    // レスポンスの圧縮形式を決定
+    let encoding = headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(ContentEncoding::negotiate)
+        .unwrap_or(ContentEncoding::Identity);
+    
+    let (badge, from_cache) = load_badge(username, &query, deps).await?;
+    
+    // レスポンスを構築
+    Ok(build_svg_response(badge, from_cache, if_none_match, encoding))
+}
+
+/// PNGバッジを生成
+/// 
+/// GET /badge/:username.png
+/// 
+/// SVGバッジと同じ経路（キャッシュを含む）で生成したSVGをPNGに変換する
+/// 
+/// # Arguments
+/// * `username` - GitHubユーザー名
+/// * `query` - クエリパラメータ（SVGと同じ）
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * 200 OK - PNGバッジ
+/// * 500 Internal Server Error - 生成・変換エラー
+#[cfg(feature = "png")]
+async fn generate_badge_png(
+    username: String,
+    query: BadgeQuery,
+    deps: &AppDependencies,
+) -> AppResult<Response> {
+    let (badge, from_cache) = load_badge(username, &query, deps).await?;
+    let png = crate::infra::png::render_png(&badge.content)?;
+    
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "image/png")
+        .header(header::CACHE_CONTROL, badge.cache_control())
+        .header("X-Cache", if from_cache { "HIT" } else { "MISS" })
+        .header("Access-Control-Allow-Origin", "https://github.com")
+        .header("Access-Control-Allow-Methods", "GET")
+        .body(Body::from(png))
+        .unwrap())
+}
+
+/// バッジのSVGを取得（キャッシュになければ生成）
+/// 
+/// # Arguments
+/// * `username` - GitHubユーザー名
+/// * `query` - クエリパラメータ
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * `Ok((badge, from_cache))` - バッジとキャッシュから取得したかどうか
+/// * `Err(HandlerError::BadRequest)` - 無効なユーザー名・ラベル・見つからない場合のメッセージ
+async fn load_badge(
+    username: String,
+    query: &BadgeQuery,
+    deps: &AppDependencies,
+) -> AppResult<(BadgeSvg, bool)> {
     // ユーザー名のバリデーション
-    let username = Username::parse(username)
-        .map_err(|_| HandlerError::BadRequest("Invalid username format".to_string()))?;
+    let username = Username::parse(username)?;
+    
+    let (label, style, theme) = parse_appearance(query)?;
+    let options = BadgeOptions {
+        label: label.to_string(),
+        style,
+        theme,
+        // 追加表示項目の解析（未知の項目は無視）
+        show_contributions: query.show
+            .as_deref()
+            .is_some_and(|show| show.split(',').any(|item| item.trim() == "contributions")),
+        sparkline: query.sparkline.unwrap_or(false),
+        not_found: parse_not_found_display(query)?,
+    };
+    
+    let interactive = query.interactive.unwrap_or(false);
     
     // キャッシュキーの生成
-    let cache_key = format!("badge:{}:v1", username.as_str());
+    let cache_key = use_case::badge_cache_key(&username, interactive, &options);
     
     // キャッシュからの取得を試みる
-    if let Some(cached_svg) = get_cached_badge(&deps, &cache_key).await? {
-        return Ok(build_svg_response(cached_svg, true));
+    if let Some(cached_svg) = get_cached_badge(
+        deps.cache_service.as_ref(),
+        deps.storage.as_ref(),
+        &cache_key,
+    ).await? {
+        return Ok((cached_svg, true));
     }
     
     // バッジ生成のユースケースを実行
     let result = use_case::execute(
         &username,
-        &deps,
-        query.interactive.unwrap_or(false),
+        deps,
+        interactive,
+        &options,
     ).await?;
     
     // キャッシュに保存
-    save_badge_to_cache(&deps, &cache_key, &result.badge).await?;
+    save_badge_to_cache(
+        deps.cache_service.as_ref(),
+        deps.storage.clone(),
+        &cache_key,
+        &result.badge,
+    ).await?;
     
-    // レスポンスを構築
-    Ok(build_svg_response(result.badge, false))
+    Ok((result.badge, false))
 }
 
-/// キャッシュからバッジを取得
+/// 既定の見た目のバッジを生成してキャッシュに保存
+///
+/// キャッシュの有無に関係なく生成し直すため、アクティビティもあわせてキャッシュされる
+///
+/// # Arguments
+/// * `username` - 対象ユーザー
+/// * `deps` - アプリケーション依存性
+///
+/// # Returns
+/// * `Ok(())` - 保存成功
+pub(crate) async fn warm_badge(username: &Username, deps: &AppDependencies) -> AppResult<()> {
+    let result = use_case::execute(username, deps, false, &BadgeOptions::default()).await?;
+    
+    save_badge_to_cache(
+        deps.cache_service.as_ref(),
+        deps.storage.clone(),
+        &result.cache_key,
+        &result.badge,
+    ).await
+}
+
+/// ラベル・スタイル・テーマを解析
+/// 
+/// # Returns
+/// * `Ok((label, style, theme))` - 未指定・未知のスタイルとテーマはデフォルト値
+/// * `Err(HandlerError::BadRequest)` - 無効なラベル
+fn parse_appearance(query: &BadgeQuery) -> AppResult<(&str, BadgeStyle, BadgeTheme)> {
+    // ラベルのバリデーション
+    let label = match query.label.as_deref() {
+        Some(label) => {
+            validate_badge_label(label)?;
+            label
+        }
+        None => DEFAULT_BADGE_LABEL,
+    };
+    
+    // バッジスタイルの解析（未知の値はFlatにフォールバック）
+    let style = query.style
+        .as_deref()
+        .map(BadgeStyle::parse_or_default)
+        .unwrap_or_default();
+    
+    // テーマの解析（未知の値はLightにフォールバック）
+    let theme = query.theme
+        .as_deref()
+        .map(BadgeTheme::parse_or_default)
+        .unwrap_or_default();
+    
+    Ok((label, style, theme))
+}
+
+/// ユーザーが見つからない場合の表示方法を解析
+/// 
+/// # Returns
+/// * `Ok(display)` - 未指定・未知の値はメッセージ表示
+/// * `Err(HandlerError::BadRequest)` - 無効なメッセージ
+fn parse_not_found_display(query: &BadgeQuery) -> AppResult<NotFoundDisplay> {
+    // メッセージはラベルと同じ制約で検証する
+    if let Some(text) = query.notfound_text.as_deref() {
+        validate_badge_label(text)?;
+    }
+    
+    Ok(NotFoundDisplay::from_query(
+        query.notfound.as_deref(),
+        query.notfound_text.as_deref(),
+    ))
+}
+
+/// 組織バッジエンドポイント
+/// 
+/// GET /badge/org/:filename
+/// 
+/// 組織の公開メンバーのうち、閾値以内に活動した人数を「5/12 active」のように表示する
 /// 
 /// # Arguments
+/// * `filename` - `{org}.svg`
+/// * `query` - クエリパラメータ（label・style・themeのみ使用）
 /// * `deps` - アプリケーション依存性
+/// * `headers` - リクエストヘッダー（ETag・圧縮形式の判定に使用）
+/// 
+/// # Returns
+/// * 200 OK - SVGバッジ
+/// * 304 Not Modified - If-None-MatchがETagと一致
+/// * 400 Bad Request - 無効な組織名・ラベル
+/// * 404 Not Found - 組織が存在しない、またはSVG以外の拡張子
+pub async fn serve_org_badge(
+    Path(filename): Path<String>,
+    Query(query): Query<BadgeQuery>,
+    State(deps): State<AppDependencies>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let Some(org) = filename.strip_suffix(".svg") else {
+        return Err(HandlerError::NotFound(format!("Unsupported badge format: {}", filename)).into());
+    };
+    
+    // 組織名はGitHubのユーザー名と同じ規則
+    let org = Username::parse(org.to_string())
+        .map_err(|_| HandlerError::BadRequest("Invalid organization name format".to_string()))?;
+    let (label, style, theme) = parse_appearance(&query)?;
+    
+    let if_none_match = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok());
+    let encoding = headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(ContentEncoding::negotiate)
+        .unwrap_or(ContentEncoding::Identity);
+    
+    let cache_key = org_use_case::org_badge_cache_key(&org, label, style, theme);
+    if let Some(cached_svg) = get_cached_badge(
+        deps.cache_service.as_ref(),
+        deps.storage.as_ref(),
+        &cache_key,
+    ).await? {
+        return Ok(build_svg_response(cached_svg, true, if_none_match, encoding));
+    }
+    
+    let badge = org_use_case::execute(&org, &deps, label, style, theme).await?;
+    save_badge_to_cache(
+        deps.cache_service.as_ref(),
+        deps.storage.clone(),
+        &cache_key,
+        &badge,
+    ).await?;
+    
+    Ok(build_svg_response(badge, false, if_none_match, encoding))
+}
+
+/// shields.io endpoint用JSONを生成
+/// 
+/// GET /badge/:username.json
+/// 
+/// shields.ioのendpointバッジから参照できるJSONを返す
+/// 例: https://img.shields.io/endpoint?url=https://gitpoke.dev/badge/octocat.json
+/// 
+/// # Arguments
+/// * `username` - GitHubユーザー名
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * 200 OK - endpoint JSON
+/// * 400 Bad Request - 無効なユーザー名
+async fn generate_badge_json(
+    username: String,
+    deps: &AppDependencies,
+) -> AppResult<Response> {
+    // ユーザー名のバリデーション
+    let username = Username::parse(username)?;
+    
+    // SVGバッジと同じ経路で状態を判定
+    let state = use_case::resolve_badge_state(&username, deps).await?;
+    
+    let cache_control = format!("public, max-age={}", state.cache_ttl());
+    Ok((
+        [(header::CACHE_CONTROL, cache_control)],
+        Json(ShieldsEndpoint::from_state(&state)),
+    ).into_response())
+}
+
+/// ラベルの最大文字数
+const MAX_LABEL_LENGTH: usize = 32;
+
+/// バッジラベルのバリデーション
+/// 
+/// - 1〜32文字
+/// - ASCIIの印字可能文字（スペースを含む）のみ
+/// 
+/// # Returns
+/// * `Ok(())` - 有効なラベル
+/// * `Err(HandlerError::BadRequest)` - 無効なラベル
+fn validate_badge_label(label: &str) -> Result<(), HandlerError> {
+    if label.is_empty() || label.len() > MAX_LABEL_LENGTH {
+        return Err(HandlerError::BadRequest(format!(
+            "Label must be 1-{} characters",
+            MAX_LABEL_LENGTH
+        )));
+    }
+    
+    if !label.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+        return Err(HandlerError::BadRequest(
+            "Label must contain only printable ASCII characters".to_string()
+        ));
+    }
+    
+    Ok(())
+}
+
+/// Redis・Cloud Storageに保存するバッジ
+/// 
+/// SVGだけでなくTTLやインタラクティブかどうかもJSONで保存し、
+/// キャッシュから返す場合も生成時と同じレスポンスにする
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedBadge {
+    /// SVGコンテンツ
+    content: String,
+    
+    /// Cache-Controlヘッダー用のTTL（秒）
+    cache_ttl: u64,
+    
+    /// インタラクティブ要素を含むかどうか
+    is_interactive: bool,
+    
+    /// バッジを生成した日時
+    generated_at: DateTime<Utc>,
+}
+
+impl CachedBadge {
+    fn new(badge: &BadgeSvg, generated_at: DateTime<Utc>) -> Self {
+        Self {
+            content: badge.content.clone(),
+            cache_ttl: badge.cache_ttl,
+            is_interactive: badge.is_interactive,
+            generated_at,
+        }
+    }
+    
+    fn into_badge(self) -> BadgeSvg {
+        BadgeSvg {
+            content: self.content,
+            cache_ttl: self.cache_ttl,
+            is_interactive: self.is_interactive,
+        }
+    }
+    
+    fn to_json(&self) -> AppResult<String> {
+        Ok(serde_json::to_string(self).map_err(InfraError::from)?)
+    }
+    
+    /// 残りのTTL（秒）
+    /// 
+    /// # Returns
+    /// * `Some(ttl)` - 有効期限内
+    /// * `None` - 有効期限切れ
+    fn remaining_ttl(&self, now: DateTime<Utc>) -> Option<u64> {
+        let elapsed = (now - self.generated_at).num_seconds().max(0) as u64;
+        self.cache_ttl
+            .checked_sub(elapsed)
+            .filter(|remaining| *remaining > 0)
+    }
+}
+
+/// キャッシュからバッジを取得
+/// 
+/// Redisになければ Cloud Storage（コールドキャッシュ）を参照し、
+/// 見つかればRedisにも保存してから返す
+/// 
+/// # Arguments
+/// * `cache_service` - キャッシュサービス（Redis）
+/// * `storage` - バッジストレージ（Cloud Storage）
 /// * `cache_key` - キャッシュキー
 /// 
 /// # Returns
 /// * `Some(BadgeSvg)` - キャッシュヒット
 /// * `None` - キャッシュミス
 async fn get_cached_badge(
-    deps: &AppDependencies,
+    cache_service: &dyn CacheService,
+    storage: &dyn StorageAdapter,
     cache_key: &str,
 ) -> AppResult<Option<BadgeSvg>> {
     // Redisからの取得を試みる
-    if let Some(cached_content) = deps.cache_service.get(cache_key).await? {
-        // TODO: BadgeSvgのデシリアライズ
-        // - JSON形式で保存されているものを復元
-        return Ok(Some(BadgeSvg {
-            content: cached_content,
-            cache_ttl: 300, // デフォルトTTL
-            is_interactive: false,
-        }));
+    // デシリアライズできないエントリ（旧形式など）はキャッシュミスとして扱う
+    let cached = cache_service
+        .get(cache_key)
+        .await?
+        .and_then(|cached| serde_json::from_str::<CachedBadge>(&cached).ok());
+    METRICS.record_badge_cache(cached.is_some());
+    
+    if let Some(cached) = cached {
+        return Ok(Some(cached.into_badge()));
     }
     
     // Cloud Storageからの取得を試みる（コールドキャッシュ）
-    // TODO: 実装
-    // - storage_service.get_badge(username) を実行
-    // - 存在すれば、Redisにも保存してから返す
+    // 障害時はキャッシュミスとして扱い、バッジを生成し直す
+    let stored = storage.get_badge(cache_key).await.unwrap_or_else(|e| {
+        tracing::warn!(cache_key, error = %e, "Cloud Storageからのバッジ取得に失敗しました");
+        None
+    });
+    
+    // デシリアライズできないエントリ（旧形式など）や有効期限切れのエントリはキャッシュミスとして扱う
+    let Some(stored) = stored.and_then(|stored| serde_json::from_str::<CachedBadge>(&stored).ok()) else {
+        return Ok(None);
+    };
+    let Some(remaining_ttl) = stored.remaining_ttl(Utc::now()) else {
+        return Ok(None);
+    };
     
-    Ok(None)
+    // 残りのTTLでRedisにも保存し、次回以降はRedisから返す
+    cache_service.set(cache_key, &stored.to_json()?, remaining_ttl).await?;
+    
+    Ok(Some(BadgeSvg {
+        cache_ttl: remaining_ttl,
+        ..stored.into_badge()
+    }))
 }
 
 /// バッジをキャッシュに保存
 /// 
+/// Cloud Storageへの保存はレスポンスを待たせないようバックグラウンドで行う
+/// 
 /// # Arguments
-/// * `deps` - アプリケーション依存性
+/// * `cache_service` - キャッシュサービス（Redis）
+/// * `storage` - バッジストレージ（Cloud Storage）
 /// * `cache_key` - キャッシュキー
 /// * `badge` - 保存するバッジ
 async fn save_badge_to_cache(
-    deps: &AppDependencies,
+    cache_service: &dyn CacheService,
+    storage: Arc<dyn StorageAdapter>,
     cache_key: &str,
     badge: &BadgeSvg,
 ) -> AppResult<()> {
     // Redisに保存
-    deps.cache_service.set(
-        cache_key,
-        &badge.content,
-        badge.cache_ttl,
-    ).await?;
+    let entry = CachedBadge::new(badge, Utc::now()).to_json()?;
+    cache_service.set(cache_key, &entry, badge.cache_ttl).await?;
     
-    // Cloud Storageにも非同期で保存（エラーは無視）
-    // TODO: 実装
-    // - tokio::spawn で非同期実行
-    // - storage_service.save_badge(username, badge) を実行
+    // Cloud Storageにも同じエントリを非同期で保存（エラーはログのみ）
+    let cache_key = cache_key.to_string();
+    spawn_background(async move {
+        if let Err(e) = storage.save_badge(&cache_key, &entry).await {
+            tracing::warn!(cache_key, error = %e, "Cloud Storageへのバッジ保存に失敗しました");
+        }
+    });
     
     Ok(())
 }
 
+/// レスポンスの圧縮形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentEncoding {
+    /// 圧縮なし
+    Identity,
+    /// gzip
+    Gzip,
+    /// deflate
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// Accept-Encodingヘッダーから圧縮形式を選択
+    /// 
+    /// gzipを優先し、`q=0`で明示的に拒否された形式は使わない
+    fn negotiate(accept_encoding: &str) -> Self {
+        let accepted: Vec<&str> = accept_encoding
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';').map(str::trim);
+                let coding = params.next()?;
+                let rejected = params.any(|param| {
+                    param
+                        .strip_prefix("q=")
+                        .and_then(|q| q.parse::<f32>().ok())
+                        .is_some_and(|q| q == 0.0)
+                });
+                (!rejected).then_some(coding)
+            })
+            .collect();
+        
+        if accepted.iter().any(|coding| coding.eq_ignore_ascii_case("gzip")) {
+            ContentEncoding::Gzip
+        } else if accepted.iter().any(|coding| coding.eq_ignore_ascii_case("deflate")) {
+            ContentEncoding::Deflate
+        } else {
+            ContentEncoding::Identity
+        }
+    }
+    
+    /// Content-Encodingヘッダーの値
+    fn header_value(&self) -> Option<&'static str> {
+        match self {
+            ContentEncoding::Identity => None,
+            ContentEncoding::Gzip => Some("gzip"),
+            ContentEncoding::Deflate => Some("deflate"),
+        }
+    }
+    
+    /// コンテンツを圧縮
+    fn encode(&self, content: &str) -> std::io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Identity => Ok(content.as_bytes().to_vec()),
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(content.as_bytes())?;
+                encoder.finish()
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(content.as_bytes())?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// SVGコンテンツからETagを計算
+/// 
+/// SVGのSHA-256ハッシュ（先頭16バイト）を強いETagとして使う
+/// 圧縮した場合は表現が異なるため、圧縮形式をETagに含める
+/// 
+/// # Returns
+/// * 引用符付きのETag（例: `"3f2a..."`、gzipの場合は`"3f2a...-gzip"`）
+fn compute_etag(content: &str, encoding: ContentEncoding) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    let hex: String = digest[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    match encoding.header_value() {
+        Some(coding) => format!("\"{}-{}\"", hex, coding),
+        None => format!("\"{}\"", hex),
+    }
+}
+
+/// If-None-MatchがETagに一致するか判定
+/// 
+/// カンマ区切りの複数指定・`*`・弱いETag（`W/`付き）に対応
+/// （If-None-Matchは弱い比較で判定する）
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.trim_start_matches("W/") == etag
+    })
+}
+
 /// SVGレスポンスを構築
 /// 
 /// # Arguments
 /// * `badge` - バッジデータ
 /// * `from_cache` - キャッシュから取得したかどうか
+/// * `if_none_match` - リクエストのIf-None-Matchヘッダー
+/// * `encoding` - レスポンスの圧縮形式
 /// 
 /// # Returns
-/// * `Response` - HTTPレスポンス
-fn build_svg_response(badge: BadgeSvg, from_cache: bool) -> Response {
+/// * `Response` - HTTPレスポンス（ETag一致時は304）
+fn build_svg_response(
+    badge: BadgeSvg,
+    from_cache: bool,
+    if_none_match: Option<&str>,
+    encoding: ContentEncoding,
+) -> Response {
+    // ETagは圧縮前のコンテンツと圧縮形式から決まるため、304の判定は圧縮せずに行う
+    let etag = compute_etag(&badge.content, encoding);
+    
+    // クライアントが最新版を持っていればボディなしで返す
+    if if_none_match.is_some_and(|value| etag_matches(value, &etag)) {
+        return Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, etag)
+            .header(header::CACHE_CONTROL, badge.cache_control())
+            .header(header::VARY, "Accept-Encoding")
+            .body(Body::empty())
+            .unwrap();
+    }
+    
+    // 圧縮に失敗した場合は非圧縮で返す
+    let (encoding, etag, body) = match encoding.encode(&badge.content) {
+        Ok(body) => (encoding, etag, body),
+        Err(_) => (
+            ContentEncoding::Identity,
+            compute_etag(&badge.content, ContentEncoding::Identity),
+            badge.content.clone().into_bytes(),
+        ),
+    };
+    
     let mut response = Response::builder()
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, badge.content_type())
         .header(header::CACHE_CONTROL, badge.cache_control())
+        .header(header::ETAG, etag)
+        .header(header::VARY, "Accept-Encoding")
         .header("X-Content-Type-Options", "nosniff");
     
+    if let Some(coding) = encoding.header_value() {
+        response = response.header(header::CONTENT_ENCODING, coding);
+    }
+    
     // キャッシュヒットの場合はヘッダーを追加
     if from_cache {
         response = response.header("X-Cache", "HIT");
@@ -175,9 +888,8 @@ fn build_svg_response(badge: BadgeSvg, from_cache: bool) -> Response {
     }
     
     response
-        .body(badge.content.into())
+        .body(Body::from(body))
         .unwrap()
-        .into_response()
 }
 
 /// バッジプレビューエンドポイント（開発用）
@@ -191,4 +903,409 @@ pub async fn preview_badges() -> impl IntoResponse {
     // - 各種状態のバッジを生成
     // - HTMLで一覧表示
     StatusCode::NOT_IMPLEMENTED
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_validate_badge_label() {
+        // 有効なラベル
+        assert!(validate_badge_label("streak").is_ok());
+        assert!(validate_badge_label("my project").is_ok());
+        assert!(validate_badge_label(&"a".repeat(MAX_LABEL_LENGTH)).is_ok());
+        
+        // 長すぎるラベル
+        assert!(matches!(
+            validate_badge_label(&"a".repeat(MAX_LABEL_LENGTH + 1)),
+            Err(HandlerError::BadRequest(_))
+        ));
+        
+        // 空のラベル
+        assert!(validate_badge_label("").is_err());
+        
+        // 印字可能でない文字
+        assert!(validate_badge_label("tab\there").is_err());
+        assert!(validate_badge_label("ラベル").is_err());
+    }
+    
+    #[test]
+    fn test_parse_badge_filename() {
+        assert_eq!(parse_badge_filename("octocat.svg"), Some(("octocat", BadgeFormat::Svg)));
+        assert_eq!(parse_badge_filename("octocat.json"), Some(("octocat", BadgeFormat::Json)));
+        // ユーザー名にドットは含まれないが、最後の拡張子だけを取り除く
+        assert_eq!(parse_badge_filename("octo.cat.svg"), Some(("octo.cat", BadgeFormat::Svg)));
+        
+        // 未対応の拡張子・拡張子なし
+        assert_eq!(parse_badge_filename("octocat.gif"), None);
+        assert_eq!(parse_badge_filename("octocat.SVG"), None);
+        assert_eq!(parse_badge_filename("octocat"), None);
+        
+        // PNGはフィーチャーが有効な場合のみ
+        let png = parse_badge_filename("octocat.png");
+        if cfg!(feature = "png") {
+            assert!(png.is_some_and(|(username, _)| username == "octocat"));
+        } else {
+            assert_eq!(png, None);
+        }
+    }
+    
+    #[test]
+    fn test_select_badge_format() {
+        // Acceptヘッダーがなければ・対応する形式がなければSVG
+        assert_eq!(select_badge_format("octocat", None, None), Some(("octocat", BadgeFormat::Svg)));
+        assert_eq!(select_badge_format("octocat", None, Some("*/*")), Some(("octocat", BadgeFormat::Svg)));
+        assert_eq!(
+            select_badge_format("octocat", None, Some("application/json")),
+            Some(("octocat", BadgeFormat::Json))
+        );
+        
+        // ?format= はAcceptヘッダーより優先する
+        assert_eq!(
+            select_badge_format("octocat", Some("json"), Some("image/svg+xml")),
+            Some(("octocat", BadgeFormat::Json))
+        );
+        assert_eq!(select_badge_format("octocat", Some("SVG"), None), Some(("octocat", BadgeFormat::Svg)));
+        assert_eq!(select_badge_format("octocat", Some("gif"), None), None);
+        
+        // 拡張子は ?format= とAcceptヘッダーより優先する
+        assert_eq!(
+            select_badge_format("octocat.svg", Some("json"), Some("application/json")),
+            Some(("octocat", BadgeFormat::Svg))
+        );
+        assert_eq!(select_badge_format("octocat.gif", None, None), None);
+        
+        // PNGはフィーチャーが有効な場合のみ（無効な場合はSVGにフォールバック）
+        let png = select_badge_format("octocat", None, Some("image/png")).map(|(_, format)| format);
+        if cfg!(feature = "png") {
+            assert!(png.is_some_and(|format| format != BadgeFormat::Svg));
+        } else {
+            assert_eq!(png, Some(BadgeFormat::Svg));
+        }
+    }
+    
+    #[test]
+    fn test_negotiate_badge_format() {
+        // 同じqの場合はSVGを優先する（Firefoxの画像リクエスト）
+        assert_eq!(
+            BadgeFormat::negotiate("image/avif,image/webp,image/png,image/svg+xml,image/*;q=0.8,*/*;q=0.5"),
+            BadgeFormat::Svg
+        );
+        // qが大きい形式を選ぶ
+        assert_eq!(
+            BadgeFormat::negotiate("image/svg+xml;q=0.5, application/json"),
+            BadgeFormat::Json
+        );
+        // q=0 は拒否
+        assert_eq!(BadgeFormat::negotiate("application/json;q=0"), BadgeFormat::Svg);
+        // メディアタイプは大文字小文字を区別しない
+        assert_eq!(BadgeFormat::negotiate("Application/JSON"), BadgeFormat::Json);
+    }
+    
+    #[test]
+    fn test_parse_not_found_display() {
+        let query = |json: serde_json::Value| serde_json::from_value::<BadgeQuery>(json).unwrap();
+        
+        assert_eq!(parse_not_found_display(&query(serde_json::json!({}))).unwrap(), NotFoundDisplay::Message);
+        assert_eq!(
+            parse_not_found_display(&query(serde_json::json!({"notfound": "hide"}))).unwrap(),
+            NotFoundDisplay::Hidden
+        );
+        assert_eq!(
+            parse_not_found_display(&query(serde_json::json!({"notfound_text": "Unknown"}))).unwrap(),
+            NotFoundDisplay::CustomMessage("Unknown".to_string())
+        );
+        
+        // メッセージはラベルと同じ制約で検証する
+        let too_long = "a".repeat(MAX_LABEL_LENGTH + 1);
+        assert!(parse_not_found_display(&query(serde_json::json!({"notfound_text": too_long}))).is_err());
+    }
+    
+    mod cold_cache_tests {
+        use super::*;
+        use crate::app::dependencies::mocks::MockCacheService;
+        use crate::infra::adapters::storage::InMemoryStorageAdapter;
+        
+        /// `age_seconds`秒前に生成したバッジをCloud Storageに保存する
+        async fn store_badge(storage: &InMemoryStorageAdapter, key: &str, age_seconds: i64, is_interactive: bool) {
+            let badge = BadgeSvg {
+                content: "<svg>stored</svg>".to_string(),
+                cache_ttl: 300,
+                is_interactive,
+            };
+            let generated_at = Utc::now() - chrono::Duration::seconds(age_seconds);
+            let entry = CachedBadge::new(&badge, generated_at).to_json().unwrap();
+            storage.save_badge(key, &entry).await.unwrap();
+        }
+        
+        /// バックグラウンドでのCloud Storageへの書き込みが終わるまで待つ
+        async fn wait_for_stored(storage: &InMemoryStorageAdapter, key: &str) -> CachedBadge {
+            let stored = tokio::time::timeout(std::time::Duration::from_secs(1), async {
+                loop {
+                    if let Some(stored) = storage.get_badge(key).await.unwrap() {
+                        return stored;
+                    }
+                    tokio::task::yield_now().await;
+                }
+            })
+            .await
+            .expect("Cloud Storageに保存されていません");
+            serde_json::from_str(&stored).unwrap()
+        }
+        
+        #[tokio::test]
+        async fn test_redis_miss_falls_back_to_storage() {
+            let cache = MockCacheService::new();
+            let storage = InMemoryStorageAdapter::new();
+            store_badge(&storage, "badge:octocat", 100, false).await;
+            
+            let badge = get_cached_badge(&cache, &storage, "badge:octocat").await.unwrap().unwrap();
+            assert_eq!(badge.content, "<svg>stored</svg>");
+            // 残りのTTLだけキャッシュさせる（時刻のずれを考慮）
+            assert!((199..=200).contains(&badge.cache_ttl), "cache_ttl = {}", badge.cache_ttl);
+            
+            // 残りのTTLでRedisに書き戻される
+            let cached = cache.get("badge:octocat").await.unwrap().unwrap();
+            let cached: CachedBadge = serde_json::from_str(&cached).unwrap();
+            assert_eq!(cached.content, "<svg>stored</svg>");
+            assert_eq!(cache.ttl("badge:octocat"), Some(badge.cache_ttl));
+        }
+        
+        #[tokio::test]
+        async fn test_expired_storage_entry_is_a_miss() {
+            let cache = MockCacheService::new();
+            let storage = InMemoryStorageAdapter::new();
+            store_badge(&storage, "badge:octocat", 301, false).await;
+            
+            assert!(get_cached_badge(&cache, &storage, "badge:octocat").await.unwrap().is_none());
+            assert!(cache.get("badge:octocat").await.unwrap().is_none());
+        }
+        
+        #[tokio::test]
+        async fn test_legacy_raw_svg_in_storage_is_a_miss() {
+            let cache = MockCacheService::new();
+            let storage = InMemoryStorageAdapter::new();
+            storage.save_badge("badge:octocat", "<svg>legacy</svg>").await.unwrap();
+            
+            assert!(get_cached_badge(&cache, &storage, "badge:octocat").await.unwrap().is_none());
+        }
+        
+        #[tokio::test]
+        async fn test_storage_entry_keeps_interactivity() {
+            let cache = MockCacheService::new();
+            let storage = InMemoryStorageAdapter::new();
+            store_badge(&storage, "badge:octocat", 0, true).await;
+            
+            let badge = get_cached_badge(&cache, &storage, "badge:octocat").await.unwrap().unwrap();
+            assert!(badge.is_interactive);
+        }
+        
+        #[tokio::test]
+        async fn test_invalidation_removes_stored_badge() {
+            let cache = MockCacheService::new();
+            let storage = Arc::new(InMemoryStorageAdapter::new());
+            let username = Username::parse("octocat".to_string()).unwrap();
+            let cache_key = "badge:octocat:v2:static:flat:light:default:GitPoke";
+            let badge = BadgeSvg {
+                content: "<svg>before</svg>".to_string(),
+                cache_ttl: 300,
+                is_interactive: false,
+            };
+            
+            save_badge_to_cache(&cache, storage.clone(), cache_key, &badge).await.unwrap();
+            wait_for_stored(&storage, cache_key).await;
+            
+            crate::use_cases::user_settings::invalidate_user_cache(&username, &cache, storage.as_ref())
+                .await
+                .unwrap();
+            
+            assert!(get_cached_badge(&cache, storage.as_ref(), cache_key).await.unwrap().is_none());
+        }
+        
+        #[tokio::test]
+        async fn test_miss_in_both_caches() {
+            let cache = MockCacheService::new();
+            let storage = InMemoryStorageAdapter::new();
+            
+            assert!(get_cached_badge(&cache, &storage, "badge:nobody").await.unwrap().is_none());
+            assert!(cache.get("badge:nobody").await.unwrap().is_none());
+        }
+        
+        #[tokio::test]
+        async fn test_save_writes_storage_in_background() {
+            let cache = MockCacheService::new();
+            let storage = Arc::new(InMemoryStorageAdapter::new());
+            let badge = BadgeSvg {
+                content: "<svg>fresh</svg>".to_string(),
+                cache_ttl: 300,
+                is_interactive: false,
+            };
+            
+            save_badge_to_cache(&cache, storage.clone(), "badge:octocat", &badge).await.unwrap();
+            assert!(cache.get("badge:octocat").await.unwrap().is_some());
+            
+            let stored = wait_for_stored(&storage, "badge:octocat").await;
+            assert_eq!(stored.content, "<svg>fresh</svg>");
+            assert_eq!(stored.cache_ttl, 300);
+        }
+        
+        #[tokio::test]
+        async fn test_cached_badge_keeps_metadata() {
+            let cache = MockCacheService::new();
+            let storage = Arc::new(InMemoryStorageAdapter::new());
+            let badge = BadgeSvg {
+                content: "<svg onclick=\"gitpokePoke(this)\"/>".to_string(),
+                cache_ttl: 3600,
+                is_interactive: true,
+            };
+            
+            save_badge_to_cache(&cache, storage.clone(), "badge:octocat", &badge).await.unwrap();
+            let restored = get_cached_badge(&cache, storage.as_ref(), "badge:octocat")
+                .await
+                .unwrap()
+                .unwrap();
+            
+            assert_eq!(restored.content, badge.content);
+            assert_eq!(restored.cache_ttl, 3600);
+            assert!(restored.is_interactive);
+            assert_eq!(restored.cache_control(), badge.cache_control());
+        }
+        
+        #[tokio::test]
+        async fn test_legacy_raw_svg_entry_is_a_miss() {
+            let cache = MockCacheService::new();
+            let storage = InMemoryStorageAdapter::new();
+            cache.set("badge:octocat", "<svg>legacy</svg>", 300).await.unwrap();
+            
+            assert!(get_cached_badge(&cache, &storage, "badge:octocat").await.unwrap().is_none());
+        }
+    }
+    
+    fn create_badge(content: &str) -> BadgeSvg {
+        BadgeSvg {
+            content: content.to_string(),
+            cache_ttl: 300,
+            is_interactive: false,
+        }
+    }
+    
+    #[test]
+    fn test_etag_header_is_set() {
+        let response = build_svg_response(create_badge("<svg/>"), false, None, ContentEncoding::Identity);
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG).unwrap(),
+            compute_etag("<svg/>", ContentEncoding::Identity).as_str()
+        );
+    }
+    
+    #[test]
+    fn test_not_modified_when_etag_matches() {
+        let etag = compute_etag("<svg/>", ContentEncoding::Identity);
+        let response = build_svg_response(create_badge("<svg/>"), true, Some(&etag), ContentEncoding::Identity);
+        
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), etag.as_str());
+        assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+    }
+    
+    #[test]
+    fn test_changed_content_produces_new_etag() {
+        let old_etag = compute_etag("<svg>old</svg>", ContentEncoding::Identity);
+        let response = build_svg_response(
+            create_badge("<svg>new</svg>"),
+            false,
+            Some(&old_etag),
+            ContentEncoding::Identity,
+        );
+        
+        // コンテンツが変わったので200で新しいETagを返す
+        assert_eq!(response.status(), StatusCode::OK);
+        let new_etag = response.headers().get(header::ETAG).unwrap();
+        assert_ne!(new_etag, old_etag.as_str());
+    }
+    
+    #[test]
+    fn test_etag_matches() {
+        let etag = compute_etag("<svg/>", ContentEncoding::Identity);
+        
+        assert!(etag_matches(&etag, &etag));
+        assert!(etag_matches(&format!("W/{}", etag), &etag));
+        assert!(etag_matches(&format!("\"other\", {}", etag), &etag));
+        assert!(etag_matches("*", &etag));
+        assert!(!etag_matches("\"other\"", &etag));
+    }
+    
+    async fn read_body(response: Response) -> Vec<u8> {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+    
+    #[test]
+    fn test_negotiate_encoding() {
+        assert_eq!(ContentEncoding::negotiate("gzip, deflate, br"), ContentEncoding::Gzip);
+        assert_eq!(ContentEncoding::negotiate("deflate"), ContentEncoding::Deflate);
+        assert_eq!(ContentEncoding::negotiate("br"), ContentEncoding::Identity);
+        assert_eq!(ContentEncoding::negotiate("gzip;q=0, deflate"), ContentEncoding::Deflate);
+        assert_eq!(ContentEncoding::negotiate(""), ContentEncoding::Identity);
+    }
+    
+    #[tokio::test]
+    async fn test_gzip_response_for_accepting_client() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+        
+        let svg = "<svg>".to_string() + &"badge".repeat(100) + "</svg>";
+        let response = build_svg_response(create_badge(&svg), false, None, ContentEncoding::Gzip);
+        
+        assert_eq!(response.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(response.headers().get(header::VARY).unwrap(), "Accept-Encoding");
+        assert_eq!(
+            response.headers().get(header::ETAG).unwrap(),
+            compute_etag(&svg, ContentEncoding::Gzip).as_str()
+        );
+        
+        // 展開すると元のSVGと一致する
+        let body = read_body(response).await;
+        assert!(body.len() < svg.len());
+        let mut decoded = String::new();
+        GzDecoder::new(body.as_slice()).read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, svg);
+    }
+    
+    #[tokio::test]
+    async fn test_uncompressed_response_for_non_accepting_client() {
+        let response = build_svg_response(create_badge("<svg/>"), false, None, ContentEncoding::Identity);
+        
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(read_body(response).await, b"<svg/>");
+    }
+    
+    #[test]
+    fn test_etag_differs_by_encoding() {
+        let identity = compute_etag("<svg/>", ContentEncoding::Identity);
+        let gzip = compute_etag("<svg/>", ContentEncoding::Gzip);
+        
+        assert_ne!(identity, gzip);
+        
+        // 非圧縮のETagではgzipレスポンスは304にならない
+        let response = build_svg_response(create_badge("<svg/>"), false, Some(&identity), ContentEncoding::Gzip);
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    
+    #[tokio::test]
+    async fn test_not_modified_for_compressed_response() {
+        let gzip = compute_etag("<svg/>", ContentEncoding::Gzip);
+        
+        let response = build_svg_response(create_badge("<svg/>"), true, Some(&gzip), ContentEncoding::Gzip);
+        
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), gzip.as_str());
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert!(read_body(response).await.is_empty());
+    }
+}
@@ -4,16 +4,23 @@
 //! - 基本的なヘルスチェック（/health）
 //! - 詳細な準備状態チェック（/ready）
 //! - 依存サービスの状態確認
+//! - Prometheusメトリクス（/metrics）
 
 use axum::{
     extract::State,
-    response::Json,
-    http::StatusCode,
+    response::{IntoResponse, Json},
+    http::{header, StatusCode},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+// テストで一時停止できるよう、応答時間の計測にはtokioの時計を使う
+use tokio::time::Instant;
 
-use crate::app::dependencies::AppDependencies;
+use crate::app::dependencies::{AppDependencies, CacheService, GitHubApi, UserRepository};
+use crate::app::metrics::METRICS;
 use crate::error::AppResult;
 
 /// ヘルスチェックレスポンス
@@ -30,6 +37,23 @@ pub struct HealthResponse {
     pub uptime_seconds: Option<u64>,
 }
 
+/// プロセスの起動時刻
+static STARTED_AT: OnceLock<std::time::Instant> = OnceLock::new();
+
+/// プロセスの起動時刻を記録
+/// 
+/// mainの最初に呼ぶ。呼ばれなかった場合は最初に稼働時間を参照した時刻が起点になる
+pub fn mark_process_start() {
+    STARTED_AT.get_or_init(std::time::Instant::now);
+}
+
+/// プロセスの稼働時間
+/// 
+/// 単調増加する時計で計測するため、システム時刻の変更に影響されない
+pub fn uptime() -> Duration {
+    STARTED_AT.get_or_init(std::time::Instant::now).elapsed()
+}
+
 /// 基本的なヘルスチェック
 /// 
 /// アプリケーションが起動していることを確認
@@ -41,7 +65,7 @@ pub async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime_seconds: None, // TODO: 実装
+        uptime_seconds: Some(uptime().as_secs()),
     })
 }
 
@@ -90,33 +114,28 @@ pub struct ServiceStatus {
 pub async fn readiness_check(
     State(deps): State<AppDependencies>,
 ) -> Result<Json<ReadinessResponse>, (StatusCode, Json<ReadinessResponse>)> {
-    use futures::future::join_all;
-    
     // 各サービスのヘルスチェックを並列実行
     let (redis_status, firestore_status, github_status) = tokio::join!(
-        check_redis(&deps),
-        check_firestore(&deps),
-        check_github_api(&deps)
+        check_redis(deps.cache_service.as_ref()),
+        check_firestore(deps.user_repository.as_ref()),
+        check_github_api(deps.github_api.as_ref())
     );
     
     // 結果を集約
+    // degraded（GitHubの残りクォータ不足など）はリクエストを処理できるため準備完了とみなす
     let mut services = HashMap::new();
     let mut overall_healthy = true;
     
-    if redis_status.status != "healthy" {
-        overall_healthy = false;
-    }
-    services.insert("redis".to_string(), redis_status);
-    
-    if firestore_status.status != "healthy" {
-        overall_healthy = false;
+    for (name, status) in [
+        ("redis", redis_status),
+        ("firestore", firestore_status),
+        ("github_api", github_status),
+    ] {
+        if status.status == STATUS_UNHEALTHY {
+            overall_healthy = false;
+        }
+        services.insert(name.to_string(), status);
     }
-    services.insert("firestore".to_string(), firestore_status);
-    
-    if github_status.status != "healthy" {
-        overall_healthy = false;
-    }
-    services.insert("github_api".to_string(), github_status);
     
     let response = ReadinessResponse {
         status: if overall_healthy { "ready".to_string() } else { "not_ready".to_string() },
@@ -131,70 +150,174 @@ pub async fn readiness_check(
     }
 }
 
-/// Redis接続チェック
+/// サービスの状態
+const STATUS_HEALTHY: &str = "healthy";
+const STATUS_DEGRADED: &str = "degraded";
+const STATUS_UNHEALTHY: &str = "unhealthy";
+
+/// Redisチェックのタイムアウト
+const REDIS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Firestoreチェックのタイムアウト
+const FIRESTORE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// GitHub APIチェックのタイムアウト
+const GITHUB_API_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Redisチェックで参照するキー（存在しなくてよい）
+const REDIS_SENTINEL_KEY: &str = "health_check_ping";
+
+/// Firestoreチェックで検索するユーザー名
 /// 
-/// PINGコマンドを送信して接続を確認
-async fn check_redis(deps: &AppDependencies) -> ServiceStatus {
-    let start = std::time::Instant::now();
+/// GitHubのユーザー名に使えない文字を含むため、実在のユーザーと衝突しない
+const FIRESTORE_SENTINEL_USERNAME: &str = "health_check";
+
+/// タイムアウト付きでサービスを確認
+/// 
+/// # Arguments
+/// * `timeout` - タイムアウト
+/// * `probe` - 確認処理
+/// * `classify` - 成功時の結果からステータスを決める
+/// 
+/// # Returns
+/// * 成功時は `classify` の結果、失敗・タイムアウト時は "unhealthy"
+async fn probe_service<T>(
+    timeout: Duration,
+    probe: impl Future<Output = AppResult<T>>,
+    classify: impl FnOnce(T) -> &'static str,
+) -> ServiceStatus {
+    let start = Instant::now();
+    let result = tokio::time::timeout(timeout, probe).await;
+    let response_time_ms = Some(start.elapsed().as_millis() as u64);
     
-    // TODO: 実装
-    // - cache_service.get("health_check_ping") を実行
-    // - タイムアウト1秒
-    // - 成功すれば "healthy"
+    let (status, error) = match result {
+        Ok(Ok(value)) => (classify(value), None),
+        Ok(Err(e)) => (STATUS_UNHEALTHY, Some(e.to_string())),
+        Err(_) => (STATUS_UNHEALTHY, Some(format!("Timed out after {}ms", timeout.as_millis()))),
+    };
     
     ServiceStatus {
-        status: "healthy".to_string(),
-        response_time_ms: Some(start.elapsed().as_millis() as u64),
-        error: None,
+        status: status.to_string(),
+        response_time_ms,
+        error,
     }
 }
 
+/// Redis接続チェック
+/// 
+/// センチネルキーを読み取って接続を確認（値の有無は問わない）
+async fn check_redis(cache: &dyn CacheService) -> ServiceStatus {
+    probe_service(REDIS_TIMEOUT, cache.get(REDIS_SENTINEL_KEY), |_| STATUS_HEALTHY).await
+}
+
 /// Firestore接続チェック
 /// 
-/// システムコレクションへのクエリで接続を確認
-async fn check_firestore(deps: &AppDependencies) -> ServiceStatus {
-    let start = std::time::Instant::now();
-    
-    // TODO: 実装
-    // - user_repository.find_by_username("health_check") を実行
-    // - タイムアウト2秒
-    // - 成功すれば "healthy"（ユーザーが存在しなくてもOK）
-    
-    ServiceStatus {
-        status: "healthy".to_string(),
-        response_time_ms: Some(start.elapsed().as_millis() as u64),
-        error: None,
-    }
+/// 存在しないユーザーを検索して接続を確認（見つからなくても正常）
+async fn check_firestore(user_repository: &dyn UserRepository) -> ServiceStatus {
+    probe_service(
+        FIRESTORE_TIMEOUT,
+        user_repository.find_by_username(FIRESTORE_SENTINEL_USERNAME),
+        |_| STATUS_HEALTHY,
+    )
+    .await
 }
 
 /// GitHub API到達性チェック
 /// 
 /// APIのrate_limitエンドポイントで接続を確認
-async fn check_github_api(deps: &AppDependencies) -> ServiceStatus {
-    let start = std::time::Instant::now();
+/// 残りクォータが少ない場合は "degraded"
+async fn check_github_api(github_api: &dyn GitHubApi) -> ServiceStatus {
+    probe_service(GITHUB_API_TIMEOUT, github_api.get_rate_limit(), |rate_limit| {
+        if rate_limit.is_low() {
+            STATUS_DEGRADED
+        } else {
+            STATUS_HEALTHY
+        }
+    })
+    .await
+}
+
+/// メトリクスエンドポイント
+/// 
+/// GET /metrics
+/// 
+/// Prometheusのテキスト形式でメトリクスを公開：
+/// - リクエスト数（ルート・ステータス別）
+/// - レスポンスタイム
+/// - バッジキャッシュのヒット・ミス数
+/// - GitHub API呼び出し数
+pub async fn metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        METRICS.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::dependencies::mocks::{
+        MockCacheService, MockGitHubApi, MockUserRepository, Outage, UnavailableService,
+    };
+    
+    #[tokio::test]
+    async fn test_health_check_reports_uptime() {
+        mark_process_start();
+        let before = uptime();
+        
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        
+        let uptime_seconds = health_check().await.0.uptime_seconds.unwrap();
+        assert!(uptime_seconds >= 1);
+        assert!(uptime() >= before + Duration::from_millis(1100));
+    }
     
-    // TODO: 実装
-    // - github_api.get_rate_limit() を実行
-    // - タイムアウト3秒
-    // - 成功すれば "healthy"
-    // - レート制限が少ない場合は "degraded"
+    #[tokio::test]
+    async fn test_healthy_services() {
+        for status in [
+            check_redis(&MockCacheService::new()).await,
+            check_firestore(&MockUserRepository::new()).await,
+            check_github_api(&MockGitHubApi::new()).await,
+        ] {
+            assert_eq!(status.status, "healthy");
+            assert_eq!(status.error, None);
+            assert!(status.response_time_ms.is_some());
+        }
+    }
     
-    ServiceStatus {
-        status: "healthy".to_string(),
-        response_time_ms: Some(start.elapsed().as_millis() as u64),
-        error: None,
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_services_time_out() {
+        let redis = check_redis(&UnavailableService(Outage::Slow)).await;
+        assert_eq!(redis.status, "unhealthy");
+        assert_eq!(redis.response_time_ms, Some(1000));
+        assert_eq!(redis.error.as_deref(), Some("Timed out after 1000ms"));
+        
+        let firestore = check_firestore(&UnavailableService(Outage::Slow)).await;
+        assert_eq!(firestore.status, "unhealthy");
+        assert_eq!(firestore.response_time_ms, Some(2000));
+        
+        let github = check_github_api(&UnavailableService(Outage::Slow)).await;
+        assert_eq!(github.status, "unhealthy");
+        assert_eq!(github.response_time_ms, Some(3000));
+    }
+    
+    #[tokio::test]
+    async fn test_failing_services() {
+        for status in [
+            check_redis(&UnavailableService(Outage::Failing)).await,
+            check_firestore(&UnavailableService(Outage::Failing)).await,
+            check_github_api(&UnavailableService(Outage::Failing)).await,
+        ] {
+            assert_eq!(status.status, "unhealthy");
+            assert!(status.error.unwrap().contains("connection refused"));
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_low_github_quota_is_degraded() {
+        let status = check_github_api(&MockGitHubApi::new().with_rate_limit_remaining(10)).await;
+        
+        assert_eq!(status.status, "degraded");
+        assert_eq!(status.error, None);
     }
 }
-
-/// メトリクスエンドポイント（将来実装）
-/// 
-/// Prometheusフォーマットでメトリクスを公開
-#[allow(dead_code)]
-pub async fn metrics() -> String {
-    // TODO: 実装
-    // - リクエスト数
-    // - レスポンスタイム
-    // - エラー率
-    // - キャッシュヒット率
-    String::from("# HELP gitpoke_requests_total Total number of HTTP requests\n")
-}
\ No newline at end of file
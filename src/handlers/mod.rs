@@ -6,11 +6,14 @@
 //! - ユースケース層の呼び出し
 //! - レスポンスの構築
 
+pub mod activity;
+pub mod admin;
 pub mod auth;
 pub mod badge;
 pub mod health;
 pub mod poke;
 pub mod user;
+pub mod webhook;
 
 // 共通のハンドラーユーティリティ
 pub mod utils;
\ No newline at end of file
@@ -2,22 +2,36 @@
 //! 
 //! このファイルは以下を定義：
 //! - Poke送信エンドポイント
+//! - 複数の受信者への一括Poke送信エンドポイント
+//! - Poke可否のプレビューエンドポイント
 //! - レート制限の実装
+//! - レート制限の残り回数ヘッダー
 //! - Poke結果の返却
 
 use axum::{
-    extract::{State, Json},
-    http::StatusCode,
+    extract::{Query, State, Json},
+    http::{HeaderMap, HeaderValue, StatusCode},
 };
 use serde::{Deserialize, Serialize};
 
-use crate::app::dependencies::AppDependencies;
-use crate::domain::poke::{PokeResult, PokeCapability};
+use crate::app::dependencies::{AppDependencies, EventStore, RateLimiter};
+use crate::domain::poke::{PokeCursor, PokeDirection, PokeResult, PokeCapability};
 use crate::domain::user::Username;
-use crate::error::{AppResult, HandlerError, DomainError};
+use crate::domain::validation::{sanitize_poke_message, validate_repo_context, MAX_POKE_MESSAGE_LENGTH};
+use crate::error::{AppError, AppResult, HandlerError, DomainError};
+use crate::handlers::utils::get_rate_limit_ip;
 use crate::middlewares::auth::AuthenticatedUser;
 use crate::use_cases::check_poke as use_case;
 
+/// IPベースのレート制限のウィンドウ（秒）
+const IP_RATE_LIMIT_WINDOW_SECONDS: u64 = 60;
+
+/// IPベースのレート制限の残り回数ヘッダー
+const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// IPベースのレート制限で枠が1つ戻るまでの秒数ヘッダー
+const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+
 /// Pokeリクエスト
 #[derive(Debug, Deserialize)]
 pub struct PokeRequest {
@@ -27,6 +41,10 @@ pub struct PokeRequest {
     /// リポジトリコンテキスト（オプション）
     /// どのリポジトリから送信されたか
     pub repository: Option<String>,
+    
+    /// Pokeに添えるメッセージ（オプション、最大140文字）
+    #[serde(default)]
+    pub message: Option<String>,
 }
 
 /// Pokeレスポンス
@@ -62,6 +80,10 @@ pub struct PokeDetails {
     /// リポジトリ
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repository: Option<String>,
+    
+    /// 送信者が添えたメッセージ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 /// Poke送信エンドポイント
@@ -74,9 +96,10 @@ pub struct PokeDetails {
 /// * `request` - Pokeリクエスト
 /// * `deps` - アプリケーション依存性
 /// * `sender` - 認証済みの送信者（認証ミドルウェアから注入）
+/// * `headers` - リクエストヘッダー（クライアントIPの取得に使用）
 /// 
 /// # Returns
-/// * 200 OK - Poke成功
+/// * 200 OK - Poke成功・Poke不可（`success: false`）、どちらも `X-RateLimit-*` ヘッダー付き
 /// * 400 Bad Request - 無効なリクエスト
 /// * 401 Unauthorized - 未認証
 /// * 403 Forbidden - Poke不可（権限なし）
@@ -84,24 +107,39 @@ pub struct PokeDetails {
 pub async fn send_poke(
     State(deps): State<AppDependencies>,
     auth_user: AuthenticatedUser,
+    headers: HeaderMap,
     Json(request): Json<PokeRequest>,
-) -> AppResult<Json<PokeResponse>> {
+) -> AppResult<(HeaderMap, Json<PokeResponse>)> {
     let sender = auth_user.username;
     
     // 受信者のユーザー名を検証
-    let recipient_username = Username::parse(request.username.clone())
-        .map_err(|_| HandlerError::BadRequest("Invalid recipient username".to_string()))?;
+    let recipient_username = Username::parse(request.username.clone())?;
     
     // 自分自身へのPokeは不可
-    if sender.as_str() == recipient_username.as_str() {
+    // ドメイン層（PokeCapability::check）でも拒否するが、受信者を取得する前に弾く
+    if sender == recipient_username {
         return Err(HandlerError::BadRequest("Cannot poke yourself".into()).into());
     }
     
+    // リポジトリコンテキストは owner/repo 形式のみ受け付ける
+    if let Some(repository) = &request.repository {
+        validate_repo_context(repository)
+            .map_err(|_| HandlerError::BadRequest("Invalid repository".to_string()))?;
+    }
+    
+    let message = parse_poke_message(request.message.as_deref())?;
+    
     // IPベースのレート制限チェック
-    // TODO: 実装
-    // - リクエストからIPアドレスを取得
-    // - rate_limiter.check_limit() を実行
-    check_ip_rate_limit(&deps, "127.0.0.1").await?; // 仮のIP
+    // 転送ヘッダーはプロキシの背後で動作する環境でのみ信頼する
+    let client_ip = get_rate_limit_ip(
+        &headers,
+        deps.config.app.environment.trusts_forwarded_headers(),
+    );
+    check_ip_rate_limit(
+        deps.rate_limiter.as_ref(),
+        deps.config.app.rate_limit.poke_per_ip_per_minute,
+        &client_ip,
+    ).await?;
     
     // ユーザーベースのレート制限チェック（同一ターゲットへの制限）
     check_user_rate_limit(&deps, &sender, &recipient_username).await?;
@@ -110,13 +148,22 @@ pub async fn send_poke(
     let result = use_case::execute(
         &sender,
         &recipient_username,
+        request.repository.clone(),
+        message,
         &deps,
     ).await?;
     
+    // 今回のPokeを反映した残り回数を通知する
+    let rate_limit_headers = ip_rate_limit_headers(
+        deps.rate_limiter.as_ref(),
+        deps.config.app.rate_limit.poke_per_ip_per_minute,
+        &client_ip,
+    ).await;
+    
     // 結果に基づいてレスポンスを構築
-    match result {
-        PokeResult::Success { event_id, message } => {
-            Ok(Json(PokeResponse {
+    let response = match result {
+        PokeResult::Success { event_id, message, poke_message } => {
+            PokeResponse {
                 success: true,
                 message,
                 event_id: Some(event_id.to_string()),
@@ -125,53 +172,150 @@ pub async fn send_poke(
                     to: recipient_username.as_str().to_string(),
                     timestamp: chrono::Utc::now().to_rfc3339(),
                     repository: request.repository,
+                    message: poke_message,
                 }),
-            }))
+            }
         }
         PokeResult::Failed { reason } => {
             // エラーをPokeResponseとして返す（ステータスコードは200）
-            Ok(Json(PokeResponse {
+            PokeResponse {
                 success: false,
                 message: reason,
                 event_id: None,
                 details: None,
-            }))
+            }
         }
-    }
+    };
+    
+    Ok((rate_limit_headers, Json(response)))
+}
+
+/// Pokeに添えるメッセージを検証
+/// 
+/// # Arguments
+/// * `message` - リクエストのメッセージ
+/// 
+/// # Returns
+/// * `Ok(Some(String))` - 正規化したメッセージ
+/// * `Ok(None)` - メッセージなし（空白・制御文字のみの場合を含む）
+/// * `Err(HandlerError::BadRequest)` - 最大文字数を超える
+fn parse_poke_message(message: Option<&str>) -> AppResult<Option<String>> {
+    let Some(message) = message else {
+        return Ok(None);
+    };
+    
+    let message = sanitize_poke_message(message).map_err(|_| {
+        HandlerError::BadRequest(format!(
+            "Message must be at most {} characters",
+            MAX_POKE_MESSAGE_LENGTH
+        ))
+    })?;
+    
+    Ok(Some(message).filter(|message| !message.is_empty()))
 }
 
 /// IPベースのレート制限チェック
 /// 
 /// # Arguments
-/// * `deps` - アプリケーション依存性
+/// * `rate_limiter` - レート制限サービス
+/// * `limit` - 1分あたりの上限
 /// * `ip_address` - クライアントのIPアドレス
 /// 
 /// # Returns
 /// * `Ok(())` - 制限内
-/// * `Err(DomainError::RateLimitExceeded)` - 制限超過
+/// * `Err(DomainError::RateLimitExceeded)` - 制限超過（再試行までの秒数付き）
 async fn check_ip_rate_limit(
-    deps: &AppDependencies,
+    rate_limiter: &dyn RateLimiter,
+    limit: u32,
+    ip_address: &str,
+) -> AppResult<()> {
+    consume_ip_rate_limit(rate_limiter, limit, ip_address, 1).await
+}
+
+/// IPベースのレート制限を指定回数分消費
+/// 
+/// 一括Pokeでは受信者の数だけ消費し、残りが足りなければ1件も送信しない
+/// 
+/// # Arguments
+/// * `rate_limiter` - レート制限サービス
+/// * `limit` - 1分あたりの上限
+/// * `ip_address` - クライアントのIPアドレス
+/// * `cost` - 消費する回数
+/// 
+/// # Returns
+/// * `Ok(())` - 制限内
+/// * `Err(DomainError::RateLimitExceeded)` - 制限超過（再試行までの秒数付き）
+async fn consume_ip_rate_limit(
+    rate_limiter: &dyn RateLimiter,
+    limit: u32,
     ip_address: &str,
+    cost: u32,
 ) -> AppResult<()> {
-    let key = format!("rate_limit:poke:ip:{}", ip_address);
-    let limit = deps.config.app.rate_limit.poke_per_ip_per_minute;
-    let window = 60; // 1分
+    let key = ip_rate_limit_key(ip_address);
+    let window = IP_RATE_LIMIT_WINDOW_SECONDS;
     
-    let allowed = deps.rate_limiter.check_limit(&key, limit, window).await?;
+    // 現在の件数 + cost が上限以下であること
+    let allowed = cost <= limit
+        && rate_limiter.check_limit(&key, limit - cost + 1, window).await?;
     
     if !allowed {
-        return Err(DomainError::RateLimitExceeded.into());
+        // 最も古いリクエストがウィンドウから外れるまでの秒数を通知
+        let retry_after_secs = rate_limiter.retry_after(&key, window).await?;
+        return Err(DomainError::RateLimitExceeded { retry_after_secs }.into());
     }
     
     // カウントをインクリメント
-    deps.rate_limiter.increment(&key, window).await?;
+    for _ in 0..cost {
+        rate_limiter.increment(&key, window).await?;
+    }
     
     Ok(())
 }
 
+/// IPベースのレート制限のキー
+fn ip_rate_limit_key(ip_address: &str) -> String {
+    format!("rate_limit:poke:ip:{}", ip_address)
+}
+
+/// IPベースのレート制限の状態をレスポンスヘッダーにする
+/// 
+/// 同一受信者へのクールダウンは回数の枠ではないため、IPベースの制限だけを通知する
+/// Poke自体は完了しているので、取得に失敗した場合はログに残してヘッダーを省略する
+/// 
+/// # Arguments
+/// * `rate_limiter` - レート制限サービス
+/// * `limit` - 1分あたりの上限
+/// * `ip_address` - クライアントのIPアドレス
+/// 
+/// # Returns
+/// * `X-RateLimit-Remaining` - ウィンドウ内であと何回Pokeできるか
+/// * `X-RateLimit-Reset` - 最も古いPokeがウィンドウから外れ、枠が1つ戻るまでの秒数
+async fn ip_rate_limit_headers(
+    rate_limiter: &dyn RateLimiter,
+    limit: u32,
+    ip_address: &str,
+) -> HeaderMap {
+    let key = ip_rate_limit_key(ip_address);
+    let state = async {
+        let remaining = rate_limiter.remaining(&key, limit, IP_RATE_LIMIT_WINDOW_SECONDS).await?;
+        let reset = rate_limiter.retry_after(&key, IP_RATE_LIMIT_WINDOW_SECONDS).await?;
+        Ok::<_, AppError>((remaining, reset))
+    };
+    
+    let mut headers = HeaderMap::new();
+    match state.await {
+        Ok((remaining, reset)) => {
+            headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from(remaining));
+            headers.insert(RATE_LIMIT_RESET_HEADER, HeaderValue::from(reset));
+        }
+        Err(e) => tracing::warn!(error = %e, "レート制限の残り回数を取得できませんでした"),
+    }
+    headers
+}
+
 /// ユーザーベースのレート制限チェック
 /// 
-/// 同一ユーザーへの1日1回制限
+/// 同一ユーザーへの前回のPokeからクールダウン期間が経過するまで不可
 /// 
 /// # Arguments
 /// * `deps` - アプリケーション依存性
@@ -186,15 +330,7 @@ async fn check_user_rate_limit(
     sender: &Username,
     recipient: &Username,
 ) -> AppResult<()> {
-    // 今日のPokeを確認
-    let today_pokes = deps.event_store.find_today_pokes_from(sender.as_str()).await?;
-    
-    // 同じ受信者へのPokeがあるかチェック
-    let already_poked = today_pokes.iter().any(|poke| {
-        poke.to.as_str() == recipient.as_str()
-    });
-    
-    if already_poked {
+    if use_case::is_duplicate_poke(sender, recipient, deps).await? {
         return Err(DomainError::PokeNotAllowed(
             crate::error::PokeError::AlreadyPoked
         ).into());
@@ -203,26 +339,771 @@ async fn check_user_rate_limit(
     Ok(())
 }
 
-/// Poke履歴取得エンドポイント（将来実装）
+/// 一括Pokeで指定できる受信者の最大数
+/// 
+/// IPベースのレート制限がこれより小さい場合はそちらが上限になる
+const MAX_BATCH_POKE_RECIPIENTS: usize = 20;
+
+/// 一括Pokeリクエスト
+#[derive(Debug, Deserialize)]
+pub struct BatchPokeRequest {
+    /// Poke対象のユーザー名（最大20件、大文字小文字違いの重複は1件として扱う）
+    pub usernames: Vec<String>,
+    
+    /// リポジトリコンテキスト（オプション）
+    pub repository: Option<String>,
+}
+
+/// 一括Pokeレスポンス
+#[derive(Debug, Serialize)]
+pub struct BatchPokeResponse {
+    /// 受信者ごとの結果（リクエストの順序）
+    pub results: Vec<BatchPokeItem>,
+}
+
+/// 一括Pokeの受信者ごとの結果
+#[derive(Debug, Serialize)]
+pub struct BatchPokeItem {
+    /// 受信者のユーザー名（リクエストで指定された表記）
+    pub username: String,
+    
+    /// 成功/失敗
+    pub success: bool,
+    
+    /// メッセージ（失敗時は理由）
+    pub message: String,
+    
+    /// PokeイベントID（成功時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+}
+
+/// 一括Poke送信エンドポイント
+/// 
+/// POST /api/poke/batch
+/// 
+/// 複数の受信者へ順にPokeを送信する。受信者ごとの失敗（未登録・クールダウン中等）は
+/// 全体を失敗させず、結果の配列で個別に返す
+/// 
+/// # Arguments
+/// * `deps` - アプリケーション依存性
+/// * `auth_user` - 認証済みの送信者（認証ミドルウェアから注入）
+/// * `headers` - リクエストヘッダー（クライアントIPの取得に使用）
+/// * `request` - 一括Pokeリクエスト
+/// 
+/// # Returns
+/// * 200 OK - 受信者ごとの結果（一部が失敗した場合を含む）
+/// * 400 Bad Request - 受信者が空・上限超過、無効なリポジトリ
+/// * 401 Unauthorized - 未認証
+/// * 429 Too Many Requests - 受信者数分のレート制限の残りがない
+pub async fn send_batch_poke(
+    State(deps): State<AppDependencies>,
+    auth_user: AuthenticatedUser,
+    headers: HeaderMap,
+    Json(request): Json<BatchPokeRequest>,
+) -> AppResult<Json<BatchPokeResponse>> {
+    let client_ip = get_rate_limit_ip(
+        &headers,
+        deps.config.app.environment.trusts_forwarded_headers(),
+    );
+    
+    let response = execute_batch_poke(&deps, &auth_user.username, &client_ip, request).await?;
+    
+    Ok(Json(response))
+}
+
+/// 一括Pokeを実行
+/// 
+/// # Arguments
+/// * `deps` - アプリケーション依存性
+/// * `sender` - 送信者
+/// * `client_ip` - レート制限に使うクライアントIP
+/// * `request` - 一括Pokeリクエスト
+/// 
+/// # Returns
+/// * `Ok(BatchPokeResponse)` - 受信者ごとの結果
+/// * `Err(HandlerError::BadRequest)` - 受信者が空・上限超過、無効なリポジトリ
+/// * `Err(DomainError::RateLimitExceeded)` - レート制限超過
+async fn execute_batch_poke(
+    deps: &AppDependencies,
+    sender: &Username,
+    client_ip: &str,
+    request: BatchPokeRequest,
+) -> AppResult<BatchPokeResponse> {
+    let ip_limit = deps.config.app.rate_limit.poke_per_ip_per_minute;
+    let max_recipients = MAX_BATCH_POKE_RECIPIENTS.min(ip_limit as usize);
+    
+    let usernames = dedup_batch_usernames(request.usernames);
+    if usernames.is_empty() {
+        return Err(HandlerError::BadRequest("At least one username is required".to_string()).into());
+    }
+    if usernames.len() > max_recipients {
+        return Err(HandlerError::BadRequest(format!(
+            "At most {} usernames can be poked at once",
+            max_recipients
+        )).into());
+    }
+    
+    if let Some(repository) = &request.repository {
+        validate_repo_context(repository)
+            .map_err(|_| HandlerError::BadRequest("Invalid repository".to_string()))?;
+    }
+    
+    // バッチ全体で受信者数分のレート制限を消費する
+    consume_ip_rate_limit(
+        deps.rate_limiter.as_ref(),
+        ip_limit,
+        client_ip,
+        usernames.len() as u32,
+    ).await?;
+    
+    let mut results = Vec::with_capacity(usernames.len());
+    for username in usernames {
+        results.push(poke_batch_recipient(deps, sender, username, request.repository.clone()).await);
+    }
+    
+    Ok(BatchPokeResponse { results })
+}
+
+/// 大文字小文字の違いを無視して重複するユーザー名を除く（最初の表記を残す）
+fn dedup_batch_usernames(usernames: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    usernames
+        .into_iter()
+        .filter(|username| seen.insert(username.to_ascii_lowercase()))
+        .collect()
+}
+
+/// 一括Pokeの1件を送信
+/// 
+/// エラーは受信者ごとの失敗として結果に含める
+async fn poke_batch_recipient(
+    deps: &AppDependencies,
+    sender: &Username,
+    username: String,
+    repository: Option<String>,
+) -> BatchPokeItem {
+    let outcome = match Username::parse(username.clone()) {
+        Err(_) => Err("Invalid recipient username".to_string()),
+        Ok(recipient) if *sender == recipient => Err("Cannot poke yourself".to_string()),
+        Ok(recipient) => match use_case::execute(sender, &recipient, repository, None, deps).await {
+            Ok(PokeResult::Success { event_id, message, .. }) => Ok((event_id, message)),
+            Ok(PokeResult::Failed { reason }) => Err(reason),
+            Err(error) => Err(batch_failure_reason(&error)),
+        },
+    };
+    
+    match outcome {
+        Ok((event_id, message)) => BatchPokeItem {
+            username,
+            success: true,
+            message,
+            event_id: Some(event_id.to_string()),
+        },
+        Err(reason) => BatchPokeItem {
+            username,
+            success: false,
+            message: reason,
+            event_id: None,
+        },
+    }
+}
+
+/// 一括Pokeの失敗理由
+/// 
+/// 利用者が対処できるドメインエラーは理由を返し、それ以外は詳細を隠蔽する
+fn batch_failure_reason(error: &AppError) -> String {
+    match error {
+        AppError::Domain(DomainError::PokeNotAllowed(reason)) => reason.to_string(),
+        AppError::Domain(error @ (DomainError::UserNotFound(_) | DomainError::InvalidUsername(_))) => {
+            error.to_string()
+        }
+        _ => {
+            tracing::warn!(error = %error, "一括Pokeの送信に失敗しました");
+            "サービスが一時的に利用できません".to_string()
+        }
+    }
+}
+
+/// Pokeプレビューのクエリパラメータ
+#[derive(Debug, Deserialize)]
+pub struct PokePreviewQuery {
+    /// Poke対象のユーザー名
+    pub username: String,
+}
+
+/// Pokeプレビューレスポンス
+#[derive(Debug, Serialize)]
+pub struct PokePreviewResponse {
+    /// Pokeできるかどうか
+    pub can_poke: bool,
+    
+    /// Pokeできない理由のエラーコード（例: `NOT_FOLLOWER`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,
+    
+    /// Pokeできない理由のメッセージ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Pokeプレビューエンドポイント
+/// 
+/// GET /api/poke/preview?username=
+/// 
+/// 実際には送信せずにPokeできるかどうかを返す
+/// イベントの保存やレート制限の消費は行わないため、ボタンの表示切り替えに使える
+/// 
+/// # Returns
+/// * 200 OK - Poke可否と、不可の場合はその理由
+/// * 400 Bad Request - 無効なユーザー名・自分自身
+/// * 401 Unauthorized - 未認証
+/// * 404 Not Found - 受信者が存在しない
+pub async fn preview_poke(
+    State(deps): State<AppDependencies>,
+    auth_user: AuthenticatedUser,
+    Query(query): Query<PokePreviewQuery>,
+) -> AppResult<Json<PokePreviewResponse>> {
+    let response = execute_preview(&deps, &auth_user.username, &query.username).await?;
+    
+    Ok(Json(response))
+}
+
+/// Pokeプレビューを実行
 /// 
-/// GET /api/poke/history
+/// # Arguments
+/// * `deps` - アプリケーション依存性
+/// * `sender` - 送信者
+/// * `username` - 受信者のユーザー名（未検証）
 /// 
-/// 認証済みユーザーのPoke履歴を取得
-#[allow(dead_code)]
+/// # Returns
+/// * `Ok(PokePreviewResponse)` - Poke可否
+/// * `Err(HandlerError::BadRequest)` - 無効なユーザー名・自分自身
+async fn execute_preview(
+    deps: &AppDependencies,
+    sender: &Username,
+    username: &str,
+) -> AppResult<PokePreviewResponse> {
+    let recipient = Username::parse(username.to_string())
+        .map_err(|_| HandlerError::BadRequest("Invalid recipient username".to_string()))?;
+    
+    if *sender == recipient {
+        return Err(HandlerError::BadRequest("Cannot poke yourself".into()).into());
+    }
+    
+    let result = use_case::preview(sender, &recipient, deps).await?;
+    
+    Ok(match result.capability {
+        PokeCapability::CanPoke { .. } => PokePreviewResponse {
+            can_poke: true,
+            reason: None,
+            message: None,
+        },
+        PokeCapability::CannotPoke(reason) => PokePreviewResponse {
+            can_poke: false,
+            reason: Some(reason.code()),
+            message: Some(reason.to_string()),
+        },
+    })
+}
+
+/// Poke履歴の1ページあたりのデフォルト件数
+const DEFAULT_HISTORY_LIMIT: u32 = 20;
+
+/// Poke履歴の1ページあたりの最大件数
+const MAX_HISTORY_LIMIT: u32 = 100;
+
+/// Poke履歴のクエリパラメータ
+#[derive(Debug, Deserialize)]
+pub struct PokeHistoryQuery {
+    /// 送信（sent）・受信（received）のどちらの履歴か
+    pub direction: PokeDirection,
+    
+    /// 1ページの件数（1〜100、省略時は20）
+    pub limit: Option<u32>,
+    
+    /// 前のレスポンスの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+/// Poke履歴取得エンドポイント
+/// 
+/// GET /api/poke/history?direction=sent|received&limit=&cursor=
+/// 
+/// 認証済みユーザーのPoke履歴を新しい順に取得
+/// 
+/// # Returns
+/// * 200 OK - Poke履歴
+/// * 400 Bad Request - 無効なカーソル・direction
+/// * 401 Unauthorized - 未認証
 pub async fn get_poke_history(
     State(deps): State<AppDependencies>,
-    // TODO: 認証ミドルウェアからの注入
+    auth_user: AuthenticatedUser,
+    Query(query): Query<PokeHistoryQuery>,
 ) -> AppResult<Json<PokeHistoryResponse>> {
-    // TODO: 実装
-    unimplemented!()
+    let response = fetch_poke_history(
+        deps.event_store.as_ref(),
+        &auth_user.username,
+        query,
+    ).await?;
+    
+    Ok(Json(response))
+}
+
+/// Poke履歴の1ページを取得
+/// 
+/// # Arguments
+/// * `event_store` - イベントストア
+/// * `username` - 履歴を取得するユーザー
+/// * `query` - クエリパラメータ（範囲外のlimitは丸める）
+/// 
+/// # Returns
+/// * `Ok(PokeHistoryResponse)` - 1ページ分の履歴と次ページのカーソル
+/// * `Err(HandlerError::BadRequest)` - 無効なカーソル
+async fn fetch_poke_history(
+    event_store: &dyn EventStore,
+    username: &Username,
+    query: PokeHistoryQuery,
+) -> AppResult<PokeHistoryResponse> {
+    let limit = query.limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_LIMIT) as usize;
+    
+    let cursor = query.cursor
+        .as_deref()
+        .map(PokeCursor::decode)
+        .transpose()
+        .map_err(|_| HandlerError::BadRequest("Invalid cursor".to_string()))?;
+    
+    // 次ページの有無を判定するため1件多く取得
+    let mut pokes = event_store
+        .find_poke_history(username.as_str(), query.direction, cursor.as_ref(), limit + 1)
+        .await?;
+    
+    let next_cursor = if pokes.len() > limit {
+        pokes.truncate(limit);
+        pokes.last().map(|event| PokeCursor::from_event(event).encode())
+    } else {
+        None
+    };
+    
+    Ok(PokeHistoryResponse { pokes, next_cursor })
 }
 
 /// Poke履歴レスポンス
 #[derive(Debug, Serialize)]
-struct PokeHistoryResponse {
-    pub sent: Vec<PokeEvent>,
-    pub received: Vec<PokeEvent>,
+pub struct PokeHistoryResponse {
+    /// Pokeイベント（新しい順）
+    pub pokes: Vec<PokeEvent>,
+    
+    /// 次ページのカーソル（最終ページではなし）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 // 一時的な型定義（domain層から移動予定）
-use crate::domain::poke::PokeEvent;
\ No newline at end of file
+use crate::domain::poke::PokeEvent;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::app::config::Config;
+    use crate::app::dependencies::mocks::{MockRateLimiter, TestDependenciesBuilder};
+    use crate::domain::github::FollowRelation;
+    use crate::domain::user::{GitHubUserId, PokeSetting, RegisteredUser};
+    use crate::error::PokeError;
+    
+    fn forwarded_ip(ip: &str) -> String {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", ip.parse().unwrap());
+        get_rate_limit_ip(&headers, true)
+    }
+    
+    #[tokio::test]
+    async fn test_same_ip_accumulates() {
+        let limiter = MockRateLimiter::default();
+        let ip = forwarded_ip("203.0.113.1");
+        
+        check_ip_rate_limit(&limiter, 2, &ip).await.unwrap();
+        check_ip_rate_limit(&limiter, 2, &ip).await.unwrap();
+        
+        let result = check_ip_rate_limit(&limiter, 2, &ip).await;
+        assert!(matches!(
+            result,
+            Err(AppError::Domain(DomainError::RateLimitExceeded { retry_after_secs: 60 }))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_different_ips_have_independent_buckets() {
+        let limiter = MockRateLimiter::default();
+        let first = forwarded_ip("203.0.113.1");
+        let second = forwarded_ip("198.51.100.7");
+        
+        check_ip_rate_limit(&limiter, 1, &first).await.unwrap();
+        assert!(check_ip_rate_limit(&limiter, 1, &first).await.is_err());
+        
+        // 別のIPは影響を受けない
+        check_ip_rate_limit(&limiter, 1, &second).await.unwrap();
+    }
+    
+    #[test]
+    fn test_parse_poke_message() {
+        assert_eq!(parse_poke_message(None).unwrap(), None);
+        assert_eq!(
+            parse_poke_message(Some("Ship it!\n")).unwrap(),
+            Some("Ship it!".to_string())
+        );
+        // 空白だけのメッセージはなしとして扱う
+        assert_eq!(parse_poke_message(Some("  ")).unwrap(), None);
+        
+        let too_long = "a".repeat(MAX_POKE_MESSAGE_LENGTH + 1);
+        assert!(matches!(
+            parse_poke_message(Some(&too_long)),
+            Err(AppError::Handler(HandlerError::BadRequest(_)))
+        ));
+    }
+    
+    fn username(name: &str) -> Username {
+        Username::new(name.to_string()).unwrap()
+    }
+    
+    /// GitPokeに登録済みのユーザー（誰からでもPokeを受け付ける）
+    fn registered(name: &str) -> RegisteredUser {
+        RegisteredUser::new(GitHubUserId::new(name.len() as i64), username(name))
+    }
+    
+    mod batch_poke_tests {
+        use super::*;
+        fn batch(usernames: &[&str]) -> BatchPokeRequest {
+            BatchPokeRequest {
+                usernames: usernames.iter().map(|name| name.to_string()).collect(),
+                repository: Some("octo-org/sprint".to_string()),
+            }
+        }
+        
+        #[tokio::test]
+        async fn test_all_recipients_poked() {
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(registered("bob"))
+                .with_registered_user(registered("carol"))
+                .build();
+            
+            let response = execute_batch_poke(&deps, &username("alice"), "203.0.113.1", batch(&["bob", "carol"]))
+                .await
+                .unwrap();
+            
+            assert_eq!(response.results.len(), 2);
+            assert!(response.results.iter().all(|item| item.success && item.event_id.is_some()));
+            assert_eq!(response.results[0].username, "bob");
+            assert_eq!(response.results[1].username, "carol");
+            assert_eq!(deps.event_store.find_pokes_from("alice").await.unwrap().len(), 2);
+        }
+        
+        #[tokio::test]
+        async fn test_mixed_batch_reports_each_recipient() {
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(registered("bob"))
+                .with_github_user("dave")
+                .build();
+            
+            let response = execute_batch_poke(&deps, &username("alice"), "203.0.113.1", batch(&["bob", "dave", "alice"]))
+                .await
+                .unwrap();
+            
+            let outcomes: Vec<(&str, bool)> = response.results
+                .iter()
+                .map(|item| (item.username.as_str(), item.success))
+                .collect();
+            assert_eq!(outcomes, vec![("bob", true), ("dave", false), ("alice", false)]);
+            assert_eq!(response.results[1].message, PokeError::RecipientNotRegistered.to_string());
+            assert_eq!(response.results[2].message, "Cannot poke yourself");
+            assert_eq!(deps.event_store.find_pokes_from("alice").await.unwrap().len(), 1);
+        }
+        
+        #[tokio::test]
+        async fn test_over_cap_is_rejected() {
+            let mut config = Config::default();
+            config.app.rate_limit.poke_per_ip_per_minute = 100;
+            let deps = TestDependenciesBuilder::new().with_config(config).build();
+            let names: Vec<String> = (0..=MAX_BATCH_POKE_RECIPIENTS).map(|i| format!("user{}", i)).collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            
+            let result = execute_batch_poke(&deps, &username("alice"), "203.0.113.1", batch(&names)).await;
+            
+            assert!(matches!(result, Err(AppError::Handler(HandlerError::BadRequest(_)))));
+            // 上限超過のリクエストはレート制限を消費しない
+            assert!(deps.rate_limiter.check_limit("rate_limit:poke:ip:203.0.113.1", 1, 60).await.unwrap());
+        }
+        
+        #[tokio::test]
+        async fn test_batch_consumes_rate_limit_per_recipient() {
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(registered("bob"))
+                .with_registered_user(registered("carol"))
+                .build();
+            let limit = deps.config.app.rate_limit.poke_per_ip_per_minute;
+            
+            // 残りが受信者数に足りなければ1件も送信しない
+            for _ in 0..limit - 1 {
+                check_ip_rate_limit(deps.rate_limiter.as_ref(), limit, "203.0.113.1").await.unwrap();
+            }
+            let result = execute_batch_poke(&deps, &username("alice"), "203.0.113.1", batch(&["bob", "carol"])).await;
+            
+            assert!(matches!(result, Err(AppError::Domain(DomainError::RateLimitExceeded { .. }))));
+            assert!(deps.event_store.find_pokes_from("alice").await.unwrap().is_empty());
+        }
+    }
+    
+    mod preview_tests {
+        use super::*;
+        
+        #[tokio::test]
+        async fn test_pokeable_target() {
+            let deps = TestDependenciesBuilder::new().with_registered_user(registered("bob")).build();
+            
+            let response = execute_preview(&deps, &username("alice"), "bob").await.unwrap();
+            
+            assert!(response.can_poke);
+            assert_eq!(response.reason, None);
+            // プレビューではイベントもレート制限も消費しない
+            assert!(deps.event_store.find_pokes_from("alice").await.unwrap().is_empty());
+            assert!(deps.rate_limiter.check_limit("rate_limit:poke:ip:unknown", 1, 60).await.unwrap());
+        }
+        
+        #[tokio::test]
+        async fn test_followers_only_target_without_follow() {
+            let mut bob = registered("bob");
+            bob.poke_setting = PokeSetting::FollowersOnly;
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(bob)
+                .with_follow_relation("alice", "bob", FollowRelation::Following)
+                .build();
+            
+            let response = execute_preview(&deps, &username("alice"), "bob").await.unwrap();
+            
+            assert!(!response.can_poke);
+            assert_eq!(response.reason, Some("NOT_FOLLOWER"));
+            assert_eq!(response.message, Some(PokeError::NotFollower.to_string()));
+        }
+        
+        #[tokio::test]
+        async fn test_already_poked_target() {
+            let deps = TestDependenciesBuilder::new().with_registered_user(registered("bob")).build();
+            deps.event_store
+                .save_poke(&PokeEvent::new(username("alice"), username("bob")))
+                .await
+                .unwrap();
+            
+            let response = execute_preview(&deps, &username("alice"), "Bob").await.unwrap();
+            
+            assert!(!response.can_poke);
+            assert_eq!(response.reason, Some(PokeError::AlreadyPoked.code()));
+        }
+    }
+    
+    mod send_poke_tests {
+        use super::*;
+        use axum::response::IntoResponse;
+        
+        #[tokio::test]
+        async fn test_invalid_recipient_reports_validation_reason() {
+            let deps = TestDependenciesBuilder::new().build();
+            let auth_user = AuthenticatedUser {
+                username: username("alice"),
+                session_id: "session".to_string(),
+            };
+            let request = PokeRequest {
+                username: "-bob".to_string(),
+                repository: None,
+                message: None,
+            };
+            
+            let result = send_poke(State(deps), auth_user, HeaderMap::new(), Json(request)).await;
+            
+            let response = result.unwrap_err().into_response();
+            assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                body["error"]["message"],
+                "無効なリクエスト: 無効な形式: usernameの形式が正しくありません"
+            );
+        }
+        
+        async fn poke_from_alice(deps: &AppDependencies, recipient: &str) -> (HeaderMap, PokeResponse) {
+            let auth_user = AuthenticatedUser {
+                username: username("alice"),
+                session_id: "session".to_string(),
+            };
+            let request = PokeRequest {
+                username: recipient.to_string(),
+                repository: None,
+                message: None,
+            };
+            
+            let (headers, Json(response)) = send_poke(State(deps.clone()), auth_user, HeaderMap::new(), Json(request))
+                .await
+                .unwrap();
+            (headers, response)
+        }
+        
+        #[tokio::test]
+        async fn test_rate_limit_headers_count_down_and_reset() {
+            let limiter = Arc::new(MockRateLimiter::default());
+            let mut deps = TestDependenciesBuilder::new()
+                .with_registered_user(registered("bob"))
+                .with_registered_user(registered("carol"))
+                .with_registered_user(registered("erin"))
+                .build();
+            deps.rate_limiter = limiter.clone();
+            let limit = deps.config.app.rate_limit.poke_per_ip_per_minute;
+            
+            let (headers, response) = poke_from_alice(&deps, "bob").await;
+            assert!(response.success);
+            assert_eq!(headers[RATE_LIMIT_REMAINING_HEADER], (limit - 1).to_string());
+            assert_eq!(headers[RATE_LIMIT_RESET_HEADER], "60");
+            
+            let (headers, _) = poke_from_alice(&deps, "carol").await;
+            assert_eq!(headers[RATE_LIMIT_REMAINING_HEADER], (limit - 2).to_string());
+            
+            // ウィンドウが過ぎて記録が消えると、今回の1回分だけが残る
+            limiter.reset();
+            let (headers, _) = poke_from_alice(&deps, "erin").await;
+            assert_eq!(headers[RATE_LIMIT_REMAINING_HEADER], (limit - 1).to_string());
+        }
+        
+        #[tokio::test]
+        async fn test_rate_limit_headers_on_failed_poke() {
+            let deps = TestDependenciesBuilder::new().with_github_user("dave").build();
+            let limit = deps.config.app.rate_limit.poke_per_ip_per_minute;
+            
+            let (headers, response) = poke_from_alice(&deps, "dave").await;
+            
+            // 200で返る失敗もIPベースの枠を消費している
+            assert!(!response.success);
+            assert_eq!(headers[RATE_LIMIT_REMAINING_HEADER], (limit - 1).to_string());
+            assert!(headers.contains_key(RATE_LIMIT_RESET_HEADER));
+        }
+    }
+    
+    mod poke_history_tests {
+        use super::*;
+        use chrono::{Duration, Utc};
+        use crate::infra::event_store::InMemoryEventStore;
+        
+        fn username(name: &str) -> Username {
+            Username::new(name.to_string()).unwrap()
+        }
+        
+        /// aliceからbobへのPokeを1分間隔で保存（新しい順に返す）
+        async fn seed_sent_pokes(store: &InMemoryEventStore, count: usize) -> Vec<PokeEvent> {
+            let now = Utc::now();
+            let mut events = Vec::new();
+            for i in 0..count {
+                let mut event = PokeEvent::new(username("alice"), username("bob"));
+                event.occurred_at = now - Duration::minutes(i as i64);
+                store.save_poke(&event).await.unwrap();
+                events.push(event);
+            }
+            events
+        }
+        
+        fn query(direction: PokeDirection, limit: Option<u32>, cursor: Option<String>) -> PokeHistoryQuery {
+            PokeHistoryQuery { direction, limit, cursor }
+        }
+        
+        #[tokio::test]
+        async fn test_first_page() {
+            let store = InMemoryEventStore::new();
+            let events = seed_sent_pokes(&store, 5).await;
+            
+            let page = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Sent, Some(2), None))
+                .await
+                .unwrap();
+            
+            assert_eq!(page.pokes, events[..2].to_vec());
+            assert_eq!(page.next_cursor, Some(PokeCursor::from_event(&events[1]).encode()));
+            
+            // 受信側の履歴には現れない
+            let received = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Received, None, None))
+                .await
+                .unwrap();
+            assert!(received.pokes.is_empty());
+            assert_eq!(received.next_cursor, None);
+        }
+        
+        #[tokio::test]
+        async fn test_follow_up_page_via_cursor() {
+            let store = InMemoryEventStore::new();
+            let events = seed_sent_pokes(&store, 5).await;
+            
+            let first = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Sent, Some(3), None))
+                .await
+                .unwrap();
+            let second = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Sent, Some(3), first.next_cursor))
+                .await
+                .unwrap();
+            
+            assert_eq!(second.pokes, events[3..].to_vec());
+            assert_eq!(second.next_cursor, None);
+        }
+        
+        #[tokio::test]
+        async fn test_same_timestamp_is_not_skipped() {
+            let store = InMemoryEventStore::new();
+            let occurred_at = Utc::now();
+            for _ in 0..3 {
+                let mut event = PokeEvent::new(username("carol"), username("alice"));
+                event.occurred_at = occurred_at;
+                store.save_poke(&event).await.unwrap();
+            }
+            
+            let first = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Received, Some(2), None))
+                .await
+                .unwrap();
+            let second = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Received, Some(2), first.next_cursor))
+                .await
+                .unwrap();
+            
+            assert_eq!(first.pokes.len() + second.pokes.len(), 3);
+            assert!(second.pokes.iter().all(|event| !first.pokes.contains(event)));
+        }
+        
+        #[tokio::test]
+        async fn test_out_of_range_limit_is_clamped() {
+            let store = InMemoryEventStore::new();
+            seed_sent_pokes(&store, 105).await;
+            
+            let page = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Sent, Some(1000), None))
+                .await
+                .unwrap();
+            assert_eq!(page.pokes.len(), 100);
+            assert!(page.next_cursor.is_some());
+            
+            let page = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Sent, Some(0), None))
+                .await
+                .unwrap();
+            assert_eq!(page.pokes.len(), 1);
+            
+            let page = fetch_poke_history(&store, &username("alice"), query(PokeDirection::Sent, None, None))
+                .await
+                .unwrap();
+            assert_eq!(page.pokes.len(), 20);
+        }
+        
+        #[tokio::test]
+        async fn test_invalid_cursor() {
+            let store = InMemoryEventStore::new();
+            
+            let result = fetch_poke_history(
+                &store,
+                &username("alice"),
+                query(PokeDirection::Sent, None, Some("broken".to_string())),
+            ).await;
+            
+            assert!(matches!(result, Err(AppError::Handler(HandlerError::BadRequest(_)))));
+        }
+    }
+}
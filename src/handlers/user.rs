@@ -2,19 +2,24 @@
 //! 
 //! このファイルは以下を定義：
 //! - ユーザー情報の取得
+//! - 公開プロフィールの取得
 //! - ユーザー設定の更新
 //! - アカウントの削除
 
 use axum::{
-    extract::{State, Json},
+    extract::{Path, State, Json},
     http::StatusCode,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::app::dependencies::AppDependencies;
-use crate::domain::user::{Username, RegisteredUser, PokeSetting};
+use crate::domain::badge::BadgeState;
+use crate::domain::user::{NotificationMode, Username, RegisteredUser, PokeSetting};
 use crate::error::{AppResult, HandlerError};
-use crate::middlewares::auth::AuthenticatedUser;
+use crate::middlewares::auth::{AuthenticatedUser, OptionalUser};
+use crate::middlewares::session::session_access_token;
+use crate::use_cases::generate_badge as badge_use_case;
+use crate::use_cases::user_settings as use_case;
 
 /// ユーザー情報レスポンス
 #[derive(Debug, Serialize)]
@@ -28,6 +33,15 @@ pub struct UserResponse {
     /// Poke受信設定
     pub poke_setting: PokeSetting,
     
+    /// ブロックしている送信者
+    pub blocked_senders: Vec<String>,
+    
+    /// インタラクティブバッジを許可するか
+    pub allow_interactive_badge: bool,
+    
+    /// Poke受信時のメール通知方法
+    pub notification_mode: NotificationMode,
+    
     /// アカウント作成日時
     pub created_at: String,
     
@@ -52,6 +66,9 @@ pub struct UserStats {
     
     /// 今日受信したPoke数
     pub pokes_received_today: u64,
+    
+    /// Pokeをよく受信したリポジトリ（多い順）
+    pub top_poke_repositories: Vec<use_case::RepositoryPokeCount>,
 }
 
 /// 現在のユーザー情報を取得
@@ -71,7 +88,7 @@ pub async fn get_current_user(
     
     // ユーザー情報を取得
     let user_state = deps.user_repository
-        .find_by_username(username.as_str())
+        .find_by_username(&username.normalized())
         .await?
         .ok_or_else(|| HandlerError::NotFound("ユーザーが見つかりません".to_string()))?;
     
@@ -89,24 +106,115 @@ pub async fn get_current_user(
         github_id: user.github_id.value(),
         username: user.username.as_str().to_string(),
         poke_setting: user.poke_setting,
+        blocked_senders: blocked_sender_names(&user),
+        allow_interactive_badge: user.allow_interactive_badge,
+        notification_mode: user.notification_mode,
         created_at: user.created_at.to_rfc3339(),
         updated_at: user.updated_at.to_rfc3339(),
         stats,
     }))
 }
 
+/// 公開プロフィールレスポンス
+#[derive(Debug, Serialize)]
+pub struct UserProfileResponse {
+    /// GitHubユーザー名
+    pub username: String,
+    
+    /// Poke受信設定
+    pub poke_setting: PokeSetting,
+    
+    /// アクティビティバッジの状態
+    pub badge_state: BadgeState,
+    
+    /// 本人にだけ返す項目（他人・未認証の場合は含めない）
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<OwnerProfile>,
+}
+
+/// プロフィールのうち本人にだけ返す項目
+#[derive(Debug, Serialize)]
+pub struct OwnerProfile {
+    /// ブロックしている送信者
+    pub blocked_senders: Vec<String>,
+    
+    /// 統計情報
+    pub stats: UserStats,
+}
+
+/// ユーザーのプロフィールを取得
+/// 
+/// GET /api/user/:username
+/// 
+/// 認証は任意。誰でもPoke受信設定とバッジの状態を参照でき、
+/// 本人が認証している場合はブロックリストと統計情報も返す
+/// 
+/// `/api/user/me` などの固定パスが優先されるため、同名のユーザーはこのパスでは参照できない
+/// 
+/// # Arguments
+/// * `username` - 対象のGitHubユーザー名
+/// 
+/// # Returns
+/// * 200 OK - プロフィール
+/// * 400 Bad Request - 無効なユーザー名
+/// * 404 Not Found - 未登録のユーザー
+pub async fn get_user_profile(
+    State(deps): State<AppDependencies>,
+    OptionalUser(viewer): OptionalUser,
+    Path(username): Path<String>,
+) -> AppResult<Json<UserProfileResponse>> {
+    let username = Username::parse(username)?;
+    
+    // 登録済みユーザーのみプロフィールを持つ
+    let user = match deps.user_repository.find_by_username(&username.normalized()).await? {
+        Some(crate::domain::user::UserState::Registered(user)) => user,
+        _ => return Err(HandlerError::NotFound("ユーザーが登録されていません".to_string()).into()),
+    };
+    
+    let badge_state = badge_use_case::resolve_badge_state(&user.username, &deps).await?;
+    
+    // 本人の場合のみ非公開の項目を含める
+    let is_owner = viewer.is_some_and(|viewer| viewer.username == user.username);
+    let owner = if is_owner {
+        Some(OwnerProfile {
+            blocked_senders: blocked_sender_names(&user),
+            stats: get_user_stats(&deps, &user.username).await?,
+        })
+    } else {
+        None
+    };
+    
+    Ok(Json(UserProfileResponse {
+        username: user.username.as_str().to_string(),
+        poke_setting: user.poke_setting,
+        badge_state,
+        owner,
+    }))
+}
+
 /// ユーザー設定更新リクエスト
+/// 
+/// 指定した項目だけを更新する
 #[derive(Debug, Deserialize)]
 pub struct UpdateSettingsRequest {
     /// Poke受信設定
-    pub poke_setting: PokeSetting,
+    #[serde(default)]
+    pub poke_setting: Option<PokeSetting>,
+    
+    /// インタラクティブバッジを許可するか
+    #[serde(default)]
+    pub allow_interactive_badge: Option<bool>,
+    
+    /// Poke受信時のメール通知方法
+    #[serde(default)]
+    pub notification_mode: Option<NotificationMode>,
 }
 
 /// ユーザー設定を更新
 /// 
 /// PUT /api/user/settings
 /// 
-/// Poke受信設定を更新
+/// Poke受信設定・インタラクティブバッジの許可・メール通知方法を更新
 /// 
 /// # Arguments
 /// * `request` - 更新リクエスト
@@ -124,7 +232,7 @@ pub async fn update_settings(
     
     // ユーザー情報を取得
     let user_state = deps.user_repository
-        .find_by_username(username.as_str())
+        .find_by_username(&username.normalized())
         .await?
         .ok_or_else(|| HandlerError::NotFound("ユーザーが見つかりません".to_string()))?;
     
@@ -134,19 +242,29 @@ pub async fn update_settings(
         _ => return Err(HandlerError::NotFound("ユーザーが登録されていません".to_string()).into()),
     };
     
-    // 設定を更新
-    user.update_poke_setting(request.poke_setting);
+    // 指定された設定だけを更新
+    if let Some(poke_setting) = request.poke_setting {
+        user.update_poke_setting(poke_setting);
+    }
+    if let Some(allow) = request.allow_interactive_badge {
+        user.set_allow_interactive_badge(allow);
+    }
+    if let Some(mode) = request.notification_mode {
+        user.set_notification_mode(mode);
+    }
     
     // データベースに保存
     deps.user_repository.update(&user).await?;
     
     // キャッシュを無効化
-    invalidate_user_cache(&deps, &username).await?;
+    use_case::invalidate_user_cache(&username, deps.cache_service.as_ref(), deps.storage.as_ref()).await?;
     
     Ok(Json(UpdateSettingsResponse {
         success: true,
         message: "設定を更新しました".to_string(),
         poke_setting: user.poke_setting,
+        allow_interactive_badge: user.allow_interactive_badge,
+        notification_mode: user.notification_mode,
     }))
 }
 
@@ -156,13 +274,92 @@ pub struct UpdateSettingsResponse {
     pub success: bool,
     pub message: String,
     pub poke_setting: PokeSetting,
+    pub allow_interactive_badge: bool,
+    pub notification_mode: NotificationMode,
+}
+
+/// 送信者ブロックリクエスト
+#[derive(Debug, Deserialize)]
+pub struct BlockSenderRequest {
+    /// ブロックする送信者のユーザー名
+    pub username: String,
+}
+
+/// ブロックリストのレスポンス
+#[derive(Debug, Serialize)]
+pub struct BlockedSendersResponse {
+    /// ブロックしている送信者
+    pub blocked_senders: Vec<String>,
+}
+
+/// 送信者をブロック
+/// 
+/// POST /api/user/blocked
+/// 
+/// 指定したユーザーからのPokeを、Poke受信設定に関係なく拒否する
+/// 
+/// # Returns
+/// * 200 OK - ブロック成功（すでにブロック済みの場合も含む）
+/// * 400 Bad Request - 無効なユーザー名・自分自身
+/// * 401 Unauthorized - 未認証
+pub async fn block_sender(
+    State(deps): State<AppDependencies>,
+    auth_user: AuthenticatedUser,
+    Json(request): Json<BlockSenderRequest>,
+) -> AppResult<Json<BlockedSendersResponse>> {
+    let username = auth_user.username;
+    
+    let sender = Username::parse(request.username)
+        .map_err(|_| HandlerError::BadRequest("Invalid username".to_string()))?;
+    if sender == username {
+        return Err(HandlerError::BadRequest("Cannot block yourself".to_string()).into());
+    }
+    
+    let user = use_case::block_sender(&username, sender, &deps).await?;
+    
+    Ok(Json(BlockedSendersResponse {
+        blocked_senders: blocked_sender_names(&user),
+    }))
+}
+
+/// 送信者のブロックを解除
+/// 
+/// DELETE /api/user/blocked/:username
+/// 
+/// # Returns
+/// * 200 OK - 解除成功（ブロックしていなかった場合も含む）
+/// * 400 Bad Request - 無効なユーザー名
+/// * 401 Unauthorized - 未認証
+pub async fn unblock_sender(
+    State(deps): State<AppDependencies>,
+    auth_user: AuthenticatedUser,
+    Path(sender): Path<String>,
+) -> AppResult<Json<BlockedSendersResponse>> {
+    let username = auth_user.username;
+    
+    let sender = Username::parse(sender)
+        .map_err(|_| HandlerError::BadRequest("Invalid username".to_string()))?;
+    
+    let user = use_case::unblock_sender(&username, &sender, &deps).await?;
+    
+    Ok(Json(BlockedSendersResponse {
+        blocked_senders: blocked_sender_names(&user),
+    }))
+}
+
+/// ブロックしている送信者のユーザー名一覧
+fn blocked_sender_names(user: &RegisteredUser) -> Vec<String> {
+    user.blocked_senders
+        .iter()
+        .map(|sender| sender.as_str().to_string())
+        .collect()
 }
 
 /// アカウントを削除
 /// 
 /// DELETE /api/user/me
 /// 
-/// ユーザーアカウントと関連データを削除
+/// ユーザーアカウントと関連データを削除し、GitHubの認可を取り消す
 /// 
 /// # Returns
 /// * 200 OK - 削除成功
@@ -173,14 +370,17 @@ pub async fn delete_account(
 ) -> AppResult<Json<DeleteAccountResponse>> {
     let username = auth_user.username;
     
-    // ユーザーを削除
-    deps.user_repository.delete(username.as_str()).await?;
+    // セッションは削除処理で消えるため、先にアクセストークンを読んでおく
+    let access_token =
+        session_access_token(deps.cache_service.as_ref(), &auth_user.session_id).await?;
     
-    // 関連データを削除
-    // TODO: 実装
-    // - Pokeイベントの削除（送信・受信両方）
-    // - セッションの削除
-    // - キャッシュの削除
+    // ユーザーと関連データ（Pokeイベント・キャッシュ・セッション）を削除
+    use_case::delete_account(
+        &username,
+        access_token.as_deref(),
+        deps.oauth_adapter.as_ref(),
+        &deps,
+    ).await?;
     
     // セッションを無効化
     // TODO: 実装
@@ -205,34 +405,14 @@ async fn get_user_stats(
     deps: &AppDependencies,
     username: &Username,
 ) -> AppResult<UserStats> {
-    // TODO: 実装
-    // - event_store から送信・受信したPokeをカウント
-    // - 今日の分と全期間の分を集計
+    let stats = use_case::get_user_statistics(username, deps).await?;
     
     Ok(UserStats {
-        pokes_sent: 0,
-        pokes_received: 0,
-        pokes_sent_today: 0,
-        pokes_received_today: 0,
+        pokes_sent: stats.total_pokes_sent,
+        pokes_received: stats.total_pokes_received,
+        pokes_sent_today: stats.pokes_sent_today,
+        pokes_received_today: stats.pokes_received_today,
+        top_poke_repositories: stats.top_poke_repositories,
     })
 }
 
-/// ユーザーキャッシュを無効化
-async fn invalidate_user_cache(
-    deps: &AppDependencies,
-    username: &Username,
-) -> AppResult<()> {
-    // ユーザー関連のキャッシュをすべて削除
-    let patterns = vec![
-        format!("user:{}", username.as_str()),
-        format!("badge:{}:*", username.as_str()),
-        format!("activity:{}:*", username.as_str()),
-    ];
-    
-    for pattern in patterns {
-        deps.cache_service.delete_pattern(&pattern).await?;
-    }
-    
-    Ok(())
-}
-
@@ -3,6 +3,7 @@
 //! このファイルは以下を定義：
 //! - リクエストからの情報抽出
 //! - レスポンスの構築ヘルパー
+//! - HTTP日付（Last-Modified・If-Modified-Since）の変換
 //! - 共通バリデーション
 
 use axum::{
@@ -10,6 +11,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::Serialize;
 use std::net::IpAddr;
 
@@ -20,6 +22,9 @@ use std::net::IpAddr;
 /// 2. X-Real-IP ヘッダー
 /// 3. RemoteAddr（直接接続）
 /// 
+/// X-Forwarded-For の先頭はクライアントが送った値がそのまま残り、偽装できる
+/// ログ等の参考情報にのみ使い、レート制限には `get_rate_limit_ip` を使う
+/// 
 /// # Arguments
 /// * `headers` - HTTPヘッダー
 /// 
@@ -47,6 +52,47 @@ pub fn get_client_ip(headers: &HeaderMap) -> String {
     "unknown".to_string()
 }
 
+/// レート制限に使用するクライアントIPアドレスを取得
+/// 
+/// Cloud Runのプロキシはクライアントが送ったX-Forwarded-Forを残したまま、
+/// 実際の接続元を末尾に追記する。先頭の値は偽装できるため、末尾の値だけを使う
+/// X-Real-IP はプロキシが付与しないため参照しない
+/// 
+/// # Arguments
+/// * `headers` - HTTPヘッダー
+/// * `trust_forwarded_headers` - プロキシが追記したX-Forwarded-Forを信頼するか
+/// 
+/// # Returns
+/// * IPアドレス文字列（信頼しない場合や取得できない場合は "unknown"）
+pub fn get_rate_limit_ip(headers: &HeaderMap, trust_forwarded_headers: bool) -> String {
+    if !trust_forwarded_headers {
+        return "unknown".to_string();
+    }
+    
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 定数時間で文字列を比較
+/// 
+/// 一致する位置によって処理時間が変わらないため、
+/// 秘密の値（stateや署名）の比較でタイミング攻撃を防げる
+/// 
+/// # Returns
+/// * `true` - 完全に一致
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// User-Agentを取得
 /// 
 /// # Arguments
@@ -123,6 +169,42 @@ struct ErrorDetail {
 }
 
 
+/// HTTP日付の形式（IMF-fixdate）
+/// 
+/// 例: `Sun, 06 Nov 1994 08:49:37 GMT`
+const IMF_FIXDATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// 受信時に受け付ける旧形式のHTTP日付（RFC 850形式・asctime形式）
+const OBSOLETE_HTTP_DATE_FORMATS: [&str; 2] = ["%A, %d-%b-%y %H:%M:%S GMT", "%a %b %e %H:%M:%S %Y"];
+
+/// 日時をHTTP日付（IMF-fixdate）に変換
+/// 
+/// HTTP日付は秒単位のため、秒未満は切り捨てられる
+/// 
+/// # Arguments
+/// * `datetime` - 変換する日時
+pub fn format_http_date(datetime: DateTime<Utc>) -> String {
+    datetime.format(IMF_FIXDATE).to_string()
+}
+
+/// HTTP日付を解析
+/// 
+/// RFC 9110に従い、IMF-fixdateに加えて旧形式（RFC 850形式・asctime形式）も受け付ける
+/// 
+/// # Arguments
+/// * `value` - ヘッダーの値
+/// 
+/// # Returns
+/// * `Some(DateTime)` - 解析成功
+/// * `None` - HTTP日付ではない（条件付きリクエストではヘッダーがないものとして扱う）
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    std::iter::once(IMF_FIXDATE)
+        .chain(OBSOLETE_HTTP_DATE_FORMATS)
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|datetime| datetime.and_utc())
+}
+
 /// リクエストIDを生成
 /// 
 /// トレーシング用の一意なリクエストIDを生成
@@ -166,4 +248,65 @@ mod tests {
         assert_eq!(get_client_ip(&headers), "unknown");
     }
     
+    #[test]
+    fn test_http_date_round_trip() {
+        use chrono::TimeZone;
+        
+        let datetime = Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 37).unwrap();
+        
+        assert_eq!(format_http_date(datetime), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Some(datetime));
+        // 旧形式
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), Some(datetime));
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Some(datetime));
+    }
+    
+    #[test]
+    fn test_parse_invalid_http_date() {
+        assert_eq!(parse_http_date(""), None);
+        assert_eq!(parse_http_date("1994-11-06T08:49:37Z"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 JST"), None);
+        // 曜日が日付と一致しない
+        assert_eq!(parse_http_date("Mon, 06 Nov 1994 08:49:37 GMT"), None);
+    }
+    
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"state-123", b"state-123"));
+        assert!(!constant_time_eq(b"state-123", b"state-124"));
+        assert!(!constant_time_eq(b"state-123", b"state-1234"));
+        assert!(constant_time_eq(b"", b""));
+    }
+    
+    #[test]
+    fn test_get_rate_limit_ip() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.5".parse().unwrap());
+        
+        // プロキシの背後では転送ヘッダーを使用
+        assert_eq!(get_rate_limit_ip(&headers, true), "203.0.113.5");
+        
+        // 信頼しない場合は偽装可能なヘッダーを無視
+        assert_eq!(get_rate_limit_ip(&headers, false), "unknown");
+    }
+    
+    #[test]
+    fn test_rate_limit_ip_uses_proxy_appended_hop() {
+        // クライアントが先頭の値を変えても、プロキシが追記した末尾の値は変わらない
+        for spoofed in ["198.51.100.1", "198.51.100.2, 192.0.2.9"] {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-for", format!("{}, 203.0.113.5", spoofed).parse().unwrap());
+            assert_eq!(get_rate_limit_ip(&headers, true), "203.0.113.5");
+        }
+        
+        // X-Real-IP はプロキシが付与しないため使わない
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", "192.0.2.1".parse().unwrap());
+        assert_eq!(get_rate_limit_ip(&headers, true), "unknown");
+        
+        // IPアドレスとして解釈できない値は無視
+        headers.insert("x-forwarded-for", "203.0.113.5, not-an-ip".parse().unwrap());
+        assert_eq!(get_rate_limit_ip(&headers, true), "unknown");
+    }
+    
 }
\ No newline at end of file
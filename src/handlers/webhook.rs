@@ -0,0 +1,334 @@
+//! GitHub Webhookハンドラー
+//!
+//! このファイルは以下を定義：
+//! - Webhook受信エンドポイント
+//! - X-Hub-Signature-256 の署名検証
+//! - イベント種別ごとのキャッシュ無効化
+//!
+//! 署名はボディを解析する前に検証し、検証できないリクエストは中身を読まずに拒否する
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::app::dependencies::{AppDependencies, CacheService};
+use crate::domain::user::Username;
+use crate::error::{AppResult, HandlerError};
+use crate::handlers::utils::constant_time_eq;
+use crate::infra::adapters::storage::StorageAdapter;
+use crate::use_cases::user_settings as use_case;
+
+/// 署名ヘッダー
+const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+/// イベント種別ヘッダー
+const EVENT_HEADER: &str = "x-github-event";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Webhookペイロード中のアカウント
+#[derive(Debug, Deserialize)]
+struct WebhookAccount {
+    login: String,
+}
+
+/// Webhookペイロード中のリポジトリ
+#[derive(Debug, Deserialize)]
+struct WebhookRepository {
+    owner: WebhookAccount,
+}
+
+/// Webhookペイロード中のインストール情報
+#[derive(Debug, Deserialize)]
+struct WebhookInstallation {
+    account: Option<WebhookAccount>,
+}
+
+/// Webhookペイロード
+///
+/// キャッシュ無効化の対象を決めるのに必要なフィールドだけを読む
+#[derive(Debug, Deserialize)]
+struct WebhookPayload {
+    action: Option<String>,
+    sender: Option<WebhookAccount>,
+    repository: Option<WebhookRepository>,
+    member: Option<WebhookAccount>,
+    installation: Option<WebhookInstallation>,
+}
+
+/// GitHub Webhook受信エンドポイント
+///
+/// POST /api/webhooks/github
+///
+/// 署名を検証し、ユーザーのアクティビティに影響するイベントであれば
+/// 関係するユーザーのキャッシュを無効化する
+///
+/// # Returns
+/// * 204 No Content - 受信成功（対象外のイベントも含む）
+/// * 400 Bad Request - イベント種別がない・ペイロードが不正
+/// * 401 Unauthorized - 署名がない・一致しない
+pub async fn github_webhook(
+    State(deps): State<AppDependencies>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<StatusCode> {
+    process_webhook(
+        deps.cache_service.as_ref(),
+        deps.storage.as_ref(),
+        deps.config.github.webhook_secret.as_deref(),
+        &headers,
+        &body,
+    ).await?;
+    
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Webhookを検証して処理
+///
+/// # Arguments
+/// * `cache` - 無効化するキャッシュ
+/// * `storage` - 無効化するバッジストレージ（Cloud Storage）
+/// * `secret` - Webhookシークレット（未設定の場合はすべて拒否）
+/// * `headers` - リクエストヘッダー
+/// * `body` - 生のリクエストボディ
+///
+/// # Returns
+/// * `Ok(())` - 処理成功
+/// * `Err(HandlerError::Unauthorized)` - 署名がない・一致しない
+/// * `Err(HandlerError::BadRequest)` - イベント種別がない・ペイロードが不正
+async fn process_webhook(
+    cache: &dyn CacheService,
+    storage: &dyn StorageAdapter,
+    secret: Option<&str>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> AppResult<()> {
+    let Some(secret) = secret else {
+        tracing::warn!("GITHUB_WEBHOOK_SECRET が未設定のためWebhookを拒否しました");
+        return Err(HandlerError::Unauthorized.into());
+    };
+    
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(HandlerError::Unauthorized)?;
+    
+    if !verify_signature(secret.as_bytes(), body, signature) {
+        return Err(HandlerError::Unauthorized.into());
+    }
+    
+    let event = headers
+        .get(EVENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| HandlerError::BadRequest("Missing X-GitHub-Event header".to_string()))?;
+    
+    let payload: WebhookPayload = serde_json::from_slice(body)
+        .map_err(|_| HandlerError::BadRequest("Invalid webhook payload".to_string()))?;
+    
+    for login in affected_users(event, &payload) {
+        // GitHubのログイン名として不正な値は無視する
+        let Ok(username) = Username::parse(login) else {
+            continue;
+        };
+        use_case::invalidate_user_cache(&username, cache, storage).await?;
+        tracing::info!(event, username = username.as_str(), "Webhookによりキャッシュを無効化しました");
+    }
+    
+    Ok(())
+}
+
+/// X-Hub-Signature-256 を検証
+///
+/// # Arguments
+/// * `secret` - Webhookシークレット
+/// * `body` - 生のリクエストボディ
+/// * `signature` - ヘッダーの値（`sha256=<hex>`）
+///
+/// # Returns
+/// * `true` - 署名が一致
+fn verify_signature(secret: &[u8], body: &[u8], signature: &str) -> bool {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMACは任意長の鍵を受け付ける");
+    mac.update(body);
+    let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    
+    constant_time_eq(expected.as_bytes(), signature.as_bytes())
+}
+
+/// キャッシュを無効化すべきユーザーを抽出
+///
+/// # Arguments
+/// * `event` - X-GitHub-Event の値
+/// * `payload` - Webhookペイロード
+///
+/// # Returns
+/// * 重複を除いたログイン名（対象外のイベントでは空）
+fn affected_users(event: &str, payload: &WebhookPayload) -> Vec<String> {
+    let sender = payload.sender.as_ref();
+    let owner = payload.repository.as_ref().map(|repository| &repository.owner);
+    let action = payload.action.as_deref();
+    
+    let accounts = match event {
+        // ブランチ・タグの削除、Webhook自体の削除
+        "delete" | "meta" => vec![owner, sender],
+        // コラボレーターの追加・削除
+        "member" => vec![owner, payload.member.as_ref()],
+        // アカウントからのアプリのアンインストール
+        "installation" if action == Some("deleted") => vec![
+            payload.installation.as_ref().and_then(|installation| installation.account.as_ref()),
+        ],
+        // ユーザーによるアプリ認可の取り消し
+        "github_app_authorization" if action == Some("revoked") => vec![sender],
+        _ => Vec::new(),
+    };
+    
+    let mut logins: Vec<String> = Vec::new();
+    for account in accounts.into_iter().flatten() {
+        if !logins.contains(&account.login) {
+            logins.push(account.login.clone());
+        }
+    }
+    logins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::dependencies::mocks::MockCacheService;
+    use crate::error::AppError;
+    use crate::infra::adapters::storage::InMemoryStorageAdapter;
+    
+    const SECRET: &str = "It's a Secret to Everybody";
+    
+    const OCTOCAT_KEYS: [&str; 2] = ["activity:octocat", "badge:octocat:v2:static:flat:light:default:GitPoke"];
+    
+    const HUBOT_KEY: &str = "activity:hubot";
+    
+    /// octocat と hubot のキャッシュを保存したキャッシュ
+    async fn seeded_cache() -> MockCacheService {
+        let cache = MockCacheService::new();
+        for key in OCTOCAT_KEYS.into_iter().chain([HUBOT_KEY]) {
+            cache.set(key, "cached", 300).await.unwrap();
+        }
+        cache
+    }
+    
+    async fn is_cached(cache: &MockCacheService, key: &str) -> bool {
+        cache.get(key).await.unwrap().is_some()
+    }
+    
+    fn sign(body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+    
+    fn headers(event: &str, signature: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(EVENT_HEADER, event.parse().unwrap());
+        if let Some(signature) = signature {
+            headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        }
+        headers
+    }
+    
+    const DELETE_PAYLOAD: &str =
+        r#"{"ref":"feature","ref_type":"branch","repository":{"owner":{"login":"octocat"}},"sender":{"login":"octocat"}}"#;
+    
+    #[test]
+    fn test_verify_signature_matches_github_example() {
+        // GitHubドキュメントの検証用の例
+        assert!(verify_signature(
+            SECRET.as_bytes(),
+            b"Hello, World!",
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_signed_payload_invalidates_cache() {
+        let cache = seeded_cache().await;
+        let storage = InMemoryStorageAdapter::new();
+        storage.save_badge(OCTOCAT_KEYS[1], "{}").await.unwrap();
+        let body = DELETE_PAYLOAD.as_bytes();
+        
+        process_webhook(&cache, &storage, Some(SECRET), &headers("delete", Some(&sign(body))), body)
+            .await
+            .unwrap();
+        
+        for key in OCTOCAT_KEYS {
+            assert!(!is_cached(&cache, key).await, "{} should be invalidated", key);
+        }
+        assert!(is_cached(&cache, HUBOT_KEY).await);
+        assert!(storage.get_badge(OCTOCAT_KEYS[1]).await.unwrap().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_tampered_body_is_rejected() {
+        let cache = seeded_cache().await;
+        let signature = sign(DELETE_PAYLOAD.as_bytes());
+        let tampered = DELETE_PAYLOAD.replace("octocat", "mallory");
+        
+        let result = process_webhook(&cache, &InMemoryStorageAdapter::new(), Some(SECRET), &headers("delete", Some(&signature)), tampered.as_bytes()).await;
+        
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+        assert!(is_cached(&cache, OCTOCAT_KEYS[0]).await);
+    }
+    
+    #[tokio::test]
+    async fn test_missing_signature_is_rejected_before_parsing() {
+        let cache = MockCacheService::new();
+        
+        // ボディが不正なJSONでも、署名がなければ400ではなく401になる
+        let result = process_webhook(&cache, &InMemoryStorageAdapter::new(), Some(SECRET), &headers("delete", None), b"not json").await;
+        
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_missing_secret_rejects_everything() {
+        let cache = MockCacheService::new();
+        let body = DELETE_PAYLOAD.as_bytes();
+        
+        let result = process_webhook(&cache, &InMemoryStorageAdapter::new(), None, &headers("delete", Some(&sign(body))), body).await;
+        
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_unrelated_event_is_ignored() {
+        let cache = seeded_cache().await;
+        let body = br#"{"zen":"Keep it logically awesome.","sender":{"login":"octocat"}}"#;
+        
+        process_webhook(&cache, &InMemoryStorageAdapter::new(), Some(SECRET), &headers("ping", Some(&sign(body))), body)
+            .await
+            .unwrap();
+        
+        for key in OCTOCAT_KEYS {
+            assert!(is_cached(&cache, key).await);
+        }
+    }
+    
+    #[test]
+    fn test_affected_users() {
+        let payload = |json: &str| serde_json::from_str::<WebhookPayload>(json).unwrap();
+        
+        let member = payload(
+            r#"{"action":"added","member":{"login":"hubot"},"repository":{"owner":{"login":"octocat"}},"sender":{"login":"octocat"}}"#,
+        );
+        assert_eq!(affected_users("member", &member), vec!["octocat", "hubot"]);
+        
+        let uninstalled = payload(r#"{"action":"deleted","installation":{"account":{"login":"octo-org"}}}"#);
+        assert_eq!(affected_users("installation", &uninstalled), vec!["octo-org"]);
+        
+        let installed = payload(r#"{"action":"created","installation":{"account":{"login":"octo-org"}}}"#);
+        assert!(affected_users("installation", &installed).is_empty());
+        
+        let revoked = payload(r#"{"action":"revoked","sender":{"login":"octocat"}}"#);
+        assert_eq!(affected_users("github_app_authorization", &revoked), vec!["octocat"]);
+    }
+}
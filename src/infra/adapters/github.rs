@@ -1,7 +1,25 @@
+//! GitHub REST APIアダプター
+//! 
+//! このファイルは以下を定義：
+//! - OAuthで取得したアクセストークンやユーザー名でGitHubユーザーを取得する `GitHubAdapter` トレイト
+//! - REST APIを使った実装
+//! 
+//! 未認証のREST APIはIPごとに1時間60回までのため、
+//! レート制限の超過はリセット時刻付きのエラーとして返す
+
 use async_trait::async_trait;
-use std::error::Error;
+use chrono::{DateTime, Utc};
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::Deserialize;
+
+use crate::app::config::GitHubConfig;
+use crate::error::{AppError, AppResult, DomainError, InfraError};
+use crate::infra::github_api::USER_AGENT;
+
+/// リセット時刻が分からない場合の再試行までの秒数
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct GitHubUser {
     pub id: u64,
     pub login: String,
@@ -12,36 +30,258 @@ pub struct GitHubUser {
 
 #[async_trait]
 pub trait GitHubAdapter: Send + Sync {
-    async fn get_user(&self, access_token: &str) -> Result<GitHubUser, Box<dyn Error>>;
+    async fn get_user(&self, access_token: &str) -> AppResult<GitHubUser>;
     
-    async fn get_user_by_username(&self, username: &str) -> Result<GitHubUser, Box<dyn Error>>;
+    /// ユーザー名でGitHubユーザーを取得
+    /// 
+    /// # Arguments
+    /// * `username` - GitHubユーザー名
+    /// 
+    /// # Returns
+    /// * `Ok(GitHubUser)` - ユーザー情報
+    /// * `Err(DomainError::UserNotFound)` - 存在しないユーザー
+    /// * `Err(DomainError::RateLimitExceeded)` - レート制限を超過（リセットまでの秒数付き）
+    async fn get_user_by_username(&self, username: &str) -> AppResult<GitHubUser>;
     
-    async fn verify_token(&self, access_token: &str) -> Result<bool, Box<dyn Error>>;
+    /// アクセストークンが有効か確認
+    /// 
+    /// ユーザーがGitHub側でアプリの認可を取り消すと、トークンは401を返すようになる
+    /// 
+    /// # Arguments
+    /// * `access_token` - OAuthで取得したアクセストークン
+    /// 
+    /// # Returns
+    /// * `Ok(true)` - 有効
+    /// * `Ok(false)` - 失効・取り消し済み
+    /// * `Err(AppError)` - 確認できなかった（GitHubの障害・レート制限等）
+    async fn verify_token(&self, access_token: &str) -> AppResult<bool>;
 }
 
 pub struct GitHubApiAdapter {
     client: reqwest::Client,
+    api_base_url: String,
 }
 
 impl GitHubApiAdapter {
-    pub fn new() -> Self {
+    /// 設定からアダプターを作成
+    /// 
+    /// # Arguments
+    /// * `config` - GitHub設定（REST APIのベースURL）
+    pub fn new(config: &GitHubConfig) -> Self {
         Self {
             client: reqwest::Client::new(),
+            api_base_url: config.api_base_url.trim_end_matches('/').to_string(),
         }
     }
 }
 
 #[async_trait]
 impl GitHubAdapter for GitHubApiAdapter {
-    async fn get_user(&self, _access_token: &str) -> Result<GitHubUser, Box<dyn Error>> {
+    async fn get_user(&self, _access_token: &str) -> AppResult<GitHubUser> {
         todo!()
     }
     
-    async fn get_user_by_username(&self, _username: &str) -> Result<GitHubUser, Box<dyn Error>> {
-        todo!()
+    async fn get_user_by_username(&self, username: &str) -> AppResult<GitHubUser> {
+        let url = format!("{}/users/{}", self.api_base_url, username);
+        let response = self
+            .client
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(InfraError::from)?;
+        
+        match response.status() {
+            StatusCode::OK => Ok(response.json().await.map_err(InfraError::from)?),
+            StatusCode::NOT_FOUND => Err(DomainError::UserNotFound(username.to_string()).into()),
+            status => Err(rate_limit_error(response.headers(), Utc::now()).unwrap_or_else(|| {
+                InfraError::GitHubResponse(format!(
+                    "ユーザーの取得に失敗しました（HTTP {}）",
+                    status.as_u16()
+                )).into()
+            })),
+        }
     }
     
-    async fn verify_token(&self, _access_token: &str) -> Result<bool, Box<dyn Error>> {
-        todo!()
+    async fn verify_token(&self, access_token: &str) -> AppResult<bool> {
+        let url = format!("{}/user", self.api_base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(InfraError::from)?;
+        
+        match response.status() {
+            StatusCode::OK => Ok(true),
+            StatusCode::UNAUTHORIZED => Ok(false),
+            status => Err(rate_limit_error(response.headers(), Utc::now()).unwrap_or_else(|| {
+                InfraError::GitHubResponse(format!(
+                    "トークンの確認に失敗しました（HTTP {}）",
+                    status.as_u16()
+                )).into()
+            })),
+        }
+    }
+}
+
+/// レート制限の超過を表すレスポンスならエラーに変換
+/// 
+/// GitHubは超過時に403（または429）と `x-ratelimit-remaining: 0` を返し、
+/// `x-ratelimit-reset` にリセット時刻（UNIX秒）を入れる
+/// 
+/// # Arguments
+/// * `headers` - レスポンスヘッダー
+/// * `now` - 現在時刻
+/// 
+/// # Returns
+/// * `Some(DomainError::RateLimitExceeded)` - レート制限を超過
+/// * `None` - レート制限以外の失敗
+fn rate_limit_error(headers: &HeaderMap, now: DateTime<Utc>) -> Option<AppError> {
+    let header_value = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    
+    if header_value("x-ratelimit-remaining")? != "0" {
+        return None;
+    }
+    
+    let retry_after_secs = header_value("x-ratelimit-reset")
+        .and_then(|reset| reset.parse::<i64>().ok())
+        .map(|reset| (reset - now.timestamp()).max(0) as u64)
+        .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+    
+    Some(DomainError::RateLimitExceeded { retry_after_secs }.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    
+    fn adapter_for(server: &MockServer) -> GitHubApiAdapter {
+        GitHubApiAdapter::new(&GitHubConfig {
+            api_base_url: server.uri(),
+            ..GitHubConfig::default()
+        })
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_get_user_by_username() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/octocat"))
+            .and(header("user-agent", USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 583231,
+                "login": "octocat",
+                "name": "The Octocat",
+                "email": null,
+                "avatar_url": "https://avatars.githubusercontent.com/u/583231?v=4",
+            })))
+            .mount(&server)
+            .await;
+        
+        let user = adapter_for(&server).get_user_by_username("octocat").await.unwrap();
+        
+        assert_eq!(user.id, 583231);
+        assert_eq!(user.login, "octocat");
+        assert_eq!(user.name.as_deref(), Some("The Octocat"));
+        assert_eq!(user.email, None);
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_user() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/ghost"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        
+        let result = adapter_for(&server).get_user_by_username("ghost").await;
+        
+        assert!(matches!(result, Err(AppError::Domain(DomainError::UserNotFound(name))) if name == "ghost"));
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limited() {
+        let server = MockServer::start().await;
+        let reset = Utc::now().timestamp() + 120;
+        Mock::given(method("GET"))
+            .and(path("/users/octocat"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("x-ratelimit-remaining", "0")
+                    .insert_header("x-ratelimit-reset", reset.to_string().as_str()),
+            )
+            .mount(&server)
+            .await;
+        
+        let result = adapter_for(&server).get_user_by_username("octocat").await;
+        
+        assert!(matches!(
+            result,
+            Err(AppError::Domain(DomainError::RateLimitExceeded { retry_after_secs })) if (110..=120).contains(&retry_after_secs)
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_forbidden_without_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403).insert_header("x-ratelimit-remaining", "42"))
+            .mount(&server)
+            .await;
+        
+        let result = adapter_for(&server).get_user_by_username("octocat").await;
+        
+        assert!(matches!(result, Err(AppError::Infra(InfraError::GitHubResponse(_)))));
+    }
+    
+    /// `GET /user` に指定したステータスを返すモックサーバーを起動
+    async fn user_server(status: u16) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .and(header("authorization", "Bearer gho_token"))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&server)
+            .await;
+        server
+    }
+    
+    #[tokio::test]
+    async fn test_verify_token() {
+        let server = user_server(200).await;
+        assert!(adapter_for(&server).verify_token("gho_token").await.unwrap());
+        
+        // 認可を取り消されたトークン
+        let server = user_server(401).await;
+        assert!(!adapter_for(&server).verify_token("gho_token").await.unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_verify_token_server_error() {
+        let server = user_server(502).await;
+        
+        let result = adapter_for(&server).verify_token("gho_token").await;
+        
+        assert!(matches!(result, Err(AppError::Infra(InfraError::GitHubResponse(_)))));
+    }
+    
+    #[test]
+    fn test_rate_limit_reset_in_the_past() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1000".parse().unwrap());
+        
+        let error = rate_limit_error(&headers, Utc::now()).unwrap();
+        
+        assert!(matches!(error, AppError::Domain(DomainError::RateLimitExceeded { retry_after_secs: 0 })));
+    }
+}
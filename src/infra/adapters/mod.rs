@@ -1,3 +1,4 @@
 pub mod github;
 pub mod database;
-pub mod oauth;
\ No newline at end of file
+pub mod oauth;
+pub mod storage;
\ No newline at end of file
@@ -0,0 +1,211 @@
+//! Cloud Storageを使用したバッジの保存
+//!
+//! このファイルは以下を定義：
+//! - バッジのコールドキャッシュを扱う `StorageAdapter` トレイト
+//! - Cloud Storageを使った実装
+//! - インメモリの実装（テスト・ローカル開発用）
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use crate::app::config::StorageConfig;
+use crate::error::{AppResult, InfraError};
+
+/// 保存するバッジのContent-Type
+///
+/// SVGだけでなく生成日時やTTLも保存するためJSONで保存する
+const BADGE_CONTENT_TYPE: &str = "application/json";
+
+/// バッジの永続ストレージ
+///
+/// Redisより遅いが失われないため、Redisのキャッシュが切れた際のフォールバックに使う
+#[async_trait]
+pub trait StorageAdapter: Send + Sync {
+    /// バッジを取得
+    ///
+    /// # Arguments
+    /// * `badge_key` - バッジのキー（Redisのキャッシュキーと同じ）
+    ///
+    /// # Returns
+    /// * `Ok(Some(entry))` - 保存済み
+    /// * `Ok(None)` - 未保存
+    async fn get_badge(&self, badge_key: &str) -> AppResult<Option<String>>;
+    
+    /// バッジを保存（既存のものは上書き）
+    ///
+    /// # Arguments
+    /// * `badge_key` - バッジのキー（Redisのキャッシュキーと同じ）
+    /// * `entry` - バッジのエントリ（SVGと生成日時・TTLなどのメタデータのJSON）
+    async fn save_badge(&self, badge_key: &str, entry: &str) -> AppResult<()>;
+    
+    /// キーが指定したプレフィックスで始まるバッジをすべて削除
+    ///
+    /// # Arguments
+    /// * `key_prefix` - バッジのキーのプレフィックス（例: `badge:octocat:`）
+    async fn delete_badges(&self, key_prefix: &str) -> AppResult<()>;
+}
+
+/// Cloud Storageを使用したバッジストレージ
+///
+/// オブジェクト名は `{badge_prefix}{badge_key}.json`
+pub struct CloudStorageAdapter {
+    client: cloud_storage::Client,
+    bucket: String,
+    badge_prefix: String,
+}
+
+impl CloudStorageAdapter {
+    pub fn new(client: cloud_storage::Client, config: &StorageConfig) -> Self {
+        Self {
+            client,
+            bucket: config.bucket_name.clone(),
+            badge_prefix: config.badge_prefix.clone(),
+        }
+    }
+    
+    /// バッジのオブジェクト名
+    fn object_name(&self, badge_key: &str) -> String {
+        badge_object_name(&self.badge_prefix, badge_key)
+    }
+}
+
+#[async_trait]
+impl StorageAdapter for CloudStorageAdapter {
+    async fn get_badge(&self, badge_key: &str) -> AppResult<Option<String>> {
+        let result = self.client
+            .object()
+            .download(&self.bucket, &self.object_name(badge_key))
+            .await;
+        
+        match result {
+            Ok(bytes) => Ok(Some(
+                String::from_utf8(bytes).map_err(|e| InfraError::Storage(e.to_string()))?,
+            )),
+            // cloud-storageは404を `Other` として返す
+            Err(cloud_storage::Error::Other(_)) => Ok(None),
+            Err(cloud_storage::Error::Google(e)) if e.error.code == 404 => Ok(None),
+            Err(e) => Err(InfraError::Storage(e.to_string()).into()),
+        }
+    }
+    
+    async fn save_badge(&self, badge_key: &str, entry: &str) -> AppResult<()> {
+        self.client
+            .object()
+            .create(
+                &self.bucket,
+                entry.as_bytes().to_vec(),
+                &self.object_name(badge_key),
+                BADGE_CONTENT_TYPE,
+            )
+            .await
+            .map_err(|e| InfraError::Storage(e.to_string()))?;
+        Ok(())
+    }
+    
+    async fn delete_badges(&self, key_prefix: &str) -> AppResult<()> {
+        let list_request = cloud_storage::ListRequest {
+            prefix: Some(format!("{}{}", self.badge_prefix, key_prefix)),
+            ..Default::default()
+        };
+        let pages: Vec<cloud_storage::object::ObjectList> = self.client
+            .object()
+            .list(&self.bucket, list_request)
+            .await
+            .map_err(|e| InfraError::Storage(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| InfraError::Storage(e.to_string()))?;
+        
+        for object in pages.into_iter().flat_map(|page| page.items) {
+            self.client
+                .object()
+                .delete(&self.bucket, &object.name)
+                .await
+                .map_err(|e| InfraError::Storage(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// インメモリのバッジストレージ
+///
+/// テストやローカル開発で使用する（プロセス終了で消える）
+#[derive(Default)]
+pub struct InMemoryStorageAdapter {
+    objects: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryStorageAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StorageAdapter for InMemoryStorageAdapter {
+    async fn get_badge(&self, badge_key: &str) -> AppResult<Option<String>> {
+        Ok(self.objects.lock().unwrap().get(badge_key).cloned())
+    }
+    
+    async fn save_badge(&self, badge_key: &str, entry: &str) -> AppResult<()> {
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(badge_key.to_string(), entry.to_string());
+        Ok(())
+    }
+    
+    async fn delete_badges(&self, key_prefix: &str) -> AppResult<()> {
+        self.objects
+            .lock()
+            .unwrap()
+            .retain(|badge_key, _| !badge_key.starts_with(key_prefix));
+        Ok(())
+    }
+}
+
+/// プレフィックスとキーからオブジェクト名を作成
+fn badge_object_name(prefix: &str, badge_key: &str) -> String {
+    format!("{}{}.json", prefix, badge_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_object_name_uses_prefix() {
+        let config = StorageConfig::default();
+        assert_eq!(
+            badge_object_name(&config.badge_prefix, "badge:octocat:v2:static:flat:light:default:GitPoke"),
+            "badges/badge:octocat:v2:static:flat:light:default:GitPoke.json"
+        );
+        assert_eq!(badge_object_name("cold/", "key"), "cold/key.json");
+    }
+    
+    #[tokio::test]
+    async fn test_in_memory_round_trip() {
+        let storage = InMemoryStorageAdapter::new();
+        
+        assert_eq!(storage.get_badge("key").await.unwrap(), None);
+        
+        storage.save_badge("key", "<svg/>").await.unwrap();
+        assert_eq!(storage.get_badge("key").await.unwrap().as_deref(), Some("<svg/>"));
+    }
+    
+    #[tokio::test]
+    async fn test_in_memory_delete_by_prefix() {
+        let storage = InMemoryStorageAdapter::new();
+        for key in ["badge:alice:v1:flat", "badge:alice:v1:plastic", "badge:alicex:v1:flat"] {
+            storage.save_badge(key, "{}").await.unwrap();
+        }
+        
+        storage.delete_badges("badge:alice:").await.unwrap();
+        
+        assert_eq!(storage.get_badge("badge:alice:v1:flat").await.unwrap(), None);
+        assert_eq!(storage.get_badge("badge:alice:v1:plastic").await.unwrap(), None);
+        assert!(storage.get_badge("badge:alicex:v1:flat").await.unwrap().is_some());
+    }
+}
@@ -1,6 +1,9 @@
 use async_trait::async_trait;
+use deadpool_redis::{redis::AsyncCommands, Pool};
+use std::collections::HashMap;
 use std::error::Error;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 
 #[async_trait]
 pub trait CacheAdapter: Send + Sync {
@@ -16,67 +19,262 @@ pub trait CacheAdapter: Send + Sync {
 }
 
 pub struct RedisCache {
-    // TODO: Add Redis client
+    pool: Pool,
 }
 
 impl RedisCache {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
     }
 }
 
+/// RedisのTTLは秒単位のため、1秒未満は1秒に切り上げる
+fn ttl_seconds(ttl: Duration) -> u64 {
+    ttl.as_secs().max(1)
+}
+
 #[async_trait]
 impl CacheAdapter for RedisCache {
-    async fn get(&self, _key: &str) -> Result<Option<String>, Box<dyn Error>> {
-        todo!()
+    async fn get(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let mut conn = self.pool.get().await?;
+        // 存在しないキーはnilが返るためNoneになる
+        let value: Option<String> = conn.get(key).await?;
+        Ok(value)
     }
     
-    async fn set(&self, _key: &str, _value: &str, _ttl: Option<Duration>) -> Result<(), Box<dyn Error>> {
-        todo!()
+    async fn set(&self, key: &str, value: &str, ttl: Option<Duration>) -> Result<(), Box<dyn Error>> {
+        let mut conn = self.pool.get().await?;
+        match ttl {
+            Some(ttl) => conn.set_ex::<_, _, ()>(key, value, ttl_seconds(ttl)).await?,
+            None => conn.set::<_, _, ()>(key, value).await?,
+        }
+        Ok(())
     }
     
-    async fn delete(&self, _key: &str) -> Result<(), Box<dyn Error>> {
-        todo!()
+    async fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        let mut conn = self.pool.get().await?;
+        conn.del::<_, ()>(key).await?;
+        Ok(())
     }
     
-    async fn exists(&self, _key: &str) -> Result<bool, Box<dyn Error>> {
-        todo!()
+    async fn exists(&self, key: &str) -> Result<bool, Box<dyn Error>> {
+        let mut conn = self.pool.get().await?;
+        let exists: bool = conn.exists(key).await?;
+        Ok(exists)
     }
     
-    async fn expire(&self, _key: &str, _ttl: Duration) -> Result<(), Box<dyn Error>> {
-        todo!()
+    async fn expire(&self, key: &str, ttl: Duration) -> Result<(), Box<dyn Error>> {
+        let mut conn = self.pool.get().await?;
+        let seconds = i64::try_from(ttl_seconds(ttl)).unwrap_or(i64::MAX);
+        conn.expire::<_, ()>(key, seconds).await?;
+        Ok(())
     }
 }
 
+/// 値と有効期限（Noneの場合は無期限）
+type CacheEntry = (String, Option<Instant>);
+
 pub struct InMemoryCache {
-    // TODO: Add HashMap with expiration
+    entries: RwLock<HashMap<String, CacheEntry>>,
 }
 
 impl InMemoryCache {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            entries: RwLock::new(HashMap::new()),
+        }
     }
 }
 
+impl Default for InMemoryCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 有効期限切れかどうか
+fn is_expired(entry: &CacheEntry, now: Instant) -> bool {
+    entry.1.is_some_and(|expires_at| expires_at <= now)
+}
+
 #[async_trait]
 impl CacheAdapter for InMemoryCache {
-    async fn get(&self, _key: &str) -> Result<Option<String>, Box<dyn Error>> {
-        todo!()
+    async fn get(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let now = Instant::now();
+        {
+            let entries = self.entries.read().await;
+            match entries.get(key) {
+                None => return Ok(None),
+                Some(entry) if !is_expired(entry, now) => return Ok(Some(entry.0.clone())),
+                Some(_) => {}
+            }
+        }
+        
+        // 期限切れのエントリはアクセス時に削除する
+        let mut entries = self.entries.write().await;
+        if entries.get(key).is_some_and(|entry| is_expired(entry, now)) {
+            entries.remove(key);
+        }
+        Ok(None)
+    }
+    
+    async fn set(&self, key: &str, value: &str, ttl: Option<Duration>) -> Result<(), Box<dyn Error>> {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries
+            .write()
+            .await
+            .insert(key.to_string(), (value.to_string(), expires_at));
+        Ok(())
+    }
+    
+    async fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        self.entries.write().await.remove(key);
+        Ok(())
+    }
+    
+    async fn exists(&self, key: &str) -> Result<bool, Box<dyn Error>> {
+        Ok(self.get(key).await?.is_some())
+    }
+    
+    async fn expire(&self, key: &str, ttl: Duration) -> Result<(), Box<dyn Error>> {
+        let now = Instant::now();
+        let mut entries = self.entries.write().await;
+        if let Some(entry) = entries.get_mut(key) {
+            if !is_expired(entry, now) {
+                entry.1 = Some(now + ttl);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_in_memory_set_get() {
+        let cache = InMemoryCache::new();
+        
+        cache.set("key", "value", None).await.unwrap();
+        assert_eq!(cache.get("key").await.unwrap(), Some("value".to_string()));
+        assert_eq!(cache.get("missing").await.unwrap(), None);
+        
+        // 上書き
+        cache.set("key", "updated", None).await.unwrap();
+        assert_eq!(cache.get("key").await.unwrap(), Some("updated".to_string()));
+        
+        cache.delete("key").await.unwrap();
+        assert_eq!(cache.get("key").await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_in_memory_expiry() {
+        let cache = InMemoryCache::new();
+        
+        cache.set("key", "value", Some(Duration::from_millis(50))).await.unwrap();
+        assert_eq!(cache.get("key").await.unwrap(), Some("value".to_string()));
+        
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(cache.get("key").await.unwrap(), None);
+        
+        // 期限切れのエントリは取得時に削除される
+        assert!(!cache.entries.read().await.contains_key("key"));
+    }
+    
+    #[tokio::test]
+    async fn test_in_memory_exists_honors_expiry() {
+        let cache = InMemoryCache::new();
+        
+        cache.set("key", "value", Some(Duration::from_millis(50))).await.unwrap();
+        assert!(cache.exists("key").await.unwrap());
+        
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(!cache.exists("key").await.unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_in_memory_expire_shortens_ttl() {
+        let cache = InMemoryCache::new();
+        
+        cache.set("key", "value", Some(Duration::from_secs(60))).await.unwrap();
+        cache.expire("key", Duration::from_millis(50)).await.unwrap();
+        assert!(cache.exists("key").await.unwrap());
+        
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(!cache.exists("key").await.unwrap());
     }
     
-    async fn set(&self, _key: &str, _value: &str, _ttl: Option<Duration>) -> Result<(), Box<dyn Error>> {
-        todo!()
+    #[tokio::test]
+    async fn test_in_memory_expire_missing_key() {
+        let cache = InMemoryCache::new();
+        
+        // 存在しないキーへのexpireは何もしない
+        cache.expire("missing", Duration::from_secs(60)).await.unwrap();
+        assert!(!cache.exists("missing").await.unwrap());
     }
+}
+
+/// ローカルのRedisに接続する統合テスト
+/// 
+/// `cargo test --features redis-tests` で実行（REDIS_URL 未設定時は localhost:6379）
+#[cfg(all(test, feature = "redis-tests"))]
+mod redis_tests {
+    use super::*;
     
-    async fn delete(&self, _key: &str) -> Result<(), Box<dyn Error>> {
-        todo!()
+    fn create_cache() -> RedisCache {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let pool = deadpool_redis::Config::from_url(url)
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap();
+        RedisCache::new(pool)
     }
     
-    async fn exists(&self, _key: &str) -> Result<bool, Box<dyn Error>> {
-        todo!()
+    fn unique_key(name: &str) -> String {
+        format!("gitpoke-test:{}:{}", name, uuid::Uuid::new_v4())
     }
     
-    async fn expire(&self, _key: &str, _ttl: Duration) -> Result<(), Box<dyn Error>> {
-        todo!()
+    #[tokio::test]
+    async fn test_set_get_round_trip() {
+        let cache = create_cache();
+        let key = unique_key("round-trip");
+        
+        cache.set(&key, "value", None).await.unwrap();
+        assert_eq!(cache.get(&key).await.unwrap(), Some("value".to_string()));
+        assert!(cache.exists(&key).await.unwrap());
+        
+        cache.delete(&key).await.unwrap();
+        assert_eq!(cache.get(&key).await.unwrap(), None);
+        assert!(!cache.exists(&key).await.unwrap());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_missing_key_is_none() {
+        let cache = create_cache();
+        assert_eq!(cache.get(&unique_key("missing")).await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_ttl_expiry() {
+        let cache = create_cache();
+        let key = unique_key("ttl");
+        
+        cache.set(&key, "value", Some(Duration::from_secs(1))).await.unwrap();
+        assert!(cache.exists(&key).await.unwrap());
+        
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(cache.get(&key).await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_expire_existing_key() {
+        let cache = create_cache();
+        let key = unique_key("expire");
+        
+        cache.set(&key, "value", None).await.unwrap();
+        cache.expire(&key, Duration::from_secs(1)).await.unwrap();
+        
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!cache.exists(&key).await.unwrap());
+    }
+}
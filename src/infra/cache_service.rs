@@ -0,0 +1,188 @@
+//! Redisを使用したキャッシュサービス
+//!
+//! このファイルは以下を実装：
+//! - `CacheService` トレイトのRedis実装
+//! - SCANによるパターン一致キーの一括削除
+
+use async_trait::async_trait;
+use deadpool_redis::{redis, redis::AsyncCommands, Pool};
+use crate::app::dependencies::CacheService;
+use crate::error::{AppResult, InfraError};
+
+/// SCAN 1回あたりに走査するキー数の目安
+const SCAN_BATCH_SIZE: usize = 100;
+
+/// Redisを使用したキャッシュサービス
+pub struct RedisCacheService {
+    pool: Pool,
+}
+
+impl RedisCacheService {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+    
+    /// 接続プールからコネクションを取得
+    async fn connection(&self) -> AppResult<deadpool_redis::Connection> {
+        Ok(self.pool.get().await.map_err(InfraError::from)?)
+    }
+}
+
+#[async_trait]
+impl CacheService for RedisCacheService {
+    async fn get(&self, key: &str) -> AppResult<Option<String>> {
+        let mut conn = self.connection().await?;
+        let value: Option<String> = conn.get(key).await.map_err(InfraError::from)?;
+        Ok(value)
+    }
+    
+    async fn set(&self, key: &str, value: &str, ttl_seconds: u64) -> AppResult<()> {
+        let mut conn = self.connection().await?;
+        // TTL 0 は有効期限なしとして扱う
+        let result = if ttl_seconds > 0 {
+            conn.set_ex::<_, _, ()>(key, value, ttl_seconds).await
+        } else {
+            conn.set::<_, _, ()>(key, value).await
+        };
+        result.map_err(InfraError::from)?;
+        Ok(())
+    }
+    
+    fn close(&self) {
+        // 接続プールを共有するレート制限の接続もまとめて閉じる
+        self.pool.close();
+    }
+    
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        let mut conn = self.connection().await?;
+        conn.del::<_, ()>(key).await.map_err(InfraError::from)?;
+        Ok(())
+    }
+    
+    async fn take(&self, key: &str) -> AppResult<Option<String>> {
+        let mut conn = self.connection().await?;
+        // GETDELは取得と削除を1コマンドで行うため、同時に消費されることはない
+        let value: Option<String> = redis::cmd("GETDEL")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(InfraError::from)?;
+        Ok(value)
+    }
+    
+    /// パターンに一致するキーを削除
+    ///
+    /// KEYSはRedisをブロックするため使用せず、SCANのカーソルを
+    /// 最後まで辿りながら見つかったキーをパイプラインでDELする
+    ///
+    /// # Arguments
+    /// * `pattern` - globパターン（例: `badge:alice:*`）
+    async fn delete_pattern(&self, pattern: &str) -> AppResult<()> {
+        let mut conn = self.connection().await?;
+        let mut cursor: u64 = 0;
+        let mut deleted = 0;
+        
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(SCAN_BATCH_SIZE)
+                .query_async(&mut conn)
+                .await
+                .map_err(InfraError::from)?;
+            
+            if !keys.is_empty() {
+                let mut pipe = redis::pipe();
+                for key in &keys {
+                    pipe.del(key).ignore();
+                }
+                pipe.query_async::<_, ()>(&mut conn)
+                    .await
+                    .map_err(InfraError::from)?;
+                deleted += keys.len();
+            }
+            
+            // カーソルが0に戻ったら走査完了
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+        
+        tracing::debug!(pattern, deleted, "パターンに一致するキャッシュを削除しました");
+        Ok(())
+    }
+}
+
+/// ローカルのRedisに接続する統合テスト
+///
+/// `cargo test --features redis-tests` で実行（REDIS_URL 未設定時は localhost:6379）
+#[cfg(all(test, feature = "redis-tests"))]
+mod redis_tests {
+    use super::*;
+    
+    fn create_service() -> RedisCacheService {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let pool = deadpool_redis::Config::from_url(url)
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap();
+        RedisCacheService::new(pool)
+    }
+    
+    #[tokio::test]
+    async fn test_delete_pattern_only_matching_keys() {
+        let service = create_service();
+        // 他のテストと衝突しないよう一意なプレフィックスを付ける
+        let prefix = format!("gitpoke-test:{}", uuid::Uuid::new_v4());
+        let alice_v1 = format!("{}:badge:alice:v1", prefix);
+        let alice_json = format!("{}:badge:alice:json", prefix);
+        let bob_v1 = format!("{}:badge:bob:v1", prefix);
+        
+        for key in [&alice_v1, &alice_json, &bob_v1] {
+            service.set(key, "<svg/>", 60).await.unwrap();
+        }
+        
+        service
+            .delete_pattern(&format!("{}:badge:alice:*", prefix))
+            .await
+            .unwrap();
+        
+        assert_eq!(service.get(&alice_v1).await.unwrap(), None);
+        assert_eq!(service.get(&alice_json).await.unwrap(), None);
+        assert_eq!(service.get(&bob_v1).await.unwrap(), Some("<svg/>".to_string()));
+        
+        service.delete(&bob_v1).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_take_consumes_once() {
+        let service = create_service();
+        let key = format!("gitpoke-test:take:{}", uuid::Uuid::new_v4());
+        service.set(&key, "value", 60).await.unwrap();
+        
+        assert_eq!(service.take(&key).await.unwrap(), Some("value".to_string()));
+        assert_eq!(service.take(&key).await.unwrap(), None);
+        assert_eq!(service.get(&key).await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_delete_pattern_spanning_multiple_scan_batches() {
+        let service = create_service();
+        let prefix = format!("gitpoke-test:{}", uuid::Uuid::new_v4());
+        
+        for i in 0..(SCAN_BATCH_SIZE * 3) {
+            service.set(&format!("{}:badge:{}", prefix, i), "v", 60).await.unwrap();
+        }
+        
+        service
+            .delete_pattern(&format!("{}:badge:*", prefix))
+            .await
+            .unwrap();
+        
+        for i in 0..(SCAN_BATCH_SIZE * 3) {
+            assert_eq!(service.get(&format!("{}:badge:{}", prefix, i)).await.unwrap(), None);
+        }
+    }
+}
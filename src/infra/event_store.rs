@@ -0,0 +1,738 @@
+//! Pokeイベントの永続化
+//!
+//! このファイルは以下を実装：
+//! - `EventStore` トレイトのFirestore実装
+//! - `PokeRepository` トレイトのFirestore実装
+//! - テスト・ローカル開発用のインメモリ実装
+//!
+//! 「今日」はUTCの日付で判定する
+//! 今日のPoke検索は `to`/`from` と `occurred_at` の複合インデックスを使用する
+//! （firestore.indexes.json を参照）
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::app::dependencies::EventStore;
+use crate::domain::poke::{PokeCursor, PokeDirection, PokeEvent};
+use crate::domain::user::Username;
+use crate::error::{AppResult, InfraError};
+use crate::infra::repositories::poke::PokeRepository;
+
+/// Pokeイベントのコレクション名
+pub const POKE_EVENTS_COLLECTION: &str = "poke_events";
+
+/// 1回のバッチ書き込みで削除するドキュメント数（Firestoreの上限は500）
+const DELETE_BATCH_SIZE: usize = 500;
+
+/// 指定日時を含むUTCの1日の範囲 [開始, 終了)
+///
+/// # Arguments
+/// * `now` - 基準日時
+///
+/// # Returns
+/// * (当日0時, 翌日0時)
+pub fn utc_day_range(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = now
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .expect("0時は常に有効")
+        .and_utc();
+    (start, start + Duration::days(1))
+}
+
+/// 件数集計クエリの結果
+#[derive(Debug, Deserialize)]
+struct CountResult {
+    count: u64,
+}
+
+/// Firestoreに保存するPokeイベントのドキュメント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PokeEventDocument {
+    id: String,
+    from: String,
+    to: String,
+    #[serde(with = "firestore::serialize_as_timestamp")]
+    occurred_at: DateTime<Utc>,
+    context: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl From<&PokeEvent> for PokeEventDocument {
+    fn from(event: &PokeEvent) -> Self {
+        Self {
+            id: event.id.to_string(),
+            from: event.from.as_str().to_string(),
+            to: event.to.as_str().to_string(),
+            occurred_at: event.occurred_at,
+            context: event.context.clone(),
+            message: event.message.clone(),
+        }
+    }
+}
+
+impl TryFrom<PokeEventDocument> for PokeEvent {
+    type Error = InfraError;
+    
+    fn try_from(document: PokeEventDocument) -> Result<Self, Self::Error> {
+        let invalid = |field: &str| {
+            InfraError::Database(format!("不正なPokeイベント（{}）: {}", field, document.id))
+        };
+        
+        Ok(PokeEvent {
+            id: Uuid::parse_str(&document.id).map_err(|_| invalid("id"))?,
+            from: Username::parse(document.from.clone()).map_err(|_| invalid("from"))?,
+            to: Username::parse(document.to.clone()).map_err(|_| invalid("to"))?,
+            occurred_at: document.occurred_at,
+            context: document.context,
+            message: document.message,
+        })
+    }
+}
+
+/// Firestoreを使用したイベントストア
+pub struct FirestoreEventStore {
+    db: firestore::FirestoreDb,
+}
+
+impl FirestoreEventStore {
+    pub fn new(db: firestore::FirestoreDb) -> Self {
+        Self { db }
+    }
+    
+    /// 指定フィールドが一致する今日のPokeを検索
+    ///
+    /// # Arguments
+    /// * `field` - 検索対象のフィールド（"to" または "from"）
+    /// * `username` - ユーザー名
+    async fn find_today_pokes_by(&self, field: &str, username: &str) -> AppResult<Vec<PokeEvent>> {
+        let (start, end) = utc_day_range(Utc::now());
+        
+        let documents: Vec<PokeEventDocument> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .filter(|q| {
+                q.for_all([
+                    q.field(field).eq(username),
+                    q.field("occurred_at").greater_than_or_equal(firestore::FirestoreTimestamp(start)),
+                    q.field("occurred_at").less_than(firestore::FirestoreTimestamp(end)),
+                ])
+            })
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let events = documents
+            .into_iter()
+            .map(PokeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(events)
+    }
+    
+    /// 指定フィールドが一致する全期間のPokeを検索
+    async fn find_pokes_by(&self, field: &str, username: &str) -> AppResult<Vec<PokeEvent>> {
+        let documents: Vec<PokeEventDocument> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .filter(|q| q.for_all([q.field(field).eq(username)]))
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let events = documents
+            .into_iter()
+            .map(PokeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl EventStore for FirestoreEventStore {
+    async fn save_poke(&self, event: &PokeEvent) -> AppResult<()> {
+        let document = PokeEventDocument::from(event);
+        
+        // イベントIDをドキュメントIDにすることで、同じイベントの二重保存を防ぐ
+        let _: PokeEventDocument = self.db
+            .fluent()
+            .insert()
+            .into(POKE_EVENTS_COLLECTION)
+            .document_id(&document.id)
+            .object(&document)
+            .execute()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        Ok(())
+    }
+    
+    async fn find_today_pokes_to(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        self.find_today_pokes_by("to", username).await
+    }
+    
+    async fn find_today_pokes_from(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        self.find_today_pokes_by("from", username).await
+    }
+    
+    async fn find_pokes_to(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        self.find_pokes_by("to", username).await
+    }
+    
+    async fn find_pokes_from(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        self.find_pokes_by("from", username).await
+    }
+    
+    /// 送信者から受信者への最新のPokeを取得
+    ///
+    /// （`from`・`to` と `occurred_at` 降順の複合インデックスが必要）
+    async fn find_latest_poke(&self, from: &str, to: &str) -> AppResult<Option<PokeEvent>> {
+        let documents: Vec<PokeEventDocument> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .filter(|q| q.for_all([q.field("from").eq(from), q.field("to").eq(to)]))
+            .order_by([("occurred_at", firestore::FirestoreQueryDirection::Descending)])
+            .limit(1)
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let latest = documents.into_iter().next().map(PokeEvent::try_from).transpose()?;
+        
+        Ok(latest)
+    }
+    
+    /// Poke履歴を新しい順に取得
+    ///
+    /// (occurred_at, id) の降順で並べ、カーソルの値の直後から読み始める
+    /// （`from`/`to` と降順の複合インデックスが必要）
+    async fn find_poke_history(
+        &self,
+        username: &str,
+        direction: PokeDirection,
+        cursor: Option<&PokeCursor>,
+        limit: usize,
+    ) -> AppResult<Vec<PokeEvent>> {
+        let field = match direction {
+            PokeDirection::Sent => "from",
+            PokeDirection::Received => "to",
+        };
+        
+        let query = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .filter(|q| q.for_all([q.field(field).eq(username)]))
+            .order_by([
+                ("occurred_at", firestore::FirestoreQueryDirection::Descending),
+                ("id", firestore::FirestoreQueryDirection::Descending),
+            ])
+            .limit(u32::try_from(limit).unwrap_or(u32::MAX));
+        
+        let query = match cursor {
+            Some(cursor) => query.start_at(firestore::FirestoreQueryCursor::AfterValue(vec![
+                firestore::FirestoreTimestamp(cursor.occurred_at).into(),
+                cursor.id.to_string().into(),
+            ])),
+            None => query,
+        };
+        
+        let documents: Vec<PokeEventDocument> = query
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let events = documents
+            .into_iter()
+            .map(PokeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(events)
+    }
+    
+    /// ユーザーが送信・受信したPokeをすべて削除
+    ///
+    /// FirestoreにはOR条件の削除がないため、`from` と `to` をそれぞれ検索し、
+    /// 重複を除いたIDをバッチ書き込みでまとめて削除する
+    async fn delete_by_user(&self, username: &str) -> AppResult<u64> {
+        let mut ids: BTreeSet<String> = BTreeSet::new();
+        for field in ["from", "to"] {
+            let events = self.find_pokes_by(field, username).await?;
+            ids.extend(events.iter().map(|event| event.id.to_string()));
+        }
+        
+        let ids: Vec<String> = ids.into_iter().collect();
+        let writer = self.db
+            .create_simple_batch_writer()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        for chunk in ids.chunks(DELETE_BATCH_SIZE) {
+            let mut batch = writer.new_batch();
+            for id in chunk {
+                batch
+                    .delete_by_id(POKE_EVENTS_COLLECTION, id, None)
+                    .map_err(|e| InfraError::Database(e.to_string()))?;
+            }
+            batch
+                .write()
+                .await
+                .map_err(|e| InfraError::Database(e.to_string()))?;
+        }
+        
+        Ok(ids.len() as u64)
+    }
+}
+
+#[async_trait]
+impl PokeRepository for FirestoreEventStore {
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<PokeEvent>> {
+        let document: Option<PokeEventDocument> = self.db
+            .fluent()
+            .select()
+            .by_id_in(POKE_EVENTS_COLLECTION)
+            .obj()
+            .one(id.to_string())
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let event = document.map(PokeEvent::try_from).transpose()?;
+        
+        Ok(event)
+    }
+    
+    async fn find_by_sender_and_recipient(
+        &self,
+        sender: &Username,
+        recipient: &Username
+    ) -> AppResult<Vec<PokeEvent>> {
+        let documents: Vec<PokeEventDocument> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .filter(|q| {
+                q.for_all([
+                    q.field("from").eq(sender.as_str()),
+                    q.field("to").eq(recipient.as_str()),
+                ])
+            })
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let events = documents
+            .into_iter()
+            .map(PokeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(events)
+    }
+    
+    async fn save(&self, poke: &PokeEvent) -> AppResult<()> {
+        self.save_poke(poke).await
+    }
+    
+    async fn count_by_recipient(&self, recipient: &Username) -> AppResult<u64> {
+        let results: Vec<CountResult> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .filter(|q| q.for_all([q.field("to").eq(recipient.as_str())]))
+            .aggregate(|a| a.fields([a.field("count").count()]))
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        Ok(results.first().map_or(0, |result| result.count))
+    }
+    
+    /// 全ユーザーのPokeを新しい順に取得
+    ///
+    /// （`occurred_at`・`id` 降順の複合インデックスが必要）
+    async fn list_recent(
+        &self,
+        limit: usize,
+        offset: usize
+    ) -> AppResult<Vec<PokeEvent>> {
+        let documents: Vec<PokeEventDocument> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .order_by([
+                ("occurred_at", firestore::FirestoreQueryDirection::Descending),
+                ("id", firestore::FirestoreQueryDirection::Descending),
+            ])
+            .offset(u32::try_from(offset).unwrap_or(u32::MAX))
+            .limit(u32::try_from(limit).unwrap_or(u32::MAX))
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        let events = documents
+            .into_iter()
+            .map(PokeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(events)
+    }
+    
+    async fn count_all(&self) -> AppResult<u64> {
+        let results: Vec<CountResult> = self.db
+            .fluent()
+            .select()
+            .from(POKE_EVENTS_COLLECTION)
+            .aggregate(|a| a.fields([a.field("count").count()]))
+            .obj()
+            .query()
+            .await
+            .map_err(|e| InfraError::Database(e.to_string()))?;
+        
+        Ok(results.first().map_or(0, |result| result.count))
+    }
+}
+
+/// インメモリのイベントストア
+///
+/// テストやローカル開発で使用する（プロセス終了で消える）
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    events: Mutex<Vec<PokeEvent>>,
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// 条件に一致するPokeを検索
+    fn find(&self, matches: impl Fn(&PokeEvent) -> bool) -> Vec<PokeEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter(|event| matches(event))
+            .cloned()
+            .collect()
+    }
+    
+    /// 条件に一致する今日のPokeを検索
+    fn find_today(&self, matches: impl Fn(&PokeEvent) -> bool) -> Vec<PokeEvent> {
+        let (start, end) = utc_day_range(Utc::now());
+        self.find(|event| start <= event.occurred_at && event.occurred_at < end && matches(event))
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn save_poke(&self, event: &PokeEvent) -> AppResult<()> {
+        let mut events = self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        
+        if !events.iter().any(|saved| saved.id == event.id) {
+            events.push(event.clone());
+        }
+        Ok(())
+    }
+    
+    async fn find_today_pokes_to(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        Ok(self.find_today(|event| event.to.as_str() == username))
+    }
+    
+    async fn find_today_pokes_from(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        Ok(self.find_today(|event| event.from.as_str() == username))
+    }
+    
+    async fn find_pokes_to(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        Ok(self.find(|event| event.to.as_str() == username))
+    }
+    
+    async fn find_pokes_from(&self, username: &str) -> AppResult<Vec<PokeEvent>> {
+        Ok(self.find(|event| event.from.as_str() == username))
+    }
+    
+    async fn find_latest_poke(&self, from: &str, to: &str) -> AppResult<Option<PokeEvent>> {
+        let latest = self
+            .find(|event| event.from.as_str() == from && event.to.as_str() == to)
+            .into_iter()
+            .max_by_key(|event| (event.occurred_at, event.id));
+        
+        Ok(latest)
+    }
+    
+    async fn find_poke_history(
+        &self,
+        username: &str,
+        direction: PokeDirection,
+        cursor: Option<&PokeCursor>,
+        limit: usize,
+    ) -> AppResult<Vec<PokeEvent>> {
+        let mut events = self.find(|event| {
+            let user = match direction {
+                PokeDirection::Sent => &event.from,
+                PokeDirection::Received => &event.to,
+            };
+            user.as_str() == username && cursor.is_none_or(|cursor| cursor.is_followed_by(event))
+        });
+        
+        events.sort_by_key(|event| Reverse((event.occurred_at, event.id)));
+        events.truncate(limit);
+        
+        Ok(events)
+    }
+    
+    async fn delete_by_user(&self, username: &str) -> AppResult<u64> {
+        let mut events = self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        
+        let before = events.len();
+        events.retain(|event| event.from.as_str() != username && event.to.as_str() != username);
+        
+        Ok((before - events.len()) as u64)
+    }
+}
+
+#[async_trait]
+impl PokeRepository for InMemoryEventStore {
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<PokeEvent>> {
+        Ok(self.find(|event| event.id == *id).into_iter().next())
+    }
+    
+    async fn find_by_sender_and_recipient(
+        &self,
+        sender: &Username,
+        recipient: &Username
+    ) -> AppResult<Vec<PokeEvent>> {
+        Ok(self.find(|event| event.from == *sender && event.to == *recipient))
+    }
+    
+    async fn save(&self, poke: &PokeEvent) -> AppResult<()> {
+        self.save_poke(poke).await
+    }
+    
+    async fn count_by_recipient(&self, recipient: &Username) -> AppResult<u64> {
+        Ok(self.find(|event| event.to == *recipient).len() as u64)
+    }
+    
+    async fn list_recent(
+        &self,
+        limit: usize,
+        offset: usize
+    ) -> AppResult<Vec<PokeEvent>> {
+        let mut events = self.find(|_| true);
+        events.sort_by_key(|event| Reverse((event.occurred_at, event.id)));
+        
+        Ok(events.into_iter().skip(offset).take(limit).collect())
+    }
+    
+    async fn count_all(&self) -> AppResult<u64> {
+        let events = self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        
+        Ok(events.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    fn username(name: &str) -> Username {
+        Username::parse(name.to_string()).unwrap()
+    }
+    
+    #[test]
+    fn test_utc_day_range() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 13, 45, 0).unwrap();
+        let (start, end) = utc_day_range(now);
+        
+        assert_eq!(start, Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2024, 3, 16, 0, 0, 0).unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_saved_poke_appears_in_today_queries() {
+        let store = InMemoryEventStore::new();
+        let event = PokeEvent::new(username("alice"), username("bob"));
+        
+        store.save_poke(&event).await.unwrap();
+        
+        assert_eq!(store.find_today_pokes_to("bob").await.unwrap(), vec![event.clone()]);
+        assert_eq!(store.find_today_pokes_from("alice").await.unwrap(), vec![event]);
+        
+        // 逆方向では見つからない
+        assert!(store.find_today_pokes_to("alice").await.unwrap().is_empty());
+        assert!(store.find_today_pokes_from("bob").await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_yesterday_poke_not_in_today_queries() {
+        let store = InMemoryEventStore::new();
+        let mut event = PokeEvent::new(username("alice"), username("bob"));
+        event.occurred_at = utc_day_range(Utc::now()).0 - Duration::seconds(1);
+        
+        store.save_poke(&event).await.unwrap();
+        
+        assert!(store.find_today_pokes_to("bob").await.unwrap().is_empty());
+        assert!(store.find_today_pokes_from("alice").await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_find_latest_poke() {
+        let store = InMemoryEventStore::new();
+        let mut older = PokeEvent::new(username("alice"), username("bob"));
+        older.occurred_at = Utc::now() - Duration::days(3);
+        let newer = PokeEvent::new(username("alice"), username("bob"));
+        let other = PokeEvent::new(username("alice"), username("carol"));
+        
+        for event in [&newer, &older, &other] {
+            store.save_poke(event).await.unwrap();
+        }
+        
+        assert_eq!(store.find_latest_poke("alice", "bob").await.unwrap(), Some(newer));
+        assert_eq!(store.find_latest_poke("bob", "alice").await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_save_same_event_twice() {
+        let store = InMemoryEventStore::new();
+        let event = PokeEvent::new(username("alice"), username("bob"));
+        
+        store.save_poke(&event).await.unwrap();
+        store.save_poke(&event).await.unwrap();
+        
+        assert_eq!(store.find_today_pokes_to("bob").await.unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_document_round_trip() {
+        let mut event = PokeEvent::with_context(username("alice"), username("bob"), "owner/repo".to_string());
+        event.message = Some("Ship it!".to_string());
+        
+        let document = PokeEventDocument::from(&event);
+        assert_eq!(document.from, "alice");
+        assert_eq!(document.to, "bob");
+        assert_eq!(document.message.as_deref(), Some("Ship it!"));
+        
+        assert_eq!(PokeEvent::try_from(document).unwrap(), event);
+    }
+    
+    #[test]
+    fn test_invalid_document() {
+        let mut document = PokeEventDocument::from(&PokeEvent::new(username("alice"), username("bob")));
+        document.id = "not-a-uuid".to_string();
+        
+        assert!(matches!(PokeEvent::try_from(document), Err(InfraError::Database(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_delete_by_user_removes_sent_and_received() {
+        let store = InMemoryEventStore::new();
+        let sent = PokeEvent::new(username("alice"), username("bob"));
+        let received = PokeEvent::new(username("carol"), username("alice"));
+        let unrelated = PokeEvent::new(username("bob"), username("carol"));
+        
+        for event in [&sent, &received, &unrelated] {
+            store.save_poke(event).await.unwrap();
+        }
+        
+        assert_eq!(store.delete_by_user("alice").await.unwrap(), 2);
+        
+        assert!(store.find_today_pokes_from("alice").await.unwrap().is_empty());
+        assert!(store.find_today_pokes_to("alice").await.unwrap().is_empty());
+        assert_eq!(store.find_today_pokes_to("carol").await.unwrap(), vec![unrelated]);
+        
+        // 削除済みのユーザーを再度削除しても0件
+        assert_eq!(store.delete_by_user("alice").await.unwrap(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_delete_by_user_includes_past_events() {
+        let store = InMemoryEventStore::new();
+        let mut old = PokeEvent::new(username("alice"), username("bob"));
+        old.occurred_at = Utc::now() - Duration::days(30);
+        
+        store.save_poke(&old).await.unwrap();
+        
+        assert_eq!(store.delete_by_user("bob").await.unwrap(), 1);
+        assert!(store.events.lock().unwrap().is_empty());
+    }
+    
+    /// 5分おきに5件のPokeを保存し、新しい順に並べたものを返す
+    async fn seed_five_pokes(store: &InMemoryEventStore) -> Vec<PokeEvent> {
+        let base = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let senders = ["alice", "bob", "carol", "dave", "erin"];
+        
+        let mut events: Vec<PokeEvent> = senders
+            .iter()
+            .enumerate()
+            .map(|(i, sender)| {
+                let mut event = PokeEvent::new(username(sender), username("octocat"));
+                event.occurred_at = base + Duration::minutes(5 * i as i64);
+                event
+            })
+            .collect();
+        
+        // 保存順は時刻順にしない
+        for i in [2, 0, 4, 1, 3] {
+            store.save_poke(&events[i]).await.unwrap();
+        }
+        
+        events.reverse();
+        events
+    }
+    
+    #[tokio::test]
+    async fn test_list_recent_is_newest_first() {
+        let store = InMemoryEventStore::new();
+        let expected = seed_five_pokes(&store).await;
+        
+        let events = store.list_recent(10, 0).await.unwrap();
+        
+        assert_eq!(events, expected);
+        assert_eq!(events[0].from, username("erin"));
+    }
+    
+    #[tokio::test]
+    async fn test_list_recent_window() {
+        let store = InMemoryEventStore::new();
+        let expected = seed_five_pokes(&store).await;
+        
+        let events = store.list_recent(2, 2).await.unwrap();
+        
+        assert_eq!(events, expected[2..4].to_vec());
+        // 末尾を越えた分は返さない
+        assert_eq!(store.list_recent(2, 4).await.unwrap(), expected[4..].to_vec());
+        assert!(store.list_recent(2, 5).await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_count_all_matches_seeded_pokes() {
+        let store = InMemoryEventStore::new();
+        seed_five_pokes(&store).await;
+        
+        assert_eq!(store.count_all().await.unwrap(), 5);
+        assert_eq!(store.count_by_recipient(&username("octocat")).await.unwrap(), 5);
+        assert_eq!(store.count_by_recipient(&username("alice")).await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,602 @@
+//! GitHub APIクライアント
+//! 
+//! このファイルは以下を実装：
+//! - `GitHubApi` トレイトの実装（GraphQL API v4 と一部REST API）
+//! - Contribution Calendarからのアクティビティ情報の組み立て
+//! - GraphQL/HTTPエラーの `InfraError` への変換
+//! 
+//! GraphQL APIはエラー時もHTTP 200で `errors` を返すため、
+//! ステータスコードとボディの両方を確認する
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::app::config::GitHubConfig;
+use crate::app::dependencies::{GitHubApi, GitHubUser};
+use crate::domain::github::{FollowRelation, GitHubActivity, GitHubRateLimit};
+use crate::error::{AppResult, DomainError, InfraError};
+
+/// GitHub APIに送るUser-Agent（未指定のリクエストは拒否される）
+pub(crate) const USER_AGENT: &str = "gitpoke";
+
+/// 直近1年分のContribution Calendarを取得するクエリ
+const CONTRIBUTIONS_QUERY: &str = "query($login: String!) { user(login: $login) { contributionsCollection { contributionCalendar { totalContributions weeks { contributionDays { date contributionCount } } } } } }";
+
+/// ユーザーの存在を確認するクエリ
+const USER_QUERY: &str = "query($login: String!) { user(login: $login) { login } }";
+
+/// レート制限状況を取得するクエリ
+const RATE_LIMIT_QUERY: &str = "query { rateLimit { limit remaining resetAt } }";
+
+/// 組織メンバー一覧の1ページあたりの件数（REST APIの上限）
+const ORG_MEMBERS_PER_PAGE: usize = 100;
+
+/// 取得する組織メンバーの上限
+/// 
+/// メンバーごとにアクティビティを取得するため、巨大な組織ではここで打ち切る
+pub const MAX_ORG_MEMBERS: usize = 500;
+
+/// GraphQLのレスポンス
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+/// GraphQLのエラー
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+    #[serde(rename = "type")]
+    error_type: Option<String>,
+}
+
+/// REST APIのユーザー（組織メンバー一覧の要素）
+#[derive(Debug, Deserialize)]
+struct RestUser {
+    login: String,
+}
+
+/// `user` を問い合わせたレスポンスのdata
+#[derive(Debug, Deserialize)]
+struct UserData<T> {
+    user: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionsUser {
+    contributions_collection: ContributionsCollection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionsCollection {
+    contribution_calendar: ContributionCalendar,
+}
+
+/// Contribution Calendar（日付の昇順）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionCalendar {
+    total_contributions: i32,
+    weeks: Vec<ContributionWeek>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionWeek {
+    contribution_days: Vec<ContributionDay>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionDay {
+    date: NaiveDate,
+    contribution_count: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct LoginUser {
+    #[allow(dead_code)]
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RateLimitData {
+    rate_limit: RateLimitNode,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RateLimitNode {
+    limit: u32,
+    remaining: u32,
+    reset_at: DateTime<Utc>,
+}
+
+impl<T> GraphQlResponse<T> {
+    /// dataを取り出す
+    /// 
+    /// # Returns
+    /// * `Ok(T)` - dataがある（部分的なエラーは呼び出し側で判断する）
+    /// * `Err(InfraError::GitHubResponse)` - dataがない
+    fn into_data(self) -> AppResult<(T, Vec<GraphQlError>)> {
+        match self.data {
+            Some(data) => Ok((data, self.errors)),
+            None => Err(InfraError::GitHubResponse(error_messages(&self.errors)).into()),
+        }
+    }
+}
+
+/// GraphQLエラーのメッセージを連結
+fn error_messages(errors: &[GraphQlError]) -> String {
+    if errors.is_empty() {
+        return "dataがありません".to_string();
+    }
+    errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// `user` が見つからなかった場合のエラー
+/// 
+/// GitHubは存在しないユーザーに対して `user: null` と NOT_FOUND エラーを返す
+fn missing_user_error(username: &str, errors: &[GraphQlError]) -> crate::error::AppError {
+    let not_found = errors
+        .iter()
+        .any(|error| error.error_type.as_deref() == Some("NOT_FOUND"));
+    
+    if not_found || errors.is_empty() {
+        DomainError::UserNotFound(username.to_string()).into()
+    } else {
+        InfraError::GitHubResponse(error_messages(errors)).into()
+    }
+}
+
+/// GraphQLのレスポンスからアクティビティ情報を組み立て
+/// 
+/// # Arguments
+/// * `username` - 問い合わせたユーザー名
+/// * `body` - レスポンスボディ
+/// * `fetched_at` - 取得日時
+/// 
+/// # Returns
+/// * `Ok(GitHubActivity)` - アクティビティ情報
+/// * `Err(DomainError::UserNotFound)` - ユーザーが存在しない
+/// * `Err(InfraError)` - レスポンスが不正・GraphQLエラー
+fn parse_user_activity(
+    username: &str,
+    body: &[u8],
+    fetched_at: DateTime<Utc>,
+) -> AppResult<GitHubActivity> {
+    let response: GraphQlResponse<UserData<ContributionsUser>> =
+        serde_json::from_slice(body).map_err(InfraError::from)?;
+    let (data, errors) = response.into_data()?;
+    
+    let user = data.user.ok_or_else(|| missing_user_error(username, &errors))?;
+    let calendar = user.contributions_collection.contribution_calendar;
+    
+    Ok(activity_from_calendar(username, calendar, fetched_at))
+}
+
+/// Contribution Calendarからアクティビティ情報を組み立て
+fn activity_from_calendar(
+    username: &str,
+    calendar: ContributionCalendar,
+    fetched_at: DateTime<Utc>,
+) -> GitHubActivity {
+    let mut days: Vec<ContributionDay> = calendar
+        .weeks
+        .into_iter()
+        .flat_map(|week| week.contribution_days)
+        .collect();
+    days.sort_by_key(|day| day.date);
+    
+    let last_activity_at = days
+        .iter()
+        .rev()
+        .find(|day| day.contribution_count > 0)
+        .and_then(|day| day.date.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc());
+    
+    let contributions: HashMap<String, i32> = days
+        .iter()
+        .map(|day| (day.date.format("%Y-%m-%d").to_string(), day.contribution_count))
+        .collect();
+    
+    GitHubActivity {
+        username: username.to_string(),
+        last_activity_at,
+        current_streak_days: Some(current_streak(&days)),
+        contributions: Some(contributions),
+        total_contributions: Some(calendar.total_contributions),
+        tz_offset_seconds: None,
+        fetched_at,
+    }
+}
+
+/// 現在の連続活動日数を計算
+/// 
+/// カレンダーの最終日（今日）がまだ0件でも、前日まで続いていれば連続とみなす
+/// 
+/// # Arguments
+/// * `days` - 日付の昇順に並んだカレンダー
+fn current_streak(days: &[ContributionDay]) -> i64 {
+    let mut remaining = days.iter().rev().peekable();
+    
+    // 今日の分はまだ活動していないだけかもしれないので数えずに飛ばす
+    if remaining.peek().is_some_and(|today| today.contribution_count == 0) {
+        remaining.next();
+    }
+    
+    remaining.take_while(|day| day.contribution_count > 0).count() as i64
+}
+
+/// GitHub APIクライアント
+/// 
+/// アクティビティとレート制限はGraphQL API、フォロー関係はREST APIで取得する
+pub struct GitHubApiClient {
+    client: reqwest::Client,
+    graphql_url: String,
+    api_base_url: String,
+    token: Option<String>,
+}
+
+impl GitHubApiClient {
+    /// 設定からクライアントを作成
+    /// 
+    /// # Arguments
+    /// * `config` - GitHub設定（URLとアクセストークン）
+    pub fn new(config: &GitHubConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            graphql_url: config.graphql_url.clone(),
+            api_base_url: config.api_base_url.trim_end_matches('/').to_string(),
+            token: config.api_token.clone(),
+        }
+    }
+    
+    /// 共通ヘッダーと認証を付与
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let request = request.header(reqwest::header::USER_AGENT, USER_AGENT);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+    
+    /// GraphQLクエリを実行
+    /// 
+    /// # Arguments
+    /// * `query` - クエリ
+    /// * `variables` - 変数
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<u8>)` - レスポンスボディ
+    /// * `Err(InfraError::Network)` - 通信エラー・HTTPエラー
+    async fn post_graphql(&self, query: &str, variables: serde_json::Value) -> AppResult<Vec<u8>> {
+        let body = self
+            .authorize(self.client.post(&self.graphql_url))
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(InfraError::from)?
+            .bytes()
+            .await
+            .map_err(InfraError::from)?;
+        
+        Ok(body.to_vec())
+    }
+    
+    /// GraphQLクエリを実行してdataを解析
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> AppResult<(T, Vec<GraphQlError>)> {
+        let body = self.post_graphql(query, variables).await?;
+        let response: GraphQlResponse<T> =
+            serde_json::from_slice(&body).map_err(InfraError::from)?;
+        response.into_data()
+    }
+    
+    /// `from` が `to` をフォローしているか
+    /// 
+    /// GET /users/{from}/following/{to} は、フォローしていれば204、していなければ404を返す
+    async fn is_following(&self, from: &str, to: &str) -> AppResult<bool> {
+        let url = format!("{}/users/{}/following/{}", self.api_base_url, from, to);
+        let response = self
+            .authorize(self.client.get(&url))
+            .send()
+            .await
+            .map_err(InfraError::from)?;
+        
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => Err(InfraError::GitHubResponse(format!(
+                "フォロー関係の取得に失敗しました（HTTP {}）",
+                status.as_u16()
+            )).into()),
+        }
+    }
+    
+    /// 組織の公開メンバーを1ページ取得
+    /// 
+    /// GET /orgs/{org}/public_members は、組織が存在しなければ404を返す
+    /// 
+    /// # Arguments
+    /// * `org` - 組織名
+    /// * `page` - ページ番号（1始まり）
+    async fn org_members_page(&self, org: &str, page: usize) -> AppResult<Vec<String>> {
+        let url = format!(
+            "{}/orgs/{}/public_members?per_page={}&page={}",
+            self.api_base_url, org, ORG_MEMBERS_PER_PAGE, page
+        );
+        let response = self
+            .authorize(self.client.get(&url))
+            .send()
+            .await
+            .map_err(InfraError::from)?;
+        
+        match response.status() {
+            StatusCode::OK => {
+                let members: Vec<RestUser> = response.json().await.map_err(InfraError::from)?;
+                Ok(members.into_iter().map(|member| member.login).collect())
+            }
+            StatusCode::NOT_FOUND => Err(DomainError::UserNotFound(org.to_string()).into()),
+            status => Err(InfraError::GitHubResponse(format!(
+                "組織メンバーの取得に失敗しました（HTTP {}）",
+                status.as_u16()
+            )).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl GitHubApi for GitHubApiClient {
+    async fn get_user_activity(&self, username: &str) -> AppResult<GitHubActivity> {
+        let body = self
+            .post_graphql(CONTRIBUTIONS_QUERY, json!({ "login": username }))
+            .await?;
+        
+        parse_user_activity(username, &body, Utc::now())
+    }
+    
+    async fn get_follow_relation(&self, from: &str, to: &str) -> AppResult<FollowRelation> {
+        // 送信者→受信者（Following）と受信者→送信者（Follower）を並行して確認
+        let (following, followed_by) = tokio::try_join!(
+            self.is_following(from, to),
+            self.is_following(to, from),
+        )?;
+        
+        Ok(match (following, followed_by) {
+            (true, true) => FollowRelation::Mutual,
+            (true, false) => FollowRelation::Following,
+            (false, true) => FollowRelation::Follower,
+            (false, false) => FollowRelation::None,
+        })
+    }
+    
+    async fn get_user(&self, username: &str) -> AppResult<GitHubUser> {
+        let (data, errors): (UserData<LoginUser>, _) =
+            self.query(USER_QUERY, json!({ "login": username })).await?;
+        
+        data.user
+            .map(|_| GitHubUser)
+            .ok_or_else(|| missing_user_error(username, &errors))
+    }
+    
+    async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit> {
+        let (data, _): (RateLimitData, _) = self.query(RATE_LIMIT_QUERY, json!({})).await?;
+        
+        Ok(GitHubRateLimit {
+            limit: data.rate_limit.limit,
+            remaining: data.rate_limit.remaining,
+            reset_at: data.rate_limit.reset_at,
+        })
+    }
+    
+    async fn get_org_members(&self, org: &str) -> AppResult<Vec<String>> {
+        let mut members = Vec::new();
+        
+        for page in 1.. {
+            let page_members = self.org_members_page(org, page).await?;
+            let is_last_page = page_members.len() < ORG_MEMBERS_PER_PAGE;
+            members.extend(page_members);
+            
+            if is_last_page || members.len() >= MAX_ORG_MEMBERS {
+                break;
+            }
+        }
+        
+        members.truncate(MAX_ORG_MEMBERS);
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use crate::error::AppError;
+    
+    const OCTOCAT_FIXTURE: &str =
+        include_str!("../../tests/fixtures/github/contributions_octocat.json");
+    const EMPTY_FIXTURE: &str =
+        include_str!("../../tests/fixtures/github/contributions_empty.json");
+    const NOT_FOUND_FIXTURE: &str =
+        include_str!("../../tests/fixtures/github/user_not_found.json");
+    
+    fn fetched_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 15, 9, 0, 0).unwrap()
+    }
+    
+    #[test]
+    fn test_parse_recorded_response() {
+        let activity = parse_user_activity("octocat", OCTOCAT_FIXTURE.as_bytes(), fetched_at()).unwrap();
+        
+        assert_eq!(activity.username, "octocat");
+        assert_eq!(activity.total_contributions, Some(12));
+        assert_eq!(
+            activity.last_activity_at,
+            Some(Utc.with_ymd_and_hms(2024, 3, 14, 0, 0, 0).unwrap())
+        );
+        // 今日（3/15）はまだ0件だが、3/12〜3/14の連続は続いている
+        assert_eq!(activity.current_streak_days, Some(3));
+        assert_eq!(activity.fetched_at, fetched_at());
+        
+        let contributions = activity.contributions.unwrap();
+        assert_eq!(contributions.len(), 13);
+        assert_eq!(contributions.get("2024-03-04"), Some(&2));
+        assert_eq!(contributions.get("2024-03-15"), Some(&0));
+    }
+    
+    #[test]
+    fn test_parse_user_without_contributions() {
+        let activity = parse_user_activity("newbie", EMPTY_FIXTURE.as_bytes(), fetched_at()).unwrap();
+        
+        assert_eq!(activity.total_contributions, Some(0));
+        assert_eq!(activity.last_activity_at, None);
+        assert_eq!(activity.current_streak_days, Some(0));
+        assert!(activity.contributions.unwrap().values().all(|count| *count == 0));
+    }
+    
+    #[test]
+    fn test_parse_unknown_user() {
+        let result = parse_user_activity("no-such-user-12345", NOT_FOUND_FIXTURE.as_bytes(), fetched_at());
+        
+        assert!(matches!(result, Err(AppError::Domain(DomainError::UserNotFound(name))) if name == "no-such-user-12345"));
+    }
+    
+    #[test]
+    fn test_parse_graphql_error_without_data() {
+        let body = br#"{"errors":[{"message":"API rate limit exceeded","type":"RATE_LIMITED"}]}"#;
+        
+        let result = parse_user_activity("octocat", body, fetched_at());
+        
+        assert!(matches!(
+            result,
+            Err(AppError::Infra(InfraError::GitHubResponse(message))) if message == "API rate limit exceeded"
+        ));
+    }
+    
+    #[test]
+    fn test_parse_malformed_body() {
+        let result = parse_user_activity("octocat", b"<html>", fetched_at());
+        
+        assert!(matches!(result, Err(AppError::Infra(InfraError::Serialization(_)))));
+    }
+    
+    /// 指定した方向のフォローだけが存在するモックサーバーを起動
+    async fn follow_server(alice_follows_bob: bool, bob_follows_alice: bool) -> MockServer {
+        let server = MockServer::start().await;
+        let status = |follows: bool| if follows { 204 } else { 404 };
+        
+        Mock::given(method("GET"))
+            .and(path("/users/alice/following/bob"))
+            .respond_with(ResponseTemplate::new(status(alice_follows_bob)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/bob/following/alice"))
+            .respond_with(ResponseTemplate::new(status(bob_follows_alice)))
+            .mount(&server)
+            .await;
+        
+        server
+    }
+    
+    fn client_for(server: &MockServer) -> GitHubApiClient {
+        GitHubApiClient::new(&GitHubConfig {
+            api_base_url: server.uri(),
+            graphql_url: format!("{}/graphql", server.uri()),
+            ..GitHubConfig::default()
+        })
+    }
+    
+    #[tokio::test]
+    async fn test_follow_relation_combinations() {
+        let cases = [
+            (false, false, FollowRelation::None),
+            (false, true, FollowRelation::Follower),
+            (true, false, FollowRelation::Following),
+            (true, true, FollowRelation::Mutual),
+        ];
+        
+        for (alice_follows_bob, bob_follows_alice, expected) in cases {
+            let server = follow_server(alice_follows_bob, bob_follows_alice).await;
+            
+            let relation = client_for(&server).get_follow_relation("alice", "bob").await.unwrap();
+            
+            assert_eq!(relation, expected, "alice→bob: {}, bob→alice: {}", alice_follows_bob, bob_follows_alice);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_follow_relation_unexpected_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        
+        let result = client_for(&server).get_follow_relation("alice", "bob").await;
+        
+        assert!(matches!(result, Err(AppError::Infra(InfraError::GitHubResponse(_)))));
+    }
+    
+    #[tokio::test]
+    async fn test_org_members_are_paginated() {
+        let server = MockServer::start().await;
+        let first_page: Vec<_> = (0..ORG_MEMBERS_PER_PAGE)
+            .map(|i| json!({ "login": format!("member{}", i) }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/orgs/octo-org/public_members"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/octo-org/public_members"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "login": "octocat" }])))
+            .mount(&server)
+            .await;
+        
+        let members = client_for(&server).get_org_members("octo-org").await.unwrap();
+        
+        assert_eq!(members.len(), ORG_MEMBERS_PER_PAGE + 1);
+        assert_eq!(members[0], "member0");
+        assert_eq!(members.last().map(String::as_str), Some("octocat"));
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_org() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/no-such-org/public_members"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        
+        let result = client_for(&server).get_org_members("no-such-org").await;
+        
+        assert!(matches!(result, Err(AppError::Domain(DomainError::UserNotFound(name))) if name == "no-such-org"));
+    }
+}
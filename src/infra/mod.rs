@@ -9,10 +9,13 @@
 pub mod adapters;
 pub mod cache;
 pub mod repositories;
+#[cfg(feature = "png")]
+pub mod png;
 
 // TODO: Migrate existing modules
 pub mod github_api;
 pub mod user_repository;
 pub mod event_store;
 pub mod cache_service;
-pub mod notification_service;
\ No newline at end of file
+pub mod notification_service;
+pub mod rate_limiter;
\ No newline at end of file
@@ -0,0 +1,819 @@
+//! 通知サービス
+//! 
+//! このファイルは以下を実装：
+//! - `NotificationService` トレイトのWebhook実装
+//! - 受信者へのメール通知と、その送信手段の `EmailSender` トレイト
+//! - 1日分のPokeを1通にまとめる日次ダイジェスト
+//! - 複数の通知サービスへの同時通知
+//! - 通知先が設定されていない場合の何もしない実装
+//! 
+//! 通知はPokeの付随処理のため、送信に失敗してもログに残すだけでエラーにしない
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::app::config::EmailConfig;
+use crate::app::dependencies::{CacheService, NotificationService, UserRepository};
+use crate::domain::poke::PokeEvent;
+use crate::domain::user::{NotificationMode, UserState, Username};
+use crate::error::{AppResult, InfraError};
+
+/// Webhook・メール送信API 1回あたりのタイムアウト
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Webhookの最大送信回数（初回 + リトライ1回）
+const WEBHOOK_MAX_ATTEMPTS: u32 = 2;
+
+/// ダイジェスト用のキャッシュを残す秒数
+/// 
+/// 送信されずに残ったPokeが溜まり続けないよう、数日で破棄する
+const DIGEST_TTL_SECONDS: u64 = 3 * 24 * 60 * 60;
+
+/// 何もしない通知サービス
+/// 
+/// 通知先が設定されていない場合に使う
+pub struct NoOpNotificationService;
+
+#[async_trait]
+impl NotificationService for NoOpNotificationService {
+    async fn notify_poke(&self, _event: &PokeEvent) -> AppResult<()> {
+        Ok(())
+    }
+}
+
+/// Webhookに送るPoke通知のペイロード
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    from: &'a str,
+    to: &'a str,
+    occurred_at: String,
+    context: Option<&'a str>,
+}
+
+impl<'a> WebhookPayload<'a> {
+    fn from_event(event: &'a PokeEvent) -> Self {
+        Self {
+            from: event.from.as_str(),
+            to: event.to.as_str(),
+            occurred_at: event.occurred_at.to_rfc3339(),
+            context: event.context.as_deref(),
+        }
+    }
+}
+
+/// 設定されたURLにPokeをPOSTする通知サービス
+pub struct WebhookNotificationService {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotificationService {
+    /// 新しいWebhook通知サービスを作成
+    /// 
+    /// # Arguments
+    /// * `url` - 通知先のURL
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+    
+    /// ペイロードを1回送信
+    /// 
+    /// 2xx以外のステータスもエラーとして扱う
+    async fn post(&self, payload: &WebhookPayload<'_>) -> Result<(), reqwest::Error> {
+        self.client
+            .post(&self.url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationService for WebhookNotificationService {
+    /// PokeをWebhookに通知
+    /// 
+    /// 失敗した場合は1回だけリトライし、それでも失敗した場合はログに残して `Ok` を返す
+    async fn notify_poke(&self, event: &PokeEvent) -> AppResult<()> {
+        let payload = WebhookPayload::from_event(event);
+        
+        for attempt in 1..=WEBHOOK_MAX_ATTEMPTS {
+            match self.post(&payload).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < WEBHOOK_MAX_ATTEMPTS => {
+                    tracing::debug!(event_id = %event.id, error = %e, "Poke通知の送信に失敗したためリトライします");
+                }
+                Err(e) => {
+                    tracing::warn!(event_id = %event.id, error = %e, "Poke通知の送信に失敗しました");
+                }
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// 複数の通知サービスにまとめて通知する
+/// 
+/// Webhookとメールの両方が設定されている場合に使う
+pub struct CompositeNotificationService {
+    services: Vec<Arc<dyn NotificationService>>,
+}
+
+impl CompositeNotificationService {
+    pub fn new(services: Vec<Arc<dyn NotificationService>>) -> Self {
+        Self { services }
+    }
+}
+
+#[async_trait]
+impl NotificationService for CompositeNotificationService {
+    /// すべての通知サービスに通知
+    /// 
+    /// 1つが失敗しても残りには通知する
+    async fn notify_poke(&self, event: &PokeEvent) -> AppResult<()> {
+        for service in &self.services {
+            if let Err(e) = service.notify_poke(event).await {
+                tracing::warn!(event_id = %event.id, error = %e, "Poke通知の送信に失敗しました");
+            }
+        }
+        Ok(())
+    }
+    
+    /// すべての通知サービスのダイジェストを送信
+    async fn flush_daily_digest(&self, date: NaiveDate) -> AppResult<usize> {
+        let mut sent = 0;
+        for service in &self.services {
+            match service.flush_daily_digest(date).await {
+                Ok(count) => sent += count,
+                Err(e) => tracing::warn!(%date, error = %e, "Pokeダイジェストの送信に失敗しました"),
+            }
+        }
+        Ok(sent)
+    }
+}
+
+/// 送信するメール
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EmailMessage {
+    /// 宛先アドレス
+    pub to: String,
+    
+    /// 件名
+    pub subject: String,
+    
+    /// 本文（プレーンテキスト）
+    pub body: String,
+}
+
+/// メールの送信手段
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    /// メールを1通送信
+    async fn send(&self, message: &EmailMessage) -> AppResult<()>;
+}
+
+/// HTTP APIでメールを送信する
+/// 
+/// `{from, to, subject, text}` のJSONを設定されたエンドポイントにPOSTする
+pub struct HttpEmailSender {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: Option<String>,
+    from_address: String,
+}
+
+impl HttpEmailSender {
+    /// 新しいメール送信クライアントを作成
+    /// 
+    /// # Arguments
+    /// * `api_url` - メール送信APIのエンドポイント
+    /// * `config` - メール送信設定（APIキーと送信元アドレス）
+    pub fn new(api_url: String, config: &EmailConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url,
+            api_key: config.api_key.clone(),
+            from_address: config.from_address.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailSender for HttpEmailSender {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        let request = self
+            .client
+            .post(&self.api_url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(&serde_json::json!({
+                "from": self.from_address,
+                "to": message.to,
+                "subject": message.subject,
+                "text": message.body,
+            }));
+        let request = match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        };
+        
+        request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(InfraError::from)?;
+        Ok(())
+    }
+}
+
+/// 受信者ごとのダイジェストのキャッシュキー
+fn digest_cache_key(date: NaiveDate, recipient: &str) -> String {
+    format!("digest:{}:{}", date, recipient)
+}
+
+/// ダイジェストが溜まっている受信者一覧のキャッシュキー
+fn digest_recipients_cache_key(date: NaiveDate) -> String {
+    format!("digest_recipients:{}", date)
+}
+
+/// ダイジェストに蓄積するPoke
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct DigestEntry {
+    event_id: Uuid,
+    from: String,
+    context: Option<String>,
+}
+
+impl DigestEntry {
+    fn from_event(event: &PokeEvent) -> Self {
+        Self {
+            event_id: event.id,
+            from: event.from.as_str().to_string(),
+            context: event.context.clone(),
+        }
+    }
+}
+
+/// ダイジェストの1行（送信者ごとの集計）
+#[derive(Debug, PartialEq, Eq)]
+struct DigestLine {
+    from: String,
+    count: usize,
+    repositories: Vec<String>,
+}
+
+/// 蓄積したPokeを送信者ごとにまとめる
+/// 
+/// 送信者は大文字小文字を区別せず、最初に現れた順に並べる
+fn summarize_digest(entries: &[DigestEntry]) -> Vec<DigestLine> {
+    let mut lines: Vec<DigestLine> = Vec::new();
+    for entry in entries {
+        let index = match lines.iter().position(|line| line.from.eq_ignore_ascii_case(&entry.from)) {
+            Some(index) => index,
+            None => {
+                lines.push(DigestLine {
+                    from: entry.from.clone(),
+                    count: 0,
+                    repositories: Vec::new(),
+                });
+                lines.len() - 1
+            }
+        };
+        
+        let line = &mut lines[index];
+        line.count += 1;
+        let new_repository = entry.context.as_ref().filter(|r| !line.repositories.contains(r));
+        if let Some(repository) = new_repository {
+            line.repositories.push(repository.clone());
+        }
+    }
+    lines
+}
+
+/// Pokeの受信者にメールで通知するサービス
+/// 
+/// メールアドレスを登録し、通知を有効にしている受信者にのみ送る。
+/// 日次ダイジェストを選んだ受信者のPokeはキャッシュに蓄積し、`flush_daily_digest` でまとめて送る
+pub struct EmailNotificationService {
+    user_repository: Arc<dyn UserRepository>,
+    cache: Arc<dyn CacheService>,
+    sender: Arc<dyn EmailSender>,
+    base_url: String,
+}
+
+impl EmailNotificationService {
+    /// 新しいメール通知サービスを作成
+    /// 
+    /// # Arguments
+    /// * `user_repository` - 受信者の通知設定の取得に使う
+    /// * `cache` - ダイジェストの蓄積先
+    /// * `sender` - メールの送信手段
+    /// * `base_url` - 本文に載せるGitPokeの公開URL
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        cache: Arc<dyn CacheService>,
+        sender: Arc<dyn EmailSender>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            user_repository,
+            cache,
+            sender,
+            base_url,
+        }
+    }
+    
+    /// 通知の送信先となる登録済みユーザーのメールアドレスを取得
+    /// 
+    /// # Returns
+    /// * `Ok(Some((mode, email)))` - 通知を有効にしていて、アドレスが登録されている
+    /// * `Ok(None)` - 通知しない
+    async fn notification_target(&self, username: &str) -> AppResult<Option<(NotificationMode, String)>> {
+        let target = match self.user_repository.find_by_username(username).await? {
+            Some(UserState::Registered(user)) => user
+                .poke_notification_email()
+                .map(|email| (user.notification_mode, email.to_string())),
+            _ => None,
+        };
+        Ok(target)
+    }
+    
+    /// Poke通知のメールを組み立てる
+    fn compose(&self, to: &str, event: &PokeEvent) -> EmailMessage {
+        let mut body = format!("{} poked you on GitPoke.\n", event.from.as_str());
+        if let Some(repository) = &event.context {
+            body.push_str(&format!("Repository: {}\n", repository));
+        }
+        if let Some(message) = &event.message {
+            body.push_str(&format!("Message: {}\n", message));
+        }
+        body.push_str(&self.footer());
+        
+        EmailMessage {
+            to: to.to_string(),
+            subject: format!("You've been poked by {}", event.from.as_str()),
+            body,
+        }
+    }
+    
+    /// 日次ダイジェストのメールを組み立てる
+    fn compose_digest(&self, to: &str, date: NaiveDate, entries: &[DigestEntry]) -> EmailMessage {
+        let mut body = format!("You received {} on GitPoke on {}.\n\n", pokes(entries.len()), date);
+        for line in summarize_digest(entries) {
+            body.push_str(&format!("- {}: {}", line.from, pokes(line.count)));
+            if !line.repositories.is_empty() {
+                body.push_str(&format!(" ({})", line.repositories.join(", ")));
+            }
+            body.push('\n');
+        }
+        body.push_str(&self.footer());
+        
+        EmailMessage {
+            to: to.to_string(),
+            subject: format!("Your GitPoke digest for {}", date),
+            body,
+        }
+    }
+    
+    /// 通知の止め方を案内する本文の末尾
+    fn footer(&self) -> String {
+        format!("\nYou can turn off these emails in your GitPoke settings: {}\n", self.base_url)
+    }
+    
+    /// Pokeをその日のダイジェストに追加
+    async fn add_to_digest(&self, recipient: &Username, event: &PokeEvent) -> AppResult<()> {
+        let date = event.occurred_at.date_naive();
+        let recipient = recipient.normalized();
+        self.push_unique(&digest_cache_key(date, &recipient), DigestEntry::from_event(event)).await?;
+        self.push_unique(&digest_recipients_cache_key(date), recipient).await
+    }
+    
+    /// キャッシュ上のリストに要素を追加（すでにあれば何もしない）
+    /// 
+    /// 読み込みと書き込みが別の操作のため、同じキーへの同時追加は片方が失われうる。
+    /// ダイジェストに1件載らないだけなので許容する
+    async fn push_unique<T>(&self, key: &str, item: T) -> AppResult<()>
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Send + Sync,
+    {
+        let mut items: Vec<T> = parse_list(self.cache.get(key).await?);
+        if items.contains(&item) {
+            return Ok(());
+        }
+        items.push(item);
+        
+        let json = serde_json::to_string(&items).map_err(InfraError::from)?;
+        self.cache.set(key, &json, DIGEST_TTL_SECONDS).await
+    }
+    
+    /// キャッシュ上のリストを取り出して削除
+    /// 
+    /// 取り出しは1操作で行うため、複数のインスタンスが同時に送信しても重複しない
+    async fn take_list<T: DeserializeOwned>(&self, key: &str) -> AppResult<Vec<T>> {
+        Ok(parse_list(self.cache.take(key).await?))
+    }
+}
+
+/// キャッシュの値をリストとして読む（壊れた値は空として扱う）
+fn parse_list<T: DeserializeOwned>(json: Option<String>) -> Vec<T> {
+    json.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default()
+}
+
+/// Poke数の表記
+fn pokes(count: usize) -> String {
+    if count == 1 {
+        "1 poke".to_string()
+    } else {
+        format!("{} pokes", count)
+    }
+}
+
+#[async_trait]
+impl NotificationService for EmailNotificationService {
+    /// 受信者にPokeをメールで通知
+    /// 
+    /// 日次ダイジェストを選んだ受信者にはすぐには送らず、その日のダイジェストに追加する。
+    /// 失敗した場合はログに残して `Ok` を返す
+    async fn notify_poke(&self, event: &PokeEvent) -> AppResult<()> {
+        let (mode, email) = match self.notification_target(event.to.as_str()).await {
+            Ok(Some(target)) => target,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                tracing::warn!(event_id = %event.id, error = %e, "メール通知の受信者を取得できませんでした");
+                return Ok(());
+            }
+        };
+        
+        let result = match mode {
+            NotificationMode::Immediate => self.sender.send(&self.compose(&email, event)).await,
+            NotificationMode::DailyDigest => self.add_to_digest(&event.to, event).await,
+            NotificationMode::Off => Ok(()),
+        };
+        if let Err(e) = result {
+            tracing::warn!(event_id = %event.id, error = %e, "Poke通知メールの送信に失敗しました");
+        }
+        Ok(())
+    }
+    
+    /// 蓄積したPokeを受信者ごとに1通のメールにまとめて送信
+    /// 
+    /// 蓄積後に通知を止めた受信者には送らない。
+    /// 受信者ごとの失敗はログに残し、残りの受信者への送信を続ける
+    async fn flush_daily_digest(&self, date: NaiveDate) -> AppResult<usize> {
+        let recipients: Vec<String> = self.take_list(&digest_recipients_cache_key(date)).await?;
+        
+        let mut sent = 0;
+        for recipient in recipients {
+            let entries: Vec<DigestEntry> = self.take_list(&digest_cache_key(date, &recipient)).await?;
+            if entries.is_empty() {
+                continue;
+            }
+            
+            let email = match self.notification_target(&recipient).await {
+                Ok(Some((_, email))) => email,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(recipient, error = %e, "ダイジェストの受信者を取得できませんでした");
+                    continue;
+                }
+            };
+            
+            match self.sender.send(&self.compose_digest(&email, date, &entries)).await {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(recipient, error = %e, "Pokeダイジェストの送信に失敗しました"),
+            }
+        }
+        Ok(sent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use crate::domain::user::Username;
+    
+    fn username(name: &str) -> Username {
+        Username::parse(name.to_string()).unwrap()
+    }
+    
+    fn poke_event() -> PokeEvent {
+        let mut event = PokeEvent::with_context(
+            username("alice"),
+            username("Bob"),
+            "octo-org/api".to_string(),
+        );
+        event.occurred_at = Utc.with_ymd_and_hms(2024, 3, 15, 9, 0, 0).unwrap();
+        event
+    }
+    
+    fn webhook_url(server: &MockServer) -> String {
+        format!("{}/hooks/poke", server.uri())
+    }
+    
+    #[tokio::test]
+    async fn test_posts_payload() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/poke"))
+            .and(body_json(json!({
+                "from": "alice",
+                "to": "Bob",
+                "occurred_at": "2024-03-15T09:00:00+00:00",
+                "context": "octo-org/api",
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let service = WebhookNotificationService::new(webhook_url(&server));
+        
+        service.notify_poke(&poke_event()).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_retries_once_after_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let service = WebhookNotificationService::new(webhook_url(&server));
+        
+        service.notify_poke(&poke_event()).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_receiver_error_is_not_returned() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(u64::from(WEBHOOK_MAX_ATTEMPTS))
+            .mount(&server)
+            .await;
+        
+        let service = WebhookNotificationService::new(webhook_url(&server));
+        
+        assert!(service.notify_poke(&poke_event()).await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_receiver_error_does_not_fail_poke() {
+        use std::sync::Arc;
+        use crate::app::dependencies::mocks::TestDependenciesBuilder;
+        use crate::domain::poke::PokeResult;
+        use crate::domain::user::{GitHubUserId, RegisteredUser};
+        use crate::use_cases::check_poke;
+        
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        
+        let mut deps = TestDependenciesBuilder::new()
+            .with_registered_user(RegisteredUser::new(GitHubUserId::new(2), username("bob")))
+            .build();
+        deps.notification_service = Arc::new(WebhookNotificationService::new(webhook_url(&server)));
+        
+        let result = check_poke::execute(&username("alice"), &username("bob"), None, None, &deps)
+            .await
+            .unwrap();
+        
+        assert!(matches!(result, PokeResult::Success { .. }));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+    
+    mod email_tests {
+        use super::*;
+        use std::sync::Mutex;
+        use chrono::Duration;
+        use crate::app::dependencies::mocks::{MockCacheService, MockUserRepository};
+        use crate::domain::user::{GitHubUserId, RegisteredUser};
+        use crate::error::AppError;
+        
+        /// 送信したメールを記録する
+        #[derive(Default)]
+        struct FakeEmailSender {
+            sent: Mutex<Vec<EmailMessage>>,
+            fail: bool,
+        }
+        
+        #[async_trait]
+        impl EmailSender for FakeEmailSender {
+            async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+                if self.fail {
+                    return Err(AppError::Internal("mail server down".to_string()));
+                }
+                self.sent.lock().unwrap().push(message.clone());
+                Ok(())
+            }
+        }
+        
+        fn recipient(mode: NotificationMode) -> RegisteredUser {
+            let mut user = RegisteredUser::new(GitHubUserId::new(2), username("bob"));
+            user.set_email(Some("bob@example.com".to_string()));
+            user.set_notification_mode(mode);
+            user
+        }
+        
+        fn service(user: RegisteredUser, sender: Arc<FakeEmailSender>) -> EmailNotificationService {
+            service_with_users(Arc::new(MockUserRepository::new().with_user(user)), sender)
+        }
+        
+        fn service_with_users(
+            users: Arc<MockUserRepository>,
+            sender: Arc<FakeEmailSender>,
+        ) -> EmailNotificationService {
+            EmailNotificationService::new(
+                users,
+                Arc::new(MockCacheService::new()),
+                sender,
+                "https://gitpoke.dev".to_string(),
+            )
+        }
+        
+        /// `from` から bob へのPoke（`poke_event` と同じ日時）
+        fn poke_from(from: &str, context: Option<&str>) -> PokeEvent {
+            let mut event = poke_event();
+            event.id = Uuid::new_v4();
+            event.from = username(from);
+            event.context = context.map(str::to_string);
+            event
+        }
+        
+        fn digest_date() -> NaiveDate {
+            NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+        }
+        
+        #[tokio::test]
+        async fn test_email_sent_to_opted_in_recipient() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let service = service(recipient(NotificationMode::Immediate), sender.clone());
+            
+            service.notify_poke(&poke_event()).await.unwrap();
+            
+            let sent = sender.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, "bob@example.com");
+            assert_eq!(sent[0].subject, "You've been poked by alice");
+            assert!(sent[0].body.contains("Repository: octo-org/api"));
+            assert!(sent[0].body.contains("https://gitpoke.dev"));
+        }
+        
+        #[tokio::test]
+        async fn test_email_skipped_for_opted_out_recipient() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let service = service(recipient(NotificationMode::Off), sender.clone());
+            
+            service.notify_poke(&poke_event()).await.unwrap();
+            
+            assert!(sender.sent.lock().unwrap().is_empty());
+        }
+        
+        #[tokio::test]
+        async fn test_email_skipped_without_address() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let mut user = recipient(NotificationMode::Immediate);
+            user.set_email(None);
+            let service = service(user, sender.clone());
+            
+            service.notify_poke(&poke_event()).await.unwrap();
+            
+            assert!(sender.sent.lock().unwrap().is_empty());
+        }
+        
+        #[tokio::test]
+        async fn test_send_failure_is_not_returned() {
+            let sender = Arc::new(FakeEmailSender { fail: true, ..Default::default() });
+            let service = service(recipient(NotificationMode::Immediate), sender);
+            
+            assert!(service.notify_poke(&poke_event()).await.is_ok());
+        }
+        
+        #[tokio::test]
+        async fn test_immediate_mode_sends_per_poke() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let service = service(recipient(NotificationMode::Immediate), sender.clone());
+            
+            service.notify_poke(&poke_from("alice", None)).await.unwrap();
+            service.notify_poke(&poke_from("carol", None)).await.unwrap();
+            
+            let subjects: Vec<String> = sender.sent.lock().unwrap().iter().map(|m| m.subject.clone()).collect();
+            assert_eq!(subjects, vec!["You've been poked by alice", "You've been poked by carol"]);
+            // 即時通知ではダイジェストに何も残らない
+            assert_eq!(service.flush_daily_digest(digest_date()).await.unwrap(), 0);
+        }
+        
+        #[tokio::test]
+        async fn test_digest_mode_accumulates_then_flushes_single_summary() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let service = service(recipient(NotificationMode::DailyDigest), sender.clone());
+            
+            let first = poke_from("alice", Some("octo-org/api"));
+            service.notify_poke(&first).await.unwrap();
+            service.notify_poke(&poke_from("carol", None)).await.unwrap();
+            service.notify_poke(&poke_from("Alice", Some("octo-org/web"))).await.unwrap();
+            // 同じイベントの再通知は数えない
+            service.notify_poke(&first).await.unwrap();
+            assert!(sender.sent.lock().unwrap().is_empty());
+            
+            let flushed = service.flush_daily_digest(digest_date()).await.unwrap();
+            
+            assert_eq!(flushed, 1);
+            let sent = sender.sent.lock().unwrap().clone();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, "bob@example.com");
+            assert_eq!(sent[0].subject, "Your GitPoke digest for 2024-03-15");
+            assert!(sent[0].body.starts_with("You received 3 pokes on GitPoke on 2024-03-15.\n\n"));
+            assert!(sent[0].body.contains("- alice: 2 pokes (octo-org/api, octo-org/web)\n- carol: 1 poke\n"));
+            
+            // 送信済みのダイジェストは再送しない
+            assert_eq!(service.flush_daily_digest(digest_date()).await.unwrap(), 0);
+            assert_eq!(sender.sent.lock().unwrap().len(), 1);
+        }
+        
+        #[tokio::test]
+        async fn test_digest_is_bucketed_by_date() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let service = service(recipient(NotificationMode::DailyDigest), sender.clone());
+            let mut next_day = poke_from("carol", None);
+            next_day.occurred_at += Duration::days(1);
+            
+            service.notify_poke(&poke_from("alice", None)).await.unwrap();
+            service.notify_poke(&next_day).await.unwrap();
+            
+            assert_eq!(service.flush_daily_digest(digest_date()).await.unwrap(), 1);
+            assert!(!sender.sent.lock().unwrap()[0].body.contains("carol"));
+            assert_eq!(service.flush_daily_digest(digest_date().succ_opt().unwrap()).await.unwrap(), 1);
+        }
+        
+        #[tokio::test]
+        async fn test_digest_skipped_after_opting_out() {
+            let sender = Arc::new(FakeEmailSender::default());
+            let users = Arc::new(MockUserRepository::new().with_user(recipient(NotificationMode::DailyDigest)));
+            let service = service_with_users(users.clone(), sender.clone());
+            service.notify_poke(&poke_from("alice", None)).await.unwrap();
+            
+            users.update(&recipient(NotificationMode::Off)).await.unwrap();
+            
+            assert_eq!(service.flush_daily_digest(digest_date()).await.unwrap(), 0);
+            assert!(sender.sent.lock().unwrap().is_empty());
+        }
+        
+        #[tokio::test]
+        async fn test_http_sender_posts_message() {
+            use wiremock::matchers::header;
+            
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/send"))
+                .and(header("authorization", "Bearer mail-key"))
+                .and(body_json(json!({
+                    "from": "GitPoke <notifications@gitpoke.dev>",
+                    "to": "bob@example.com",
+                    "subject": "You've been poked by alice",
+                    "text": "alice poked you on GitPoke.\n",
+                })))
+                .respond_with(ResponseTemplate::new(202))
+                .expect(1)
+                .mount(&server)
+                .await;
+            let config = EmailConfig {
+                api_key: Some("mail-key".to_string()),
+                ..EmailConfig::default()
+            };
+            let sender = HttpEmailSender::new(format!("{}/send", server.uri()), &config);
+            let message = EmailMessage {
+                to: "bob@example.com".to_string(),
+                subject: "You've been poked by alice".to_string(),
+                body: "alice poked you on GitPoke.\n".to_string(),
+            };
+            
+            sender.send(&message).await.unwrap();
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! バッジのPNG変換
+//! 
+//! このファイルは以下を定義：
+//! - SVGバッジをPNGにラスタライズする関数
+//! 
+//! SVGを表示できないクライアント（古いチャットクライアントやメール）向け。
+//! `png` フィーチャーが有効な場合のみビルドされる
+
+use std::sync::{Arc, LazyLock};
+
+use resvg::{tiny_skia, usvg};
+
+use crate::error::{AppError, AppResult};
+
+/// 出力の倍率
+/// 
+/// 高解像度ディスプレイでぼやけないよう、SVGの2倍のサイズで出力する
+const PNG_SCALE: f32 = 2.0;
+
+/// テキストの描画に使うフォント
+/// 
+/// システムフォントの読み込みは重いため、プロセスで一度だけ行う
+static FONT_DATABASE: LazyLock<Arc<usvg::fontdb::Database>> = LazyLock::new(|| {
+    let mut database = usvg::fontdb::Database::new();
+    database.load_system_fonts();
+    Arc::new(database)
+});
+
+/// SVGをPNGに変換
+/// 
+/// # Arguments
+/// * `svg` - SVGコンテンツ
+/// 
+/// # Returns
+/// * `Ok(Vec<u8>)` - PNGのバイト列
+/// * `Err(AppError::Internal)` - SVGの解析または描画に失敗
+pub fn render_png(svg: &str) -> AppResult<Vec<u8>> {
+    let options = usvg::Options {
+        fontdb: FONT_DATABASE.clone(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|e| AppError::Internal(format!("バッジSVGを解析できません: {}", e)))?;
+    
+    let size = tree.size().to_int_size().scale_by(PNG_SCALE).ok_or_else(|| {
+        AppError::Internal("バッジのサイズが不正です".to_string())
+    })?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| AppError::Internal("PNGの描画領域を確保できません".to_string()))?;
+    
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(PNG_SCALE, PNG_SCALE),
+        &mut pixmap.as_mut(),
+    );
+    
+    pixmap
+        .encode_png()
+        .map_err(|e| AppError::Internal(format!("PNGをエンコードできません: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// PNGのシグネチャ
+    const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+    
+    /// PNGのIHDRチャンクから幅と高さを読む
+    fn png_dimensions(png: &[u8]) -> (u32, u32) {
+        let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+        (width, height)
+    }
+    
+    #[test]
+    fn test_render_png_scales_svg() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="90" height="20"><rect width="90" height="20" fill="#44cc11"/></svg>"##;
+        
+        let png = render_png(svg).unwrap();
+        
+        assert!(png.starts_with(PNG_MAGIC));
+        assert_eq!(png_dimensions(&png), (180, 40));
+    }
+    
+    #[test]
+    fn test_invalid_svg_is_an_error() {
+        assert!(matches!(render_png("not an svg"), Err(AppError::Internal(_))));
+    }
+}
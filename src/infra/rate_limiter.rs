@@ -0,0 +1,211 @@
+//! Redisを使用したレート制限
+//!
+//! このファイルは以下を実装：
+//! - `RateLimiter` トレイトのRedis実装（スライディングウィンドウ）
+//!
+//! キーごとにソート済みセットを持ち、メンバーにリクエスト時刻（ミリ秒）を記録する
+//! ウィンドウより古いメンバーを削除してから数えることで、
+//! 固定ウィンドウの境界でリクエストが集中する問題を避ける
+
+use async_trait::async_trait;
+use chrono::Utc;
+use deadpool_redis::{redis, Pool};
+use crate::app::dependencies::RateLimiter;
+use crate::error::{AppResult, InfraError};
+
+/// Redisを使用したレート制限サービス
+pub struct RedisRateLimiter {
+    pool: Pool,
+}
+
+impl RedisRateLimiter {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+    
+    /// 接続プールからコネクションを取得
+    async fn connection(&self) -> AppResult<deadpool_redis::Connection> {
+        Ok(self.pool.get().await.map_err(InfraError::from)?)
+    }
+    
+    /// ウィンドウ外の記録を削除してから、ウィンドウ内の件数を数える
+    async fn count(&self, key: &str, window_seconds: u64) -> AppResult<u32> {
+        let mut conn = self.connection().await?;
+        let now_ms = Utc::now().timestamp_millis();
+        
+        let (count,): (u32,) = redis::pipe()
+            .atomic()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(key)
+            .arg("-inf")
+            .arg(format!("({}", window_start_ms(now_ms, window_seconds)))
+            .ignore()
+            .cmd("ZCARD")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(InfraError::from)?;
+        
+        Ok(count)
+    }
+}
+
+/// ウィンドウの開始時刻（ミリ秒）
+fn window_start_ms(now_ms: i64, window_seconds: u64) -> i64 {
+    now_ms - window_ms(window_seconds)
+}
+
+/// ウィンドウ幅（ミリ秒）
+fn window_ms(window_seconds: u64) -> i64 {
+    i64::try_from(window_seconds.saturating_mul(1000)).unwrap_or(i64::MAX)
+}
+
+#[async_trait]
+impl RateLimiter for RedisRateLimiter {
+    async fn check_limit(&self, key: &str, limit: u32, window_seconds: u64) -> AppResult<bool> {
+        Ok(self.count(key, window_seconds).await? < limit)
+    }
+    
+    async fn increment(&self, key: &str, window_seconds: u64) -> AppResult<u32> {
+        let mut conn = self.connection().await?;
+        let now_ms = Utc::now().timestamp_millis();
+        // 同一ミリ秒のリクエストも別メンバーとして記録する
+        let member = format!("{}-{}", now_ms, uuid::Uuid::new_v4());
+        
+        let (count,): (u32,) = redis::pipe()
+            .atomic()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(key)
+            .arg("-inf")
+            .arg(format!("({}", window_start_ms(now_ms, window_seconds)))
+            .ignore()
+            .cmd("ZADD")
+            .arg(key)
+            .arg(now_ms)
+            .arg(member)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(key)
+            .arg(window_seconds.max(1))
+            .ignore()
+            .cmd("ZCARD")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(InfraError::from)?;
+        
+        Ok(count)
+    }
+    
+    /// 最も古い記録がウィンドウから外れるまでの秒数
+    async fn retry_after(&self, key: &str, window_seconds: u64) -> AppResult<u64> {
+        let mut conn = self.connection().await?;
+        let now_ms = Utc::now().timestamp_millis();
+        
+        let oldest: Vec<(String, i64)> = redis::cmd("ZRANGE")
+            .arg(key)
+            .arg(0)
+            .arg(0)
+            .arg("WITHSCORES")
+            .query_async(&mut conn)
+            .await
+            .map_err(InfraError::from)?;
+        
+        let Some((_, oldest_ms)) = oldest.into_iter().next() else {
+            return Ok(0);
+        };
+        
+        // 秒単位に切り上げる（早すぎる再試行で再び制限されないように）
+        let remaining_ms = (oldest_ms + window_ms(window_seconds) - now_ms).max(0);
+        Ok((remaining_ms as u64).div_ceil(1000))
+    }
+    
+    async fn remaining(&self, key: &str, limit: u32, window_seconds: u64) -> AppResult<u32> {
+        Ok(limit.saturating_sub(self.count(key, window_seconds).await?))
+    }
+}
+
+/// ローカルのRedisに接続する統合テスト
+///
+/// `cargo test --features redis-tests` で実行（REDIS_URL 未設定時は localhost:6379）
+#[cfg(all(test, feature = "redis-tests"))]
+mod redis_tests {
+    use super::*;
+    use std::time::Duration;
+    
+    fn create_pool() -> Pool {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        deadpool_redis::Config::from_url(url)
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap()
+    }
+    
+    fn unique_key(name: &str) -> String {
+        format!("gitpoke-test:rate_limit:{}:{}", name, uuid::Uuid::new_v4())
+    }
+    
+    #[tokio::test]
+    async fn test_limit_reached_within_window() {
+        let limiter = RedisRateLimiter::new(create_pool());
+        let key = unique_key("limit");
+        
+        assert!(limiter.check_limit(&key, 2, 60).await.unwrap());
+        assert_eq!(limiter.increment(&key, 60).await.unwrap(), 1);
+        assert_eq!(limiter.increment(&key, 60).await.unwrap(), 2);
+        assert!(!limiter.check_limit(&key, 2, 60).await.unwrap());
+        
+        let retry_after = limiter.retry_after(&key, 60).await.unwrap();
+        assert!(retry_after > 0 && retry_after <= 60);
+    }
+    
+    #[tokio::test]
+    async fn test_remaining_counts_down_and_recovers() {
+        let limiter = RedisRateLimiter::new(create_pool());
+        let key = unique_key("remaining");
+        
+        assert_eq!(limiter.remaining(&key, 2, 1).await.unwrap(), 2);
+        limiter.increment(&key, 1).await.unwrap();
+        assert_eq!(limiter.remaining(&key, 2, 1).await.unwrap(), 1);
+        limiter.increment(&key, 1).await.unwrap();
+        limiter.increment(&key, 1).await.unwrap();
+        assert_eq!(limiter.remaining(&key, 2, 1).await.unwrap(), 0);
+        
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        assert_eq!(limiter.remaining(&key, 2, 1).await.unwrap(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_window_slides() {
+        let limiter = RedisRateLimiter::new(create_pool());
+        let key = unique_key("slide");
+        
+        limiter.increment(&key, 2).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        limiter.increment(&key, 2).await.unwrap();
+        assert!(!limiter.check_limit(&key, 2, 2).await.unwrap());
+        
+        // 最初の記録だけがウィンドウから外れる
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        assert!(limiter.check_limit(&key, 2, 2).await.unwrap());
+        assert_eq!(limiter.increment(&key, 2).await.unwrap(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_key_expires_after_window() {
+        let pool = create_pool();
+        let limiter = RedisRateLimiter::new(pool.clone());
+        let key = unique_key("expire");
+        
+        limiter.increment(&key, 1).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        
+        let mut conn = pool.get().await.unwrap();
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(&key)
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        assert!(!exists);
+        assert_eq!(limiter.retry_after(&key, 1).await.unwrap(), 0);
+    }
+}
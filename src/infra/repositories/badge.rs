@@ -1,15 +1,15 @@
 use crate::domain::badge::{Badge, BadgeStyle};
-use crate::domain::user::UserId;
+use crate::domain::user::GitHubUserId;
 use async_trait::async_trait;
 use std::error::Error;
 
 #[async_trait]
 pub trait BadgeRepository: Send + Sync {
-    async fn get_poke_count(&self, user_id: &UserId) -> Result<u64, Box<dyn Error>>;
+    async fn get_poke_count(&self, user_id: &GitHubUserId) -> Result<u64, Box<dyn Error>>;
     
     async fn generate_badge(
         &self,
-        user_id: &UserId,
+        user_id: &GitHubUserId,
         style: BadgeStyle
     ) -> Result<Badge, Box<dyn Error>>;
 }
@@ -26,13 +26,13 @@ impl PostgresBadgeRepository {
 
 #[async_trait]
 impl BadgeRepository for PostgresBadgeRepository {
-    async fn get_poke_count(&self, _user_id: &UserId) -> Result<u64, Box<dyn Error>> {
+    async fn get_poke_count(&self, _user_id: &GitHubUserId) -> Result<u64, Box<dyn Error>> {
         todo!()
     }
     
     async fn generate_badge(
         &self,
-        _user_id: &UserId,
+        _user_id: &GitHubUserId,
         _style: BadgeStyle
     ) -> Result<Badge, Box<dyn Error>> {
         todo!()
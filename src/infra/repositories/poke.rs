@@ -1,27 +1,55 @@
-use crate::domain::poke::{Poke, PokeId};
-use crate::domain::user::UserId;
+//! Pokeリポジトリ
+//! 
+//! このファイルは以下を定義：
+//! - Pokeイベントを検索・集計する `PokeRepository` トレイト
+//! - Postgresを使った実装
+//! 
+//! Firestore実装とインメモリ実装は、同じコレクションを扱う
+//! `infra::event_store` のイベントストアが兼ねる
+
 use async_trait::async_trait;
-use std::error::Error;
+use uuid::Uuid;
+
+use crate::domain::poke::PokeEvent;
+use crate::domain::user::Username;
+use crate::error::AppResult;
 
 #[async_trait]
 pub trait PokeRepository: Send + Sync {
-    async fn find_by_id(&self, id: &PokeId) -> Result<Option<Poke>, Box<dyn Error>>;
+    /// IDでPokeを取得
+    async fn find_by_id(&self, id: &Uuid) -> AppResult<Option<PokeEvent>>;
     
+    /// 送信者から受信者へのPokeをすべて取得
     async fn find_by_sender_and_recipient(
         &self,
-        sender_id: &UserId,
-        recipient_id: &UserId
-    ) -> Result<Vec<Poke>, Box<dyn Error>>;
+        sender: &Username,
+        recipient: &Username
+    ) -> AppResult<Vec<PokeEvent>>;
     
-    async fn save(&self, poke: &Poke) -> Result<(), Box<dyn Error>>;
+    /// Pokeを保存
+    async fn save(&self, poke: &PokeEvent) -> AppResult<()>;
     
-    async fn count_by_recipient(&self, recipient_id: &UserId) -> Result<u64, Box<dyn Error>>;
+    /// ユーザーが受信したPokeの件数
+    async fn count_by_recipient(&self, recipient: &Username) -> AppResult<u64>;
     
+    /// 全ユーザーのPokeを新しい順に取得
+    /// 
+    /// `occurred_at` の降順で並べ、同時刻のものはIDの降順にする。
+    /// 並び順が決まっているため、`offset` をずらして読めばページ間で重複・欠落しない
+    /// 
+    /// # Arguments
+    /// * `limit` - 最大件数
+    /// * `offset` - 先頭から読み飛ばす件数
     async fn list_recent(
         &self,
         limit: usize,
         offset: usize
-    ) -> Result<Vec<Poke>, Box<dyn Error>>;
+    ) -> AppResult<Vec<PokeEvent>>;
+    
+    /// 全ユーザーのPokeの件数
+    /// 
+    /// `list_recent` と組み合わせてページ数を求めるのに使う
+    async fn count_all(&self) -> AppResult<u64>;
 }
 
 pub struct PostgresPokeRepository {
@@ -36,23 +64,23 @@ impl PostgresPokeRepository {
 
 #[async_trait]
 impl PokeRepository for PostgresPokeRepository {
-    async fn find_by_id(&self, _id: &PokeId) -> Result<Option<Poke>, Box<dyn Error>> {
+    async fn find_by_id(&self, _id: &Uuid) -> AppResult<Option<PokeEvent>> {
         todo!()
     }
     
     async fn find_by_sender_and_recipient(
         &self,
-        _sender_id: &UserId,
-        _recipient_id: &UserId
-    ) -> Result<Vec<Poke>, Box<dyn Error>> {
+        _sender: &Username,
+        _recipient: &Username
+    ) -> AppResult<Vec<PokeEvent>> {
         todo!()
     }
     
-    async fn save(&self, _poke: &Poke) -> Result<(), Box<dyn Error>> {
+    async fn save(&self, _poke: &PokeEvent) -> AppResult<()> {
         todo!()
     }
     
-    async fn count_by_recipient(&self, _recipient_id: &UserId) -> Result<u64, Box<dyn Error>> {
+    async fn count_by_recipient(&self, _recipient: &Username) -> AppResult<u64> {
         todo!()
     }
     
@@ -60,7 +88,13 @@ impl PokeRepository for PostgresPokeRepository {
         &self,
         _limit: usize,
         _offset: usize
-    ) -> Result<Vec<Poke>, Box<dyn Error>> {
+    ) -> AppResult<Vec<PokeEvent>> {
+        // SELECT ... ORDER BY occurred_at DESC, id DESC LIMIT $1 OFFSET $2
         todo!()
     }
-}
\ No newline at end of file
+    
+    async fn count_all(&self) -> AppResult<u64> {
+        // SELECT COUNT(*) FROM poke_events
+        todo!()
+    }
+}
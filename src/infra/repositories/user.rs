@@ -1,18 +1,145 @@
-use crate::domain::user::{User, UserId};
+//! ユーザーリポジトリ
+//! 
+//! このファイルは以下を定義：
+//! - 登録済みユーザーを永続化する `UserRepository` トレイト
+//! - 永続化用のレコード型とドメインモデルとの相互変換
+//! - Postgresを使った実装
+//! - インメモリの実装（テスト・ローカル開発用）
+//! 
+//! リポジトリはドメインの `RegisteredUser` / `GitHubUserId` をそのまま受け渡しし、
+//! 保存形式との変換はこのファイル内で閉じる
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
 use async_trait::async_trait;
-use std::error::Error;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
+use crate::domain::user::{GitHubUserId, NotificationMode, PokeSetting, RegisteredUser, Username};
+use crate::domain::validation::ValidationError;
+use crate::error::{AppResult, InfraError};
+
+/// 登録済みユーザーの永続化
 #[async_trait]
 pub trait UserRepository: Send + Sync {
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, Box<dyn Error>>;
+    /// GitHub IDでユーザーを検索
+    /// 
+    /// # Arguments
+    /// * `github_id` - GitHub ID
+    /// 
+    /// # Returns
+    /// * `Ok(Some(user))` - 登録済み
+    /// * `Ok(None)` - 未登録
+    async fn find_by_github_id(&self, github_id: &GitHubUserId) -> AppResult<Option<RegisteredUser>>;
+    
+    /// ユーザー名でユーザーを検索（大文字小文字は区別しない）
+    /// 
+    /// # Arguments
+    /// * `username` - GitHubユーザー名
+    async fn find_by_username(&self, username: &Username) -> AppResult<Option<RegisteredUser>>;
+    
+    /// ユーザーを保存
+    async fn save(&self, user: &RegisteredUser) -> AppResult<()>;
+    
+    /// ユーザーを更新
+    async fn update(&self, user: &RegisteredUser) -> AppResult<()>;
+    
+    /// ユーザーを削除
+    async fn delete(&self, github_id: &GitHubUserId) -> AppResult<()>;
+}
+
+/// 永続化用のユーザーレコード
+/// 
+/// 保存先の行・ドキュメントと1対1に対応する。
+/// 読み込み時に値オブジェクトの検証を通してから `RegisteredUser` に戻す
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserRecord {
+    /// GitHub ID
+    pub github_id: i64,
+    
+    /// GitHubユーザー名
+    pub username: String,
+    
+    /// Poke受信設定
+    pub poke_setting: PokeSetting,
+    
+    /// Pokeを受け付けない送信者
+    pub blocked_senders: Vec<String>,
+    
+    /// インタラクティブバッジを許可するか
+    #[serde(default = "default_allow_interactive_badge")]
+    pub allow_interactive_badge: bool,
+    
+    /// 通知用のメールアドレス
+    #[serde(default)]
+    pub email: Option<String>,
     
-    async fn find_by_github_id(&self, github_id: u64) -> Result<Option<User>, Box<dyn Error>>;
+    /// Poke受信時のメール通知方法
+    #[serde(default)]
+    pub notification_mode: NotificationMode,
     
-    async fn save(&self, user: &User) -> Result<(), Box<dyn Error>>;
+    /// アカウント作成日時
+    pub created_at: DateTime<Utc>,
     
-    async fn update(&self, user: &User) -> Result<(), Box<dyn Error>>;
+    /// 最終更新日時
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 項目追加前のレコードはインタラクティブバッジを許可として扱う
+fn default_allow_interactive_badge() -> bool {
+    true
+}
+
+impl From<&RegisteredUser> for UserRecord {
+    fn from(user: &RegisteredUser) -> Self {
+        Self {
+            github_id: user.github_id.value(),
+            username: user.username.as_str().to_string(),
+            poke_setting: user.poke_setting,
+            blocked_senders: user
+                .blocked_senders
+                .iter()
+                .map(|sender| sender.as_str().to_string())
+                .collect(),
+            allow_interactive_badge: user.allow_interactive_badge,
+            email: user.email.clone(),
+            notification_mode: user.notification_mode,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// レコードからドメインモデルへの変換
+/// 
+/// 保存済みのユーザー名が現在の規則に合わない場合はエラーにする
+impl TryFrom<UserRecord> for RegisteredUser {
+    type Error = ValidationError;
     
-    async fn delete(&self, id: &UserId) -> Result<(), Box<dyn Error>>;
+    fn try_from(record: UserRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            github_id: GitHubUserId::new(record.github_id),
+            username: Username::parse(record.username)?,
+            poke_setting: record.poke_setting,
+            blocked_senders: record
+                .blocked_senders
+                .into_iter()
+                .map(Username::parse)
+                .collect::<Result<_, _>>()?,
+            allow_interactive_badge: record.allow_interactive_badge,
+            email: record.email,
+            notification_mode: record.notification_mode,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+/// レコードを読み込み、変換できないものはデータベースエラーとして扱う
+fn into_registered_user(record: UserRecord) -> AppResult<RegisteredUser> {
+    RegisteredUser::try_from(record)
+        .map_err(|e| InfraError::Database(format!("保存済みのユーザーを読み込めません: {}", e)).into())
 }
 
 pub struct PostgresUserRepository {
@@ -27,23 +154,145 @@ impl PostgresUserRepository {
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
-    async fn find_by_id(&self, _id: &UserId) -> Result<Option<User>, Box<dyn Error>> {
+    async fn find_by_github_id(&self, _github_id: &GitHubUserId) -> AppResult<Option<RegisteredUser>> {
         todo!()
     }
     
-    async fn find_by_github_id(&self, _github_id: u64) -> Result<Option<User>, Box<dyn Error>> {
+    async fn find_by_username(&self, _username: &Username) -> AppResult<Option<RegisteredUser>> {
         todo!()
     }
     
-    async fn save(&self, _user: &User) -> Result<(), Box<dyn Error>> {
+    async fn save(&self, _user: &RegisteredUser) -> AppResult<()> {
         todo!()
     }
     
-    async fn update(&self, _user: &User) -> Result<(), Box<dyn Error>> {
+    async fn update(&self, _user: &RegisteredUser) -> AppResult<()> {
         todo!()
     }
     
-    async fn delete(&self, _id: &UserId) -> Result<(), Box<dyn Error>> {
+    async fn delete(&self, _github_id: &GitHubUserId) -> AppResult<()> {
         todo!()
     }
-}
\ No newline at end of file
+}
+
+/// インメモリのユーザーリポジトリ
+/// 
+/// テストやローカル開発で使用する（プロセス終了で消える）。
+/// 永続化と同じくレコード形式で保持する
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    records: Mutex<HashMap<i64, UserRecord>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_github_id(&self, github_id: &GitHubUserId) -> AppResult<Option<RegisteredUser>> {
+        let record = self.records.lock().unwrap().get(&github_id.value()).cloned();
+        record.map(into_registered_user).transpose()
+    }
+    
+    async fn find_by_username(&self, username: &Username) -> AppResult<Option<RegisteredUser>> {
+        let normalized = username.normalized();
+        let record = self.records
+            .lock()
+            .unwrap()
+            .values()
+            .find(|record| record.username.to_lowercase() == normalized)
+            .cloned();
+        record.map(into_registered_user).transpose()
+    }
+    
+    async fn save(&self, user: &RegisteredUser) -> AppResult<()> {
+        self.records
+            .lock()
+            .unwrap()
+            .insert(user.github_id.value(), UserRecord::from(user));
+        Ok(())
+    }
+    
+    async fn update(&self, user: &RegisteredUser) -> AppResult<()> {
+        self.save(user).await
+    }
+    
+    async fn delete(&self, github_id: &GitHubUserId) -> AppResult<()> {
+        self.records.lock().unwrap().remove(&github_id.value());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    
+    fn registered_user() -> RegisteredUser {
+        let mut user = RegisteredUser::new(
+            GitHubUserId::new(583231),
+            Username::parse("Octocat".to_string()).unwrap(),
+        );
+        user.update_poke_setting(PokeSetting::MutualOnly);
+        user.block_sender(Username::parse("spammer".to_string()).unwrap());
+        user.set_email(Some("octocat@example.com".to_string()));
+        user.set_notification_mode(NotificationMode::DailyDigest);
+        user
+    }
+    
+    #[test]
+    fn test_record_round_trip() {
+        let user = registered_user();
+        
+        let record = UserRecord::from(&user);
+        assert_eq!(record.github_id, 583231);
+        assert_eq!(record.username, "Octocat");
+        assert_eq!(record.blocked_senders, vec!["spammer".to_string()]);
+        
+        assert_eq!(RegisteredUser::try_from(record).unwrap(), user);
+    }
+    
+    #[test]
+    fn test_record_with_invalid_username_is_rejected() {
+        let mut record = UserRecord::from(&registered_user());
+        record.username = "-octocat".to_string();
+        assert!(RegisteredUser::try_from(record).is_err());
+        
+        let mut record = UserRecord::from(&registered_user());
+        record.blocked_senders.push("bad--name".to_string());
+        assert!(RegisteredUser::try_from(record).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_in_memory_lookup() {
+        let repository = InMemoryUserRepository::new();
+        let user = registered_user();
+        repository.save(&user).await.unwrap();
+        
+        let by_id = repository.find_by_github_id(&GitHubUserId::new(583231)).await.unwrap();
+        assert_eq!(by_id, Some(user.clone()));
+        let by_name = repository
+            .find_by_username(&Username::parse("octocat".to_string()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(by_name, Some(user));
+        
+        repository.delete(&GitHubUserId::new(583231)).await.unwrap();
+        assert_eq!(repository.find_by_github_id(&GitHubUserId::new(583231)).await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_corrupt_record_is_a_database_error() {
+        let repository = InMemoryUserRepository::new();
+        let mut record = UserRecord::from(&registered_user());
+        record.username = "-octocat".to_string();
+        repository.records.lock().unwrap().insert(record.github_id, record);
+        
+        let result = repository.find_by_github_id(&GitHubUserId::new(583231)).await;
+        
+        assert!(matches!(result, Err(AppError::Infra(InfraError::Database(_)))));
+    }
+}
@@ -8,6 +8,7 @@
 
 use axum::{Router, serve};
 use std::net::SocketAddr;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -23,10 +24,14 @@ mod util;
 
 use crate::app::config::Config;
 use crate::app::dependencies::AppDependencies;
+use crate::app::shutdown::{drain_background_tasks, shutdown_signal};
 use crate::error::AppResult;
 
 #[tokio::main]
 async fn main() -> AppResult<()> {
+    // 稼働時間の起点を記録
+    handlers::health::mark_process_start();
+    
     // ロギング初期化
     init_tracing();
 
@@ -38,7 +43,8 @@ async fn main() -> AppResult<()> {
     // - GITHUB_APP_PRIVATE_KEY: GitHub App秘密鍵
     // - REDIS_URL: Redis接続URL
     // - FIRESTORE_PROJECT_ID: Firestoreプロジェクト
-    let config = Config::from_env()?;
+    // GITPOKE_CONFIG が設定されていればTOMLファイルを読み込み、環境変数で上書きする
+    let config = Config::load()?;
     info!("設定を読み込みました");
 
     // 依存関係の初期化
@@ -50,6 +56,15 @@ async fn main() -> AppResult<()> {
     let deps = AppDependencies::new(&config).await?;
     info!("依存関係を初期化しました");
 
+    // シャットダウン時に閉じるため、キャッシュサービスへの参照を残しておく
+    let cache_service = deps.cache_service.clone();
+
+    // 前日分のPokeダイジェストを毎日送信する
+    tokio::spawn(app::scheduler::run_daily_digest(
+        deps.notification_service.clone(),
+        deps.clock.clone(),
+    ));
+
     // ルーター構築
     let app = create_app(deps);
 
@@ -58,17 +73,34 @@ async fn main() -> AppResult<()> {
     info!("サーバーを起動します: {}", addr);
     
     let listener = TcpListener::bind(addr).await?;
-    serve(listener, app).await?;
+    // SIGTERM/SIGINTを受信したら新しい接続の受け付けを止め、処理中のリクエストを待つ
+    serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    info!("処理中のリクエストが完了しました");
+
+    // バックグラウンドタスク（キャッシュ更新・バッジ保存）の完了を待つ
+    drain_background_tasks(BACKGROUND_TASK_DRAIN_TIMEOUT).await;
+    info!("バックグラウンドタスクが完了しました");
+
+    // Redisの接続を閉じる
+    cache_service.close();
+    info!("シャットダウンしました");
 
     Ok(())
 }
 
+/// シャットダウン時にバックグラウンドタスクを待つ最大時間
+/// 
+/// Cloud RunのSIGTERMから強制終了までの猶予（10秒）に収まるようにする
+const BACKGROUND_TASK_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// アプリケーションルーターを構築
 /// 
 /// 以下のミドルウェアを適用：
 /// - TraceLayer: リクエストのトレーシング
-/// - TimeoutLayer: リクエストタイムアウト（30秒）
 /// - CorsLayer: CORS設定（github.comからのアクセスを許可）
+/// - タイムアウト: リクエストタイムアウト（設定値、超過時は408）
 fn create_app(deps: AppDependencies) -> Router {
     routes::create_routes(deps)
 }
@@ -14,7 +14,9 @@ use axum_extra::extract::CookieJar;
 
 use crate::app::dependencies::AppDependencies;
 use crate::domain::user::{Username, RegisteredUser};
-use crate::error::HandlerError;
+use crate::error::{AppError, HandlerError};
+use crate::infra::adapters::github::GitHubApiAdapter;
+use crate::middlewares::session::{authenticate_session, SESSION_COOKIE_NAME};
 
 /// <
 /// 
@@ -39,7 +41,7 @@ where
             .map_err(|_| HandlerError::Unauthorized)?;
         
         let session_id = cookies
-            .get("gitpoke_session")
+            .get(SESSION_COOKIE_NAME)
             .map(|c| c.value().to_string())
             .ok_or(HandlerError::Unauthorized)?;
         
@@ -48,27 +50,17 @@ where
             .await
             .map_err(|_| HandlerError::InternalServerError("Failed to get dependencies".to_string()))?;
         
-        // RedisK÷1֗
-        let session_key = format!("session:{}", session_id);
-        let session_data = deps.cache_service
-            .get(&session_key)
+        // セッションを解決（存在しない・期限切れのセッションはUnauthorized）
+        authenticate_session(
+            deps.cache_service.as_ref(),
+            &GitHubApiAdapter::new(&deps.config.github),
+            &session_id,
+        )
             .await
-            .map_err(|_| HandlerError::InternalServerError("Session lookup failed".to_string()))?
-            .ok_or(HandlerError::Unauthorized)?;
-        
-        // ÷
-        let session: serde_json::Value = serde_json::from_str(&session_data)
-            .map_err(|_| HandlerError::InternalServerError("Invalid session data".to_string()))?;
-        
-        let username = session["username"]
-            .as_str()
-            .ok_or(HandlerError::Unauthorized)?;
-        
-        Ok(AuthenticatedUser {
-            username: Username::new(username.to_string())
-                .map_err(|_| HandlerError::Unauthorized)?,
-            session_id,
-        })
+            .map_err(|e| match e {
+                AppError::Handler(e) => e,
+                _ => HandlerError::InternalServerError("Session lookup failed".to_string()),
+            })
     }
 }
 
@@ -0,0 +1,88 @@
+//! リクエストメトリクス
+//! 
+//! このファイルは以下を定義：
+//! - HTTPリクエスト数と処理時間を記録するミドルウェア
+//! 
+//! ルートのラベルには実際のパスではなくマッチしたパターンを使い、
+//! ユーザー名ごとに系列が増えないようにする
+
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::app::metrics::METRICS;
+
+/// リクエストのメトリクスを記録
+/// 
+/// `Router::route_layer` で適用する（マッチしたルートのみが対象）
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    
+    let response = next.run(request).await;
+    
+    METRICS.record_http_request(&method, &route, response.status().as_u16(), start.elapsed());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        http::StatusCode,
+        middleware,
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+    use crate::handlers::health;
+    
+    fn app() -> Router {
+        Router::new()
+            .route("/metrics-test/:name", get(|| async { "ok" }))
+            .route("/metrics-test-missing", get(|| async { StatusCode::NOT_FOUND }))
+            .route("/metrics", get(health::metrics))
+            .route_layer(middleware::from_fn(track_metrics))
+    }
+    
+    async fn get_body(app: Router, uri: &str) -> (StatusCode, String) {
+        let response = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+    
+    #[tokio::test]
+    async fn test_metrics_reflect_requests() {
+        get_body(app(), "/metrics-test/alice").await;
+        get_body(app(), "/metrics-test/bob").await;
+        get_body(app(), "/metrics-test-missing").await;
+        
+        let (status, text) = get_body(app(), "/metrics").await;
+        
+        assert_eq!(status, StatusCode::OK);
+        // パスパラメータが異なっても同じルートとして数える
+        assert!(text.contains(
+            r#"gitpoke_http_requests_total{method="GET",route="/metrics-test/:name",status="200"} 2"#
+        ), "{}", text);
+        assert!(text.contains(
+            r#"gitpoke_http_requests_total{method="GET",route="/metrics-test-missing",status="404"} 1"#
+        ), "{}", text);
+        assert!(text.contains(
+            r#"gitpoke_http_request_duration_seconds_count{method="GET",route="/metrics-test/:name"} 2"#
+        ), "{}", text);
+    }
+}
@@ -6,5 +6,12 @@
 //! - ꯨn*Ë
 
 pub mod auth;
+pub mod metrics;
+pub mod problem_json;
+pub mod rate_limit;
+pub mod request_id;
+pub mod security_headers;
+pub mod session;
+pub mod timeout;
 
 pub use auth::{require_auth, optional_auth};
\ No newline at end of file
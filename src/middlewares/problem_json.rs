@@ -0,0 +1,163 @@
+//! problem+jsonのコンテントネゴシエーション
+//! 
+//! このファイルは以下を定義：
+//! - `Accept: application/problem+json` を指定したリクエストのエラーを
+//!   RFC 7807形式に差し替えるミドルウェア
+//! 
+//! 指定がない場合は従来の `{"error": {...}}` 形式のまま返す
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::error::{ProblemDetails, PROBLEM_JSON_CONTENT_TYPE};
+
+/// エラーレスポンスをproblem+jsonに切り替える
+/// 
+/// `AppError` のレスポンスに付与された `ProblemDetails` をボディにし、
+/// ステータスコードと他のヘッダー（Retry-After等）はそのまま残す
+pub async fn negotiate_problem_json(request: Request, next: Next) -> Response {
+    let wants_problem_json = accepts_problem_json(request.headers());
+    
+    let response = next.run(request).await;
+    
+    let (mut parts, body) = response.into_parts();
+    let problem = parts.extensions.remove::<ProblemDetails>();
+    
+    match problem {
+        Some(problem) if wants_problem_json => {
+            let Ok(document) = serde_json::to_vec(&problem) else {
+                return Response::from_parts(parts, body);
+            };
+            parts.headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+            );
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(document))
+        }
+        _ => Response::from_parts(parts, body),
+    }
+}
+
+/// Acceptヘッダーにproblem+jsonが含まれるか
+/// 
+/// パラメータ（`;q=0.9` 等）は無視し、メディアタイプだけを比較する
+fn accepts_problem_json(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| media_range.split(';').next())
+        .any(|media_type| media_type.trim().eq_ignore_ascii_case(PROBLEM_JSON_CONTENT_TYPE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::to_bytes,
+        http::StatusCode,
+        middleware,
+        routing::get,
+        Router,
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use crate::error::{AppError, DomainError};
+    
+    fn app() -> Router {
+        Router::new()
+            .route("/missing", get(|| async {
+                Err::<(), AppError>(DomainError::UserNotFound("octocat".to_string()).into())
+            }))
+            .route("/limited", get(|| async {
+                Err::<(), AppError>(DomainError::RateLimitExceeded { retry_after_secs: 42 }.into())
+            }))
+            .route("/ok", get(|| async { "ok" }))
+            .layer(middleware::from_fn(negotiate_problem_json))
+    }
+    
+    async fn send(uri: &str, accept: Option<&str>) -> (StatusCode, HeaderMap, Value) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, headers, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+    
+    #[tokio::test]
+    async fn test_not_found_representations() {
+        let (status, headers, body) = send("/missing", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+        assert_eq!(body, json!({
+            "error": {
+                "message": "ユーザーが見つかりません: octocat",
+                "code": 404,
+                "error_code": "USER_NOT_FOUND",
+            }
+        }));
+        
+        let (status, headers, body) = send("/missing", Some("application/problem+json")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(headers[header::CONTENT_TYPE], PROBLEM_JSON_CONTENT_TYPE);
+        assert_eq!(body, json!({
+            "type": "urn:gitpoke:problem:not-found",
+            "title": "リソースが見つかりません",
+            "status": 404,
+            "detail": "ユーザーが見つかりません: octocat",
+            "code": "USER_NOT_FOUND",
+        }));
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limited_representations() {
+        let (status, headers, body) = send("/limited", Some("application/json")).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers[header::RETRY_AFTER], "42");
+        assert_eq!(body["error"]["code"], 429);
+        
+        // 他のメディアタイプと併記されていても選択される
+        let (status, headers, body) = send(
+            "/limited",
+            Some("application/json;q=0.5, application/problem+json"),
+        ).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers[header::CONTENT_TYPE], PROBLEM_JSON_CONTENT_TYPE);
+        assert_eq!(headers[header::RETRY_AFTER], "42");
+        assert_eq!(body["type"], "urn:gitpoke:problem:rate-limited");
+        assert_eq!(body["status"], 429);
+    }
+    
+    #[tokio::test]
+    async fn test_success_is_untouched() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/ok")
+                    .header(header::ACCEPT, PROBLEM_JSON_CONTENT_TYPE)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"ok");
+    }
+}
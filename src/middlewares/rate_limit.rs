@@ -0,0 +1,202 @@
+//! IPベースのレート制限
+//! 
+//! このファイルは以下を定義：
+//! - クライアントIPごとに1分あたりのリクエスト数を制限するtowerの `Layer`
+//! - 制限を適用する `Service`
+//! 
+//! ハンドラーを通らずに制限できるため、バッジのような
+//! 認証不要で大量に呼ばれるルートに個別に適用する
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use tower::{Layer, Service};
+
+use crate::app::dependencies::RateLimiter;
+use crate::error::{AppError, AppResult, DomainError};
+use crate::handlers::utils::get_rate_limit_ip;
+
+/// レート制限のウィンドウ（秒）
+const WINDOW_SECONDS: u64 = 60;
+
+/// IPベースのレート制限レイヤー
+#[derive(Clone)]
+pub struct IpRateLimitLayer {
+    rate_limiter: Arc<dyn RateLimiter>,
+    scope: &'static str,
+    limit_per_minute: u32,
+    trust_forwarded_headers: bool,
+}
+
+impl IpRateLimitLayer {
+    /// レイヤーを作成
+    /// 
+    /// # Arguments
+    /// * `rate_limiter` - レート制限サービス
+    /// * `scope` - レート制限キーの区分（"badge" 等）
+    /// * `limit_per_minute` - IPアドレスごとの1分あたりの上限
+    /// * `trust_forwarded_headers` - プロキシが追記したX-Forwarded-Forを信頼するか
+    pub fn new(
+        rate_limiter: Arc<dyn RateLimiter>,
+        scope: &'static str,
+        limit_per_minute: u32,
+        trust_forwarded_headers: bool,
+    ) -> Self {
+        Self {
+            rate_limiter,
+            scope,
+            limit_per_minute,
+            trust_forwarded_headers,
+        }
+    }
+}
+
+impl<S> Layer<S> for IpRateLimitLayer {
+    type Service = IpRateLimit<S>;
+    
+    fn layer(&self, inner: S) -> Self::Service {
+        IpRateLimit {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// IPベースのレート制限を適用するサービス
+/// 
+/// 上限を超えたリクエストは内側のサービスを呼ばずに
+/// 429 Too Many Requests（Retry-After付き）を返す
+#[derive(Clone)]
+pub struct IpRateLimit<S> {
+    inner: S,
+    layer: IpRateLimitLayer,
+}
+
+impl<S> Service<Request> for IpRateLimit<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+    
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+    
+    fn call(&mut self, request: Request) -> Self::Future {
+        // poll_readyで準備済みのサービスを使い、代わりにクローンを残す
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+        
+        Box::pin(async move {
+            let client_ip = get_rate_limit_ip(request.headers(), layer.trust_forwarded_headers);
+            
+            match consume(&layer, &client_ip).await {
+                Ok(()) => inner.call(request).await,
+                Err(error @ AppError::Domain(DomainError::RateLimitExceeded { .. })) => {
+                    Ok(error.into_response())
+                }
+                Err(error) => {
+                    // レート制限の障害でバッジが表示されなくならないよう、制限せずに通す
+                    tracing::warn!(
+                        "Rate limiter unavailable for {}, allowing request: {}",
+                        layer.scope,
+                        error
+                    );
+                    inner.call(request).await
+                }
+            }
+        })
+    }
+}
+
+/// クライアントIPの枠を1回分消費
+/// 
+/// # Returns
+/// * `Ok(())` - 制限内
+/// * `Err(DomainError::RateLimitExceeded)` - 制限超過（再試行までの秒数付き）
+async fn consume(layer: &IpRateLimitLayer, client_ip: &str) -> AppResult<()> {
+    let key = format!("rate_limit:{}:ip:{}", layer.scope, client_ip);
+    
+    if !layer.rate_limiter.check_limit(&key, layer.limit_per_minute, WINDOW_SECONDS).await? {
+        let retry_after_secs = layer.rate_limiter.retry_after(&key, WINDOW_SECONDS).await?;
+        return Err(DomainError::RateLimitExceeded { retry_after_secs }.into());
+    }
+    
+    layer.rate_limiter.increment(&key, WINDOW_SECONDS).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, StatusCode},
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+    use crate::app::dependencies::mocks::MockRateLimiter;
+    
+    fn app(limit: u32) -> Router {
+        let layer = IpRateLimitLayer::new(
+            Arc::new(MockRateLimiter::default()),
+            "badge",
+            limit,
+            true,
+        );
+        
+        Router::new()
+            .route("/badge/:filename", get(|| async { "<svg/>" }))
+            .route_layer(layer)
+    }
+    
+    async fn send(app: &Router, ip: &str) -> Response {
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/badge/octocat.svg")
+                    .header("x-forwarded-for", ip)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_burst_from_one_ip_is_throttled() {
+        let app = app(3);
+        
+        for _ in 0..3 {
+            assert_eq!(send(&app, "203.0.113.1").await.status(), StatusCode::OK);
+        }
+        
+        let response = send(&app, "203.0.113.1").await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], WINDOW_SECONDS.to_string());
+    }
+    
+    #[tokio::test]
+    async fn test_other_ip_is_unaffected() {
+        let app = app(1);
+        
+        assert_eq!(send(&app, "203.0.113.1").await.status(), StatusCode::OK);
+        assert_eq!(send(&app, "203.0.113.1").await.status(), StatusCode::TOO_MANY_REQUESTS);
+        
+        // 別のIPは独立して数えられる
+        assert_eq!(send(&app, "198.51.100.7").await.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,134 @@
+//! リクエストID
+//! 
+//! このファイルは以下を定義：
+//! - リクエストごとのID（`X-Request-Id`）を付与するミドルウェア
+//! - ハンドラーから参照するための `RequestId` 拡張
+//! 
+//! クライアントやロードバランサーが付けたIDはそのまま引き継ぎ、
+//! ないか不正な場合は新しく生成する
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+
+use crate::handlers::utils::generate_request_id;
+
+/// リクエストIDのヘッダー名
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 引き継ぐリクエストIDの最大長
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// リクエストID
+/// 
+/// リクエストの拡張に保存されるため、ハンドラーでは `Extension<RequestId>` で取得できる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// リクエストIDを付与する
+/// 
+/// リクエストの拡張とトレーシングのスパンに保存し、レスポンスヘッダーにも同じIDを設定する
+pub async fn propagate_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(generate_request_id);
+    
+    request.extensions_mut().insert(RequestId(request_id.clone()));
+    
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let mut response = next.run(request).instrument(span).await;
+    
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    
+    response
+}
+
+/// 引き継いでよいリクエストIDか
+/// 
+/// ログに出力するため、長すぎるものや表示可能なASCII以外を含むものは使わない
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LENGTH
+        && value.bytes().all(|byte| byte.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        middleware,
+        routing::get,
+        Extension, Router,
+    };
+    use tower::ServiceExt;
+    
+    /// ハンドラーが受け取ったリクエストIDをボディで返す
+    fn app() -> Router {
+        Router::new()
+            .route("/echo", get(|Extension(RequestId(id)): Extension<RequestId>| async move { id }))
+            .layer(middleware::from_fn(propagate_request_id))
+    }
+    
+    async fn send(request_id: Option<&str>) -> (Option<String>, String) {
+        let mut request = Request::builder().uri("/echo");
+        if let Some(request_id) = request_id {
+            request = request.header(&REQUEST_ID_HEADER, request_id);
+        }
+        
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let header = response
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (header, String::from_utf8(body.to_vec()).unwrap())
+    }
+    
+    #[tokio::test]
+    async fn test_client_request_id_is_preserved() {
+        let (header, seen_by_handler) = send(Some("lb-7f3a9c")).await;
+        
+        assert_eq!(header.as_deref(), Some("lb-7f3a9c"));
+        assert_eq!(seen_by_handler, "lb-7f3a9c");
+    }
+    
+    #[tokio::test]
+    async fn test_missing_request_id_is_generated() {
+        let (header, seen_by_handler) = send(None).await;
+        
+        let header = header.expect("X-Request-Id should be set");
+        assert!(uuid::Uuid::parse_str(&header).is_ok(), "{}", header);
+        assert_eq!(seen_by_handler, header);
+        
+        // リクエストごとに異なるIDになる
+        let (other, _) = send(None).await;
+        assert_ne!(other.unwrap(), header);
+    }
+    
+    #[tokio::test]
+    async fn test_invalid_request_id_is_replaced() {
+        let too_long = "a".repeat(MAX_REQUEST_ID_LENGTH + 1);
+        
+        for invalid in ["", "has space", too_long.as_str()] {
+            let (header, _) = send(Some(invalid)).await;
+            
+            let header = header.unwrap();
+            assert_ne!(header, invalid);
+            assert!(uuid::Uuid::parse_str(&header).is_ok());
+        }
+    }
+}
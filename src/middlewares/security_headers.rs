@@ -0,0 +1,157 @@
+//! セキュリティヘッダー
+//! 
+//! このファイルは以下を定義：
+//! - すべてのレスポンスにブラウザ向けのセキュリティヘッダーを付与するミドルウェア
+//! 
+//! バッジは他サイト（GitHubのREADME等）に埋め込まれるため、
+//! フレーム埋め込みの禁止は適用しない
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+/// バッジルートのプレフィックス
+const BADGE_PATH_PREFIX: &str = "/badge/";
+
+/// HTML/JSONレスポンスに付与するCSP
+/// 
+/// APIはスクリプトや外部リソースを一切読み込まないため、すべて禁止する
+const API_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; frame-ancestors 'none'";
+
+/// バッジのJSONレスポンスに付与するCSP（埋め込みは許可）
+const BADGE_CONTENT_SECURITY_POLICY: &str = "default-src 'none'";
+
+/// セキュリティヘッダーを付与する
+/// 
+/// ハンドラーが同じヘッダーを設定済みの場合はそちらを優先する
+pub async fn set_security_headers(request: Request, next: Next) -> Response {
+    let is_badge = request.uri().path().starts_with(BADGE_PATH_PREFIX);
+    
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    
+    insert_if_absent(headers, header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    insert_if_absent(headers, header::REFERRER_POLICY, "no-referrer");
+    
+    if !is_badge {
+        insert_if_absent(headers, header::X_FRAME_OPTIONS, "DENY");
+    }
+    
+    if is_html_or_json(headers) {
+        let policy = if is_badge {
+            BADGE_CONTENT_SECURITY_POLICY
+        } else {
+            API_CONTENT_SECURITY_POLICY
+        };
+        insert_if_absent(headers, header::CONTENT_SECURITY_POLICY, policy);
+    }
+    
+    response
+}
+
+/// ヘッダーが未設定の場合のみ設定
+fn insert_if_absent(headers: &mut HeaderMap, name: HeaderName, value: &'static str) {
+    headers.entry(name).or_insert(HeaderValue::from_static(value));
+}
+
+/// Content-TypeがHTMLまたはJSON（problem+json等を含む）か
+fn is_html_or_json(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    
+    media_type == "text/html" || media_type == "application/json" || media_type.ends_with("+json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        middleware,
+        response::IntoResponse,
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+    
+    fn app() -> Router {
+        Router::new()
+            .route("/api/user/me", get(|| async { Json(json!({ "username": "octocat" })) }))
+            .route("/badge/:filename", get(|| async {
+                (
+                    [
+                        (header::CONTENT_TYPE, "image/svg+xml"),
+                        (header::ACCESS_CONTROL_ALLOW_ORIGIN, "https://github.com"),
+                    ],
+                    "<svg/>",
+                ).into_response()
+            }))
+            .layer(middleware::from_fn(set_security_headers))
+    }
+    
+    async fn get_headers(uri: &str) -> HeaderMap {
+        app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .headers()
+            .clone()
+    }
+    
+    #[tokio::test]
+    async fn test_api_response_is_hardened() {
+        let headers = get_headers("/api/user/me").await;
+        
+        assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+        assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[header::CONTENT_SECURITY_POLICY], API_CONTENT_SECURITY_POLICY);
+    }
+    
+    #[tokio::test]
+    async fn test_badge_can_be_embedded() {
+        let headers = get_headers("/badge/octocat.svg").await;
+        
+        // フレーム埋め込みを禁止しない
+        assert!(headers.get(header::X_FRAME_OPTIONS).is_none());
+        assert!(headers.get(header::CONTENT_SECURITY_POLICY).is_none());
+        // ハンドラーのCORS設定はそのまま
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://github.com");
+        
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+    }
+    
+    #[test]
+    fn test_is_html_or_json() {
+        let cases = [
+            ("application/json", true),
+            ("application/problem+json", true),
+            ("text/html; charset=utf-8", true),
+            ("image/svg+xml", false),
+            ("text/plain", false),
+        ];
+        
+        for (content_type, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+            assert_eq!(is_html_or_json(&headers), expected, "{}", content_type);
+        }
+        assert!(!is_html_or_json(&HeaderMap::new()));
+    }
+}
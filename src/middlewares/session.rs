@@ -0,0 +1,435 @@
+//! ユーザーセッション
+//!
+//! このファイルは以下を定義：
+//! - キャッシュに保存するセッションデータの形式
+//! - セッションの作成と解決
+//! - GitHub側で認可を取り消されたセッションの無効化
+//!
+//! OAuthコールバック（作成側）と認証Extractor（参照側）の両方がこの定義を使うため、
+//! 保存形式が食い違うことはない
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::app::dependencies::CacheService;
+use crate::domain::user::{RegisteredUser, Username};
+use crate::error::{AppResult, HandlerError, InfraError};
+use crate::infra::adapters::github::GitHubAdapter;
+use crate::middlewares::auth::AuthenticatedUser;
+
+/// セッションの有効期間（秒）
+pub const SESSION_TTL_SECONDS: u64 = 7 * 24 * 60 * 60; // 7日間
+
+/// アクセストークンを再確認する間隔（秒）
+/// 
+/// リクエストごとにGitHubへ問い合わせないよう、確認結果をこの間だけ信用する
+pub const TOKEN_VERIFY_INTERVAL_SECONDS: i64 = 60 * 60; // 1時間
+
+/// セッションIDを保持するCookie名
+pub const SESSION_COOKIE_NAME: &str = "gitpoke_session";
+
+/// キャッシュに保存するセッションデータ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionData {
+    /// GitHubユーザー名
+    pub username: String,
+    
+    /// GitHub ID
+    pub github_id: i64,
+    
+    /// セッション作成日時
+    pub created_at: DateTime<Utc>,
+    
+    /// OAuthで取得したアクセストークン
+    /// 
+    /// 認可の取り消しを検知するために使う。トークンを持たない古いセッションは確認しない
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    
+    /// アクセストークンを最後に確認した日時
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_verified_at: Option<DateTime<Utc>>,
+}
+
+/// セッションのキャッシュキーを生成
+pub fn session_key(session_id: &str) -> String {
+    format!("session:{}", session_id)
+}
+
+/// セッションを設定するSet-Cookieヘッダーの値を生成
+pub fn session_cookie(session_id: &str) -> String {
+    format!(
+        "{}={}; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age={}",
+        SESSION_COOKIE_NAME, session_id, SESSION_TTL_SECONDS
+    )
+}
+
+/// セッションを削除するSet-Cookieヘッダーの値を生成
+/// 
+/// 属性を設定時と揃えないとブラウザが別のCookieとして扱うため、Path等は同じにする
+pub fn clear_session_cookie() -> String {
+    format!(
+        "{}=; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=0",
+        SESSION_COOKIE_NAME
+    )
+}
+
+/// セッションを作成
+///
+/// # Arguments
+/// * `cache` - セッションを保存するキャッシュ
+/// * `user` - ログインしたユーザー
+/// * `access_token` - OAuthで取得したアクセストークン（取得直後のため確認済みとして扱う）
+///
+/// # Returns
+/// * `Ok(String)` - 新しいセッションID（UUID）
+/// * `Err(AppError)` - 保存失敗
+pub async fn create_session(
+    cache: &dyn CacheService,
+    user: &RegisteredUser,
+    access_token: Option<&str>,
+) -> AppResult<String> {
+    let session_id = Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let session = SessionData {
+        username: user.username.as_str().to_string(),
+        github_id: user.github_id.value(),
+        created_at: now,
+        access_token: access_token.map(str::to_string),
+        token_verified_at: access_token.map(|_| now),
+    };
+    
+    save_session(cache, &session_id, &session, SESSION_TTL_SECONDS).await?;
+    
+    Ok(session_id)
+}
+
+/// セッションデータを保存
+async fn save_session(
+    cache: &dyn CacheService,
+    session_id: &str,
+    session: &SessionData,
+    ttl_seconds: u64,
+) -> AppResult<()> {
+    let session_json = serde_json::to_string(session).map_err(InfraError::from)?;
+    cache.set(&session_key(session_id), &session_json, ttl_seconds).await
+}
+
+/// セッションを削除
+/// 
+/// 存在しないセッションを指定してもエラーにはならない
+pub async fn delete_session(cache: &dyn CacheService, session_id: &str) -> AppResult<()> {
+    cache.delete(&session_key(session_id)).await
+}
+
+/// セッションに保存したアクセストークンを取得
+/// 
+/// # Returns
+/// * `Ok(Some(token))` - トークンを保存したセッション
+/// * `Ok(None)` - セッションが存在しない・トークンを持たない
+pub async fn session_access_token(
+    cache: &dyn CacheService,
+    session_id: &str,
+) -> AppResult<Option<String>> {
+    let Some(session_data) = cache.get(&session_key(session_id)).await? else {
+        return Ok(None);
+    };
+    
+    Ok(serde_json::from_str::<SessionData>(&session_data)
+        .ok()
+        .and_then(|session| session.access_token))
+}
+
+/// セッションIDから認証済みユーザーを解決
+///
+/// アクセストークンの確認から `TOKEN_VERIFY_INTERVAL_SECONDS` 以上経っていれば再確認し、
+/// 認可が取り消されていたらセッションを削除する
+///
+/// # Arguments
+/// * `cache` - セッションを保存しているキャッシュ
+/// * `token_verifier` - アクセストークンの確認に使うGitHubアダプター
+/// * `session_id` - CookieのセッションID
+///
+/// # Returns
+/// * `Ok(AuthenticatedUser)` - 有効なセッション
+/// * `Err(HandlerError::Unauthorized)` - 存在しない・期限切れ・不正・認可を取り消されたセッション
+/// * `Err(AppError)` - キャッシュの参照に失敗
+pub async fn authenticate_session(
+    cache: &dyn CacheService,
+    token_verifier: &dyn GitHubAdapter,
+    session_id: &str,
+) -> AppResult<AuthenticatedUser> {
+    // 期限切れのセッションはキャッシュから消えているため、存在しない場合と同じ扱い
+    let session_data = cache
+        .get(&session_key(session_id))
+        .await?
+        .ok_or(HandlerError::Unauthorized)?;
+    
+    let session: SessionData =
+        serde_json::from_str(&session_data).map_err(|_| HandlerError::Unauthorized)?;
+    
+    let username = Username::new(session.username.clone()).map_err(|_| HandlerError::Unauthorized)?;
+    
+    verify_session_token(cache, token_verifier, session_id, session).await?;
+    
+    Ok(AuthenticatedUser {
+        username,
+        session_id: session_id.to_string(),
+    })
+}
+
+/// セッションのアクセストークンが取り消されていないか確認
+/// 
+/// GitHubの障害で確認できない場合はログインしたままにし、次のリクエストで再確認する
+/// 
+/// # Returns
+/// * `Ok(())` - 有効、または確認不要・確認できなかった
+/// * `Err(HandlerError::Unauthorized)` - 取り消されていたため、セッションを削除した
+async fn verify_session_token(
+    cache: &dyn CacheService,
+    token_verifier: &dyn GitHubAdapter,
+    session_id: &str,
+    mut session: SessionData,
+) -> AppResult<()> {
+    let Some(access_token) = session.access_token.as_deref() else {
+        return Ok(());
+    };
+    
+    let now = Utc::now();
+    let verified_recently = session.token_verified_at.is_some_and(|verified_at| {
+        now - verified_at < Duration::seconds(TOKEN_VERIFY_INTERVAL_SECONDS)
+    });
+    if verified_recently {
+        return Ok(());
+    }
+    
+    match token_verifier.verify_token(access_token).await {
+        Ok(true) => {
+            // 作成時の有効期限を延ばさないよう、残りのTTLで保存し直す
+            let elapsed = (now - session.created_at).num_seconds().max(0) as u64;
+            let remaining_ttl = SESSION_TTL_SECONDS.saturating_sub(elapsed);
+            if remaining_ttl > 0 {
+                session.token_verified_at = Some(now);
+                save_session(cache, session_id, &session, remaining_ttl).await?;
+            }
+            Ok(())
+        }
+        Ok(false) => {
+            tracing::info!("認可が取り消されたため、{}のセッションを削除します", session.username);
+            delete_session(cache, session_id).await?;
+            Err(HandlerError::Unauthorized.into())
+        }
+        Err(e) => {
+            tracing::warn!("アクセストークンを確認できませんでした: {}", e);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use crate::app::dependencies::mocks::MockCacheService;
+    use crate::domain::user::GitHubUserId;
+    use crate::error::AppError;
+    use crate::infra::adapters::github::GitHubUser;
+    
+    /// 決まった確認結果を返し、呼び出し回数を数えるトークン確認モック
+    struct TokenVerifier {
+        /// `None` の場合はGitHubの障害としてエラーを返す
+        result: Option<bool>,
+        calls: AtomicUsize,
+    }
+    
+    impl TokenVerifier {
+        fn new(result: Option<bool>) -> Self {
+            Self { result, calls: AtomicUsize::new(0) }
+        }
+        
+        fn valid() -> Self {
+            Self::new(Some(true))
+        }
+    }
+    
+    #[async_trait]
+    impl GitHubAdapter for TokenVerifier {
+        async fn get_user(&self, _access_token: &str) -> AppResult<GitHubUser> {
+            unimplemented!()
+        }
+        
+        async fn get_user_by_username(&self, _username: &str) -> AppResult<GitHubUser> {
+            unimplemented!()
+        }
+        
+        async fn verify_token(&self, _access_token: &str) -> AppResult<bool> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.result
+                .ok_or_else(|| InfraError::GitHubResponse("HTTP 502".to_string()).into())
+        }
+    }
+    
+    /// 最後にトークンを確認してから指定した秒数が経ったセッションを保存
+    async fn session_verified_ago(cache: &MockCacheService, verified_secs_ago: i64) -> String {
+        let session_id = create_session(cache, &test_user(), Some("gho_token")).await.unwrap();
+        let key = session_key(&session_id);
+        let mut session: SessionData =
+            serde_json::from_str(&cache.get(&key).await.unwrap().unwrap()).unwrap();
+        session.token_verified_at = Some(Utc::now() - Duration::seconds(verified_secs_ago));
+        save_session(cache, &session_id, &session, SESSION_TTL_SECONDS).await.unwrap();
+        session_id
+    }
+    
+    fn test_user() -> RegisteredUser {
+        RegisteredUser::new(
+            GitHubUserId::new(583231),
+            Username::new("octocat".to_string()).unwrap(),
+        )
+    }
+    
+    #[tokio::test]
+    async fn test_created_session_is_resolvable() {
+        let cache = MockCacheService::new();
+        let session_id = create_session(&cache, &test_user(), None).await.unwrap();
+        
+        assert!(Uuid::parse_str(&session_id).is_ok());
+        
+        let user = authenticate_session(&cache, &TokenVerifier::valid(), &session_id).await.unwrap();
+        assert_eq!(user.username.as_str(), "octocat");
+        assert_eq!(user.session_id, session_id);
+    }
+    
+    #[tokio::test]
+    async fn test_session_json_shape() {
+        let cache = MockCacheService::new();
+        let session_id = create_session(&cache, &test_user(), None).await.unwrap();
+        
+        let key = session_key(&session_id);
+        let json = cache.get(&key).await.unwrap().unwrap();
+        
+        // Extractorが読むフィールドと一致している
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["username"], "octocat");
+        assert_eq!(value["github_id"], 583231);
+        assert!(value["created_at"].is_string());
+        assert_eq!(cache.ttl(&key), Some(7 * 24 * 60 * 60));
+    }
+    
+    #[tokio::test]
+    async fn test_missing_session_is_unauthorized() {
+        let cache = MockCacheService::new();
+        
+        let result = authenticate_session(&cache, &TokenVerifier::valid(), "missing").await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_expired_session_is_unauthorized() {
+        let cache = MockCacheService::new();
+        let session_id = create_session(&cache, &test_user(), None).await.unwrap();
+        
+        // TTL切れでキャッシュから消えた状態
+        cache.delete(&session_key(&session_id)).await.unwrap();
+        
+        let result = authenticate_session(&cache, &TokenVerifier::valid(), &session_id).await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_deleted_session_is_unauthorized() {
+        let cache = MockCacheService::new();
+        let session_id = create_session(&cache, &test_user(), None).await.unwrap();
+        
+        delete_session(&cache, &session_id).await.unwrap();
+        
+        let result = authenticate_session(&cache, &TokenVerifier::valid(), &session_id).await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+        
+        // 二重に削除してもエラーにならない
+        delete_session(&cache, &session_id).await.unwrap();
+    }
+    
+    #[test]
+    fn test_session_cookies() {
+        assert_eq!(
+            session_cookie("abc"),
+            "gitpoke_session=abc; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=604800"
+        );
+        assert_eq!(
+            clear_session_cookie(),
+            "gitpoke_session=; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=0"
+        );
+    }
+    
+    #[tokio::test]
+    async fn test_malformed_session_is_unauthorized() {
+        let cache = MockCacheService::new();
+        cache.set(&session_key("broken"), "not json", 60).await.unwrap();
+        
+        let result = authenticate_session(&cache, &TokenVerifier::valid(), "broken").await;
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+    }
+    
+    #[tokio::test]
+    async fn test_recently_verified_token_is_not_rechecked() {
+        let cache = MockCacheService::new();
+        let session_id = session_verified_ago(&cache, 60).await;
+        let verifier = TokenVerifier::new(Some(false));
+        
+        assert!(authenticate_session(&cache, &verifier, &session_id).await.is_ok());
+        assert_eq!(verifier.calls.load(Ordering::SeqCst), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_valid_token_is_rechecked_after_interval() {
+        let cache = MockCacheService::new();
+        let session_id = session_verified_ago(&cache, TOKEN_VERIFY_INTERVAL_SECONDS).await;
+        let verifier = TokenVerifier::valid();
+        
+        assert!(authenticate_session(&cache, &verifier, &session_id).await.is_ok());
+        assert!(authenticate_session(&cache, &verifier, &session_id).await.is_ok());
+        
+        // 確認結果を保存し、次のリクエストでは問い合わせない
+        assert_eq!(verifier.calls.load(Ordering::SeqCst), 1);
+        let key = session_key(&session_id);
+        let session: SessionData =
+            serde_json::from_str(&cache.get(&key).await.unwrap().unwrap()).unwrap();
+        assert!(Utc::now() - session.token_verified_at.unwrap() < Duration::seconds(60));
+        assert!(cache.ttl(&key).unwrap() <= SESSION_TTL_SECONDS);
+    }
+    
+    #[tokio::test]
+    async fn test_revoked_token_invalidates_session() {
+        let cache = MockCacheService::new();
+        let session_id = session_verified_ago(&cache, TOKEN_VERIFY_INTERVAL_SECONDS).await;
+        
+        let result = authenticate_session(&cache, &TokenVerifier::new(Some(false)), &session_id).await;
+        
+        assert!(matches!(result, Err(AppError::Handler(HandlerError::Unauthorized))));
+        assert_eq!(cache.get(&session_key(&session_id)).await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_verification_failure_keeps_session() {
+        let cache = MockCacheService::new();
+        let session_id = session_verified_ago(&cache, TOKEN_VERIFY_INTERVAL_SECONDS).await;
+        let verifier = TokenVerifier::new(None);
+        
+        // GitHubの障害ではログアウトさせず、次のリクエストで再確認する
+        assert!(authenticate_session(&cache, &verifier, &session_id).await.is_ok());
+        assert!(authenticate_session(&cache, &verifier, &session_id).await.is_ok());
+        assert_eq!(verifier.calls.load(Ordering::SeqCst), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_session_without_token_is_not_checked() {
+        let cache = MockCacheService::new();
+        let session_id = create_session(&cache, &test_user(), None).await.unwrap();
+        let verifier = TokenVerifier::new(Some(false));
+        
+        assert!(authenticate_session(&cache, &verifier, &session_id).await.is_ok());
+        assert_eq!(verifier.calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,85 @@
+//! リクエストタイムアウト
+//! 
+//! このファイルは以下を定義：
+//! - 設定した時間内に終わらないリクエストを打ち切るミドルウェア
+//! 
+//! 打ち切ったリクエストは他のエラーと同じ形式で 408 Request Timeout を返す
+
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::{AppError, HandlerError};
+
+/// リクエストにタイムアウトを適用する
+/// 
+/// `middleware::from_fn_with_state` でタイムアウト時間を渡して使う
+/// 
+/// # Arguments
+/// * `timeout` - リクエストの最大処理時間
+pub async fn enforce_timeout(
+    State(timeout): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("リクエストが{}秒以内に完了しませんでした", timeout.as_secs_f64());
+            AppError::from(HandlerError::Timeout).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        http::StatusCode,
+        middleware,
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+    
+    /// 指定した秒数かかるハンドラーにタイムアウトを適用する
+    fn app(handler_secs: u64, timeout_secs: u64) -> Router {
+        Router::new()
+            .route("/slow", get(move || async move {
+                tokio::time::sleep(Duration::from_secs(handler_secs)).await;
+                "done"
+            }))
+            .layer(middleware::from_fn_with_state(
+                Duration::from_secs(timeout_secs),
+                enforce_timeout,
+            ))
+    }
+    
+    async fn send(app: Router) -> Response {
+        app.oneshot(Request::builder().uri("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_handler_times_out() {
+        let response = send(app(31, 30)).await;
+        
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["error_code"], "TIMEOUT");
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_configured_timeout_is_used() {
+        // 同じハンドラーでも設定したタイムアウトに従う
+        assert_eq!(send(app(5, 10)).await.status(), StatusCode::OK);
+        assert_eq!(send(app(5, 3)).await.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+}
@@ -6,48 +6,118 @@
 //! - ハンドラーへのマッピング
 
 use axum::{
-    routing::{get, post},
+    middleware,
+    routing::{delete, get, post, put},
     Router,
 };
 use tower_http::{
-    cors::{CorsLayer, Any},
+    cors::CorsLayer,
     trace::TraceLayer,
-    timeout::TimeoutLayer,
 };
 use std::time::Duration;
 
+use crate::app::config::AppConfig;
 use crate::app::dependencies::AppDependencies;
-use crate::handlers::{auth, badge, health, poke, user};
+use crate::handlers::{activity, admin, auth, badge, health, poke, user, webhook};
+use crate::middlewares::metrics::track_metrics;
+use crate::middlewares::problem_json::negotiate_problem_json;
+use crate::middlewares::rate_limit::IpRateLimitLayer;
+use crate::middlewares::request_id::propagate_request_id;
+use crate::middlewares::security_headers::set_security_headers;
+use crate::middlewares::timeout::enforce_timeout;
 
 /// アプリケーションのルートを作成
 /// 
 /// 以下のエンドポイントを定義：
 /// - GET  /health - ヘルスチェック
+/// - GET  /metrics - Prometheusメトリクス
+/// - GET  /badge/:username - バッジ生成（Acceptヘッダーまたは `?format=` で形式を選択）
 /// - GET  /badge/:username.svg - バッジ生成
+/// - GET  /badge/:username.json - shields.io endpoint用JSON
+/// - GET  /badge/org/:org.svg - 組織メンバーのアクティビティバッジ
 /// - POST /api/poke - Poke送信
+/// - POST /api/poke/batch - 複数の受信者への一括Poke送信
+/// - GET  /api/poke/preview - Poke可否のプレビュー
+/// - GET  /api/poke/history - Poke履歴
+/// - GET  /api/activity/:username - GitHubアクティビティ（JSON）
+/// - POST /api/webhooks/github - GitHub Webhook受信
 /// - GET  /api/auth/github - GitHub OAuth開始
 /// - GET  /api/auth/callback - GitHub OAuthコールバック
 /// - GET  /api/user/me - 現在のユーザー情報
+/// - GET  /api/user/:username - ユーザーの公開プロフィール（本人には非公開の項目も返す）
 /// - PUT  /api/user/settings - ユーザー設定更新
+/// - POST /api/user/blocked - 送信者をブロック
+/// - DELETE /api/user/blocked/:username - 送信者のブロックを解除
 /// - DELETE /api/user/me - アカウント削除
+/// - POST /api/admin/warm - バッジキャッシュのウォームアップ（共有シークレットが必要）
 /// 
 /// # Arguments
 /// * `deps` - アプリケーション依存性
 pub fn create_routes(deps: AppDependencies) -> Router {
-    // ヘルスチェックルート（依存性不要）
+    // ヘルスチェックルート（レディネスチェックのみ依存性を使用）
     let health_routes = Router::new()
         .route("/health", get(health::health_check))
-        .route("/ready", get(health::readiness_check));
+        .route("/ready", get(health::readiness_check))
+        .route("/metrics", get(health::metrics))
+        .with_state(deps.clone());
     
     // バッジ生成ルート
     let badge_routes = Router::new()
-        .route("/badge/:username.svg", get(badge::generate_badge))
+        // パスパラメータはセグメント全体に一致するため、拡張子はハンドラーで判定する
+        .route("/badge/:filename", get(badge::serve_badge))
+        .route("/badge/org/:filename", get(badge::serve_org_badge))
+        // IPアドレスごとのレート制限（バッジのみ）
+        .route_layer(IpRateLimitLayer::new(
+            deps.rate_limiter.clone(),
+            "badge",
+            deps.config.app.rate_limit.badge_per_ip_per_minute,
+            deps.config.app.environment.trusts_forwarded_headers(),
+        ))
+        // README表示をブロックしないよう、全体より短いタイムアウトを適用
+        .route_layer(middleware::from_fn_with_state(
+            Duration::from_secs(deps.config.app.badge_request_timeout_secs),
+            enforce_timeout,
+        ))
         .with_state(deps.clone());
     
     // API ルート（認証が必要な場合あり）
-    let api_routes = Router::new()
+    let api_routes = create_api_routes(deps.clone());
+    
+    // ルートを組み合わせる
+    let app = Router::new()
+        .merge(health_routes)
+        .merge(badge_routes)
+        .nest("/api", api_routes)
+        // リクエストメトリクス（マッチしたルートのみ記録）
+        .route_layer(middleware::from_fn(track_metrics))
+        // Acceptに応じてエラーをproblem+json形式に切り替える
+        .layer(middleware::from_fn(negotiate_problem_json))
+        // すべてのレスポンスにX-Request-Idを付与
+        .layer(middleware::from_fn(propagate_request_id));
+    
+    apply_middleware_stack(app, &deps.config.app)
+}
+
+/// `/api` 以下のルートを作成
+/// 
+/// 同じパスに複数のメソッドを登録する場合は、`.route` を重ねずに
+/// 1つのMethodRouter（`get(...).delete(...)`）にまとめる
+/// 
+/// # Arguments
+/// * `deps` - アプリケーション依存性
+fn create_api_routes(deps: AppDependencies) -> Router {
+    Router::new()
         // Poke機能
         .route("/poke", post(poke::send_poke))
+        .route("/poke/batch", post(poke::send_batch_poke))
+        .route("/poke/preview", get(poke::preview_poke))
+        .route("/poke/history", get(poke::get_poke_history))
+        
+        // アクティビティ
+        .route("/activity/:username", get(activity::get_activity))
+        
+        // GitHub Webhook
+        .route("/webhooks/github", post(webhook::github_webhook))
         
         // 認証
         .route("/auth/github", get(auth::github_oauth_start))
@@ -55,38 +125,48 @@ pub fn create_routes(deps: AppDependencies) -> Router {
         .route("/auth/logout", post(auth::logout))
         
         // ユーザー管理
-        .route("/user/me", get(user::get_current_user))
+        .route("/user/me", get(user::get_current_user).delete(user::delete_account))
         .route("/user/settings", put(user::update_settings))
-        .route("/user/me", delete(user::delete_account))
+        .route("/user/blocked", post(user::block_sender))
+        .route("/user/blocked/:username", delete(user::unblock_sender))
+        .route("/user/:username", get(user::get_user_profile))
         
-        .with_state(deps.clone());
-    
-    // ルートを組み合わせる
-    let app = Router::new()
-        .merge(health_routes)
-        .merge(badge_routes)
-        .nest("/api", api_routes)
-        .layer(create_middleware_stack());
-    
-    app
+        // 管理用
+        .route("/admin/warm", post(admin::warm_badges))
+        
+        .with_state(deps)
 }
 
-/// ミドルウェアスタックを作成
+/// 共通のミドルウェアスタックを適用
 /// 
 /// 以下のミドルウェアを適用（外側から順に）：
 /// 1. TraceLayer - リクエストのトレーシング
-/// 2. TimeoutLayer - リクエストタイムアウト（30秒）
-/// 3. CorsLayer - CORS設定
-fn create_middleware_stack() -> Router {
-    Router::new()
-        // トレーシング（ロギング）
-        .layer(TraceLayer::new_for_http())
+/// 2. CorsLayer - CORS設定
+/// 3. セキュリティヘッダー（バッジはフレーム埋め込みを許可）
+/// 4. タイムアウト - `request_timeout_secs` を超えたら408を返す
+/// 
+/// タイムアウトの408にもCORS・セキュリティヘッダーが付くよう、タイムアウトを最も内側にする。
+/// `Router::layer` は後から追加したものほど外側になるため、内側から順に適用する
+/// 
+/// # Arguments
+/// * `app` - ミドルウェアを適用するルーター
+/// * `config` - アプリケーション動作設定
+fn apply_middleware_stack(app: Router, config: &AppConfig) -> Router {
+    app
+        // タイムアウト
+        .layer(middleware::from_fn_with_state(
+            Duration::from_secs(config.request_timeout_secs),
+            enforce_timeout,
+        ))
         
-        // タイムアウト（30秒）
-        .layer(TimeoutLayer::new(Duration::from_secs(30)))
+        // セキュリティヘッダー
+        .layer(middleware::from_fn(set_security_headers))
         
         // CORS設定
         .layer(create_cors_layer())
+        
+        // トレーシング（ロギング）
+        .layer(TraceLayer::new_for_http())
 }
 
 /// CORS設定を作成
@@ -116,10 +196,544 @@ fn create_cors_layer() -> CorsLayer {
             axum::http::Method::OPTIONS,
         ])
         // 許可するヘッダー
-        .allow_headers(Any)
+        // クレデンシャルを許可する場合はワイルドカードを使えないため、明示的に列挙する
+        .allow_headers([
+            axum::http::header::CONTENT_TYPE,
+            axum::http::header::AUTHORIZATION,
+        ])
         // クレデンシャル（Cookie）の送信を許可
         .allow_credentials(true)
         // プリフライトリクエストのキャッシュ時間（1時間）
         .max_age(Duration::from_secs(3600))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "png")]
+    use std::sync::Arc;
+    use axum::{
+        body::{to_bytes, Body},
+        extract::Request,
+        http::{header, Method, StatusCode},
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use crate::app::dependencies::mocks::TestDependenciesBuilder;
+    #[cfg(feature = "png")]
+    use crate::app::dependencies::mocks::MockGitHubApi;
+    use crate::domain::github::GitHubActivity;
+    use crate::domain::user::{GitHubUserId, RegisteredUser, Username};
+    use crate::middlewares::session::{create_session, SESSION_COOKIE_NAME};
+    
+    /// ミドルウェアスタックを適用したテスト用ルーター
+    fn app(config: &AppConfig) -> Router {
+        let routes = Router::new()
+            .route("/fast", get(|| async { "ok" }))
+            .route("/slow", get(|| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                "too late"
+            }));
+        
+        apply_middleware_stack(routes, config)
+    }
+    
+    async fn send(config: &AppConfig, uri: &str) -> axum::response::Response {
+        app(config)
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header(header::ORIGIN, "https://github.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_cors_headers_are_applied() {
+        let response = send(&AppConfig::default(), "/fast").await;
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://github.com"
+        );
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        // セキュリティヘッダーも付与される
+        assert_eq!(response.headers()[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_timeout_is_enforced() {
+        let config = AppConfig {
+            request_timeout_secs: 5,
+            ..AppConfig::default()
+        };
+        
+        let response = send(&config, "/slow").await;
+        
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+        // タイムアウト時のレスポンスにもCORS・セキュリティヘッダーが付く
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://github.com"
+        );
+        assert_eq!(response.headers()[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    }
+    
+    /// 今日活動したユーザーのアクティビティ
+    fn active_activity(username: &str) -> GitHubActivity {
+        GitHubActivity {
+            username: username.to_string(),
+            last_activity_at: Some(chrono::Utc::now()),
+            current_streak_days: Some(1),
+            contributions: None,
+            total_contributions: None,
+            tz_offset_seconds: None,
+            fetched_at: chrono::Utc::now(),
+        }
+    }
+    
+    /// 登録済みユーザー alice のセッションを持つ依存性
+    /// 
+    /// # Returns
+    /// * `(依存性, セッションCookie)`
+    async fn deps_with_session() -> (AppDependencies, String) {
+        let alice = RegisteredUser::new(
+            GitHubUserId::new(1),
+            Username::parse("alice".to_string()).unwrap(),
+        );
+        let deps = TestDependenciesBuilder::new()
+            .with_registered_user(alice.clone())
+            // 存在しないメンバーだけを持つ組織
+            .with_org_members("octo-org", &["ghost"])
+            .build();
+        let session_id = create_session(deps.cache_service.as_ref(), &alice, None).await.unwrap();
+        
+        (deps, format!("{}={}", SESSION_COOKIE_NAME, session_id))
+    }
+    
+    /// `/api` 以下のルートだけを持つアプリケーション
+    async fn app_with_session() -> (Router, AppDependencies, String) {
+        let (deps, cookie) = deps_with_session().await;
+        let app = Router::new().nest("/api", create_api_routes(deps.clone()));
+        (app, deps, cookie)
+    }
+    
+    /// リクエストを送り、ステータスとJSONボディを返す
+    async fn call(
+        app: &Router,
+        cookie: &str,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::COOKIE, cookie);
+        let body = match body {
+            Some(body) => {
+                request = request.header(header::CONTENT_TYPE, "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+        
+        let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+    
+    #[tokio::test]
+    async fn test_get_and_delete_user_me_share_path() {
+        let (app, deps, cookie) = app_with_session().await;
+        
+        let (status, body) = call(&app, &cookie, Method::GET, "/api/user/me", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["username"], "alice");
+        assert!(body["stats"].is_object());
+        
+        let (status, body) = call(&app, &cookie, Method::DELETE, "/api/user/me", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["message"], "アカウントを削除しました");
+        assert!(deps.user_repository.find_by_username("alice").await.unwrap().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_user_verbs_reach_their_handlers() {
+        let (app, _deps, cookie) = app_with_session().await;
+        
+        let (status, body) = call(
+            &app,
+            &cookie,
+            Method::PUT,
+            "/api/user/settings",
+            Some(json!({ "poke_setting": "MutualOnly" })),
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["poke_setting"], "MutualOnly");
+        assert_eq!(body["allow_interactive_badge"], true);
+        
+        // 指定しなかった設定はそのまま
+        let (status, body) = call(
+            &app,
+            &cookie,
+            Method::PUT,
+            "/api/user/settings",
+            Some(json!({ "allow_interactive_badge": false })),
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["poke_setting"], "MutualOnly");
+        assert_eq!(body["allow_interactive_badge"], false);
+        assert_eq!(body["notification_mode"], "Off");
+        
+        let (status, body) = call(
+            &app,
+            &cookie,
+            Method::PUT,
+            "/api/user/settings",
+            Some(json!({ "notification_mode": "DailyDigest" })),
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["notification_mode"], "DailyDigest");
+        assert_eq!(body["allow_interactive_badge"], false);
+        
+        let (status, body) = call(
+            &app,
+            &cookie,
+            Method::POST,
+            "/api/user/blocked",
+            Some(json!({ "username": "bob" })),
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["blocked_senders"], json!(["bob"]));
+        
+        let (status, body) = call(&app, &cookie, Method::DELETE, "/api/user/blocked/bob", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["blocked_senders"], json!([]));
+    }
+    
+    #[tokio::test]
+    async fn test_unregistered_verbs_are_rejected() {
+        let (app, _deps, cookie) = app_with_session().await;
+        
+        for (method, uri) in [
+            (Method::PATCH, "/api/user/me"),
+            (Method::GET, "/api/user/settings"),
+            (Method::GET, "/api/poke"),
+            (Method::DELETE, "/api/poke/history"),
+        ] {
+            let (status, _) = call(&app, &cookie, method.clone(), uri, None).await;
+            assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED, "{} {}", method, uri);
+        }
+    }
+    
+    /// 登録済みユーザー bob を追加し、そのセッションCookieを返す
+    async fn register_bob(deps: &AppDependencies) -> String {
+        let bob = RegisteredUser::new(
+            GitHubUserId::new(2),
+            Username::parse("bob".to_string()).unwrap(),
+        );
+        deps.user_repository.save(&bob).await.unwrap();
+        let session_id = create_session(deps.cache_service.as_ref(), &bob, None).await.unwrap();
+        format!("{}={}", SESSION_COOKIE_NAME, session_id)
+    }
+    
+    /// 公開プロフィールの項目だけを含むか
+    fn assert_public_profile(body: &Value) {
+        assert_eq!(body["username"], "alice");
+        assert_eq!(body["poke_setting"], "Anyone");
+        assert_eq!(body["badge_state"], "NotFound");
+        assert!(body.get("blocked_senders").is_none());
+        assert!(body.get("stats").is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_anonymous_profile_is_public_subset() {
+        let (app, _deps, _cookie) = app_with_session().await;
+        
+        let (status, body) = call(&app, "", Method::GET, "/api/user/alice", None).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_public_profile(&body);
+    }
+    
+    #[tokio::test]
+    async fn test_owner_profile_includes_private_fields() {
+        let (app, _deps, cookie) = app_with_session().await;
+        
+        // ユーザー名の大文字小文字は区別しない
+        let (status, body) = call(&app, &cookie, Method::GET, "/api/user/Alice", None).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["username"], "alice");
+        assert_eq!(body["blocked_senders"], json!([]));
+        assert_eq!(body["stats"]["pokes_received"], 0);
+    }
+    
+    #[tokio::test]
+    async fn test_other_user_sees_public_profile() {
+        let (app, deps, _cookie) = app_with_session().await;
+        let bob_cookie = register_bob(&deps).await;
+        
+        let (status, body) = call(&app, &bob_cookie, Method::GET, "/api/user/alice", None).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_public_profile(&body);
+    }
+    
+    #[tokio::test]
+    async fn test_unregistered_profile_is_not_found() {
+        let (app, _deps, cookie) = app_with_session().await;
+        
+        let (status, _) = call(&app, &cookie, Method::GET, "/api/user/carol", None).await;
+        
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+    
+    /// バッジルートにGETリクエストを送り、ステータス・Content-Type・ボディを返す
+    async fn get_badge(app: &Router, uri: &str) -> (StatusCode, Option<String>, String) {
+        get_badge_accepting(app, uri, None).await
+    }
+    
+    /// Acceptヘッダーを指定してバッジルートにGETリクエストを送る
+    async fn get_badge_accepting(
+        app: &Router,
+        uri: &str,
+        accept: Option<&str>,
+    ) -> (StatusCode, Option<String>, String) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|value| value.to_str().unwrap().to_string());
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, content_type, String::from_utf8_lossy(&bytes).to_string())
+    }
+    
+    #[tokio::test]
+    async fn test_badge_extension_selects_format() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        let (status, content_type, body) = get_badge(&app, "/badge/octocat.svg").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("image/svg+xml"));
+        assert!(body.contains("<svg"));
+        
+        let (status, content_type, body) = get_badge(&app, "/badge/octocat.json").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["schemaVersion"], 1);
+    }
+    
+    #[tokio::test]
+    async fn test_unsupported_badge_extension_is_not_found() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        let (status, _, _) = get_badge(&app, "/badge/octocat.gif").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        
+        let (status, _, _) = get_badge(&app, "/badge/octocat?format=gif").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+    
+    #[tokio::test]
+    async fn test_badge_without_extension_defaults_to_svg() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/badge/octocat").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/svg+xml");
+        // 同じURLでもAcceptによって形式が変わる
+        let vary: Vec<_> = response.headers().get_all(header::VARY).iter().collect();
+        assert!(vary.iter().any(|value| *value == "Accept"));
+    }
+    
+    #[tokio::test]
+    async fn test_shields_endpoint_for_active_user() {
+        let deps = TestDependenciesBuilder::new()
+            .with_activity(active_activity("octocat"))
+            .build();
+        let app = create_routes(deps);
+        
+        let (status, content_type, body) = get_badge(&app, "/badge/octocat.json").await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            json!({
+                "schemaVersion": 1,
+                "label": "gitpoke",
+                "message": "Active today",
+                "color": "brightgreen",
+            })
+        );
+    }
+    
+    #[tokio::test]
+    async fn test_shields_endpoint_for_unknown_user() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        let (status, _, body) = get_badge(&app, "/badge/octocat.json").await;
+        
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<Value>(&body).unwrap();
+        assert_eq!(body["message"], "User not found");
+        assert_eq!(body["color"], "lightgrey");
+    }
+    
+    #[tokio::test]
+    async fn test_interactive_and_static_badges_are_cached_separately() {
+        let octocat = RegisteredUser::new(
+            GitHubUserId::new(2),
+            Username::parse("octocat".to_string()).unwrap(),
+        );
+        let deps = TestDependenciesBuilder::new()
+            .with_registered_user(octocat)
+            .with_activity(GitHubActivity {
+                last_activity_at: Some(chrono::Utc::now() - chrono::Duration::days(60)),
+                ..active_activity("octocat")
+            })
+            .build();
+        let app = create_routes(deps);
+        
+        // インタラクティブバッジを先にキャッシュさせ、2回目はどちらもキャッシュから返す
+        for expected_cache in ["MISS", "HIT"] {
+            let (cache, interactive) = get_badge_cached(&app, "/badge/octocat?interactive=true").await;
+            assert_eq!(cache, expected_cache);
+            assert!(interactive.contains("fetch('/api/poke'"));
+            
+            // 同じ見た目でも静的バッジにはPokeのスクリプトを含めない
+            let (cache, plain) = get_badge_cached(&app, "/badge/octocat").await;
+            assert_eq!(cache, expected_cache);
+            assert!(!plain.contains("/api/poke"));
+            assert_ne!(interactive, plain);
+        }
+    }
+    
+    /// バッジルートにGETリクエストを送り、X-Cacheヘッダーとボディを返す
+    async fn get_badge_cached(app: &Router, uri: &str) -> (String, String) {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let cache = response.headers()["X-Cache"].to_str().unwrap().to_string();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (cache, String::from_utf8_lossy(&bytes).to_string())
+    }
+    
+    #[tokio::test]
+    async fn test_badge_accept_json_selects_shields_endpoint() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        let (status, content_type, body) =
+            get_badge_accepting(&app, "/badge/octocat", Some("application/json")).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["schemaVersion"], 1);
+    }
+    
+    #[tokio::test]
+    async fn test_badge_format_query_beats_accept_header() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        let (status, content_type, body) =
+            get_badge_accepting(&app, "/badge/octocat?format=json", Some("image/svg+xml")).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["schemaVersion"], 1);
+    }
+    
+    #[tokio::test]
+    async fn test_invalid_username_badge_reports_reason() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        for uri in ["/badge/-octocat.svg", "/badge/-octocat.json"] {
+            let (status, _, body) = get_badge(&app, uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+            assert_eq!(
+                serde_json::from_str::<Value>(&body).unwrap()["error"]["message"],
+                "無効なリクエスト: 無効な形式: usernameの形式が正しくありません",
+                "{}",
+                uri
+            );
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_org_badge_route() {
+        let (deps, _) = deps_with_session().await;
+        let app = create_routes(deps);
+        
+        // 存在しないメンバーは集計から除く
+        let (status, content_type, body) = get_badge(&app, "/badge/org/octo-org.svg").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("image/svg+xml"));
+        assert!(body.contains("0/0 active"));
+        
+        let (status, _, _) = get_badge(&app, "/badge/org/octo-org.json").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+    
+    #[cfg(feature = "png")]
+    #[tokio::test]
+    async fn test_png_badge_for_active_user() {
+        let (mut deps, _) = deps_with_session().await;
+        deps.github_api = Arc::new(MockGitHubApi::new().with_activity(active_activity("octocat")));
+        let app = create_routes(deps);
+        
+        let response = app
+            .oneshot(Request::builder().uri("/badge/octocat.png").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert!(response.headers()[header::CACHE_CONTROL].to_str().unwrap().contains("max-age="));
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+    
+    #[cfg(feature = "png")]
+    #[tokio::test]
+    async fn test_badge_accept_png_selects_png() {
+        let (mut deps, _) = deps_with_session().await;
+        deps.github_api = Arc::new(MockGitHubApi::new().with_activity(active_activity("octocat")));
+        let app = create_routes(deps);
+        
+        let (status, content_type, _) =
+            get_badge_accepting(&app, "/badge/octocat", Some("image/png")).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("image/png"));
+    }
+}
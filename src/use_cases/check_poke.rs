@@ -5,11 +5,13 @@
 //! - ビジネスルールの適用
 //! - Pokeイベントの生成
 
-use crate::app::dependencies::AppDependencies;
+use chrono::Duration;
+
+use crate::app::dependencies::{AppDependencies, CacheService, EventStore, GitHubApi, UserRepository};
 use crate::domain::user::{Username, UserState};
 use crate::domain::poke::{PokeCapability, PokeEvent, PokeResult};
 use crate::domain::github::FollowRelation;
-use crate::error::{AppResult, DomainError, PokeError};
+use crate::error::{AppResult, InfraError, PokeError};
 
 /// Poke可否チェックの実行結果
 pub struct CheckPokeResult {
@@ -32,6 +34,8 @@ pub struct CheckPokeResult {
 /// # Arguments
 /// * `sender` - Poke送信者
 /// * `recipient_username` - Poke受信者のユーザー名
+/// * `repository` - 送信元のリポジトリ（`owner/repo`、検証済み）
+/// * `message` - Pokeに添えるメッセージ（検証済み）
 /// * `deps` - アプリケーション依存性
 /// 
 /// # Returns
@@ -40,13 +44,16 @@ pub struct CheckPokeResult {
 pub async fn execute(
     sender: &Username,
     recipient_username: &Username,
+    repository: Option<String>,
+    message: Option<String>,
     deps: &AppDependencies,
 ) -> AppResult<PokeResult> {
     // 受信者の存在確認
-    let recipient_state = deps.user_repository
-        .find_by_username(recipient_username.as_str())
-        .await?
-        .ok_or_else(|| DomainError::UserNotFound(recipient_username.as_str().to_string()))?;
+    let recipient_state = find_recipient(
+        deps.user_repository.as_ref(),
+        deps.github_api.as_ref(),
+        recipient_username,
+    ).await?;
     
     // 登録済みユーザーでない場合はPoke不可
     let recipient = match &recipient_state {
@@ -56,10 +63,17 @@ pub async fn execute(
         }
     };
     
+    // 以降は登録時の表記を使う（イベントストアは大文字小文字を区別して検索するため）
+    let recipient_username = &recipient.username;
+    
     // フォロー関係の確認
-    let follow_relation = deps.github_api
-        .get_follow_relation(sender.as_str(), recipient_username.as_str())
-        .await?;
+    let follow_relation = get_follow_relation(
+        deps.github_api.as_ref(),
+        deps.cache_service.as_ref(),
+        sender,
+        recipient_username,
+        deps.config.app.cache.follow_relation_ttl,
+    ).await?;
     
     // Poke可能性をチェック（純粋関数）
     let capability = PokeCapability::check(sender, recipient, &follow_relation);
@@ -77,7 +91,9 @@ pub async fn execute(
     }
     
     // Pokeイベントを生成
-    let event = PokeEvent::new(sender.clone(), recipient_username.clone());
+    let mut event = PokeEvent::with_clock(sender.clone(), recipient_username.clone(), deps.clock.as_ref());
+    event.context = repository;
+    event.message = message;
     
     // イベントを保存
     deps.event_store.save_poke(&event).await?;
@@ -89,9 +105,99 @@ pub async fn execute(
     Ok(PokeResult::success(&event))
 }
 
+/// 受信者の状態を取得
+/// 
+/// GitPokeにレコードがない場合はGitHubに存在するかを確認し、
+/// 存在すれば未登録ユーザー（`UserState::Anonymous`）として扱う
+/// 
+/// # Arguments
+/// * `user_repository` - ユーザーリポジトリ
+/// * `github_api` - GitHub APIクライアント
+/// * `recipient_username` - 受信者のユーザー名
+/// 
+/// # Returns
+/// * `Ok(UserState)` - 受信者の状態
+/// * `Err(DomainError::UserNotFound)` - GitHubにも存在しない
+async fn find_recipient(
+    user_repository: &dyn UserRepository,
+    github_api: &dyn GitHubApi,
+    recipient_username: &Username,
+) -> AppResult<UserState> {
+    if let Some(state) = user_repository
+        .find_by_username(&recipient_username.normalized())
+        .await?
+    {
+        return Ok(state);
+    }
+    
+    // GitHubに存在しなければ UserNotFound がそのまま返る
+    github_api.get_user(recipient_username.as_str()).await?;
+    Ok(UserState::Anonymous(recipient_username.clone()))
+}
+
+/// フォロー関係のキャッシュキーを生成
+/// 
+/// 送信者から見た関係なので、送信者と受信者を入れ替えると別のキーになる
+pub fn follow_relation_cache_key(sender: &Username, recipient: &Username) -> String {
+    format!("follow:{}:{}", sender.normalized(), recipient.normalized())
+}
+
+/// ユーザーが関係するフォロー関係のキャッシュキーのパターン
+/// 
+/// 送信者・受信者のどちらとしてキャッシュされたエントリにも一致する
+pub fn follow_relation_cache_patterns(username: &Username) -> [String; 2] {
+    [
+        format!("follow:{}:*", username.normalized()),
+        format!("follow:*:{}", username.normalized()),
+    ]
+}
+
+/// フォロー関係を取得
+/// 
+/// 同じ相手への連続したPokeやプレビューのたびにGitHub APIを呼ばないよう、
+/// 結果を短時間キャッシュする
+/// 
+/// # Arguments
+/// * `github_api` - GitHub APIクライアント
+/// * `cache_service` - キャッシュサービス
+/// * `sender` - 送信者
+/// * `recipient` - 受信者
+/// * `ttl_seconds` - キャッシュの有効期間（秒）
+async fn get_follow_relation(
+    github_api: &dyn GitHubApi,
+    cache_service: &dyn CacheService,
+    sender: &Username,
+    recipient: &Username,
+    ttl_seconds: u64,
+) -> AppResult<FollowRelation> {
+    let cache_key = follow_relation_cache_key(sender, recipient);
+    
+    // デシリアライズできないエントリはキャッシュミスとして扱う
+    let cached = cache_service
+        .get(&cache_key)
+        .await?
+        .and_then(|cached| serde_json::from_str::<FollowRelation>(&cached).ok());
+    if let Some(relation) = cached {
+        return Ok(relation);
+    }
+    
+    let relation = github_api
+        .get_follow_relation(sender.as_str(), recipient.as_str())
+        .await?;
+    
+    let _ = cache_service.set(
+        &cache_key,
+        &serde_json::to_string(&relation).map_err(InfraError::from)?,
+        ttl_seconds,
+    ).await;
+    
+    Ok(relation)
+}
+
 /// 重複Pokeかどうかをチェック
 /// 
-/// 同一ユーザーへの同日のPokeは不可
+/// 同一ユーザーへの前回のPokeからクールダウン期間（`poke_cooldown_secs`）が
+/// 経過していなければ不可
 /// 
 /// # Arguments
 /// * `sender` - 送信者
@@ -99,38 +205,64 @@ pub async fn execute(
 /// * `deps` - アプリケーション依存性
 /// 
 /// # Returns
-/// * `true` - すでにPoke済み
-/// * `false` - まだPokeしていない
-async fn is_duplicate_poke(
+/// * `true` - クールダウン中
+/// * `false` - Poke可能
+pub async fn is_duplicate_poke(
     sender: &Username,
     recipient: &Username,
     deps: &AppDependencies,
 ) -> AppResult<bool> {
-    // 今日の送信済みPokeを取得
-    let today_pokes = deps.event_store
-        .find_today_pokes_from(sender.as_str())
+    let poke = PokeEvent::with_clock(sender.clone(), recipient.clone(), deps.clock.as_ref());
+    
+    is_within_cooldown(
+        deps.event_store.as_ref(),
+        &poke,
+        deps.config.app.rate_limit.poke_cooldown(),
+    ).await
+}
+
+/// 直近のPokeとの間隔がクールダウン未満かどうかをチェック
+/// 
+/// # Arguments
+/// * `event_store` - イベントストア
+/// * `poke` - これから送信するPoke
+/// * `cooldown` - クールダウン期間
+async fn is_within_cooldown(
+    event_store: &dyn EventStore,
+    poke: &PokeEvent,
+    cooldown: Duration,
+) -> AppResult<bool> {
+    let latest = event_store
+        .find_latest_poke(poke.from.as_str(), poke.to.as_str())
         .await?;
     
-    // 同じ受信者へのPokeがあるかチェック
-    Ok(today_pokes.iter().any(|poke| {
-        poke.to.as_str() == recipient.as_str()
-    }))
+    Ok(latest.is_some_and(|latest| poke.is_within_cooldown(&latest, cooldown)))
 }
 
-/// Pokeのプレビュー（テスト用）
+/// Pokeのプレビュー
+/// 
+/// 実際にはPokeを送信せず、`execute` と同じ条件（クールダウンを含む）で可能性のみをチェック
+/// イベントの保存・通知・レート制限の消費は行わない
+/// 
+/// # Arguments
+/// * `sender` - Poke送信者
+/// * `recipient_username` - Poke受信者のユーザー名
+/// * `deps` - アプリケーション依存性
 /// 
-/// 実際にはPokeを送信せず、可能性のみをチェック
-#[allow(dead_code)]
+/// # Returns
+/// * `Ok(CheckPokeResult)` - Poke可能性と、可能な場合は送信されるイベント
+/// * `Err(AppError)` - エラー
 pub async fn preview(
     sender: &Username,
     recipient_username: &Username,
     deps: &AppDependencies,
 ) -> AppResult<CheckPokeResult> {
     // 受信者の情報を取得
-    let recipient_state = deps.user_repository
-        .find_by_username(recipient_username.as_str())
-        .await?
-        .ok_or_else(|| DomainError::UserNotFound(recipient_username.as_str().to_string()))?;
+    let recipient_state = find_recipient(
+        deps.user_repository.as_ref(),
+        deps.github_api.as_ref(),
+        recipient_username,
+    ).await?;
     
     let recipient = match &recipient_state {
         UserState::Registered(user) => user,
@@ -142,17 +274,29 @@ pub async fn preview(
         }
     };
     
+    // 以降は登録時の表記を使う（イベントストアは大文字小文字を区別して検索するため）
+    let recipient_username = &recipient.username;
+    
     // フォロー関係を確認
-    let follow_relation = deps.github_api
-        .get_follow_relation(sender.as_str(), recipient_username.as_str())
-        .await?;
+    let follow_relation = get_follow_relation(
+        deps.github_api.as_ref(),
+        deps.cache_service.as_ref(),
+        sender,
+        recipient_username,
+        deps.config.app.cache.follow_relation_ttl,
+    ).await?;
     
     // Poke可能性をチェック
-    let capability = PokeCapability::check(sender, recipient, &follow_relation);
+    let mut capability = PokeCapability::check(sender, recipient, &follow_relation);
+    
+    // 重複Pokeのチェック
+    if capability.can_poke() && is_duplicate_poke(sender, recipient_username, deps).await? {
+        capability = PokeCapability::CannotPoke(PokeError::AlreadyPoked);
+    }
     
     // イベントは生成するが保存しない
     let event = if capability.can_poke() {
-        Some(PokeEvent::new(sender.clone(), recipient_username.clone()))
+        Some(PokeEvent::with_clock(sender.clone(), recipient_username.clone(), deps.clock.as_ref()))
     } else {
         None
     };
@@ -166,10 +310,166 @@ pub async fn preview(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use crate::app::dependencies::GitHubUser;
+    use crate::app::dependencies::mocks::MockCacheService;
+    use crate::domain::github::{GitHubActivity, GitHubRateLimit};
+    use crate::domain::user::{GitHubUserId, RegisteredUser};
+    use crate::error::{AppError, DomainError};
+    use crate::infra::event_store::InMemoryEventStore;
+    
+    fn username(name: &str) -> Username {
+        Username::parse(name.to_string()).unwrap()
+    }
     
-    // TODO: テストを実装
-    // - 正常系：Poke可能なケース
-    // - 異常系：受信者が未登録
-    // - 異常系：フォロワーではない
-    // - 異常系：重複Poke
+    /// フォロー関係の問い合わせ回数を数えるGitHub API
+    struct CountingFollowApi {
+        relation: FollowRelation,
+        calls: AtomicUsize,
+    }
+    
+    #[async_trait]
+    impl GitHubApi for CountingFollowApi {
+        async fn get_user_activity(&self, _username: &str) -> AppResult<GitHubActivity> {
+            unimplemented!()
+        }
+        
+        async fn get_follow_relation(&self, _from: &str, _to: &str) -> AppResult<FollowRelation> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.relation)
+        }
+        
+        async fn get_user(&self, _username: &str) -> AppResult<GitHubUser> {
+            unimplemented!()
+        }
+        
+        async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit> {
+            unimplemented!()
+        }
+        
+        async fn get_org_members(&self, _org: &str) -> AppResult<Vec<String>> {
+            unimplemented!()
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_follow_relation_is_cached_per_direction() {
+        let api = CountingFollowApi { relation: FollowRelation::Mutual, calls: AtomicUsize::new(0) };
+        let cache = MockCacheService::new();
+        
+        // 2回目はキャッシュから返す（大文字小文字の違いは同じキー）
+        let first = get_follow_relation(&api, &cache, &username("alice"), &username("bob"), 300).await.unwrap();
+        let second = get_follow_relation(&api, &cache, &username("Alice"), &username("BOB"), 300).await.unwrap();
+        assert_eq!(first, FollowRelation::Mutual);
+        assert_eq!(second, FollowRelation::Mutual);
+        assert_eq!(api.calls.load(Ordering::SeqCst), 1);
+        
+        // 逆方向は別の関係なので再取得する
+        get_follow_relation(&api, &cache, &username("bob"), &username("alice"), 300).await.unwrap();
+        assert_eq!(api.calls.load(Ordering::SeqCst), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_cooldown_against_latest_poke() {
+        let store = InMemoryEventStore::new();
+        let cooldown = Duration::seconds(3600);
+        let mut previous = PokeEvent::new(username("alice"), username("bob"));
+        previous.occurred_at = chrono::Utc::now() - Duration::minutes(30);
+        store.save_poke(&previous).await.unwrap();
+        
+        let poke = PokeEvent::new(username("alice"), username("bob"));
+        
+        // 30分前のPokeがあればブロック
+        assert!(is_within_cooldown(&store, &poke, cooldown).await.unwrap());
+        
+        // 90分前のPokeだけなら許可
+        let store = InMemoryEventStore::new();
+        previous.occurred_at = chrono::Utc::now() - Duration::minutes(90);
+        store.save_poke(&previous).await.unwrap();
+        assert!(!is_within_cooldown(&store, &poke, cooldown).await.unwrap());
+        
+        // 別の受信者へのPokeは影響しない
+        let other = PokeEvent::new(username("alice"), username("carol"));
+        assert!(!is_within_cooldown(&store, &other, cooldown).await.unwrap());
+    }
+    
+    mod execute_tests {
+        use super::*;
+        use crate::app::dependencies::mocks::TestDependenciesBuilder;
+        
+        fn bob() -> RegisteredUser {
+            RegisteredUser::new(GitHubUserId::new(2), username("bob"))
+        }
+        
+        #[tokio::test]
+        async fn test_poke_registered_recipient() {
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(bob())
+                .with_follow_relation("alice", "bob", FollowRelation::Mutual)
+                .build();
+            
+            let result = execute(&username("alice"), &username("Bob"), None, Some("ship it".to_string()), &deps)
+                .await
+                .unwrap();
+            
+            assert!(matches!(
+                result,
+                PokeResult::Success { ref poke_message, .. } if poke_message.as_deref() == Some("ship it")
+            ));
+            let saved = deps.event_store.find_pokes_to("bob").await.unwrap();
+            assert_eq!(saved.len(), 1);
+            assert_eq!(saved[0].from, username("alice"));
+            
+            // クールダウン中の再送は保存しない
+            let again = execute(&username("alice"), &username("bob"), None, None, &deps).await.unwrap();
+            assert!(matches!(
+                again,
+                PokeResult::Failed { ref reason } if *reason == PokeError::AlreadyPoked.to_string()
+            ));
+            assert_eq!(deps.event_store.find_pokes_to("bob").await.unwrap().len(), 1);
+        }
+        
+        #[tokio::test]
+        async fn test_repository_context_is_stored() {
+            let deps = TestDependenciesBuilder::new().with_registered_user(bob()).build();
+            
+            let result = execute(
+                &username("alice"),
+                &username("bob"),
+                Some("octo-org/api".to_string()),
+                None,
+                &deps,
+            ).await.unwrap();
+            
+            assert!(matches!(result, PokeResult::Success { .. }));
+            let saved = deps.event_store.find_pokes_to("bob").await.unwrap();
+            assert_eq!(saved[0].context.as_deref(), Some("octo-org/api"));
+        }
+        
+        #[tokio::test]
+        async fn test_poke_unregistered_recipient() {
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(bob())
+                .with_github_user("carol")
+                .build();
+            
+            let result = execute(&username("alice"), &username("carol"), None, None, &deps).await.unwrap();
+            
+            assert!(matches!(
+                result,
+                PokeResult::Failed { ref reason } if *reason == PokeError::RecipientNotRegistered.to_string()
+            ));
+            assert!(deps.event_store.find_pokes_to("carol").await.unwrap().is_empty());
+        }
+        
+        #[tokio::test]
+        async fn test_poke_unknown_recipient() {
+            let deps = TestDependenciesBuilder::new().build();
+            
+            let result = execute(&username("alice"), &username("ghost"), None, None, &deps).await;
+            
+            assert!(matches!(result, Err(AppError::Domain(DomainError::UserNotFound(_)))));
+        }
+    }
 }
\ No newline at end of file
@@ -5,11 +5,19 @@
 //! - バッジ状態の判定
 //! - SVGバッジの生成
 
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use crate::app::dependencies::AppDependencies;
+use crate::app::shutdown::spawn_background;
 use crate::domain::user::{Username, UserState};
-use crate::domain::badge::{BadgeState, BadgeSvg};
+use crate::domain::badge::{
+    BadgeState, BadgeStyle, BadgeSvg, BadgeTheme, NotFoundDisplay, DEFAULT_BADGE_LABEL,
+};
 use crate::domain::github::GitHubActivity;
-use crate::error::AppResult;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::error::{AppError, AppResult, DomainError, InfraError};
 
 /// バッジ生成の実行結果
 pub struct GenerateBadgeResult {
@@ -23,6 +31,43 @@ pub struct GenerateBadgeResult {
     pub cache_key: String,
 }
 
+/// バッジの見た目と表示項目
+/// 
+/// 同じオプションからは同じSVGが生成されるため、キャッシュキーもこれから作る
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadgeOptions {
+    /// バッジ左側のラベル
+    pub label: String,
+    
+    /// バッジのスタイル
+    pub style: BadgeStyle,
+    
+    /// バッジのテーマ
+    pub theme: BadgeTheme,
+    
+    /// 総コントリビューション数を表示するか
+    pub show_contributions: bool,
+    
+    /// 直近のコントリビューション推移を表示するか
+    pub sparkline: bool,
+    
+    /// ユーザーが見つからない場合の表示方法
+    pub not_found: NotFoundDisplay,
+}
+
+impl Default for BadgeOptions {
+    fn default() -> Self {
+        Self {
+            label: DEFAULT_BADGE_LABEL.to_string(),
+            style: BadgeStyle::default(),
+            theme: BadgeTheme::default(),
+            show_contributions: false,
+            sparkline: false,
+            not_found: NotFoundDisplay::default(),
+        }
+    }
+}
+
 /// バッジを生成
 /// 
 /// このユースケースは以下を行う：
@@ -35,6 +80,7 @@ pub struct GenerateBadgeResult {
 /// * `username` - GitHubユーザー名
 /// * `deps` - アプリケーション依存性
 /// * `interactive` - インタラクティブバッジを生成するか
+/// * `options` - バッジの見た目と表示項目
 /// 
 /// # Returns
 /// * `Ok(GenerateBadgeResult)` - 生成結果
@@ -43,34 +89,189 @@ pub async fn execute(
     username: &Username,
     deps: &AppDependencies,
     interactive: bool,
+    options: &BadgeOptions,
 ) -> AppResult<GenerateBadgeResult> {
-    // 1. GitHubアクティビティを取得
-    let activity = get_github_activity(username, deps).await?;
+    let BadgeOptions { label, style, theme, show_contributions, sparkline, not_found } = options;
     
-    // 2. ユーザー状態を確認
-    let user_state = get_user_state(username, deps).await?;
+    // 1〜3. アクティビティとユーザー状態からバッジ状態を判定
+    let (badge_state, user_state, activity) = resolve_states(username, deps).await?;
     
-    // 3. バッジ状態を判定（純粋関数）
-    let badge_state = BadgeState::from_activity(&activity, &user_state);
+    // 総コントリビューション数は指定時のみ表示（データがなければ省略）
+    let total_contributions = activity
+        .as_ref()
+        .and_then(|activity| activity.total_contributions)
+        .filter(|_| *show_contributions);
+    
+    // ツールチップに表示する最終活動日
+    let last_active_date = activity.as_ref().and_then(GitHubActivity::last_active_date);
     
     // 4. SVGを生成（純粋関数）
-    let badge = if interactive && should_show_interactive(&badge_state, &user_state) {
-        BadgeSvg::interactive_badge(&badge_state, username.as_str())
+    let badge = if badge_state == BadgeState::NotFound {
+        BadgeSvg::not_found_badge(not_found, username.as_str(), label, *style, *theme)
+    } else if interactive && should_show_interactive(&badge_state, &user_state) {
+        BadgeSvg::interactive_badge(
+            &badge_state,
+            username.as_str(),
+            label,
+            *style,
+            *theme,
+            total_contributions,
+            last_active_date,
+        )
     } else {
-        BadgeSvg::static_badge(&badge_state, username.as_str())
+        BadgeSvg::static_badge(
+            &badge_state,
+            username.as_str(),
+            label,
+            *style,
+            *theme,
+            total_contributions,
+            last_active_date,
+        )
+    };
+    
+    // 日別データがない場合はスパークラインを省略
+    let badge = if *sparkline {
+        let contributions = activity.as_ref().and_then(|activity| activity.contributions.as_ref());
+        badge.with_sparkline(contributions, deps.clock.now().date_naive())
+    } else {
+        badge
     };
     
     // 5. 結果を返す
     Ok(GenerateBadgeResult {
         badge,
         state: badge_state,
-        cache_key: format!("badge:{}:v1", username.as_str()),
+        cache_key: badge_cache_key(username, interactive, options),
     })
 }
 
+/// バッジ状態のみを判定
+/// 
+/// SVGを生成せずに状態だけが必要な場合（JSONエンドポイント等）に使用
+/// アクティビティの取得・キャッシュはSVGバッジと同じ経路を通る
+/// 
+/// # Arguments
+/// * `username` - GitHubユーザー名
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * `Ok(BadgeState)` - 判定されたバッジ状態
+/// * `Err(AppError)` - エラー
+pub async fn resolve_badge_state(
+    username: &Username,
+    deps: &AppDependencies,
+) -> AppResult<BadgeState> {
+    let (badge_state, _, _) = resolve_states(username, deps).await?;
+    Ok(badge_state)
+}
+
+/// バッジ状態・ユーザー状態・アクティビティを取得
+/// 
+/// 1. GitHubアクティビティの取得（キャッシュ優先）
+/// 2. ユーザー状態の確認
+/// 3. バッジ状態の判定
+/// 
+/// GitHubにユーザーが存在しない場合はエラーにせず `BadgeState::NotFound` とする
+/// （アクティビティは `None`）。一時的なAPIエラーはそのまま返す
+async fn resolve_states(
+    username: &Username,
+    deps: &AppDependencies,
+) -> AppResult<(BadgeState, UserState, Option<GitHubActivity>)> {
+    // 1. GitHubアクティビティを取得
+    let activity = match get_github_activity(username, deps).await {
+        Ok(activity) => activity,
+        Err(AppError::Domain(DomainError::UserNotFound(_))) => {
+            return Ok((BadgeState::NotFound, UserState::Anonymous(username.clone()), None));
+        }
+        Err(e) => return Err(e),
+    };
+    
+    // 2. ユーザー状態を確認
+    let user_state = get_user_state(username, deps).await?;
+    
+    // 3. バッジ状態を判定（純粋関数）
+    let badge_state = BadgeState::from_activity(
+        &activity,
+        &user_state,
+        deps.config.app.badge.active_threshold_days,
+        deps.config.app.badge.warning_threshold_days,
+    );
+    
+    Ok((badge_state, user_state, Some(activity)))
+}
+
+/// バッジのキャッシュキーを生成
+/// 
+/// ラベル・スタイル・テーマ・表示項目・インタラクティブかどうかごとに異なるSVGになるため、それらをキーに含める
+/// ユーザー名は正規化するため、大文字小文字が異なっても同じキーになる
+/// 見つからないユーザーの表示方法は既定以外の場合のみ表示項目に付加する
+/// 
+/// # Arguments
+/// * `username` - GitHubユーザー名
+/// * `interactive` - インタラクティブバッジを要求しているか
+/// * `options` - バッジの見た目と表示項目
+pub fn badge_cache_key(username: &Username, interactive: bool, options: &BadgeOptions) -> String {
+    let show = match (options.show_contributions, options.sparkline) {
+        (false, false) => "default",
+        (true, false) => "contributions",
+        (false, true) => "sparkline",
+        (true, true) => "contributions+sparkline",
+    };
+    // 任意の文字列のメッセージはハッシュにして、区切り文字を含まないようにする
+    let show = match &options.not_found {
+        NotFoundDisplay::Message => show.to_string(),
+        NotFoundDisplay::Hidden => format!("{}+notfound-hidden", show),
+        NotFoundDisplay::CustomMessage(text) => {
+            let digest = hex::encode(Sha256::digest(text.as_bytes()));
+            format!("{}+notfound-{}", show, &digest[..16])
+        }
+    };
+    let mode = if interactive { "interactive" } else { "static" };
+    format!(
+        "badge:{}:v2:{}:{}:{}:{}:{}",
+        username.normalized(),
+        mode,
+        options.style.as_str(),
+        options.theme.as_str(),
+        show,
+        options.label
+    )
+}
+
+/// アクティビティのキャッシュキーを生成
+pub fn activity_cache_key(username: &Username) -> String {
+    format!("activity:{}", username.normalized())
+}
+
+/// キャッシュに保存するアクティビティ
+/// 
+/// キャッシュ自体のTTL（ハードTTL）とは別に、再取得を始める時刻（ソフトTTL）を保持する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedActivity {
+    /// アクティビティ情報
+    activity: GitHubActivity,
+    
+    /// キャッシュに保存した日時
+    cached_at: DateTime<Utc>,
+    
+    /// この日時を過ぎたら古い値を返しつつ裏で再取得する
+    soft_expires_at: DateTime<Utc>,
+}
+
+impl CachedActivity {
+    /// ソフトTTL内かどうか
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        now < self.soft_expires_at
+    }
+}
+
 /// GitHubアクティビティを取得
 /// 
 /// キャッシュがあればキャッシュから、なければAPIから取得
+/// ソフトTTLを過ぎたキャッシュは即座に返し、バックグラウンドで再取得する
+/// キャッシュ切れ時の同時リクエストはユーザーごとに直列化し、
+/// GitHub APIを呼ぶのは最初の1リクエストのみとする
 /// 
 /// # Arguments
 /// * `username` - ユーザー名
@@ -79,34 +280,168 @@ pub async fn execute(
 /// # Returns
 /// * `Ok(GitHubActivity)` - アクティビティ情報
 /// * `Err(AppError)` - 取得エラー
-async fn get_github_activity(
+pub(crate) async fn get_github_activity(
     username: &Username,
     deps: &AppDependencies,
 ) -> AppResult<GitHubActivity> {
-    let cache_key = format!("activity:{}", username.as_str());
+    let cache_key = activity_cache_key(username);
     
     // キャッシュから取得を試みる
-    if let Some(cached) = deps.cache_service.get(&cache_key).await? {
-        // JSONからデシリアライズ
-        if let Ok(activity) = serde_json::from_str::<GitHubActivity>(&cached) {
-            return Ok(activity);
+    if let Some(cached) = get_cached_activity(&cache_key, deps).await? {
+        if !cached.is_fresh(deps.clock.now()) {
+            // stale-while-revalidate: 古い値を返しつつ裏で更新する
+            spawn_activity_refresh(username, &cache_key, deps);
         }
+        return Ok(cached.activity);
     }
     
+    // 同一ユーザーの取得を直列化（エラー時もドロップでロックは解放される）
+    let fetch_lock = ActivityFetchLock::acquire(&deps.activity_fetch_locks, &username.normalized());
+    let _guard = fetch_lock.lock.lock().await;
+    
+    // 待機中に先行リクエストがキャッシュを更新していればそれを使う
+    if let Some(cached) = get_cached_activity(&cache_key, deps).await? {
+        return Ok(cached.activity);
+    }
+    
+    fetch_and_cache_activity(username, &cache_key, deps).await
+}
+
+/// アクティビティをバックグラウンドで再取得
+/// 
+/// 同じユーザーの取得が進行中であれば何もしない
+fn spawn_activity_refresh(username: &Username, cache_key: &str, deps: &AppDependencies) {
+    let fetch_lock = ActivityFetchLock::acquire(&deps.activity_fetch_locks, &username.normalized());
+    let username = username.clone();
+    let cache_key = cache_key.to_string();
+    let deps = deps.clone();
+    
+    spawn_background(async move {
+        let Ok(_guard) = fetch_lock.lock.try_lock() else {
+            return;
+        };
+        
+        // 直前に他のリクエストが更新済みであれば再取得しない
+        if let Ok(Some(cached)) = get_cached_activity(&cache_key, &deps).await
+            && cached.is_fresh(deps.clock.now())
+        {
+            return;
+        }
+        
+        if let Err(e) = fetch_and_cache_activity(&username, &cache_key, &deps).await {
+            tracing::warn!(
+                username = username.as_str(),
+                error = %e,
+                "アクティビティのバックグラウンド更新に失敗しました"
+            );
+        }
+    });
+}
+
+/// キャッシュからアクティビティを取得
+/// 
+/// デシリアライズできないエントリはキャッシュミスとして扱う
+async fn get_cached_activity(
+    cache_key: &str,
+    deps: &AppDependencies,
+) -> AppResult<Option<CachedActivity>> {
+    let cached = deps.cache_service
+        .get(cache_key)
+        .await?
+        .and_then(|cached| serde_json::from_str::<CachedActivity>(&cached).ok());
+    
+    Ok(cached)
+}
+
+/// GitHub APIからアクティビティを取得してキャッシュに保存
+async fn fetch_and_cache_activity(
+    username: &Username,
+    cache_key: &str,
+    deps: &AppDependencies,
+) -> AppResult<GitHubActivity> {
     // GitHub APIから取得
     let activity = deps.github_api
         .get_user_activity(username.as_str())
         .await?;
     
     // キャッシュに保存
+    // ソフトTTLを過ぎても猶予期間中は古い値を返せるよう、実際のTTLは猶予分だけ長くする
     let ttl = calculate_activity_cache_ttl(&activity, deps);
-    let _ = deps.cache_service.set(
-        &cache_key,
-        &serde_json::to_string(&activity)?,
-        ttl,
-    ).await;
+    let now = deps.clock.now();
+    let cached = CachedActivity {
+        activity,
+        cached_at: now,
+        soft_expires_at: now + Duration::seconds(ttl as i64),
+    };
+    // 保存に失敗しても取得したアクティビティは返す（エラーはログのみ）
+    if let Err(e) = deps.cache_service.set(
+        cache_key,
+        &serde_json::to_string(&cached).map_err(InfraError::from)?,
+        ttl + deps.config.app.cache.stale_while_revalidate_ttl,
+    ).await {
+        tracing::warn!(
+            username = username.as_str(),
+            error = %e,
+            "アクティビティのキャッシュ保存に失敗しました"
+        );
+    }
     
-    Ok(activity)
+    Ok(cached.activity)
+}
+
+/// 取得中のユーザーごとのロック
+/// 
+/// `AppDependencies` が保持し、同じ依存性を共有するリクエスト間で取得を直列化する
+#[derive(Default)]
+pub struct ActivityFetchLocks {
+    /// キー: ユーザー名
+    /// 値: 取得処理を直列化するための非同期ミューテックス
+    locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl ActivityFetchLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// ユーザーごとのアクティビティ取得ロックへの参照
+/// 
+/// ドロップ時に他に待機者がいなければマップからエントリを取り除く
+/// マップ自体のロックはawaitをまたいで保持しない
+struct ActivityFetchLock {
+    locks: Arc<ActivityFetchLocks>,
+    username: String,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ActivityFetchLock {
+    fn acquire(locks: &Arc<ActivityFetchLocks>, username: &str) -> Self {
+        let lock = locks.locks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(username.to_string())
+            .or_default()
+            .clone();
+        
+        Self {
+            locks: locks.clone(),
+            username: username.to_string(),
+            lock,
+        }
+    }
+}
+
+impl Drop for ActivityFetchLock {
+    fn drop(&mut self) {
+        let mut locks = self.locks.locks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // マップと自分以外に参照がなければ最後の利用者
+        if Arc::strong_count(&self.lock) == 2 {
+            locks.remove(&self.username);
+        }
+    }
 }
 
 /// ユーザー状態を取得
@@ -123,7 +458,7 @@ async fn get_user_state(
 ) -> AppResult<UserState> {
     // データベースから取得
     let user_state = deps.user_repository
-        .find_by_username(username.as_str())
+        .find_by_username(&username.normalized())
         .await?
         .unwrap_or_else(|| UserState::Anonymous(username.clone()));
     
@@ -133,9 +468,10 @@ async fn get_user_state(
 /// インタラクティブバッジを表示すべきか判定
 /// 
 /// 以下の条件をすべて満たす場合にtrue：
-/// - バッジが非アクティブ状態
+/// - バッジが警告または非アクティブ状態
 /// - ユーザーが登録済み
 /// - Pokeが有効
+/// - ユーザーがインタラクティブバッジを無効化していない
 /// 
 /// # Arguments
 /// * `badge_state` - バッジの状態
@@ -146,9 +482,9 @@ async fn get_user_state(
 /// * `false` - 静的バッジを表示
 fn should_show_interactive(badge_state: &BadgeState, user_state: &UserState) -> bool {
     match (badge_state, user_state) {
-        (BadgeState::Inactive { pokeable: true, .. }, UserState::Registered(user)) => {
-            // Pokeが無効化されていないかチェック
-            user.poke_setting.is_enabled()
+        (state, UserState::Registered(user)) if state.is_pokeable() => {
+            // Pokeとインタラクティブバッジが無効化されていないかチェック
+            user.poke_setting.is_enabled() && user.allow_interactive_badge
         }
         _ => false,
     }
@@ -157,6 +493,7 @@ fn should_show_interactive(badge_state: &BadgeState, user_state: &UserState) ->
 /// アクティビティキャッシュのTTLを計算
 /// 
 /// アクティブユーザーは短め、非アクティブユーザーは長めのTTL
+/// アクティブかどうかはバッジと同じ閾値で判定する
 /// 
 /// # Arguments
 /// * `activity` - GitHubアクティビティ
@@ -165,9 +502,9 @@ fn should_show_interactive(badge_state: &BadgeState, user_state: &UserState) ->
 /// # Returns
 /// * TTL（秒）
 fn calculate_activity_cache_ttl(activity: &GitHubActivity, deps: &AppDependencies) -> u64 {
-    let days_inactive = activity.days_since_last_activity();
+    let days_inactive = activity.days_since_last_activity_with(deps.clock.as_ref());
     
-    if days_inactive <= 7 {
+    if days_inactive <= deps.config.app.badge.active_threshold_days {
         deps.config.app.cache.active_user_ttl
     } else {
         deps.config.app.cache.inactive_user_ttl
@@ -188,7 +525,17 @@ pub async fn generate_preview_badges() -> Vec<(String, BadgeSvg)> {
     };
     badges.push((
         "Active Today".to_string(),
-        BadgeSvg::static_badge(&active_state, "octocat"),
+        BadgeSvg::static_badge(&active_state, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None),
+    ));
+    
+    // 警告状態
+    let warning = BadgeState::Warning {
+        days_since_last_activity: 10,
+        pokeable: false,
+    };
+    badges.push((
+        "Warning".to_string(),
+        BadgeSvg::static_badge(&warning, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None),
     ));
     
     // 非アクティブ状態（Poke可能）
@@ -198,7 +545,7 @@ pub async fn generate_preview_badges() -> Vec<(String, BadgeSvg)> {
     };
     badges.push((
         "Inactive (Pokeable)".to_string(),
-        BadgeSvg::interactive_badge(&inactive_pokeable, "octocat"),
+        BadgeSvg::interactive_badge(&inactive_pokeable, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None),
     ));
     
     // 非アクティブ状態（Poke不可）
@@ -208,14 +555,14 @@ pub async fn generate_preview_badges() -> Vec<(String, BadgeSvg)> {
     };
     badges.push((
         "Inactive (Not Pokeable)".to_string(),
-        BadgeSvg::static_badge(&inactive_not_pokeable, "octocat"),
+        BadgeSvg::static_badge(&inactive_not_pokeable, "octocat", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None),
     ));
     
     // ユーザーが見つからない
     let not_found = BadgeState::NotFound;
     badges.push((
         "User Not Found".to_string(),
-        BadgeSvg::static_badge(&not_found, "unknown"),
+        BadgeSvg::static_badge(&not_found, "unknown", DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light, None, None),
     ));
     
     badges
@@ -224,10 +571,355 @@ pub async fn generate_preview_badges() -> Vec<(String, BadgeSvg)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use crate::app::dependencies::{GitHubApi, GitHubUser};
+    use crate::app::dependencies::mocks::TestDependenciesBuilder;
+    use crate::domain::clock::{Clock, FixedClock};
+    use crate::domain::github::{FollowRelation, GitHubRateLimit};
+    use crate::domain::user::RegisteredUser;
+    
+    /// 呼び出し回数を数えるGitHub APIモック
+    struct CountingGitHubApi {
+        calls: AtomicUsize,
+        fail: bool,
+        not_found: bool,
+    }
+    
+    #[async_trait]
+    impl GitHubApi for CountingGitHubApi {
+        async fn get_user_activity(&self, username: &str) -> AppResult<GitHubActivity> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            // 同時リクエストが重なるよう応答を遅らせる
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            
+            if self.fail {
+                return Err(InfraError::Database("GitHub APIエラー".to_string()).into());
+            }
+            if self.not_found {
+                return Err(DomainError::UserNotFound(username.to_string()).into());
+            }
+            
+            Ok(GitHubActivity {
+                username: username.to_string(),
+                last_activity_at: Some(Utc::now()),
+                current_streak_days: Some(1),
+                contributions: None,
+                total_contributions: None,
+                tz_offset_seconds: None,
+                fetched_at: Utc::now(),
+            })
+        }
+        
+        async fn get_follow_relation(&self, _from: &str, _to: &str) -> AppResult<FollowRelation> {
+            Ok(FollowRelation::None)
+        }
+        
+        async fn get_user(&self, _username: &str) -> AppResult<GitHubUser> {
+            Ok(GitHubUser)
+        }
+        
+        async fn get_rate_limit(&self) -> AppResult<GitHubRateLimit> {
+            Ok(GitHubRateLimit { limit: 5000, remaining: 5000, reset_at: Utc::now() })
+        }
+        
+        async fn get_org_members(&self, _org: &str) -> AppResult<Vec<String>> {
+            Ok(vec![])
+        }
+    }
+    
+    fn counting_api(fail: bool) -> Arc<CountingGitHubApi> {
+        Arc::new(CountingGitHubApi {
+            calls: AtomicUsize::new(0),
+            fail,
+            not_found: false,
+        })
+    }
+    
+    /// 存在しないユーザーとして応答するモック
+    fn not_found_api() -> Arc<CountingGitHubApi> {
+        Arc::new(CountingGitHubApi {
+            calls: AtomicUsize::new(0),
+            fail: false,
+            not_found: true,
+        })
+    }
+    
+    fn has_fetch_lock(deps: &AppDependencies, username: &str) -> bool {
+        deps.activity_fetch_locks.locks.lock().unwrap().contains_key(username)
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_requests_fetch_once() {
+        let github_api = counting_api(false);
+        let deps = TestDependenciesBuilder::new().with_github_api(github_api.clone()).build();
+        let username = Username::parse("single-flight-user".to_string()).unwrap();
+        
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let deps = deps.clone();
+                let username = username.clone();
+                tokio::spawn(async move { get_github_activity(&username, &deps).await })
+            })
+            .collect();
+        
+        for task in tasks {
+            let activity = task.await.unwrap().unwrap();
+            assert_eq!(activity.username, "single-flight-user");
+        }
+        
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 1);
+        // 全員が終われば取得ロックは残らない
+        assert!(!has_fetch_lock(&deps, "single-flight-user"));
+    }
+    
+    #[tokio::test]
+    async fn test_fetch_error_releases_lock() {
+        let github_api = counting_api(true);
+        let deps = TestDependenciesBuilder::new().with_github_api(github_api.clone()).build();
+        let username = Username::parse("single-flight-error".to_string()).unwrap();
+        
+        let result = get_github_activity(&username, &deps).await;
+        assert!(matches!(result, Err(AppError::Infra(_))));
+        assert!(!has_fetch_lock(&deps, "single-flight-error"));
+        
+        // ロックが解放されているので再試行できる
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            get_github_activity(&username, &deps),
+        )
+        .await
+        .expect("ロックが解放されていません");
+        assert!(result.is_err());
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 2);
+    }
+    
+    /// 固定時刻で止まった時計
+    fn fixed_clock() -> Arc<FixedClock> {
+        Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap()))
+    }
+    
+    /// `now` の時点でソフトTTLを1時間過ぎたキャッシュ
+    fn stale_cached_activity(username: &str, now: DateTime<Utc>) -> CachedActivity {
+        let cached_at = now - Duration::hours(2);
+        CachedActivity {
+            activity: GitHubActivity {
+                username: username.to_string(),
+                last_activity_at: Some(cached_at),
+                current_streak_days: Some(99),
+                contributions: None,
+                total_contributions: None,
+                tz_offset_seconds: None,
+                fetched_at: cached_at,
+            },
+            cached_at,
+            soft_expires_at: cached_at + Duration::hours(1),
+        }
+    }
+    
+    async fn seed_cache(deps: &AppDependencies, username: &str, cached: &CachedActivity) {
+        deps.cache_service
+            .set(
+                &format!("activity:{}", username),
+                &serde_json::to_string(cached).unwrap(),
+                3600,
+            )
+            .await
+            .unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_stale_entry_returned_without_blocking() {
+        let github_api = counting_api(false);
+        let clock = fixed_clock();
+        let deps = TestDependenciesBuilder::new()
+            .with_github_api(github_api.clone())
+            .with_clock(clock.clone())
+            .build();
+        let username = Username::parse("stale-user".to_string()).unwrap();
+        seed_cache(&deps, "stale-user", &stale_cached_activity("stale-user", clock.now())).await;
+        
+        let activity = get_github_activity(&username, &deps).await.unwrap();
+        
+        // 古い値がそのまま返り、GitHub APIの応答を待っていない
+        assert_eq!(activity.current_streak_days, Some(99));
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_stale_entry_refreshed_in_background() {
+        let github_api = counting_api(false);
+        let clock = fixed_clock();
+        let deps = TestDependenciesBuilder::new()
+            .with_github_api(github_api.clone())
+            .with_clock(clock.clone())
+            .build();
+        let username = Username::parse("refresh-user".to_string()).unwrap();
+        seed_cache(&deps, "refresh-user", &stale_cached_activity("refresh-user", clock.now())).await;
+        
+        get_github_activity(&username, &deps).await.unwrap();
+        
+        // バックグラウンド更新の完了を待つ
+        let refreshed = tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            loop {
+                let cached = get_cached_activity("activity:refresh-user", &deps)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                if cached.is_fresh(clock.now()) {
+                    return cached;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("キャッシュが更新されていません");
+        
+        assert_eq!(refreshed.activity.current_streak_days, Some(1));
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 1);
+        
+        // 更新後は新しい値が返る
+        let activity = get_github_activity(&username, &deps).await.unwrap();
+        assert_eq!(activity.current_streak_days, Some(1));
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_fresh_entry_not_refreshed() {
+        let github_api = counting_api(false);
+        let clock = fixed_clock();
+        let deps = TestDependenciesBuilder::new()
+            .with_github_api(github_api.clone())
+            .with_clock(clock.clone())
+            .build();
+        let username = Username::parse("fresh-user".to_string()).unwrap();
+        let mut cached = stale_cached_activity("fresh-user", clock.now());
+        cached.soft_expires_at = clock.now() + Duration::hours(1);
+        seed_cache(&deps, "fresh-user", &cached).await;
+        
+        get_github_activity(&username, &deps).await.unwrap();
+        tokio::task::yield_now().await;
+        
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 0);
+        assert!(!has_fetch_lock(&deps, "fresh-user"));
+    }
+    
+    #[tokio::test]
+    async fn test_username_case_shares_cache_but_keeps_display_case() {
+        let github_api = counting_api(false);
+        let deps = TestDependenciesBuilder::new().with_github_api(github_api.clone()).build();
+        let upper = Username::parse("Case-User".to_string()).unwrap();
+        let lower = Username::parse("case-user".to_string()).unwrap();
+        
+        let upper_result = execute(&upper, &deps, false, &BadgeOptions::default())
+            .await
+            .unwrap();
+        let lower_result = execute(&lower, &deps, false, &BadgeOptions::default())
+            .await
+            .unwrap();
+        
+        // 同じキャッシュキーを参照し、GitHub APIは1回しか呼ばれない
+        assert_eq!(upper_result.cache_key, lower_result.cache_key);
+        assert_eq!(activity_cache_key(&upper), "activity:case-user");
+        assert_eq!(github_api.calls.load(Ordering::SeqCst), 1);
+        
+        // 表示はリクエストされた表記のまま
+        assert!(upper_result.badge.content.contains("status for Case-User"));
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_user_gets_not_found_badge() {
+        let deps = TestDependenciesBuilder::new().with_github_api(not_found_api()).build();
+        let username = Username::parse("no-such-user-12345".to_string()).unwrap();
+        let options = BadgeOptions { show_contributions: true, ..BadgeOptions::default() };
+        
+        let result = execute(&username, &deps, true, &options).await.unwrap();
+        
+        assert_eq!(result.state, BadgeState::NotFound);
+        assert!(!result.badge.is_interactive);
+        assert!(result.badge.content.contains("User not found"));
+        assert_eq!(resolve_badge_state(&username, &deps).await.unwrap(), BadgeState::NotFound);
+    }
+    
+    #[tokio::test]
+    async fn test_not_found_display_changes_badge_and_cache_key() {
+        let deps = TestDependenciesBuilder::new().with_github_api(not_found_api()).build();
+        let username = Username::parse("no-such-user-12345".to_string()).unwrap();
+        let with_not_found = |not_found| BadgeOptions { not_found, ..BadgeOptions::default() };
+        
+        let default = execute(&username, &deps, false, &BadgeOptions::default())
+            .await
+            .unwrap();
+        let hidden = execute(&username, &deps, false, &with_not_found(NotFoundDisplay::Hidden))
+            .await
+            .unwrap();
+        let custom = execute(
+            &username,
+            &deps,
+            false,
+            &with_not_found(NotFoundDisplay::CustomMessage("Unknown".to_string())),
+        ).await.unwrap();
+        
+        assert!(hidden.badge.content.contains(r#"width="1""#));
+        assert!(custom.badge.content.contains(">Unknown</text>"));
+        
+        // 表示方法ごとに別のキャッシュを使う（既定のキーに表示方法は付加しない）
+        assert_eq!(default.cache_key, "badge:no-such-user-12345:v2:static:flat:light:default:GitPoke");
+        assert_eq!(hidden.cache_key, "badge:no-such-user-12345:v2:static:flat:light:default+notfound-hidden:GitPoke");
+        assert!(custom.cache_key.starts_with("badge:no-such-user-12345:v2:static:flat:light:default+notfound-"));
+        assert_ne!(custom.cache_key, hidden.cache_key);
+    }
+    
+    #[tokio::test]
+    async fn test_transient_api_error_is_not_a_not_found_badge() {
+        let deps = TestDependenciesBuilder::new().with_github_api(counting_api(true)).build();
+        let username = Username::parse("flaky-github-user".to_string()).unwrap();
+        
+        let result = execute(&username, &deps, false, &BadgeOptions::default()).await;
+        
+        assert!(matches!(result, Err(AppError::Infra(_))));
+    }
+    
+    /// Pokeできる状態の登録済みユーザー
+    fn pokeable_user(allow_interactive_badge: bool) -> UserState {
+        let mut user = RegisteredUser::new(
+            crate::domain::user::GitHubUserId::new(1),
+            Username::parse("sleepy-user".to_string()).unwrap(),
+        );
+        user.set_allow_interactive_badge(allow_interactive_badge);
+        UserState::Registered(user)
+    }
+    
+    /// Pokeできるバッジ状態
+    fn pokeable_states() -> [BadgeState; 2] {
+        [
+            BadgeState::Warning { days_since_last_activity: 10, pokeable: true },
+            BadgeState::Inactive { days_since_last_activity: 60, pokeable: true },
+        ]
+    }
+    
+    #[test]
+    fn test_pokeable_user_is_interactive_by_default() {
+        let user_state = UserState::Registered(RegisteredUser::new(
+            crate::domain::user::GitHubUserId::new(1),
+            Username::parse("sleepy-user".to_string()).unwrap(),
+        ));
+        
+        for badge_state in pokeable_states() {
+            assert!(should_show_interactive(&badge_state, &user_state), "{:?}", badge_state);
+        }
+    }
+    
+    #[test]
+    fn test_disabled_interactive_badge_is_always_static() {
+        for badge_state in pokeable_states() {
+            assert!(should_show_interactive(&badge_state, &pokeable_user(true)));
+            assert!(!should_show_interactive(&badge_state, &pokeable_user(false)), "{:?}", badge_state);
+        }
+    }
     
     // TODO: テストを実装
     // - アクティブユーザーのバッジ生成
     // - 非アクティブユーザーのバッジ生成
-    // - キャッシュヒットのテスト
-    // - インタラクティブバッジの条件テスト
-}
\ No newline at end of file
+}
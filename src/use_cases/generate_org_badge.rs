@@ -0,0 +1,202 @@
+//! 組織バッジ生成のユースケース
+//! 
+//! このファイルは以下を定義：
+//! - 組織メンバーのアクティビティ集計
+//! - 集計のキャッシュ
+//! - 組織バッジの生成
+
+use crate::app::dependencies::AppDependencies;
+use crate::domain::badge::{BadgeStyle, BadgeSvg, BadgeTheme, OrgActivitySummary};
+use crate::domain::user::Username;
+use crate::error::{AppError, AppResult, DomainError, InfraError};
+use crate::use_cases::generate_badge::get_github_activity;
+use crate::util::functional::try_map_all_concurrent;
+
+/// メンバーのアクティビティを同時に取得する数
+const MEMBER_FETCH_CONCURRENCY: usize = 8;
+
+/// 組織バッジを生成
+/// 
+/// 1. メンバーのアクティビティ集計を取得（キャッシュ優先）
+/// 2. SVGの生成
+/// 
+/// # Arguments
+/// * `org` - 組織名（GitHubのユーザー名と同じ形式）
+/// * `deps` - アプリケーション依存性
+/// * `label` - バッジ左側のラベル
+/// * `style` - バッジのスタイル
+/// * `theme` - バッジのテーマ
+/// 
+/// # Returns
+/// * `Ok(BadgeSvg)` - 生成されたバッジ
+/// * `Err(DomainError::UserNotFound)` - 組織が存在しない
+pub async fn execute(
+    org: &Username,
+    deps: &AppDependencies,
+    label: &str,
+    style: BadgeStyle,
+    theme: BadgeTheme,
+) -> AppResult<BadgeSvg> {
+    let summary = get_org_summary(org, deps).await?;
+    Ok(BadgeSvg::org_badge(&summary, org.as_str(), label, style, theme))
+}
+
+/// 組織バッジのキャッシュキーを生成
+/// 
+/// 個人バッジと同様に、ラベル・スタイル・テーマごとに異なるSVGになるためキーに含める
+pub fn org_badge_cache_key(
+    org: &Username,
+    label: &str,
+    style: BadgeStyle,
+    theme: BadgeTheme,
+) -> String {
+    format!(
+        "org_badge:{}:v1:{}:{}:{}",
+        org.normalized(),
+        style.as_str(),
+        theme.as_str(),
+        label
+    )
+}
+
+/// 組織の集計のキャッシュキーを生成
+pub fn org_activity_cache_key(org: &Username) -> String {
+    format!("org_activity:{}", org.normalized())
+}
+
+/// メンバーのアクティビティ集計を取得
+/// 
+/// キャッシュがあればキャッシュから、なければメンバー全員のアクティビティを取得して集計する
+/// 各メンバーのアクティビティは個人バッジと同じ経路（キャッシュを含む）で取得する
+async fn get_org_summary(
+    org: &Username,
+    deps: &AppDependencies,
+) -> AppResult<OrgActivitySummary> {
+    let cache_key = org_activity_cache_key(org);
+    
+    // デシリアライズできないエントリはキャッシュミスとして扱う
+    let cached = deps.cache_service
+        .get(&cache_key)
+        .await?
+        .and_then(|cached| serde_json::from_str::<OrgActivitySummary>(&cached).ok());
+    if let Some(summary) = cached {
+        return Ok(summary);
+    }
+    
+    let members = deps.github_api.get_org_members(org.as_str()).await?;
+    let activities = try_map_all_concurrent(members, MEMBER_FETCH_CONCURRENCY, |member| async move {
+        let Ok(username) = Username::parse(member) else {
+            return Ok(None);
+        };
+        // 取得の間に退会・改名したメンバーは集計から除く
+        match get_github_activity(&username, deps).await {
+            Ok(activity) => Ok(Some(activity)),
+            Err(AppError::Domain(DomainError::UserNotFound(_))) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+    .await?;
+    let activities: Vec<_> = activities.into_iter().flatten().collect();
+    
+    let summary = OrgActivitySummary::from_activities(
+        &activities,
+        deps.config.app.badge.active_threshold_days,
+    );
+    
+    let _ = deps.cache_service.set(
+        &cache_key,
+        &serde_json::to_string(&summary).map_err(InfraError::from)?,
+        summary.cache_ttl(),
+    ).await;
+    
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use chrono::{Duration, Utc};
+    use crate::app::dependencies::mocks::{MockGitHubApi, TestDependenciesBuilder};
+    use crate::domain::badge::DEFAULT_BADGE_LABEL;
+    use crate::domain::github::GitHubActivity;
+    
+    /// `days` 日前に最終活動したユーザーのアクティビティ
+    fn activity(username: &str, days: i64) -> GitHubActivity {
+        GitHubActivity {
+            username: username.to_string(),
+            last_activity_at: Some(Utc::now() - Duration::days(days)),
+            current_streak_days: None,
+            contributions: None,
+            total_contributions: None,
+            tz_offset_seconds: None,
+            fetched_at: Utc::now(),
+        }
+    }
+    
+    /// 5人がアクティブ、6人が非アクティブ、1人が存在しない組織 octo-org を持つ依存性
+    fn mixed_org_deps() -> AppDependencies {
+        let members = [
+            ("org-alice", 0),
+            ("org-bob", 1),
+            ("org-carol", 3),
+            ("org-dave", 6),
+            ("org-erin", 7),
+            ("org-frank", 8),
+            ("org-grace", 14),
+            ("org-heidi", 30),
+            ("org-ivan", 90),
+            ("org-judy", 200),
+            ("org-mallory", 400),
+        ];
+        let mut names: Vec<&str> = members.iter().map(|(member, _)| *member).collect();
+        names.push("org-ghost");
+        
+        members
+            .iter()
+            .fold(TestDependenciesBuilder::new(), |builder, (member, days)| {
+                builder.with_activity(activity(member, *days))
+            })
+            .with_org_members("octo-org", &names)
+            .build()
+    }
+    
+    fn org(name: &str) -> Username {
+        Username::parse(name.to_string()).unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_badge_shows_active_fraction() {
+        let deps = mixed_org_deps();
+        
+        let badge = execute(&org("octo-org"), &deps, DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light)
+            .await
+            .unwrap();
+        
+        // 存在しないメンバーは集計から除く
+        assert!(badge.content.contains("5/11 active"));
+        assert!(!badge.is_interactive);
+    }
+    
+    #[tokio::test]
+    async fn test_summary_is_cached() {
+        let mut deps = mixed_org_deps();
+        
+        let first = get_org_summary(&org("octo-org"), &deps).await.unwrap();
+        assert_eq!(first, OrgActivitySummary { active_members: 5, total_members: 11 });
+        
+        // 2回目はGitHub APIを呼ばずにキャッシュから返す（大文字小文字の違いは同じキー）
+        deps.github_api = Arc::new(MockGitHubApi::new());
+        let second = get_org_summary(&org("Octo-Org"), &deps).await.unwrap();
+        assert_eq!(second, first);
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_org() {
+        let deps = mixed_org_deps();
+        
+        let result = execute(&org("no-such-org"), &deps, DEFAULT_BADGE_LABEL, BadgeStyle::Flat, BadgeTheme::Light).await;
+        
+        assert!(matches!(result, Err(AppError::Domain(DomainError::UserNotFound(_)))));
+    }
+}
@@ -7,4 +7,5 @@
 
 pub mod check_poke;
 pub mod generate_badge;
+pub mod generate_org_badge;
 pub mod user_settings;
\ No newline at end of file
@@ -5,9 +5,16 @@
 //! - 設定更新処理
 //! - アカウント削除処理
 
-use crate::app::dependencies::AppDependencies;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::app::dependencies::{AppDependencies, CacheService, EventStore};
+use crate::domain::poke::PokeEvent;
 use crate::domain::user::{Username, GitHubUserId, RegisteredUser, PokeSetting, UserState};
-use crate::error::{AppResult, DomainError};
+use crate::error::{AppResult, DomainError, InfraError};
+use crate::infra::adapters::oauth::OAuthAdapter;
+use crate::infra::adapters::storage::StorageAdapter;
+use crate::use_cases::{check_poke, generate_badge};
 
 /// ユーザー登録の実行結果
 pub struct RegisterUserResult {
@@ -41,7 +48,8 @@ pub async fn register_or_update_user(
     match existing_user {
         Some(mut user) => {
             // 既存ユーザーの場合、ユーザー名を更新（変更されている可能性）
-            if user.username != username {
+            // 表記だけの変更も反映するため、大文字小文字を区別して比較する
+            if user.username.as_str() != username.as_str() {
                 user.update_username(username);
                 deps.user_repository.update(&user).await?;
             }
@@ -81,7 +89,7 @@ pub async fn update_poke_setting(
 ) -> AppResult<RegisteredUser> {
     // ユーザーを取得
     let user_state = deps.user_repository
-        .find_by_username(username.as_str())
+        .find_by_username(&username.normalized())
         .await?
         .ok_or_else(|| DomainError::UserNotFound(username.as_str().to_string()))?;
     
@@ -100,17 +108,95 @@ pub async fn update_poke_setting(
     deps.user_repository.update(&user).await?;
     
     // キャッシュを無効化
-    invalidate_user_cache(username, deps).await?;
+    invalidate_user_cache(username, deps.cache_service.as_ref(), deps.storage.as_ref()).await?;
+    
+    Ok(user)
+}
+
+/// 送信者をブロック
+/// 
+/// # Arguments
+/// * `username` - ブロックするユーザー（受信者）
+/// * `sender` - ブロックされる送信者
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * `Ok(RegisteredUser)` - 更新後のユーザー
+/// * `Err(AppError)` - エラー
+pub async fn block_sender(
+    username: &Username,
+    sender: Username,
+    deps: &AppDependencies,
+) -> AppResult<RegisteredUser> {
+    let mut user = find_registered_user(username, deps).await?;
+    
+    // すでにブロック済みなら保存しない
+    if user.block_sender(sender) {
+        deps.user_repository.update(&user).await?;
+        invalidate_user_cache(username, deps.cache_service.as_ref(), deps.storage.as_ref()).await?;
+    }
+    
+    Ok(user)
+}
+
+/// 送信者のブロックを解除
+/// 
+/// # Arguments
+/// * `username` - ブロックを解除するユーザー（受信者）
+/// * `sender` - ブロックを解除される送信者
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * `Ok(RegisteredUser)` - 更新後のユーザー
+/// * `Err(AppError)` - エラー
+pub async fn unblock_sender(
+    username: &Username,
+    sender: &Username,
+    deps: &AppDependencies,
+) -> AppResult<RegisteredUser> {
+    let mut user = find_registered_user(username, deps).await?;
+    
+    // ブロックしていなければ保存しない
+    if user.unblock_sender(sender) {
+        deps.user_repository.update(&user).await?;
+        invalidate_user_cache(username, deps.cache_service.as_ref(), deps.storage.as_ref()).await?;
+    }
     
     Ok(user)
 }
 
+/// 登録済みユーザーを取得
+/// 
+/// # Returns
+/// * `Ok(RegisteredUser)` - 登録済みユーザー
+/// * `Err(DomainError::UserNotFound)` - 存在しない・未登録
+async fn find_registered_user(
+    username: &Username,
+    deps: &AppDependencies,
+) -> AppResult<RegisteredUser> {
+    let user_state = deps.user_repository
+        .find_by_username(&username.normalized())
+        .await?
+        .ok_or_else(|| DomainError::UserNotFound(username.as_str().to_string()))?;
+    
+    match user_state {
+        UserState::Registered(user) => Ok(user),
+        UserState::Anonymous(_) => {
+            Err(DomainError::UserNotFound(username.as_str().to_string()).into())
+        }
+    }
+}
+
 /// アカウントを削除
 /// 
-/// ユーザーアカウントと関連するすべてのデータを削除
+/// ユーザーアカウントと関連するすべてのデータを削除し、GitHubの認可を取り消す
+/// 
+/// 認可の取り消しに失敗してもアカウントの削除は完了させる
 /// 
 /// # Arguments
 /// * `username` - ユーザー名
+/// * `access_token` - セッションに保存していたアクセストークン（ない場合は取り消さない）
+/// * `oauth` - 認可の取り消しに使うOAuthアダプター
 /// * `deps` - アプリケーション依存性
 /// 
 /// # Returns
@@ -118,11 +204,13 @@ pub async fn update_poke_setting(
 /// * `Err(AppError)` - エラー
 pub async fn delete_account(
     username: &Username,
+    access_token: Option<&str>,
+    oauth: &dyn OAuthAdapter,
     deps: &AppDependencies,
 ) -> AppResult<()> {
     // ユーザーの存在確認
     let user_state = deps.user_repository
-        .find_by_username(username.as_str())
+        .find_by_username(&username.normalized())
         .await?
         .ok_or_else(|| DomainError::UserNotFound(username.as_str().to_string()))?;
     
@@ -138,17 +226,37 @@ pub async fn delete_account(
     delete_user_poke_events(username, deps).await?;
     
     // 2. ユーザーデータを削除
-    deps.user_repository.delete(username.as_str()).await?;
+    deps.user_repository.delete(&username.normalized()).await?;
     
     // 3. キャッシュを削除
-    invalidate_user_cache(username, deps).await?;
+    invalidate_user_cache(username, deps.cache_service.as_ref(), deps.storage.as_ref()).await?;
     
     // 4. セッションを削除
     delete_user_sessions(username, deps).await?;
     
+    // 5. GitHubの認可を取り消す
+    if let Some(access_token) = access_token {
+        revoke_authorization(username, access_token, oauth).await;
+    }
+    
     Ok(())
 }
 
+/// GitHubの認可を取り消す
+/// 
+/// アカウントはすでに削除済みのため、失敗はログに残すだけにする
+/// （ユーザーはGitHubの設定画面から自分で取り消せる）
+async fn revoke_authorization(username: &Username, access_token: &str, oauth: &dyn OAuthAdapter) {
+    match oauth.revoke_token(access_token).await {
+        Ok(()) => tracing::info!(username = username.as_str(), "GitHubの認可を取り消しました"),
+        Err(e) => tracing::warn!(
+            username = username.as_str(),
+            "GitHubの認可を取り消せませんでした: {}",
+            e
+        ),
+    }
+}
+
 /// GitHub IDでユーザーを検索
 async fn find_user_by_github_id(
     github_id: GitHubUserId,
@@ -165,9 +273,9 @@ async fn delete_user_poke_events(
     username: &Username,
     deps: &AppDependencies,
 ) -> AppResult<()> {
-    // TODO: 実装
-    // - event_store に delete_by_user メソッドを追加
-    // - 送信したPokeと受信したPokeの両方を削除
+    // 送信したPokeと受信したPokeの両方を削除
+    let deleted = deps.event_store.delete_by_user(username.as_str()).await?;
+    tracing::info!(username = username.as_str(), deleted, "Pokeイベントを削除しました");
     Ok(())
 }
 
@@ -184,57 +292,473 @@ async fn delete_user_sessions(
 
 /// ユーザーキャッシュを無効化
 /// 
-/// ユーザー情報が更新された際に呼ばれる
-async fn invalidate_user_cache(
+/// ユーザー情報が更新された際や、GitHub側の変更をWebhookで受け取った際に呼ばれる
+/// 
+/// # Arguments
+/// * `username` - ユーザー名
+/// * `cache` - キャッシュサービス
+/// * `storage` - バッジストレージ（Cloud Storage）
+pub async fn invalidate_user_cache(
     username: &Username,
-    deps: &AppDependencies,
+    cache: &dyn CacheService,
+    storage: &dyn StorageAdapter,
 ) -> AppResult<()> {
+    // Cloud Storageのバッジを先に削除し、Redisから復元されないようにする
+    storage.delete_badges(&format!("badge:{}:", username.normalized())).await?;
+    
     // 関連するキャッシュキーをすべて削除
-    let patterns = vec![
-        format!("user:{}", username.as_str()),
-        format!("badge:{}:*", username.as_str()),
-        format!("activity:{}:*", username.as_str()),
+    let mut patterns = vec![
+        format!("user:{}", username.normalized()),
+        format!("badge:{}:*", username.normalized()),
+        generate_badge::activity_cache_key(username),
+        user_statistics_cache_key(username),
     ];
+    patterns.extend(check_poke::follow_relation_cache_patterns(username));
     
     for pattern in patterns {
-        deps.cache_service.delete_pattern(&pattern).await?;
+        cache.delete_pattern(&pattern).await?;
     }
     
     Ok(())
 }
 
+/// 全期間の統計をキャッシュする秒数
+/// 
+/// 全期間の集計はイベント全件の読み込みが必要なため短時間だけ使い回す
+const USER_STATISTICS_CACHE_TTL: u64 = 60;
+
+/// 全期間の統計のキャッシュキー
+fn user_statistics_cache_key(username: &Username) -> String {
+    format!("stats:{}", username.normalized())
+}
+
 /// ユーザー統計を取得
 /// 
 /// Poke送信・受信数などの統計情報を集計
-#[allow(dead_code)]
+/// 
+/// # Arguments
+/// * `username` - ユーザー名
+/// * `deps` - アプリケーション依存性
+/// 
+/// # Returns
+/// * `Ok(UserStatistics)` - 統計情報
+/// * `Err(AppError)` - イベントストアまたはキャッシュのエラー
 pub async fn get_user_statistics(
     username: &Username,
     deps: &AppDependencies,
 ) -> AppResult<UserStatistics> {
-    // TODO: 実装
-    // - event_store から集計
-    // - キャッシュして高速化
-    unimplemented!()
+    collect_user_statistics(
+        deps.event_store.as_ref(),
+        deps.cache_service.as_ref(),
+        username,
+    )
+    .await
+}
+
+/// イベントストアからユーザー統計を集計
+/// 
+/// 全期間の集計はキャッシュし、今日の件数は毎回イベントストアから取得する
+async fn collect_user_statistics(
+    event_store: &dyn EventStore,
+    cache: &dyn CacheService,
+    username: &Username,
+) -> AppResult<UserStatistics> {
+    let cache_key = user_statistics_cache_key(username);
+    
+    // 壊れたキャッシュは無視して集計し直す
+    let cached = cache
+        .get(&cache_key)
+        .await?
+        .and_then(|json| serde_json::from_str::<AllTimePokeStatistics>(&json).ok());
+    
+    let all_time = match cached {
+        Some(all_time) => all_time,
+        None => {
+            let sent = event_store.find_pokes_from(username.as_str()).await?;
+            let received = event_store.find_pokes_to(username.as_str()).await?;
+            let all_time = aggregate_all_time(&sent, &received);
+            
+            let json = serde_json::to_string(&all_time).map_err(InfraError::from)?;
+            cache.set(&cache_key, &json, USER_STATISTICS_CACHE_TTL).await?;
+            all_time
+        }
+    };
+    
+    let pokes_sent_today = event_store.find_today_pokes_from(username.as_str()).await?.len() as u64;
+    let pokes_received_today = event_store.find_today_pokes_to(username.as_str()).await?.len() as u64;
+    
+    Ok(UserStatistics {
+        total_pokes_sent: all_time.total_pokes_sent,
+        total_pokes_received: all_time.total_pokes_received,
+        pokes_sent_today,
+        pokes_received_today,
+        unique_poke_recipients: all_time.unique_poke_recipients,
+        unique_poke_senders: all_time.unique_poke_senders,
+        most_poked_user: all_time.most_poked_user,
+        most_poked_by: all_time.most_poked_by,
+        top_poke_repositories: all_time.top_poke_repositories,
+    })
+}
+
+/// 全期間のPokeを集計
+/// 
+/// # Arguments
+/// * `sent` - ユーザーが送信したPoke
+/// * `received` - ユーザーが受信したPoke
+fn aggregate_all_time(sent: &[PokeEvent], received: &[PokeEvent]) -> AllTimePokeStatistics {
+    let recipients = count_by_user(sent.iter().map(|event| &event.to));
+    let senders = count_by_user(received.iter().map(|event| &event.from));
+    
+    AllTimePokeStatistics {
+        total_pokes_sent: sent.len() as u64,
+        total_pokes_received: received.len() as u64,
+        unique_poke_recipients: recipients.len() as u64,
+        unique_poke_senders: senders.len() as u64,
+        most_poked_user: most_frequent(&recipients),
+        most_poked_by: most_frequent(&senders),
+        top_poke_repositories: top_poke_repositories(received),
+    }
+}
+
+/// 受信したPokeが送られてきたリポジトリの上位
+/// 
+/// 件数の多い順、同数の場合はリポジトリ名順に `TOP_POKE_REPOSITORIES_LIMIT` 件まで返す。
+/// リポジトリコンテキストのないPokeは数えない
+fn top_poke_repositories(received: &[PokeEvent]) -> Vec<RepositoryPokeCount> {
+    let mut counts: HashMap<&str, u64> = HashMap::new();
+    for repository in received.iter().filter_map(|event| event.context.as_deref()) {
+        *counts.entry(repository).or_insert(0) += 1;
+    }
+    
+    let mut ranking: Vec<RepositoryPokeCount> = counts
+        .into_iter()
+        .map(|(repository, count)| RepositoryPokeCount {
+            repository: repository.to_string(),
+            count,
+        })
+        .collect();
+    ranking.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.repository.cmp(&b.repository)));
+    ranking.truncate(TOP_POKE_REPOSITORIES_LIMIT);
+    ranking
+}
+
+/// ユーザーごとの出現回数を数える
+fn count_by_user<'a>(users: impl Iterator<Item = &'a Username>) -> HashMap<&'a Username, u64> {
+    let mut counts = HashMap::new();
+    for user in users {
+        *counts.entry(user).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// 最も多く出現したユーザー
+/// 
+/// 同数の場合は名前順で先のユーザーを返す（結果を安定させるため）
+fn most_frequent(counts: &HashMap<&Username, u64>) -> Option<Username> {
+    counts
+        .iter()
+        .max_by(|(a_user, a_count), (b_user, b_count)| {
+            a_count.cmp(b_count).then_with(|| b_user.as_str().cmp(a_user.as_str()))
+        })
+        .map(|(user, _)| (*user).clone())
+}
+
+/// キャッシュする全期間の統計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct AllTimePokeStatistics {
+    total_pokes_sent: u64,
+    total_pokes_received: u64,
+    unique_poke_recipients: u64,
+    unique_poke_senders: u64,
+    most_poked_user: Option<Username>,
+    most_poked_by: Option<Username>,
+    #[serde(default)]
+    top_poke_repositories: Vec<RepositoryPokeCount>,
+}
+
+/// 統計に含めるリポジトリの最大数
+const TOP_POKE_REPOSITORIES_LIMIT: usize = 5;
+
+/// リポジトリごとの受信Poke数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryPokeCount {
+    /// リポジトリ（`owner/repo`）
+    pub repository: String,
+    
+    /// そのリポジトリから受信したPoke数
+    pub count: u64,
 }
 
 /// ユーザー統計情報
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UserStatistics {
     pub total_pokes_sent: u64,
     pub total_pokes_received: u64,
+    pub pokes_sent_today: u64,
+    pub pokes_received_today: u64,
     pub unique_poke_recipients: u64,
     pub unique_poke_senders: u64,
     pub most_poked_user: Option<Username>,
     pub most_poked_by: Option<Username>,
+    pub top_poke_repositories: Vec<RepositoryPokeCount>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use crate::app::dependencies::mocks::MockCacheService;
+    use crate::infra::adapters::storage::InMemoryStorageAdapter;
+    use crate::infra::event_store::InMemoryEventStore;
     
     // TODO: テストを実装
     // - 新規ユーザー登録
     // - 既存ユーザーの更新
     // - 設定更新
     // - アカウント削除
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_invalidate_user_cache_clears_follow_relations() {
+        let cache = MockCacheService::new();
+        for key in ["follow:alice:bob", "follow:bob:alice", "follow:bob:carol", "follow:alicex:bob"] {
+            cache.set(key, "\"Mutual\"", 600).await.unwrap();
+        }
+        
+        invalidate_user_cache(&username("Alice"), &cache, &InMemoryStorageAdapter::new()).await.unwrap();
+        
+        assert_eq!(cache.keys(), vec!["follow:alicex:bob", "follow:bob:carol"]);
+    }
+    
+    fn username(name: &str) -> Username {
+        Username::new(name.to_string()).unwrap()
+    }
+    
+    /// aliceを中心とした既知のイベントを保存
+    /// 
+    /// - 送信: bob×2（うち1件は昨日）、carol×1
+    /// - 受信: dave×2、bob×1（昨日）
+    async fn seed_events(store: &InMemoryEventStore) {
+        let yesterday = Utc::now() - Duration::days(1);
+        let mut events = vec![
+            PokeEvent::new(username("alice"), username("bob")),
+            PokeEvent::new(username("alice"), username("bob")),
+            PokeEvent::new(username("alice"), username("carol")),
+            PokeEvent::new(username("dave"), username("alice")),
+            PokeEvent::new(username("dave"), username("alice")),
+            PokeEvent::new(username("bob"), username("alice")),
+            // aliceに関係ないイベント
+            PokeEvent::new(username("bob"), username("carol")),
+        ];
+        events[1].occurred_at = yesterday;
+        events[5].occurred_at = yesterday;
+        
+        for event in &events {
+            store.save_poke(event).await.unwrap();
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_collect_user_statistics() {
+        let store = InMemoryEventStore::new();
+        let cache = MockCacheService::new();
+        seed_events(&store).await;
+        
+        let stats = collect_user_statistics(&store, &cache, &username("alice")).await.unwrap();
+        
+        assert_eq!(stats, UserStatistics {
+            total_pokes_sent: 3,
+            total_pokes_received: 3,
+            pokes_sent_today: 2,
+            pokes_received_today: 2,
+            unique_poke_recipients: 2,
+            unique_poke_senders: 2,
+            most_poked_user: Some(username("bob")),
+            most_poked_by: Some(username("dave")),
+            top_poke_repositories: Vec::new(),
+        });
+    }
+    
+    #[tokio::test]
+    async fn test_statistics_without_events() {
+        let store = InMemoryEventStore::new();
+        let cache = MockCacheService::new();
+        
+        let stats = collect_user_statistics(&store, &cache, &username("alice")).await.unwrap();
+        
+        assert_eq!(stats, UserStatistics {
+            total_pokes_sent: 0,
+            total_pokes_received: 0,
+            pokes_sent_today: 0,
+            pokes_received_today: 0,
+            unique_poke_recipients: 0,
+            unique_poke_senders: 0,
+            most_poked_user: None,
+            most_poked_by: None,
+            top_poke_repositories: Vec::new(),
+        });
+    }
+    
+    #[tokio::test]
+    async fn test_all_time_statistics_are_cached() {
+        let store = InMemoryEventStore::new();
+        let cache = MockCacheService::new();
+        seed_events(&store).await;
+        
+        collect_user_statistics(&store, &cache, &username("alice")).await.unwrap();
+        assert!(cache.get("stats:alice").await.unwrap().is_some());
+        
+        // キャッシュ有効中は全期間の件数は変わらず、今日の件数だけ反映される
+        store.save_poke(&PokeEvent::new(username("alice"), username("erin"))).await.unwrap();
+        let stats = collect_user_statistics(&store, &cache, &username("alice")).await.unwrap();
+        assert_eq!(stats.total_pokes_sent, 3);
+        assert_eq!(stats.pokes_sent_today, 3);
+        
+        // キャッシュ削除後は再集計される
+        cache.delete("stats:alice").await.unwrap();
+        let stats = collect_user_statistics(&store, &cache, &username("alice")).await.unwrap();
+        assert_eq!(stats.total_pokes_sent, 4);
+        assert_eq!(stats.unique_poke_recipients, 3);
+    }
+    
+    #[tokio::test]
+    async fn test_top_poke_repositories_ranked_by_frequency() {
+        let store = InMemoryEventStore::new();
+        let cache = MockCacheService::new();
+        let contexts = [
+            Some("octo-org/api"),
+            Some("octo-org/web"),
+            Some("octo-org/api"),
+            Some("alice/dotfiles"),
+            Some("octo-org/web"),
+            Some("octo-org/api"),
+            None,
+        ];
+        for context in contexts {
+            let mut event = PokeEvent::new(username("bob"), username("alice"));
+            event.context = context.map(str::to_string);
+            store.save_poke(&event).await.unwrap();
+        }
+        // 送信したPokeのリポジトリは数えない
+        store
+            .save_poke(&PokeEvent::with_context(username("alice"), username("bob"), "bob/site".to_string()))
+            .await
+            .unwrap();
+        
+        let stats = collect_user_statistics(&store, &cache, &username("alice")).await.unwrap();
+        
+        let ranking: Vec<(&str, u64)> = stats
+            .top_poke_repositories
+            .iter()
+            .map(|entry| (entry.repository.as_str(), entry.count))
+            .collect();
+        assert_eq!(ranking, vec![("octo-org/api", 3), ("octo-org/web", 2), ("alice/dotfiles", 1)]);
+    }
+    
+    #[test]
+    fn test_top_poke_repositories_limit_and_tie_break() {
+        let received: Vec<PokeEvent> = ["f/f", "e/e", "d/d", "c/c", "b/b", "a/a"]
+            .into_iter()
+            .map(|repository| {
+                PokeEvent::with_context(username("bob"), username("alice"), repository.to_string())
+            })
+            .collect();
+        
+        let ranking = top_poke_repositories(&received);
+        
+        let names: Vec<&str> = ranking.iter().map(|entry| entry.repository.as_str()).collect();
+        assert_eq!(names, vec!["a/a", "b/b", "c/c", "d/d", "e/e"]);
+    }
+    
+    #[test]
+    fn test_most_frequent_tie_breaks_by_name() {
+        let carol = username("carol");
+        let bob = username("bob");
+        let counts = count_by_user([&carol, &bob, &carol, &bob].into_iter());
+        
+        assert_eq!(most_frequent(&counts), Some(username("bob")));
+    }
+    
+    mod delete_account_tests {
+        use super::*;
+        use std::error::Error;
+        use crate::app::dependencies::mocks::TestDependenciesBuilder;
+        use crate::infra::adapters::oauth::OAuthToken;
+        
+        /// 取り消したトークンを記録するOAuthアダプター
+        #[derive(Default)]
+        struct RecordingOAuth {
+            /// trueの場合、取り消しは常に失敗する
+            fail: bool,
+            revoked: Mutex<Vec<String>>,
+        }
+        
+        #[async_trait]
+        impl OAuthAdapter for RecordingOAuth {
+            async fn exchange_code(&self, _code: &str) -> Result<OAuthToken, Box<dyn Error>> {
+                unimplemented!()
+            }
+            
+            async fn refresh_token(&self, _refresh_token: &str) -> Result<OAuthToken, Box<dyn Error>> {
+                unimplemented!()
+            }
+            
+            async fn revoke_token(&self, token: &str) -> Result<(), Box<dyn Error>> {
+                self.revoked.lock().unwrap().push(token.to_string());
+                if self.fail {
+                    return Err("HTTP 502".into());
+                }
+                Ok(())
+            }
+        }
+        
+        /// 登録済みユーザー alice とそのPokeを持つ依存性
+        async fn deps_with_alice() -> AppDependencies {
+            let deps = TestDependenciesBuilder::new()
+                .with_registered_user(RegisteredUser::new(GitHubUserId::new(1), username("alice")))
+                .build();
+            deps.event_store.save_poke(&PokeEvent::new(username("alice"), username("bob"))).await.unwrap();
+            deps
+        }
+        
+        /// aliceのアカウントとPokeがすべて削除されているか
+        async fn assert_alice_deleted(deps: &AppDependencies) {
+            assert!(deps.user_repository.find_by_username("alice").await.unwrap().is_none());
+            assert!(deps.event_store.find_pokes_from("alice").await.unwrap().is_empty());
+        }
+        
+        #[tokio::test]
+        async fn test_delete_account_revokes_authorization() {
+            let deps = deps_with_alice().await;
+            let oauth = RecordingOAuth::default();
+            
+            delete_account(&username("alice"), Some("gho_token"), &oauth, &deps).await.unwrap();
+            
+            assert_alice_deleted(&deps).await;
+            assert_eq!(*oauth.revoked.lock().unwrap(), vec!["gho_token".to_string()]);
+        }
+        
+        #[tokio::test]
+        async fn test_revoke_failure_does_not_block_deletion() {
+            let deps = deps_with_alice().await;
+            let oauth = RecordingOAuth { fail: true, ..RecordingOAuth::default() };
+            
+            delete_account(&username("alice"), Some("gho_token"), &oauth, &deps).await.unwrap();
+            
+            assert_alice_deleted(&deps).await;
+            assert_eq!(oauth.revoked.lock().unwrap().len(), 1);
+        }
+        
+        #[tokio::test]
+        async fn test_session_without_token_skips_revocation() {
+            let deps = deps_with_alice().await;
+            let oauth = RecordingOAuth::default();
+            
+            delete_account(&username("alice"), None, &oauth, &deps).await.unwrap();
+            
+            assert_alice_deleted(&deps).await;
+            assert!(oauth.revoked.lock().unwrap().is_empty());
+        }
+    }
+}
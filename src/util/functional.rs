@@ -5,7 +5,13 @@
 //! - パイプライン処理
 //! - Result/Option型の変換
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::future::Future;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use futures::stream::{self, StreamExt};
 
 /// 2つの関数を合成
 /// 
@@ -36,6 +42,47 @@ pub fn pipe3<A, B, C, D>(
     move |a| h(g(f(a)))
 }
 
+/// 4つの関数を合成
+/// 
+/// f: A -> B, g: B -> C, h: C -> D, i: D -> E から A -> E を作る
+/// 
+/// # Example
+/// ```
+/// let pipeline = pipe4(
+///     |x: i32| x + 1,
+///     |x: i32| x * 2,
+///     |x: i32| x - 3,
+///     |x: i32| x.to_string(),
+/// );
+/// assert_eq!(pipeline(5), "9"); // ((5 + 1) * 2) - 3 = 9
+/// ```
+pub fn pipe4<A, B, C, D, E>(
+    f: impl Fn(A) -> B,
+    g: impl Fn(B) -> C,
+    h: impl Fn(C) -> D,
+    i: impl Fn(D) -> E,
+) -> impl Fn(A) -> E {
+    move |a| i(h(g(f(a))))
+}
+
+/// 2つの関数を数学の記法（g ∘ f）の順で合成
+/// 
+/// `pipe` の引数を逆にしたもの。g: B -> C と f: A -> B から A -> C を作る
+/// 
+/// # Example
+/// ```
+/// let add_one = |x: i32| x + 1;
+/// let double = |x: i32| x * 2;
+/// let double_after_add_one = compose(double, add_one);
+/// assert_eq!(double_after_add_one(5), 12); // double(add_one(5)) = 12
+/// ```
+pub fn compose<A, B, C>(
+    g: impl Fn(B) -> C,
+    f: impl Fn(A) -> B,
+) -> impl Fn(A) -> C {
+    move |a| g(f(a))
+}
+
 /// Result型を返す関数の合成
 /// 
 /// エラーが発生した場合は早期リターン
@@ -106,6 +153,44 @@ where
     values.into_iter().map(f).collect()
 }
 
+/// 複数の値に対して非同期関数を並行に適用
+/// 
+/// 同時に実行するのは最大 `concurrency` 件まで。
+/// 結果は完了順ではなく入力と同じ順序で返す
+/// 
+/// いずれかがエラーになった時点でそのエラーを返し、実行中の残りはキャンセルする
+/// 
+/// # Arguments
+/// * `values` - 入力値
+/// * `concurrency` - 同時実行数の上限（0の場合は1として扱う）
+/// * `f` - 各値に適用する非同期関数
+pub async fn try_map_all_concurrent<T, U, E, F, Fut>(
+    values: Vec<T>,
+    concurrency: usize,
+    f: F,
+) -> Result<Vec<U>, E>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = Result<U, E>>,
+{
+    let total = values.len();
+    let mut results = stream::iter(values.into_iter().enumerate())
+        .map(|(index, value)| {
+            let future = f(value);
+            async move { future.await.map(|output| (index, output)) }
+        })
+        .buffer_unordered(concurrency.max(1));
+    
+    let mut outputs = Vec::with_capacity(total);
+    while let Some(result) = results.next().await {
+        // エラー時はストリームごと破棄され、実行中のFutureもキャンセルされる
+        outputs.push(result?);
+    }
+    
+    outputs.sort_by_key(|(index, _)| *index);
+    Ok(outputs.into_iter().map(|(_, output)| output).collect())
+}
+
 /// 条件に基づいて関数を選択
 /// 
 /// # Example
@@ -132,6 +217,59 @@ pub fn if_else<T, U>(
     }
 }
 
+/// 関数の結果をキーごとにキャッシュ（メモ化）
+/// 
+/// 同じキーで2回目以降に呼ばれた場合は関数を呼ばずにキャッシュした値を返す。
+/// 副作用のない純粋な関数にのみ使うこと
+/// 
+/// キャッシュは上限なしで保持し続けるため、キーの種類が限られる場合に使う。
+/// 単一スレッド用（`RefCell`）で、スレッド間で共有する場合は `memoize_sync` を使う
+/// 
+/// # Example
+/// ```
+/// let square = memoize(|x: u64| x * x);
+/// assert_eq!(square(4), 16);
+/// assert_eq!(square(4), 16); // 2回目はキャッシュから返す
+/// ```
+pub fn memoize<K, V>(f: impl Fn(K) -> V) -> impl Fn(K) -> V
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    let cache = RefCell::new(HashMap::<K, V>::new());
+    move |key| {
+        if let Some(value) = cache.borrow().get(&key) {
+            return value.clone();
+        }
+        
+        let value = f(key.clone());
+        cache.borrow_mut().insert(key, value.clone());
+        value
+    }
+}
+
+/// 関数の結果をキーごとにキャッシュ（スレッドセーフ版）
+/// 
+/// `memoize` と同じく上限なしでキャッシュする。
+/// 関数の実行中はロックを保持しないため、同じキーで同時に呼ばれた場合は
+/// 複数回実行されることがある
+pub fn memoize_sync<K, V>(f: impl Fn(K) -> V + Send + Sync) -> impl Fn(K) -> V + Send + Sync
+where
+    K: Eq + Hash + Clone + Send,
+    V: Clone + Send,
+{
+    let cache = Mutex::new(HashMap::<K, V>::new());
+    move |key| {
+        if let Some(value) = cache.lock().unwrap().get(&key) {
+            return value.clone();
+        }
+        
+        let value = f(key.clone());
+        cache.lock().unwrap().insert(key, value.clone());
+        value
+    }
+}
+
 /// タプルの要素を入れ替え
 pub fn swap<A, B>((a, b): (A, B)) -> (B, A) {
     (b, a)
@@ -167,6 +305,30 @@ mod tests {
         assert_eq!(add_one_then_double(5), 12);
     }
     
+    #[test]
+    fn test_pipe4() {
+        let pipeline = pipe4(
+            |x: i32| x + 1,
+            |x: i32| x * 2,
+            |x: i32| x - 3,
+            |x: i32| x * x,
+        );
+        
+        assert_eq!(pipeline(5), 81); // (((5 + 1) * 2) - 3)^2
+        assert_eq!(pipeline(-1), 9);
+    }
+    
+    #[test]
+    fn test_compose_is_reversed_pipe() {
+        let add_one = |x: i32| x + 1;
+        let double = |x: i32| x * 2;
+        
+        assert_eq!(compose(double, add_one)(5), pipe(add_one, double)(5));
+        assert_eq!(compose(double, add_one)(5), 12);
+        // 順序を入れ替えると結果が変わる
+        assert_eq!(compose(add_one, double)(5), 11);
+    }
+    
     #[test]
     fn test_pipe_result() {
         let parse = |s: &str| s.parse::<i32>();
@@ -180,6 +342,49 @@ mod tests {
         assert!(parse_positive("abc").is_err());
     }
     
+    #[test]
+    fn test_memoize_calls_once_per_key() {
+        let calls = std::cell::Cell::new(0);
+        let square = memoize(|x: u32| {
+            calls.set(calls.get() + 1);
+            x * x
+        });
+        
+        assert_eq!(square(3), 9);
+        assert_eq!(square(3), 9);
+        assert_eq!(square(4), 16);
+        assert_eq!(square(3), 9);
+        
+        assert_eq!(calls.get(), 2);
+    }
+    
+    #[test]
+    fn test_memoize_sync_calls_once_per_key() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let length = Arc::new(memoize_sync(move |s: String| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            s.len()
+        }));
+        
+        assert_eq!(length("octocat".to_string()), 7);
+        
+        // 別スレッドからもキャッシュを共有する
+        let shared = Arc::clone(&length);
+        std::thread::spawn(move || {
+            assert_eq!(shared("octocat".to_string()), 7);
+            assert_eq!(shared("hubot".to_string()), 5);
+        })
+        .join()
+        .unwrap();
+        
+        assert_eq!(length("hubot".to_string()), 5);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+    
     #[test]
     fn test_try_map_all() {
         let values = vec!["1", "2", "3"];
@@ -190,4 +395,44 @@ mod tests {
         let parse_with_error = try_map_all(values_with_error, |s| s.parse::<i32>());
         assert!(parse_with_error.is_err());
     }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_try_map_all_concurrent_preserves_order() {
+        use std::time::Duration;
+        
+        // 後の要素ほど早く完了する
+        let result: Result<Vec<u64>, ()> = try_map_all_concurrent(vec![1, 2, 3, 4, 5], 3, |x: u64| async move {
+            tokio::time::sleep(Duration::from_millis(100 - x * 10)).await;
+            Ok(x * 10)
+        }).await;
+        
+        assert_eq!(result, Ok(vec![10, 20, 30, 40, 50]));
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_try_map_all_concurrent_stops_on_error() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+        
+        let started = AtomicUsize::new(0);
+        let finished = AtomicUsize::new(0);
+        
+        let result = try_map_all_concurrent(vec![1, 2, 3, 4, 5, 6], 2, |x: u64| {
+            started.fetch_add(1, Ordering::SeqCst);
+            let finished = &finished;
+            async move {
+                if x == 3 {
+                    return Err(format!("failed at {}", x));
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                finished.fetch_add(1, Ordering::SeqCst);
+                Ok(x)
+            }
+        }).await;
+        
+        assert_eq!(result, Err("failed at 3".to_string()));
+        // エラー以降の要素は開始されず、実行中だった要素（4）は完了前にキャンセルされる
+        assert_eq!(started.load(Ordering::SeqCst), 4);
+        assert_eq!(finished.load(Ordering::SeqCst), 2);
+    }
 }
\ No newline at end of file